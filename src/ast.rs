@@ -6,6 +6,8 @@ pub struct Program {
     pub classes: Vec<ClassDecl>,
     pub interfaces: Vec<InterfaceDecl>,
     pub top_level_functions: Vec<TopLevelFunction>,
+    pub constants: Vec<FieldDecl>,  // 顶层 const 常量声明
+    pub static_asserts: Vec<CallExpr>,  // 顶层 static_assert(condition, "message");
 }
 
 /// 顶层函数声明（类外函数）
@@ -98,15 +100,19 @@ pub enum Modifier {
     Protected,
     Static,
     Final,
+    Const,     // const 编译期常量，隐含 static final
     Abstract,
     Native,
     Main,      // 标记主类，用于解决多main冲突
     Override,  // @Override 注解，标记方法重写
+    SinceVersion(String),  // @SinceVersion("0.X.Y") 注解，标记方法引入的版本
+    Inline,    // @inline 注解，提示总是内联该方法
 }
 
 #[derive(Debug, Clone)]
 pub struct Block {
     pub statements: Vec<Stmt>,
+    pub label: Option<String>,  // 带标签的语句块: name: { ... }，可用 break name; 跳出
     pub loc: SourceLocation,
 }
 
@@ -119,10 +125,13 @@ pub enum Stmt {
     While(WhileStmt),
     For(ForStmt),
     DoWhile(DoWhileStmt),
+    Repeat(RepeatStmt),
     Switch(SwitchStmt),
     Block(Block),
-    Break,
+    TupleDestructure(TupleDestructure),  // 元组解构: var (x, y) = expr;
+    Break(Option<String>),  // break; 或 break label;
     Continue,
+    Empty,  // 单独的分号 ;
 }
 
 #[derive(Debug, Clone)]
@@ -134,6 +143,15 @@ pub struct VarDecl {
     pub loc: SourceLocation,
 }
 
+/// 元组解构声明: var (x, y) = expr;
+#[derive(Debug, Clone)]
+pub struct TupleDestructure {
+    pub names: Vec<String>,
+    pub initializer: Expr,
+    pub is_final: bool,
+    pub loc: SourceLocation,
+}
+
 #[derive(Debug, Clone)]
 pub struct IfStmt {
     pub condition: Expr,
@@ -166,11 +184,20 @@ pub struct DoWhileStmt {
     pub loc: SourceLocation,
 }
 
+/// repeat 循环语句: repeat (n) { ... }，循环体执行 n 次
+#[derive(Debug, Clone)]
+pub struct RepeatStmt {
+    pub count: Expr,
+    pub body: Box<Stmt>,
+    pub loc: SourceLocation,
+}
+
 /// switch case 分支
 #[derive(Debug, Clone)]
 pub struct Case {
-    pub value: i64,
+    pub value: Expr,  // case 标签，必须能在编译期折叠为整数常量（字符取字符码）
     pub body: Vec<Stmt>,
+    pub fallthrough: bool,  // 以 fallthrough; 结尾，执行完后进入下一个 case
 }
 
 /// switch 语句
@@ -179,6 +206,7 @@ pub struct SwitchStmt {
     pub expr: Expr,
     pub cases: Vec<Case>,
     pub default: Option<Vec<Stmt>>,
+    pub default_index: usize,  // default 在源代码中的位置：它前面的 case 数量
     pub loc: SourceLocation,
 }
 
@@ -200,6 +228,7 @@ pub enum Expr {
     Lambda(LambdaExpr),        // Lambda 表达式: (params) -> { body }
     Ternary(TernaryExpr),      // 三元运算符: condition ? true_expr : false_expr
     InstanceOf(InstanceOfExpr), // instanceof 运算符: obj instanceof Type
+    Tuple(TupleExpr),          // 元组: (a, b)
 }
 
 #[derive(Debug, Clone)]
@@ -231,6 +260,8 @@ pub enum BinaryOp {
     Mod,
     Eq,
     Ne,
+    Is,     // === 引用同一性比较
+    IsNot,  // !== 引用同一性比较
     Lt,
     Le,
     Gt,
@@ -255,6 +286,7 @@ pub struct UnaryExpr {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UnaryOp {
     Neg,
+    Plus,  // 一元 +，只做数值提升
     Not,
     BitNot,
     PreInc,
@@ -336,8 +368,8 @@ pub struct ArrayAccessExpr {
 /// 方法引用表达式: ClassName::methodName 或 obj::methodName
 #[derive(Debug, Clone)]
 pub struct MethodRefExpr {
-    pub class_name: Option<String>,  // 类名（静态方法引用）
-    pub object: Option<Box<Expr>>,   // 对象表达式（实例方法引用）
+    pub class_name: Option<String>,  // 类名或对象变量名（ClassName::m / obj::m）
+    pub object: Option<Box<Expr>>,   // 对象表达式（this::m）
     pub method_name: String,
     pub loc: SourceLocation,
 }
@@ -381,6 +413,13 @@ pub struct InstanceOfExpr {
     pub loc: SourceLocation,
 }
 
+/// 元组表达式: (a, b)
+#[derive(Debug, Clone)]
+pub struct TupleExpr {
+    pub elements: Vec<Expr>,
+    pub loc: SourceLocation,
+}
+
 impl Program {
     pub fn find_main_class(&self) -> Option<&ClassDecl> {
         self.classes.iter().find(|c| {
@@ -405,6 +444,51 @@ impl Default for Program {
             classes: Vec::new(),
             interfaces: Vec::new(),
             top_level_functions: Vec::new(),
+            constants: Vec::new(),
+            static_asserts: Vec::new(),
+        }
+    }
+}
+
+impl Stmt {
+    /// 语句在源代码中的位置；没有记录位置的语句（如 `break;`）返回 None
+    pub fn loc(&self) -> Option<&SourceLocation> {
+        match self {
+            Stmt::Expr(expr) | Stmt::Return(Some(expr)) => expr.loc(),
+            Stmt::VarDecl(var) => Some(&var.loc),
+            Stmt::If(stmt) => Some(&stmt.loc),
+            Stmt::While(stmt) => Some(&stmt.loc),
+            Stmt::For(stmt) => Some(&stmt.loc),
+            Stmt::DoWhile(stmt) => Some(&stmt.loc),
+            Stmt::Repeat(stmt) => Some(&stmt.loc),
+            Stmt::Switch(stmt) => Some(&stmt.loc),
+            Stmt::Block(block) => Some(&block.loc),
+            Stmt::TupleDestructure(destructure) => Some(&destructure.loc),
+            Stmt::Return(None) | Stmt::Break(_) | Stmt::Continue | Stmt::Empty => None,
+        }
+    }
+}
+
+impl Expr {
+    /// 表达式在源代码中的位置；字面量和标识符没有记录位置
+    pub fn loc(&self) -> Option<&SourceLocation> {
+        match self {
+            Expr::Literal(_) | Expr::Identifier(_) => None,
+            Expr::Binary(e) => Some(&e.loc),
+            Expr::Unary(e) => Some(&e.loc),
+            Expr::Call(e) => Some(&e.loc),
+            Expr::MemberAccess(e) => Some(&e.loc),
+            Expr::New(e) => Some(&e.loc),
+            Expr::Assignment(e) => Some(&e.loc),
+            Expr::Cast(e) => Some(&e.loc),
+            Expr::ArrayCreation(e) => Some(&e.loc),
+            Expr::ArrayAccess(e) => Some(&e.loc),
+            Expr::ArrayInit(e) => Some(&e.loc),
+            Expr::MethodRef(e) => Some(&e.loc),
+            Expr::Lambda(e) => Some(&e.loc),
+            Expr::Ternary(e) => Some(&e.loc),
+            Expr::InstanceOf(e) => Some(&e.loc),
+            Expr::Tuple(e) => Some(&e.loc),
         }
     }
 }
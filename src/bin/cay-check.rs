@@ -199,6 +199,9 @@ fn main() {
             let mut analyzer = semantic::SemanticAnalyzer::new();
             match analyzer.analyze(&ast) {
                 Ok(_) => {
+                    for warning in analyzer.take_warnings() {
+                        eprintln!("  {}", warning);
+                    }
                     let elapsed = start_time.elapsed();
                     println!("  [+] 语义分析通过");
                     println!("");
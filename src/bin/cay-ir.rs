@@ -2,7 +2,7 @@ use std::env;
 use std::fs;
 use std::process;
 use std::path::{Path, PathBuf};
-use cavvy::Compiler;
+use cavvy::{Compiler, RuntimeMode, SymbolFormat};
 use cavvy::error::print_error_with_context;
 
 /// 查找 clang 可执行文件
@@ -38,11 +38,26 @@ struct CompileOptions {
     optimize_ir: bool,       // --opt-ir: 使用 clang 优化 IR
     emit_optimized: bool,    // --emit-optimized: 输出发优化后的 IR
     target_os: String,       // --target: 目标操作系统
+    target_triple: Option<String>, // --target-triple: 显式指定目标三元组
     features: Vec<String>,   // -f:XX 或 --feature:XX 开启特性
     no_features: Vec<String>, // -No:XX 关闭特性
     defines: Vec<String>,    // -D:XX 定义宏
     undefines: Vec<String>,  // -U:XX 取消定义宏
     obfuscate: bool,         // --obfuscate 混淆 IR 代码
+    werror: bool,            // --werror 将警告视为错误
+    dump_ir_stats: bool,     // --dump-ir-stats 输出 IR 指令统计
+    dump_symbols: Option<SymbolFormat>,     // --dump-symbols[=json] 输出符号表
+    no_runtime: bool,        // --no-runtime 不输出运行时函数
+    implicit_string_concat: bool, // --implicit-string-concat 字符串与基本类型拼接时隐式转换
+    warn_shadow: bool,       // --warn-shadow 局部变量遮蔽参数时警告
+    warn_infinite_loop: bool, // --warn-infinite-loop 条件恒为真且无法退出的循环时警告
+    warn_float_precision: bool, // --warn-float-precision double 字面量赋给 float 丢失精度时警告
+    immutable_final_arrays: bool, // --immutable-final-arrays final 数组的元素不可修改
+    bounds_check: bool,      // --bounds-check 数组访问时检查下标范围
+    target_version: Option<String>, // --target-version 目标 Cavvy 版本
+    max_errors: usize,       // --max-errors 语义错误数量上限
+    library: bool,           // --library 编译为库模块，不生成 main 入口
+    inline_threshold: Option<usize>,  // --inline-small <n> 内联小型静态方法
 }
 
 impl Default for CompileOptions {
@@ -52,11 +67,26 @@ impl Default for CompileOptions {
             optimize_ir: false,
             emit_optimized: false,
             target_os: std::env::consts::OS.to_string(),
+            target_triple: None,
             features: Vec::new(),
             no_features: Vec::new(),
             defines: Vec::new(),
             undefines: Vec::new(),
             obfuscate: false,
+            werror: false,
+            dump_ir_stats: false,
+            dump_symbols: None,
+            no_runtime: false,
+            implicit_string_concat: false,
+            warn_shadow: false,
+            warn_infinite_loop: false,
+            warn_float_precision: false,
+            immutable_final_arrays: false,
+            bounds_check: false,
+            target_version: None,
+            max_errors: 100,
+            library: false,
+            inline_threshold: None,
         }
     }
 }
@@ -71,7 +101,22 @@ fn print_usage() {
     println!("  --opt-ir              使用 LLVM 优化 IR (增加编译时间，提高运行时性能)");
     println!("  --emit-optimized      输出优化后的 IR (与 --opt-ir 一起使用)");
     println!("  --target <os>         目标操作系统 (windows, linux, macos)");
+    println!("  --target-triple <t>   显式指定目标三元组，覆盖 --target 推导的默认值");
     println!("  --obfuscate           混淆 IR 代码");
+    println!("  --werror              将所有警告视为错误");
+    println!("  --dump-ir-stats       输出每个函数的 IR 指令统计");
+    println!("  --dump-symbols[=json] 语义分析后输出所有类的字段和方法");
+    println!("  --no-runtime          不输出运行时函数，程序需要运行时时报错");
+    println!("  --implicit-string-concat  允许字符串与基本类型用 + 拼接（隐式转换为字符串）");
+    println!("  --warn-shadow         局部变量遮蔽方法参数时发出警告");
+    println!("  --warn-infinite-loop  循环条件恒为真且循环体中没有 break/return 时发出警告");
+    println!("  --warn-float-precision  double 字面量赋给 float 并丢失精度时发出警告");
+    println!("  --immutable-final-arrays  final 数组的元素也不可修改，对其元素赋值时报错");
+    println!("  --bounds-check        数组访问时检查下标范围，越界时终止程序");
+    println!("  --target-version <v>  目标 Cavvy 版本，使用更高版本的方法时发出警告");
+    println!("  --max-errors <n>      语义错误达到 n 个后停止分析（默认 100，0 表示不限制）");
+    println!("  --library             编译为库模块：不生成 main 入口，只导出 public 方法");
+    println!("  --inline-small <n>    内联只有一个调用点、不超过 n 条指令的静态方法");
     println!("  -f:XX, --feature:XX   启用特定功能");
     println!("  -No:XX                禁用特定功能");
     println!("  -D:XX                 定义宏");
@@ -121,9 +166,79 @@ fn parse_args(args: &[String]) -> Result<(CompileOptions, String, String), Strin
                     return Err("--target 需要一个参数，如 windows、linux、macos".to_string());
                 }
             }
+            "--target-triple" => {
+                if i + 1 < args.len() {
+                    options.target_triple = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    return Err("--target-triple 需要一个参数，如 aarch64-unknown-linux-gnu".to_string());
+                }
+            }
             "--obfuscate" => {
                 options.obfuscate = true;
             }
+            "--werror" => {
+                options.werror = true;
+            }
+            "--dump-ir-stats" => {
+                options.dump_ir_stats = true;
+            }
+            "--dump-symbols" => {
+                options.dump_symbols = Some(SymbolFormat::Text);
+            }
+            "--dump-symbols=json" => {
+                options.dump_symbols = Some(SymbolFormat::Json);
+            }
+            "--no-runtime" => {
+                options.no_runtime = true;
+            }
+            "--implicit-string-concat" => {
+                options.implicit_string_concat = true;
+            }
+            "--warn-shadow" => {
+                options.warn_shadow = true;
+            }
+            "--warn-infinite-loop" => {
+                options.warn_infinite_loop = true;
+            }
+            "--warn-float-precision" => {
+                options.warn_float_precision = true;
+            }
+            "--immutable-final-arrays" => {
+                options.immutable_final_arrays = true;
+            }
+            "--bounds-check" => {
+                options.bounds_check = true;
+            }
+            "--library" => {
+                options.library = true;
+            }
+            "--target-version" => {
+                if i + 1 < args.len() {
+                    options.target_version = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    return Err("--target-version 需要一个版本号参数，如 0.3.3".to_string());
+                }
+            }
+            "--max-errors" => {
+                if i + 1 < args.len() {
+                    options.max_errors = args[i + 1].parse()
+                        .map_err(|_| format!("--max-errors 需要非负整数，得到 '{}'", args[i + 1]))?;
+                    i += 1;
+                } else {
+                    return Err("--max-errors 需要一个数量参数，如 20".to_string());
+                }
+            }
+            "--inline-small" => {
+                if i + 1 < args.len() {
+                    options.inline_threshold = Some(args[i + 1].parse()
+                        .map_err(|_| format!("--inline-small 需要非负整数，得到 '{}'", args[i + 1]))?);
+                    i += 1;
+                } else {
+                    return Err("--inline-small 需要一个指令数参数，如 16".to_string());
+                }
+            }
             arg if arg.starts_with("-f:") || arg.starts_with("--feature:") => {
                 let feature = if arg.starts_with("-f:") {
                     &arg[3..]
@@ -234,11 +349,27 @@ fn main() {
     // 创建多平台编译器配置
     let compiler_options = cavvy::CompilerOptions {
         target_os: options.target_os,
+        target_triple: options.target_triple,
         features: options.features,
         no_features: options.no_features,
         defines: options.defines,
         undefines: options.undefines,
         obfuscate: options.obfuscate,
+        warnings_as_errors: options.werror,
+        dump_ir_stats: options.dump_ir_stats,
+        dump_symbols: options.dump_symbols,
+        target_version: options.target_version,
+        emit_runtime: if options.no_runtime { RuntimeMode::Never } else { RuntimeMode::OnDemand },
+        implicit_string_concat: options.implicit_string_concat,
+        warn_shadowed_params: options.warn_shadow,
+        warn_infinite_loops: options.warn_infinite_loop,
+        warn_float_precision: options.warn_float_precision,
+        immutable_final_arrays: options.immutable_final_arrays,
+        bounds_check: options.bounds_check,
+        max_errors: options.max_errors,
+        library: options.library,
+        inline_threshold: options.inline_threshold,
+        optimization: options.optimize_ir.then(|| options.optimization.clone()),
     };
 
     // 编译 Cavvy → IR
@@ -257,10 +388,10 @@ fn main() {
     }
 
     // 如果需要优化 IR
-    let final_ir_file = if options.optimize_ir {
+    let final_ir_file = if let Some(level) = &compiler.options().optimization {
         println!("");
-        println!("[2] 优化 IR ({})...", options.optimization);
-        match optimize_ir(&temp_ir_file, &options.optimization) {
+        println!("[2] 优化 IR ({})...", level);
+        match optimize_ir(&temp_ir_file, level) {
             Ok(optimized_file) => {
                 println!("  [+] IR 优化完成");
                 // 删除临时文件
@@ -2,7 +2,7 @@ use std::env;
 use std::fs;
 use std::process;
 use std::path::{Path, PathBuf};
-use cavvy::Compiler;
+use cavvy::{Compiler, CompilerOptions, RuntimeMode, SymbolFormat};
 use cavvy::error::{print_error_with_context, cayError};
 
 /// 根据平台获取 llvm-minimal 下的 clang 路径
@@ -83,6 +83,20 @@ struct CompileOptions {
     funroll_loops: bool,          // -funroll-loops
     fvectorize: bool,             // -fvectorize
     fslp_vectorize: bool,         // -fslp-vectorize
+    // 诊断
+    werror: bool,                 // --werror
+    dump_ir_stats: bool,          // --dump-ir-stats
+    dump_symbols: Option<SymbolFormat>,          // --dump-symbols[=json]
+    no_runtime: bool,             // --no-runtime
+    implicit_string_concat: bool, // --implicit-string-concat
+    warn_shadow: bool,            // --warn-shadow
+    warn_infinite_loop: bool,     // --warn-infinite-loop
+    warn_float_precision: bool,   // --warn-float-precision
+    immutable_final_arrays: bool, // --immutable-final-arrays
+    bounds_check: bool,           // --bounds-check
+    target_version: Option<String>, // --target-version
+    max_errors: usize,            // --max-errors
+    inline_threshold: Option<usize>,  // --inline-small
 }
 
 /// 根据当前操作系统自动选择默认目标平台
@@ -139,6 +153,19 @@ impl Default for CompileOptions {
             funroll_loops: false,
             fvectorize: false,
             fslp_vectorize: false,
+            werror: false,
+            dump_ir_stats: false,
+            dump_symbols: None,
+            no_runtime: false,
+            implicit_string_concat: false,
+            warn_shadow: false,
+            warn_infinite_loop: false,
+            warn_float_precision: false,
+            immutable_final_arrays: false,
+            bounds_check: false,
+            target_version: None,
+            max_errors: 100,
+            inline_threshold: None,
         }
     }
 }
@@ -181,6 +208,19 @@ fn print_usage() {
     println!("  -fno-rtti             禁用运行时类型信息");
     println!("");
     println!("Other Options:");
+    println!("  --werror              将所有警告视为错误");
+    println!("  --dump-ir-stats       输出每个函数的 IR 指令统计");
+    println!("  --dump-symbols[=json] 语义分析后输出所有类的字段和方法");
+    println!("  --no-runtime          不输出运行时函数，程序需要运行时时报错");
+    println!("  --implicit-string-concat  允许字符串与基本类型用 + 拼接（隐式转换为字符串）");
+    println!("  --warn-shadow         局部变量遮蔽方法参数时发出警告");
+    println!("  --warn-infinite-loop  循环条件恒为真且循环体中没有 break/return 时发出警告");
+    println!("  --warn-float-precision  double 字面量赋给 float 并丢失精度时发出警告");
+    println!("  --immutable-final-arrays  final 数组的元素也不可修改，对其元素赋值时报错");
+    println!("  --bounds-check        数组访问时检查下标范围，越界时终止程序");
+    println!("  --target-version <v>  目标 Cavvy 版本，使用更高版本的方法时发出警告");
+    println!("  --max-errors <n>      语义错误达到 n 个后停止分析（默认 100，0 表示不限制）");
+    println!("  --inline-small <n>    内联只有一个调用点、不超过 n 条指令的静态方法");
     println!("  --version, -v         显示版本号");
     println!("  --help, -h            显示帮助信息");
     println!("");
@@ -258,6 +298,62 @@ fn parse_args(args: &[String]) -> Result<(CompileOptions, String, String), Strin
             "--lto" => {
                 options.lto = true;
             }
+            "--werror" => {
+                options.werror = true;
+            }
+            "--dump-ir-stats" => {
+                options.dump_ir_stats = true;
+            }
+            "--dump-symbols" => {
+                options.dump_symbols = Some(SymbolFormat::Text);
+            }
+            "--dump-symbols=json" => {
+                options.dump_symbols = Some(SymbolFormat::Json);
+            }
+            "--no-runtime" => {
+                options.no_runtime = true;
+            }
+            "--implicit-string-concat" => {
+                options.implicit_string_concat = true;
+            }
+            "--warn-shadow" => {
+                options.warn_shadow = true;
+            }
+            "--warn-infinite-loop" => {
+                options.warn_infinite_loop = true;
+            }
+            "--warn-float-precision" => {
+                options.warn_float_precision = true;
+            }
+            "--immutable-final-arrays" => {
+                options.immutable_final_arrays = true;
+            }
+            "--bounds-check" => {
+                options.bounds_check = true;
+            }
+            "--target-version" => {
+                i += 1;
+                if i >= args.len() {
+                    return Err("--target-version 需要参数".to_string());
+                }
+                options.target_version = Some(args[i].clone());
+            }
+            "--max-errors" => {
+                i += 1;
+                if i >= args.len() {
+                    return Err("--max-errors 需要参数".to_string());
+                }
+                options.max_errors = args[i].parse()
+                    .map_err(|_| format!("--max-errors 需要非负整数，得到 '{}'", args[i]))?;
+            }
+            "--inline-small" => {
+                i += 1;
+                if i >= args.len() {
+                    return Err("--inline-small 需要参数".to_string());
+                }
+                options.inline_threshold = Some(args[i].parse()
+                    .map_err(|_| format!("--inline-small 需要非负整数，得到 '{}'", args[i]))?);
+            }
             "--target" => {
                 i += 1;
                 if i >= args.len() {
@@ -491,7 +587,24 @@ fn main() {
         }
     };
 
-    let compiler = Compiler::new();
+    let compiler_options = CompilerOptions {
+        warnings_as_errors: options.werror,
+        dump_ir_stats: options.dump_ir_stats,
+        dump_symbols: options.dump_symbols,
+        target_version: options.target_version,
+        emit_runtime: if options.no_runtime { RuntimeMode::Never } else { RuntimeMode::OnDemand },
+        implicit_string_concat: options.implicit_string_concat,
+        warn_shadowed_params: options.warn_shadow,
+        warn_infinite_loops: options.warn_infinite_loop,
+        warn_float_precision: options.warn_float_precision,
+        immutable_final_arrays: options.immutable_final_arrays,
+        bounds_check: options.bounds_check,
+        max_errors: options.max_errors,
+        inline_threshold: options.inline_threshold,
+        optimization: options.opt_ir.then(|| options.optimization.clone()),
+        ..CompilerOptions::default()
+    };
+    let compiler = Compiler::with_options(compiler_options);
     match compiler.compile_file(&source_path, &ir_file) {
         Ok(_) => {
             println!("  [+] Cavvy 编译成功");
@@ -503,10 +616,10 @@ fn main() {
     }
 
     // 2. IR 优化 (如果启用)
-    if options.opt_ir {
+    if let Some(level) = &compiler.options().optimization {
         println!("");
-        println!("[2] IR 优化 ({})...", options.optimization);
-        match optimize_ir(&ir_file, &options.optimization) {
+        println!("[2] IR 优化 ({})...", level);
+        match optimize_ir(&ir_file, level) {
             Ok(_) => {
                 println!("  [+] IR 优化完成");
             }
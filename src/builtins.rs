@@ -0,0 +1,92 @@
+//! 宿主程序注册的自定义内置函数
+//!
+//! 把 Cavvy 嵌入到其他工具时，可以用 [`CompilerBuilder::builtin`](crate::CompilerBuilder::builtin)
+//! 注入自己的内置函数：语义分析按声明的参数类型检查调用并推断返回类型，
+//! 代码生成先把实参转换为参数类型，再交给注册的闭包输出调用的 IR。
+//! 与其他内置函数一样，当前类中同名的方法优先于自定义内置函数。
+//!
+//! ```
+//! use cavvy::prelude::*;
+//! use cavvy::builtins::BuiltinSpec;
+//!
+//! let hypot = BuiltinSpec::new("hypot", vec![Type::Float64, Type::Float64], Type::Float64, |ir_gen, args| {
+//!     let result = ir_gen.new_temp();
+//!     ir_gen.emit_line(&format!("  {} = call double @hypot({}, {})", result, args[0], args[1]));
+//!     Ok(format!("double {}", result))
+//! })
+//! .declare("declare double @hypot(double, double)");
+//!
+//! let compiler = Compiler::builder().builtin(hypot).build();
+//! let ir = compiler.compile_to_ir(r#"
+//! public class Main {
+//!     public static void main() {
+//!         double d = hypot(3, 4.0);
+//!         println(d);
+//!     }
+//! }
+//! "#)?;
+//! assert!(ir.contains("call double @hypot(double"));
+//! # Ok::<(), cayError>(())
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::codegen::IRGenerator;
+use crate::error::cayResult;
+use crate::types::Type;
+
+/// 输出内置函数调用的闭包
+///
+/// 参数是已转换为声明参数类型的实参（`"类型 值"` 形式，如 `double %t3`），
+/// 返回调用结果（同样是 `"类型 值"` 形式，void 函数返回 `void %dummy`）。
+pub type BuiltinEmitter = dyn Fn(&mut IRGenerator, &[String]) -> cayResult<String> + Send + Sync;
+
+/// 自定义内置函数的签名和代码生成方式
+#[derive(Clone)]
+pub struct BuiltinSpec {
+    pub name: String,
+    pub params: Vec<Type>,
+    pub return_type: Type,
+    /// 模块级声明（如 `declare double @hypot(double, double)`），函数被调用时输出一次
+    pub declarations: Vec<String>,
+    pub emit: Arc<BuiltinEmitter>,
+}
+
+impl BuiltinSpec {
+    pub fn new(
+        name: impl Into<String>,
+        params: Vec<Type>,
+        return_type: Type,
+        emit: impl Fn(&mut IRGenerator, &[String]) -> cayResult<String> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            params,
+            return_type,
+            declarations: Vec::new(),
+            emit: Arc::new(emit),
+        }
+    }
+
+    /// 添加一条模块级声明
+    pub fn declare(mut self, declaration: impl Into<String>) -> Self {
+        self.declarations.push(declaration.into());
+        self
+    }
+}
+
+impl fmt::Debug for BuiltinSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BuiltinSpec")
+            .field("name", &self.name)
+            .field("params", &self.params)
+            .field("return_type", &self.return_type)
+            .field("declarations", &self.declarations)
+            .finish_non_exhaustive()
+    }
+}
+
+/// 按函数名索引的自定义内置函数
+pub type BuiltinRegistry = HashMap<String, BuiltinSpec>;
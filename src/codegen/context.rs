@@ -1,6 +1,6 @@
 //! IR生成上下文和状态管理
-use std::collections::HashMap;
-use crate::types::TypeRegistry;
+use std::collections::{HashMap, HashSet};
+use crate::types::{Type, TypeRegistry};
 use crate::codegen::platform::PlatformConfig;
 
 /// 循环上下文，用于支持 break/continue
@@ -10,6 +10,13 @@ pub struct LoopContext {
     pub end_label: String,   // break 跳转的目标（循环结束）
 }
 
+/// 带标签语句块的上下文，用于支持 break label
+#[derive(Debug, Clone)]
+pub struct LabelContext {
+    pub label: String,      // 源码中的标签名
+    pub end_label: String,  // break label 跳转的目标（语句块结束）
+}
+
 /// 静态字段信息
 #[derive(Debug, Clone)]
 pub struct StaticFieldInfo {
@@ -53,12 +60,15 @@ pub struct VarScope {
     pub name: String,           // 原始变量名
     pub llvm_name: String,      // LLVM 中的唯一名称（带作用域后缀）
     pub var_type: String,       // 变量类型
+    pub const_array_len: Option<usize>,  // 由字面量初始化且从不重新赋值的数组长度
+    pub cay_type: Option<Type>,  // 声明的 Cavvy 类型（LLVM 类型无法区分 char/int8、String/对象引用）
 }
 
 /// 作用域栈管理
 pub struct ScopeManager {
     scopes: Vec<HashMap<String, VarScope>>,  // 作用域栈
     scope_counter: usize,                     // 作用域计数器（用于生成唯一名称）
+    used_names: HashSet<String>,              // 当前函数内已分配的 LLVM 名称
 }
 
 impl ScopeManager {
@@ -66,6 +76,7 @@ impl ScopeManager {
         Self {
             scopes: vec![HashMap::new()],  // 全局作用域
             scope_counter: 0,
+            used_names: HashSet::new(),
         }
     }
 
@@ -84,7 +95,7 @@ impl ScopeManager {
 
     /// 声明变量（在当前作用域）
     pub fn declare_var(&mut self, name: &str, var_type: &str) -> String {
-        let llvm_name = if self.scopes.len() == 1 {
+        let base_name = if self.scopes.len() == 1 {
             // 全局作用域，使用原始名称
             name.to_string()
         } else {
@@ -92,10 +103,22 @@ impl ScopeManager {
             format!("{}_s{}", name, self.scope_counter)
         };
 
+        // 同名变量可能在同一函数的兄弟作用域中重复声明（如连续的 for 循环），
+        // 名称已被占用时追加序号，避免 LLVM 中重复定义
+        let mut llvm_name = base_name.clone();
+        let mut suffix = 1;
+        while self.used_names.contains(&llvm_name) {
+            llvm_name = format!("{}_{}", base_name, suffix);
+            suffix += 1;
+        }
+        self.used_names.insert(llvm_name.clone());
+
         let var_scope = VarScope {
             name: name.to_string(),
             llvm_name: llvm_name.clone(),
             var_type: var_type.to_string(),
+            const_array_len: None,
+            cay_type: None,
         };
 
         if let Some(scope) = self.scopes.last_mut() {
@@ -115,6 +138,30 @@ impl ScopeManager {
         None
     }
 
+    /// 记录数组变量在编译期已知的长度（作用于当前作用域中最近声明的同名变量）
+    pub fn set_const_array_len(&mut self, name: &str, len: usize) {
+        if let Some(var) = self.scopes.iter_mut().rev().find_map(|s| s.get_mut(name)) {
+            var.const_array_len = Some(len);
+        }
+    }
+
+    /// 记录变量声明的 Cavvy 类型（作用于当前作用域中最近声明的同名变量）
+    pub fn set_cay_type(&mut self, name: &str, ty: &Type) {
+        if let Some(var) = self.scopes.iter_mut().rev().find_map(|s| s.get_mut(name)) {
+            var.cay_type = Some(ty.clone());
+        }
+    }
+
+    /// 获取变量声明的 Cavvy 类型
+    pub fn get_cay_type(&self, name: &str) -> Option<&Type> {
+        self.lookup_var(name)?.cay_type.as_ref()
+    }
+
+    /// 获取数组变量在编译期已知的长度
+    pub fn get_const_array_len(&self, name: &str) -> Option<usize> {
+        self.lookup_var(name)?.const_array_len
+    }
+
     /// 获取变量类型
     pub fn get_var_type(&self, name: &str) -> Option<String> {
         self.lookup_var(name).map(|v| v.var_type.clone())
@@ -135,6 +182,7 @@ impl ScopeManager {
         self.scopes.clear();
         self.scopes.push(HashMap::new());
         self.scope_counter = 0;
+        self.used_names.clear();
     }
 }
 
@@ -160,7 +208,11 @@ pub struct IRGenerator {
     pub current_return_type: String,
     pub var_types: HashMap<String, String>,
     pub var_class_map: HashMap<String, String>,
+    pub array_class_map: HashMap<String, String>,  // 对象数组变量名 -> 元素类名
+    pub list_element_map: HashMap<String, Type>,  // List 变量名或 "类名.字段名" -> 元素类型
+    pub ssa_vars: HashMap<String, String>,  // 保存在 SSA 寄存器中的变量: LLVM 名称 -> 带类型的值
     pub loop_stack: Vec<LoopContext>,
+    pub label_stack: Vec<LabelContext>,
     pub target_triple: String,
     pub static_fields: Vec<StaticFieldInfo>,
     pub static_field_map: HashMap<String, StaticFieldInfo>,
@@ -173,6 +225,13 @@ pub struct IRGenerator {
     pub type_id_counter: usize,
     pub class_layouts: HashMap<String, ClassLayoutInfo>,  // 类实例布局信息
     pub platform_config: Option<PlatformConfig>, 
+    pub runtime_mode: crate::RuntimeMode,
+    pub bounds_check: bool,  // 数组访问时检查下标范围（--bounds-check）
+    pub library_mode: bool,  // 编译为库模块，不生成 C 入口（--library）
+    pub used_runtime_functions: HashSet<String>,  // 已生成调用的运行时函数（如 __cay_string_concat）
+    pub builtins: crate::builtins::BuiltinRegistry,  // 宿主程序注册的自定义内置函数
+    pub used_builtins: Vec<String>,  // 已生成调用的自定义内置函数，按首次调用的顺序
+    pub source_markers: bool,  // 在每条语句前后输出源代码位置标记，用于生成源码映射
 }
 
 impl IRGenerator {
@@ -193,7 +252,11 @@ impl IRGenerator {
             current_return_type: String::new(),
             var_types: HashMap::new(),
             var_class_map: HashMap::new(),
+            array_class_map: HashMap::new(),
+            list_element_map: HashMap::new(),
+            ssa_vars: HashMap::new(),
             loop_stack: Vec::new(),
+            label_stack: Vec::new(),
             target_triple,
             static_fields: Vec::new(),
             static_field_map: HashMap::new(),
@@ -206,6 +269,13 @@ impl IRGenerator {
             type_id_counter: 0,
             class_layouts: HashMap::new(),
             platform_config: None,
+            runtime_mode: crate::RuntimeMode::default(),
+            bounds_check: false,
+            library_mode: false,
+            used_runtime_functions: HashSet::new(),
+            builtins: HashMap::new(),
+            used_builtins: Vec::new(),
+            source_markers: false,
         }
     }
 
@@ -214,6 +284,16 @@ impl IRGenerator {
         self.type_registry = Some(registry);
     }
 
+    /// 设置自定义内置函数
+    pub fn set_builtins(&mut self, builtins: crate::builtins::BuiltinRegistry) {
+        self.builtins = builtins;
+    }
+
+    /// 设置是否输出源代码位置标记（见 [`crate::codegen::source_map`]）
+    pub fn set_source_markers(&mut self, enabled: bool) {
+        self.source_markers = enabled;
+    }
+
     /// 检查是否是 Windows 目标平台
     pub fn is_windows_target(&self) -> bool {
         if let Some(config) = &self.platform_config {
@@ -240,6 +320,19 @@ impl IRGenerator {
         }
         self.code.push_str(line);
         self.code.push('\n');
+        self.record_runtime_calls(line);
+    }
+
+    /// 记录代码行中引用的运行时函数，供按需输出运行时使用
+    fn record_runtime_calls(&mut self, line: &str) {
+        let mut rest = line;
+        while let Some(pos) = rest.find("@__cay_") {
+            let name: String = rest[pos + 1..].chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+                .collect();
+            rest = &rest[pos + 1 + name.len()..];
+            self.used_runtime_functions.insert(name);
+        }
     }
 
     /// 发射代码但不添加缩进（用于全局声明）
@@ -261,6 +354,15 @@ impl IRGenerator {
         }
     }
 
+    /// 开始生成一个新函数：临时变量和标签从 0 重新编号
+    ///
+    /// 编号只需在函数内唯一，每个函数从头编号可以让结构相同的函数生成相同的 IR，
+    /// 修改一个函数也不会改变其后所有函数的寄存器名。
+    pub fn reset_function_counters(&mut self) {
+        self.temp_counter = 0;
+        self.label_counter = 0;
+    }
+
     /// 创建新标签
     pub fn new_label(&mut self, prefix: &str) -> String {
         let label = format!("{}.{}", prefix, self.label_counter);
@@ -290,6 +392,11 @@ impl IRGenerator {
         self.loop_stack.last()
     }
 
+    /// 查找 break label 对应的语句块（由内向外，内层同名标签优先）
+    pub fn find_label(&self, label: &str) -> Option<&LabelContext> {
+        self.label_stack.iter().rev().find(|ctx| ctx.label == label)
+    }
+
     /// 获取或创建字符串常量
     pub fn get_or_create_string_constant(&mut self, s: &str) -> String {
         if let Some(name) = self.global_strings.get(s) {
@@ -363,16 +470,23 @@ impl IRGenerator {
         use crate::types::Type;
         match ty {
             Type::Void => "v".to_string(),
+            Type::Int8 => "i8".to_string(),
+            Type::Int16 => "i16".to_string(),
             Type::Int32 => "i".to_string(),
             Type::Int64 => "l".to_string(),
             Type::Float32 => "f".to_string(),
             Type::Float64 => "d".to_string(),
             Type::Bool => "b".to_string(),
             Type::String => "s".to_string(),
+            Type::StringView => "sv".to_string(),
             Type::Char => "c".to_string(),
             Type::Object(name) => format!("o{}", name),
             Type::Array(inner) => format!("a{}", self.type_to_signature(inner)),
             Type::Function(_) => "fn".to_string(),
+            Type::Tuple(elements) => format!("t{}_{}", elements.len(), elements.iter()
+                .map(|element| self.type_to_signature(element))
+                .collect::<Vec<_>>()
+                .join("_")),
             Type::Auto => panic!("Type::Auto should have been resolved before code generation"),
         }
     }
@@ -456,13 +570,16 @@ impl IRGenerator {
 
     /// 计算类的实例布局
     /// 
-    /// 对象内存布局: [type_id: i32][padding: i32][field1][field2]...
+    /// 对象内存布局: [type_id: i32][padding: i32][父类字段...][field1][field2]...
+    /// 父类字段保持与父类布局相同的偏移，父类方法可以直接作用于子类对象。
     /// 返回对象总大小（字节）
-    pub fn compute_class_layout(&mut self, class_name: &str, fields: &[crate::ast::FieldDecl]) -> usize {
+    pub fn compute_class_layout(&mut self, class_name: &str, parent: Option<&str>, fields: &[crate::ast::FieldDecl]) -> usize {
         // 对象头大小：type_id (4 bytes) + padding (4 bytes) = 8 bytes
         let header_size = 8usize;
-        let mut current_offset = header_size;
-        let mut field_map = HashMap::new();
+        let (mut current_offset, mut field_map) = match parent.and_then(|p| self.class_layouts.get(p)) {
+            Some(parent_layout) => (parent_layout.total_size, parent_layout.fields.clone()),
+            None => (header_size, HashMap::new()),
+        };
 
         for field in fields {
             // 跳过静态字段
@@ -516,6 +633,7 @@ impl IRGenerator {
     pub fn set_platform_config(&mut self, config: &crate::CompilerOptions) {
         let platform_config = PlatformConfig {
             target_os: config.target_os.clone(),
+            target_triple: config.target_triple.clone(),
             features: config.features.clone(),
             no_features: config.no_features.clone(),
             defines: config.defines.clone(),
@@ -523,6 +641,9 @@ impl IRGenerator {
             obfuscate: config.obfuscate,
         };
         self.platform_config = Some(platform_config);
+        self.runtime_mode = config.emit_runtime;
+        self.bounds_check = config.bounds_check;
+        self.library_mode = config.library;
     }
 
     /// 获取平台配置
@@ -547,4 +668,172 @@ impl IRGenerator {
             String::new()
         }
     }
-}
\ No newline at end of file
+
+    /// 生成后的 IR 健全性检查
+    ///
+    /// 逐个函数检查：跳转目标标签是否存在、每个基本块是否以唯一的终结指令结束、
+    /// phi 节点的来源块是否确为前驱块、寄存器是否被重复定义。
+    /// 返回所有发现的问题，消息中包含函数名及相关的标签或寄存器。
+    pub fn validate_ir(ir: &str) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let mut lines = ir.lines();
+
+        while let Some(line) = lines.next() {
+            let header = line.trim();
+            if !(header.starts_with("define ") && header.ends_with('{')) {
+                continue;
+            }
+            let body: Vec<&str> = lines.by_ref()
+                .take_while(|l| l.trim() != "}")
+                .collect();
+            Self::validate_function(header, &body, &mut errors);
+        }
+
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
+
+    /// 检查单个函数体
+    fn validate_function(header: &str, body: &[&str], errors: &mut Vec<String>) {
+        /// 基本块信息
+        struct BasicBlock {
+            name: String,
+            successors: Vec<String>,
+            terminated: bool,
+            phis: Vec<(String, Vec<String>)>,  // (phi 寄存器, 来源块)
+        }
+
+        let func_name = header.find('@')
+            .map(|start| &header[start + 1..])
+            .and_then(|rest| rest.find('(').map(|end| &rest[..end]))
+            .unwrap_or("<unknown>")
+            .to_string();
+
+        // 参数也是寄存器定义
+        let mut defined: HashSet<String> = HashSet::new();
+        if let (Some(open), Some(close)) = (header.find('('), header.rfind(')')) {
+            for param in header[open + 1..close].split(',') {
+                if let Some(name) = param.split_whitespace().last().filter(|n| n.starts_with('%')) {
+                    defined.insert(name.to_string());
+                }
+            }
+        }
+
+        let mut blocks: Vec<BasicBlock> = Vec::new();
+        let mut in_switch = false;
+
+        for raw in body {
+            // 去掉行尾注释
+            let line = raw.split(';').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            // 多行 switch 的分支列表
+            if in_switch {
+                if let Some(block) = blocks.last_mut() {
+                    block.successors.extend(Self::label_operands(line));
+                }
+                if line.starts_with(']') {
+                    in_switch = false;
+                }
+                continue;
+            }
+
+            if line.ends_with(':') && !line.contains(' ') {
+                let name = line.trim_end_matches(':').to_string();
+                if let Some(prev) = blocks.last().filter(|b| !b.terminated) {
+                    errors.push(format!(
+                        "Function '{}': basic block '{}' does not end with a terminator", func_name, prev.name));
+                }
+                if blocks.iter().any(|b| b.name == name) {
+                    errors.push(format!("Function '{}': label '{}' is defined more than once", func_name, name));
+                }
+                blocks.push(BasicBlock { name, successors: Vec::new(), terminated: false, phis: Vec::new() });
+                continue;
+            }
+
+            // 函数体不以标签开头时存在隐式入口块
+            if blocks.is_empty() {
+                blocks.push(BasicBlock { name: "<entry>".to_string(), successors: Vec::new(), terminated: false, phis: Vec::new() });
+            }
+
+            // 寄存器定义
+            let (def, inst) = match line.split_once(" = ") {
+                Some((reg, rest)) if reg.starts_with('%') && !reg.contains(' ') => (Some(reg), rest.trim()),
+                _ => (None, line),
+            };
+            if let Some(reg) = def.filter(|reg| !defined.insert(reg.to_string())) {
+                errors.push(format!("Function '{}': register '{}' is defined more than once", func_name, reg));
+            }
+
+            let block = blocks.last_mut().expect("当前基本块必然存在");
+            if block.terminated {
+                errors.push(format!(
+                    "Function '{}': basic block '{}' has instructions after its terminator", func_name, block.name));
+            }
+
+            let opcode = inst.split_whitespace().next().unwrap_or("");
+            match opcode {
+                "br" | "switch" | "indirectbr" => {
+                    block.successors.extend(Self::label_operands(inst));
+                    block.terminated = true;
+                    in_switch = opcode == "switch" && inst.ends_with('[');
+                }
+                "ret" | "unreachable" | "resume" => block.terminated = true,
+                "phi" => {
+                    let incoming = inst.split('[')
+                        .skip(1)
+                        .filter_map(|pair| pair.split(']').next())
+                        .filter_map(|pair| pair.rsplit(',').next())
+                        .map(|label| label.trim().trim_start_matches('%').to_string())
+                        .collect();
+                    block.phis.push((def.unwrap_or("<unnamed>").to_string(), incoming));
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(last) = blocks.last().filter(|b| !b.terminated) {
+            errors.push(format!(
+                "Function '{}': basic block '{}' does not end with a terminator", func_name, last.name));
+        }
+
+        // 跳转目标必须是本函数内定义的基本块
+        let names: HashSet<&str> = blocks.iter().map(|b| b.name.as_str()).collect();
+        for block in &blocks {
+            for target in &block.successors {
+                if !names.contains(target.as_str()) {
+                    errors.push(format!(
+                        "Function '{}': branch in block '{}' targets undefined label '{}'", func_name, block.name, target));
+                }
+            }
+        }
+
+        // phi 的每个来源块都必须跳转到 phi 所在的块
+        for block in &blocks {
+            for (reg, incoming) in &block.phis {
+                for pred in incoming {
+                    let is_pred = blocks.iter()
+                        .any(|b| &b.name == pred && b.successors.iter().any(|s| s == &block.name));
+                    if !is_pred {
+                        errors.push(format!(
+                            "Function '{}': phi '{}' in block '{}' lists '{}' which is not a predecessor",
+                            func_name, reg, block.name, pred));
+                    }
+                }
+            }
+        }
+    }
+
+    /// 提取指令中所有 `label %name` 操作数
+    fn label_operands(inst: &str) -> Vec<String> {
+        inst.split("label %")
+            .skip(1)
+            .filter_map(|rest| {
+                let end = rest.find([',', ' ', ']']).unwrap_or(rest.len());
+                let name = &rest[..end];
+                (!name.is_empty()).then(|| name.to_string())
+            })
+            .collect()
+    }
+}
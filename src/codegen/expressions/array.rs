@@ -62,6 +62,8 @@ impl IRGenerator {
         
         // 计算元素大小
         let elem_size = match element_type {
+            Type::Int8 => 1,
+            Type::Int16 => 2,
             Type::Int32 => 4,
             Type::Int64 => 8,
             Type::Float32 => 4,
@@ -69,6 +71,7 @@ impl IRGenerator {
             Type::Bool => 1,
             Type::Char => 1,
             Type::String => 8, // 指针大小
+            Type::StringView => 16, // { i8*, i32 }
             Type::Object(_) => 8, // 指针大小
             Type::Array(_) => 8, // 指针大小
             _ => 8, // 默认
@@ -147,16 +150,26 @@ impl IRGenerator {
             format!("{}*", self.get_md_array_type(element_type, sub_sizes.len()))
         };
 
-        // 分配指针数组 (elem_type** 用于存储子数组指针)
+        // 分配指针数组 (elem_type** 用于存储子数组指针)，与一维数组一样前面留 8 字节存放长度
         let ptr_array_bytes = self.new_temp();
         self.emit_line(&format!("  {} = mul i64 {}, 8", ptr_array_bytes, first_size_i64));
+        let total_bytes = self.new_temp();
+        self.emit_line(&format!("  {} = add i64 {}, 8", total_bytes, ptr_array_bytes));
 
         let calloc_ptr_array = self.new_temp();
-        self.emit_line(&format!("  {} = call i8* @calloc(i64 1, i64 {})", calloc_ptr_array, ptr_array_bytes));
+        self.emit_line(&format!("  {} = call i8* @calloc(i64 1, i64 {})", calloc_ptr_array, total_bytes));
 
-        // 转换为正确的指针类型
+        let len_ptr = self.new_temp();
+        self.emit_line(&format!("  {} = bitcast i8* {} to i32*", len_ptr, calloc_ptr_array));
+        let first_size_i32 = self.new_temp();
+        self.emit_line(&format!("  {} = trunc i64 {} to i32", first_size_i32, first_size_i64));
+        self.emit_line(&format!("  store i32 {}, i32* {}, align 4", first_size_i32, len_ptr));
+
+        // 跳过长度头并转换为正确的指针类型
+        let data_ptr = self.new_temp();
+        self.emit_line(&format!("  {} = getelementptr i8, i8* {}, i64 8", data_ptr, calloc_ptr_array));
         let ptr_array = self.new_temp();
-        self.emit_line(&format!("  {} = bitcast i8* {} to {}*", ptr_array, calloc_ptr_array, sub_array_llvm_type));
+        self.emit_line(&format!("  {} = bitcast i8* {} to {}*", ptr_array, data_ptr, sub_array_llvm_type));
 
         // 生成循环来分配每个子数组
         let loop_label = self.new_label("md_loop");
@@ -265,6 +278,10 @@ impl IRGenerator {
             "i64".to_string()
         };
 
+        if self.bounds_check {
+            self.generate_bounds_check(&array_type, &array_val, &index_i64);
+        }
+
         // 计算元素地址
         let elem_ptr_temp = self.new_temp();
         self.emit_line(&format!("  {} = getelementptr {}, {}* {}, i64 {}",
@@ -272,6 +289,161 @@ impl IRGenerator {
 
         Ok((elem_type, elem_ptr_temp, index_i64))
     }
+
+    /// 生成数组 clone() 代码
+    ///
+    /// 连同长度头一起复制整块内存，返回指向新数组数据的指针。
+    /// 元素按值复制，多维数组和对象数组只复制外层的指针（浅拷贝）。
+    pub fn generate_array_clone(&mut self, array: &Expr) -> cayResult<String> {
+        let array_expr = self.generate_expression(array)?;
+        let (array_type, array_val) = self.parse_typed_value(&array_expr);
+        let elem_type = match array_type.strip_suffix('*') {
+            Some(elem_type) => elem_type.to_string(),
+            None => return Err(codegen_error(format!("clone() requires an array, got {}", array_type))),
+        };
+
+        // 读取长度头
+        let src_block = self.array_header_ptr(&array_type, &array_val);
+        let len_i32 = self.load_array_length(&array_type, &array_val);
+        let len_i64 = self.new_temp();
+        self.emit_line(&format!("  {} = sext i32 {} to i64", len_i64, len_i32));
+
+        // 元素大小用 getelementptr null, 1 计算，适用于任意元素类型
+        let size_ptr = self.new_temp();
+        self.emit_line(&format!("  {} = getelementptr {}, {}* null, i64 1", size_ptr, elem_type, elem_type));
+        let elem_size = self.new_temp();
+        self.emit_line(&format!("  {} = ptrtoint {}* {} to i64", elem_size, elem_type, size_ptr));
+        let data_bytes = self.new_temp();
+        self.emit_line(&format!("  {} = mul i64 {}, {}", data_bytes, len_i64, elem_size));
+        let total_bytes = self.new_temp();
+        self.emit_line(&format!("  {} = add i64 {}, 8", total_bytes, data_bytes));
+
+        let dst_block = self.new_temp();
+        self.emit_line(&format!("  {} = call i8* @calloc(i64 1, i64 {})", dst_block, total_bytes));
+        self.emit_line(&format!("  call void @llvm.memcpy.p0i8.p0i8.i64(i8* {}, i8* {}, i64 {}, i1 false)",
+            dst_block, src_block, total_bytes));
+
+        let dst_data = self.new_temp();
+        self.emit_line(&format!("  {} = getelementptr i8, i8* {}, i64 8", dst_data, dst_block));
+        let result = self.new_temp();
+        self.emit_line(&format!("  {} = bitcast i8* {} to {}", result, dst_data, array_type));
+        Ok(format!("{} {}", array_type, result))
+    }
+
+    /// 生成 arrayEquals(a, b) 调用代码
+    ///
+    /// 先比较长度，再逐个比较元素，遇到不同的元素立即返回 false。
+    /// 浮点元素用 fcmp oeq 比较，与 == 的语义一致。
+    pub fn generate_array_equals_call(&mut self, args: &[Expr]) -> cayResult<String> {
+        if args.len() != 2 {
+            return Err(codegen_error(format!("arrayEquals() takes 2 arguments (a, b), got {}", args.len())));
+        }
+        let left = self.generate_expression(&args[0])?;
+        let (left_type, left_val) = self.parse_typed_value(&left);
+        let right = self.generate_expression(&args[1])?;
+        let (right_type, right_val) = self.parse_typed_value(&right);
+        let elem_type = match left_type.strip_suffix('*') {
+            Some(elem_type) if left_type == right_type => elem_type.to_string(),
+            _ => return Err(codegen_error(format!("arrayEquals() requires two arrays of the same type, got {} and {}", left_type, right_type))),
+        };
+
+        let check_label = self.new_label("arreq.check");
+        let loop_label = self.new_label("arreq.loop");
+        let body_label = self.new_label("arreq.body");
+        let end_label = self.new_label("arreq.end");
+
+        self.emit_line(&format!("  br label %{}", check_label));
+        self.emit_line(&format!("\n{}:", check_label));
+        let left_len = self.load_array_length(&left_type, &left_val);
+        let right_len = self.load_array_length(&right_type, &right_val);
+        let same_len = self.new_temp();
+        self.emit_line(&format!("  {} = icmp eq i32 {}, {}", same_len, left_len, right_len));
+        let len_i64 = self.new_temp();
+        self.emit_line(&format!("  {} = sext i32 {} to i64", len_i64, left_len));
+        self.emit_line(&format!("  br i1 {}, label %{}, label %{}", same_len, loop_label, end_label));
+
+        // 循环：i 从 0 到 length
+        self.emit_line(&format!("\n{}:", loop_label));
+        let index = self.new_temp();
+        let next = self.new_temp();
+        self.emit_line(&format!("  {} = phi i64 [ 0, %{} ], [ {}, %{} ]", index, check_label, next, body_label));
+        let done = self.new_temp();
+        self.emit_line(&format!("  {} = icmp sge i64 {}, {}", done, index, len_i64));
+        self.emit_line(&format!("  br i1 {}, label %{}, label %{}", done, end_label, body_label));
+
+        self.emit_line(&format!("\n{}:", body_label));
+        let load_elem = |this: &mut Self, array_val: &str| {
+            let ptr = this.new_temp();
+            this.emit_line(&format!("  {} = getelementptr {}, {}* {}, i64 {}", ptr, elem_type, elem_type, array_val, index));
+            let val = this.new_temp();
+            this.emit_line(&format!("  {} = load {}, {}* {}, align {}", val, elem_type, elem_type, ptr, this.get_type_align(&elem_type)));
+            val
+        };
+        let left_elem = load_elem(self, &left_val);
+        let right_elem = load_elem(self, &right_val);
+        let elem_eq = self.new_temp();
+        let cmp = if elem_type == "float" || elem_type == "double" { "fcmp oeq" } else { "icmp eq" };
+        self.emit_line(&format!("  {} = {} {} {}, {}", elem_eq, cmp, elem_type, left_elem, right_elem));
+        self.emit_line(&format!("  {} = add i64 {}, 1", next, index));
+        self.emit_line(&format!("  br i1 {}, label %{}, label %{}", elem_eq, loop_label, end_label));
+
+        // 长度不同或元素不同时为 false，循环走完为 true
+        self.emit_line(&format!("\n{}:", end_label));
+        let result = self.new_temp();
+        self.emit_line(&format!("  {} = phi i1 [ false, %{} ], [ true, %{} ], [ false, %{} ]",
+            result, check_label, loop_label, body_label));
+        Ok(format!("i1 {}", result))
+    }
+
+    /// 数组长度头的地址（i8*）
+    ///
+    /// 数组指针指向数据，长度（i32）存放在数据前 8 字节中，长度头也是整块内存的起始地址。
+    fn array_header_ptr(&mut self, array_type: &str, array_val: &str) -> String {
+        let array_i8 = self.new_temp();
+        self.emit_line(&format!("  {} = bitcast {} {} to i8*", array_i8, array_type, array_val));
+        let header = self.new_temp();
+        self.emit_line(&format!("  {} = getelementptr i8, i8* {}, i64 -8", header, array_i8));
+        header
+    }
+
+    /// 读取数组长度头中保存的长度（i32）
+    pub fn load_array_length(&mut self, array_type: &str, array_val: &str) -> String {
+        let header = self.array_header_ptr(array_type, array_val);
+        let len_ptr = self.new_temp();
+        self.emit_line(&format!("  {} = bitcast i8* {} to i32*", len_ptr, header));
+        let len = self.new_temp();
+        self.emit_line(&format!("  {} = load i32, i32* {}, align 4", len, len_ptr));
+        len
+    }
+
+    /// 生成数组下标范围检查代码
+    ///
+    /// 长度存放在数组数据前 8 字节中。下标按无符号数与长度比较，
+    /// 负数下标会变成很大的无符号数，因此一次比较同时覆盖 < 0 和 >= length 两种情况。
+    fn generate_bounds_check(&mut self, array_type: &str, array_val: &str, index_i64: &str) {
+        let len_i32 = self.load_array_length(array_type, array_val);
+        let len_i64 = self.new_temp();
+        self.emit_line(&format!("  {} = sext i32 {} to i64", len_i64, len_i32));
+
+        let in_bounds = self.new_temp();
+        self.emit_line(&format!("  {} = icmp ult i64 {}, {}", in_bounds, index_i64, len_i64));
+        let error_label = self.new_label("bounds.error");
+        let ok_label = self.new_label("bounds.ok");
+        self.emit_line(&format!("  br i1 {}, label %{}, label %{}", in_bounds, ok_label, error_label));
+
+        self.emit_line(&format!("\n{}:", error_label));
+        let fmt = format!("Error: Array index {} out of bounds for length %d\n", self.get_i64_format_specifier());
+        let fmt_name = self.get_or_create_string_constant(&fmt);
+        let fmt_len = fmt.len() + 1;
+        let fmt_ptr = self.new_temp();
+        self.emit_line(&format!("  {} = getelementptr [{} x i8], [{} x i8]* {}, i64 0, i64 0",
+            fmt_ptr, fmt_len, fmt_len, fmt_name));
+        self.emit_line(&format!("  call i32 (i8*, ...) @printf(i8* {}, i64 {}, i32 {})", fmt_ptr, index_i64, len_i32));
+        self.emit_line("  call void @exit(i32 1)");
+        self.emit_line("  unreachable");
+
+        self.emit_line(&format!("\n{}:", ok_label));
+    }
     
     /// 生成数组访问表达式代码: arr[index]
     ///
@@ -421,8 +593,9 @@ impl IRGenerator {
                 let temp = self.new_temp();
                 // 整数到浮点数转换
                 if elem_value_type.starts_with("i") && (elem_llvm_type == "float" || elem_llvm_type == "double") {
-                    self.emit_line(&format!("  {} = sitofp {} {} to {}",
-                        temp, elem_value_type, val, elem_llvm_type));
+                    let conv = self.int_to_float(&elem_value_type, elem);
+                    self.emit_line(&format!("  {} = {} {} {} to {}",
+                        temp, conv, elem_value_type, val, elem_llvm_type));
                 }
                 // 浮点数到整数转换
                 else if (elem_value_type == "float" || elem_value_type == "double") && elem_llvm_type.starts_with("i") {
@@ -436,13 +609,20 @@ impl IRGenerator {
                 else if elem_value_type == "float" && elem_llvm_type == "double" {
                     self.emit_line(&format!("  {} = fpext float {} to double", temp, val));
                 }
+                // 指针元素（对象、字符串、子数组）：指针之间 bitcast，null（整数 0）用 inttoptr
+                else if elem_llvm_type.ends_with('*') {
+                    let cast_op = if elem_value_type.ends_with('*') { "bitcast" } else { "inttoptr" };
+                    self.emit_line(&format!("  {} = {} {} {} to {}",
+                        temp, cast_op, elem_value_type, val, elem_llvm_type));
+                }
                 // 整数类型转换
                 else if elem_value_type.starts_with("i") && elem_llvm_type.starts_with("i") {
                     let from_bits: u32 = elem_value_type.trim_start_matches('i').parse().unwrap_or(64);
                     let to_bits: u32 = elem_llvm_type.trim_start_matches('i').parse().unwrap_or(64);
                     if to_bits > from_bits {
-                        self.emit_line(&format!("  {} = sext {} {} to {}",
-                            temp, elem_value_type, val, elem_llvm_type));
+                        let ext = self.int_extension(&elem_value_type, elem);
+                        self.emit_line(&format!("  {} = {} {} {} to {}",
+                            temp, ext, elem_value_type, val, elem_llvm_type));
                     } else {
                         self.emit_line(&format!("  {} = trunc {} {} to {}",
                             temp, elem_value_type, val, elem_llvm_type));
@@ -5,6 +5,7 @@
 use crate::codegen::context::IRGenerator;
 use crate::ast::*;
 use crate::error::{cayResult, codegen_error};
+use crate::types::LIST_CLASS;
 
 impl IRGenerator {
     /// 生成赋值表达式代码
@@ -14,23 +15,34 @@ impl IRGenerator {
     pub fn generate_assignment(&mut self, assign: &AssignmentExpr) -> cayResult<String> {
         let value = self.generate_expression(&assign.value)?;
         let (value_type, val) = self.parse_typed_value(&value);
+        let list_slot = self.list_slot(&assign.target)
+            .filter(|_| self.resolve_object_class(&assign.value).as_deref() == Some(LIST_CLASS));
+        if let Some(slot) = list_slot {
+            self.track_list_assignment(slot, &assign.value);
+        }
         
         match assign.target.as_ref() {
             Expr::MemberAccess(member) => {
-                self.generate_member_assignment(member, &value_type, &val, &value)
+                self.generate_member_assignment(member, &value_type, &val, &value, &assign.value)
             }
             Expr::Identifier(name) => {
-                self.generate_variable_assignment(name, &value_type, &val, &value)
+                // 变量尚未记录类名时，按所赋对象的类记录，以便后续方法调用解析
+                let untracked = !self.var_class_map.contains_key(name)
+                    && self.var_types.get(name).is_some_and(|t| t == "i8*");
+                if let Some(class_name) = self.resolve_object_class(&assign.value).filter(|_| untracked) {
+                    self.var_class_map.insert(name.clone(), class_name);
+                }
+                self.generate_variable_assignment(name, &value_type, &val, &value, &assign.value)
             }
             Expr::ArrayAccess(arr_access) => {
-                self.generate_array_assignment(arr_access, &value_type, &val, &value)
+                self.generate_array_assignment(arr_access, &value_type, &val, &value, &assign.value)
             }
             _ => Err(codegen_error("Invalid assignment target".to_string()))
         }
     }
 
     /// 生成成员赋值（静态字段或实例字段赋值）
-    fn generate_member_assignment(&mut self, member: &MemberAccessExpr, value_type: &str, val: &str, value: &str) -> cayResult<String> {
+    fn generate_member_assignment(&mut self, member: &MemberAccessExpr, value_type: &str, val: &str, value: &str, source: &Expr) -> cayResult<String> {
         // 检查是否是静态字段赋值: ClassName.fieldName = value
         if let Expr::Identifier(class_name) = &*member.object {
             let static_key = format!("{}.{}", class_name, member.member);
@@ -40,22 +52,10 @@ impl IRGenerator {
                 
                 // 如果值类型与字段类型不匹配，需要转换
                 if value_type != field_info.llvm_type {
-                    let temp = self.new_temp();
-                    // 类型转换逻辑（简化版）
-                    if value_type.starts_with("i") && field_info.llvm_type.starts_with("i") {
-                        let from_bits: u32 = value_type.trim_start_matches('i').parse().unwrap_or(64);
-                        let to_bits: u32 = field_info.llvm_type.trim_start_matches('i').parse().unwrap_or(64);
-                        if to_bits > from_bits {
-                            self.emit_line(&format!("  {} = sext {} {} to {}",
-                                temp, value_type, val, field_info.llvm_type));
-                        } else {
-                            self.emit_line(&format!("  {} = trunc {} {} to {}",
-                                temp, value_type, val, field_info.llvm_type));
-                        }
-                        self.emit_line(&format!("  store {} {}, {}* {}, align {}", 
-                            field_info.llvm_type, temp, field_info.llvm_type, field_info.name, align));
-                        return Ok(format!("{} {}", field_info.llvm_type, temp));
-                    }
+                    let converted = self.convert_field_value(value_type, val, &field_info.llvm_type, source);
+                    self.emit_line(&format!("  store {} {}, {}* {}, align {}", 
+                        field_info.llvm_type, converted, field_info.llvm_type, field_info.name, align));
+                    return Ok(format!("{} {}", field_info.llvm_type, converted));
                 }
                 
                 // 类型匹配，直接存储
@@ -67,16 +67,8 @@ impl IRGenerator {
         
         // 处理实例字段赋值: this.fieldName = value 或 obj.fieldName = value
         
-        // 确定对象所属的类
-        let class_name_opt: Option<String> = if let Expr::Identifier(name) = &*member.object {
-            if name == "this" {
-                Some(self.current_class.clone())
-            } else {
-                self.var_class_map.get(name).cloned()
-            }
-        } else {
-            None
-        };
+        // 确定对象所属的类（支持 a.b.c = value 这样的字段链赋值）
+        let class_name_opt = self.resolve_object_class(&member.object);
         
         if let Some(class_name) = class_name_opt {
             if let Some(field_info) = self.get_instance_field(&class_name, &member.member).cloned() {
@@ -118,22 +110,7 @@ impl IRGenerator {
                 
                 // 如果值类型与字段类型不匹配，需要转换
                 let final_val = if value_type != field_info.llvm_type {
-                    let temp = self.new_temp();
-                    if value_type.starts_with("i") && field_info.llvm_type.starts_with("i") {
-                        let from_bits: u32 = value_type.trim_start_matches('i').parse().unwrap_or(64);
-                        let to_bits: u32 = field_info.llvm_type.trim_start_matches('i').parse().unwrap_or(64);
-                        if to_bits > from_bits {
-                            self.emit_line(&format!("  {} = sext {} {} to {}",
-                                temp, value_type, val, field_info.llvm_type));
-                        } else {
-                            self.emit_line(&format!("  {} = trunc {} {} to {}",
-                                temp, value_type, val, field_info.llvm_type));
-                        }
-                    } else {
-                        // 其他类型转换，直接使用原值
-                        self.emit_line(&format!("  {} = {} {}", temp, value_type, val));
-                    }
-                    temp
+                    self.convert_field_value(value_type, val, &field_info.llvm_type, source)
                 } else {
                     val.to_string()
                 };
@@ -148,8 +125,23 @@ impl IRGenerator {
         Err(codegen_error("Invalid member access assignment target".to_string()))
     }
 
+    /// 把赋给字段的值转换为字段类型，返回转换后的值
+    ///
+    /// 更窄的整数字段截断；其余按实参的拓宽规则转换（char 零扩展，整数转浮点数，float/double 互转）。
+    fn convert_field_value(&mut self, value_type: &str, val: &str, field_type: &str, source: &Expr) -> String {
+        let is_int = |t: &str| t.starts_with('i') && !t.ends_with('*');
+        let bits = |t: &str| t.trim_start_matches('i').parse::<u32>().unwrap_or(64);
+        if is_int(value_type) && is_int(field_type) && bits(field_type) < bits(value_type) {
+            let temp = self.new_temp();
+            self.emit_line(&format!("  {} = trunc {} {} to {}", temp, value_type, val, field_type));
+            return temp;
+        }
+        let widened = self.widen_value(&format!("{} {}", value_type, val), Some(source), field_type);
+        self.parse_typed_value(&widened).1
+    }
+
     /// 生成变量赋值
-    fn generate_variable_assignment(&mut self, name: &str, value_type: &str, val: &str, value: &str) -> cayResult<String> {
+    fn generate_variable_assignment(&mut self, name: &str, value_type: &str, val: &str, value: &str, source: &Expr) -> cayResult<String> {
         // 优先使用作用域管理器获取变量类型和 LLVM 名称
         let (var_type, llvm_name) = if let Some(scope_type) = self.scope_manager.get_var_type(name) {
             let llvm_name = self.scope_manager.get_llvm_name(name).unwrap_or_else(|| name.to_string());
@@ -160,10 +152,25 @@ impl IRGenerator {
                 let static_key = format!("{}.{}", self.current_class, name);
                 if let Some(field_info) = self.static_field_map.get(&static_key).cloned() {
                     let align = self.get_type_align(&field_info.llvm_type);
+                    let stored = if value_type != field_info.llvm_type {
+                        self.convert_field_value(value_type, val, &field_info.llvm_type, source)
+                    } else {
+                        val.to_string()
+                    };
                     self.emit_line(&format!("  store {} {}, {}* {}, align {}",
-                        field_info.llvm_type, val, field_info.llvm_type, field_info.name, align));
+                        field_info.llvm_type, stored, field_info.llvm_type, field_info.name, align));
                     return Ok(value.to_string());
                 }
+
+                // 当前类（含继承自父类）的实例字段，按 this.name 赋值
+                if self.get_instance_field(&self.current_class, name).is_some() {
+                    let member = MemberAccessExpr {
+                        object: Box::new(Expr::Identifier("this".to_string())),
+                        member: name.to_string(),
+                        loc: crate::error::SourceLocation { line: 0, column: 0 },
+                    };
+                    return self.generate_member_assignment(&member, value_type, val, value, source);
+                }
             }
             // 回退到旧系统
             let var_type = self.var_types.get(name)
@@ -174,7 +181,7 @@ impl IRGenerator {
 
         // 如果值类型与变量类型不匹配，需要转换
         if value_type != var_type {
-            return self.generate_assignment_with_conversion(&var_type, &llvm_name, value_type, val);
+            return self.generate_assignment_with_conversion(&var_type, &llvm_name, value_type, val, source);
         }
 
         // 类型匹配，直接存储
@@ -184,13 +191,13 @@ impl IRGenerator {
     }
 
     /// 生成数组元素赋值
-    fn generate_array_assignment(&mut self, arr_access: &ArrayAccessExpr, value_type: &str, val: &str, value: &str) -> cayResult<String> {
+    fn generate_array_assignment(&mut self, arr_access: &ArrayAccessExpr, value_type: &str, val: &str, value: &str, source: &Expr) -> cayResult<String> {
         // 获取数组元素指针
         let (elem_type, elem_ptr, _) = self.get_array_element_ptr(arr_access)?;
 
         // 如果值类型与元素类型不匹配，需要转换
         if value_type != elem_type {
-            return self.generate_array_assignment_with_conversion(&elem_type, &elem_ptr, value_type, val, value);
+            return self.generate_array_assignment_with_conversion(&elem_type, &elem_ptr, value_type, val, value, source);
         }
 
         // 类型匹配，直接存储到数组元素
@@ -200,7 +207,7 @@ impl IRGenerator {
     }
 
     /// 生成带类型转换的变量赋值
-    fn generate_assignment_with_conversion(&mut self, var_type: &str, llvm_name: &str, value_type: &str, val: &str) -> cayResult<String> {
+    fn generate_assignment_with_conversion(&mut self, var_type: &str, llvm_name: &str, value_type: &str, val: &str, source: &Expr) -> cayResult<String> {
         let temp = self.new_temp();
 
         // 浮点类型转换
@@ -220,7 +227,8 @@ impl IRGenerator {
         // 整数到浮点数转换
         else if value_type.starts_with("i") && (var_type == "float" || var_type == "double") {
             // 整数 -> 浮点数转换
-            self.emit_line(&format!("  {} = sitofp {} {} to {}", temp, value_type, val, var_type));
+            let conv = self.int_to_float(value_type, source);
+            self.emit_line(&format!("  {} = {} {} {} to {}", temp, conv, value_type, val, var_type));
             let align = self.get_type_align(var_type);
             self.emit_line(&format!("  store {} {}, {}* %{}, align {}", var_type, temp, var_type, llvm_name, align));
             return Ok(format!("{} {}", var_type, temp));
@@ -231,9 +239,10 @@ impl IRGenerator {
             let to_bits: u32 = var_type.trim_start_matches('i').parse().unwrap_or(64);
 
             if to_bits > from_bits {
-                // 符号扩展
-                self.emit_line(&format!("  {} = sext {} {} to {}",
-                    temp, value_type, val, var_type));
+                // 符号扩展（char 零扩展）
+                let ext = self.int_extension(value_type, source);
+                self.emit_line(&format!("  {} = {} {} {} to {}",
+                    temp, ext, value_type, val, var_type));
             } else {
                 // 截断
                 self.emit_line(&format!("  {} = trunc {} {} to {}",
@@ -251,7 +260,7 @@ impl IRGenerator {
     }
 
     /// 生成带类型转换的数组元素赋值
-    fn generate_array_assignment_with_conversion(&mut self, elem_type: &str, elem_ptr: &str, value_type: &str, val: &str, value: &str) -> cayResult<String> {
+    fn generate_array_assignment_with_conversion(&mut self, elem_type: &str, elem_ptr: &str, value_type: &str, val: &str, value: &str, source: &Expr) -> cayResult<String> {
         let temp = self.new_temp();
 
         // 浮点类型转换
@@ -271,7 +280,16 @@ impl IRGenerator {
         // 整数到浮点数转换
         else if value_type.starts_with("i") && (elem_type == "float" || elem_type == "double") {
             // 整数 -> 浮点数转换
-            self.emit_line(&format!("  {} = sitofp {} {} to {}", temp, value_type, val, elem_type));
+            let conv = self.int_to_float(value_type, source);
+            self.emit_line(&format!("  {} = {} {} {} to {}", temp, conv, value_type, val, elem_type));
+            let align = self.get_type_align(elem_type);
+            self.emit_line(&format!("  store {} {}, {}* {}, align {}", elem_type, temp, elem_type, elem_ptr, align));
+            return Ok(format!("{} {}", elem_type, temp));
+        }
+        // 对象/字符串/子数组元素统一按指针存储：指针之间 bitcast，null（整数 0）用 inttoptr
+        else if elem_type.ends_with('*') {
+            let cast_op = if value_type.ends_with('*') { "bitcast" } else { "inttoptr" };
+            self.emit_line(&format!("  {} = {} {} {} to {}", temp, cast_op, value_type, val, elem_type));
             let align = self.get_type_align(elem_type);
             self.emit_line(&format!("  store {} {}, {}* {}, align {}", elem_type, temp, elem_type, elem_ptr, align));
             return Ok(format!("{} {}", elem_type, temp));
@@ -282,9 +300,10 @@ impl IRGenerator {
             let to_bits: u32 = elem_type.trim_start_matches('i').parse().unwrap_or(64);
 
             if to_bits > from_bits {
-                // 符号扩展
-                self.emit_line(&format!("  {} = sext {} {} to {}",
-                    temp, value_type, val, elem_type));
+                // 符号扩展（char 零扩展）
+                let ext = self.int_extension(value_type, source);
+                self.emit_line(&format!("  {} = {} {} {} to {}",
+                    temp, ext, value_type, val, elem_type));
             } else {
                 // 截断
                 self.emit_line(&format!("  {} = trunc {} {} to {}",
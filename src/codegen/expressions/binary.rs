@@ -4,6 +4,7 @@
 
 use crate::codegen::context::IRGenerator;
 use crate::ast::*;
+use crate::types::Type;
 use crate::error::{cayResult, codegen_error};
 
 impl IRGenerator {
@@ -12,23 +13,47 @@ impl IRGenerator {
     /// # Arguments
     /// * `bin` - 二元表达式
     pub fn generate_binary_expression(&mut self, bin: &BinaryExpr) -> cayResult<String> {
+        // 字符串字面量相加在编译期折叠为一个常量，不生成运行时拼接
+        if let Some(folded) = Self::fold_string_literals(bin) {
+            return self.generate_literal(&LiteralValue::String(folded));
+        }
+
         let left = self.generate_expression(&bin.left)?;
         let right = self.generate_expression(&bin.right)?;
         
         // 解析类型和值
         let (left_type, left_val) = self.parse_typed_value(&left);
         let (right_type, right_val) = self.parse_typed_value(&right);
+
+        // char 按无符号值参与算术和比较，先零扩展为 int；与字符串拼接时保持为 char
+        let (left_type, left_val, right_type, right_val) = if left_type == "i8*" || right_type == "i8*" {
+            (left_type, left_val, right_type, right_val)
+        } else {
+            let (left_type, left_val) = self.promote_char_operand(&bin.left, left_type, left_val);
+            let (right_type, right_val) = self.promote_char_operand(&bin.right, right_type, right_val);
+            (left_type, left_val, right_type, right_val)
+        };
         
         let temp = self.new_temp();
-        
+
+        // String 的 == / != 比较内容；对象引用（同为 i8*）和 null 比较仍是引用同一性
+        if matches!(bin.op, BinaryOp::Eq | BinaryOp::Ne)
+            && left_type == "i8*" && right_type == "i8*"
+            && self.is_string_expr(&bin.left) && self.is_string_expr(&bin.right)
+        {
+            return self.generate_string_equality(&left_val, &right_val, &temp, bin.op == BinaryOp::Ne);
+        }
+
         match bin.op {
-            BinaryOp::Add => self.generate_add(&left_type, &left_val, &right_type, &right_val, &temp),
+            BinaryOp::Add => self.generate_add(bin, &left_type, &left_val, &right_type, &right_val, &temp),
             BinaryOp::Sub => self.generate_sub(&left_type, &left_val, &right_type, &right_val, &temp),
             BinaryOp::Mul => self.generate_mul(&left_type, &left_val, &right_type, &right_val, &temp),
             BinaryOp::Div => self.generate_div(&left_type, &left_val, &right_type, &right_val, &temp),
             BinaryOp::Mod => self.generate_mod(&left_type, &left_val, &right_type, &right_val, &temp),
             BinaryOp::Eq => self.generate_eq(&left_type, &left_val, &right_type, &right_val, &temp),
             BinaryOp::Ne => self.generate_ne(&left_type, &left_val, &right_type, &right_val, &temp),
+            BinaryOp::Is => self.generate_identity(&left_type, &left_val, &right_type, &right_val, &temp, "eq"),
+            BinaryOp::IsNot => self.generate_identity(&left_type, &left_val, &right_type, &right_val, &temp, "ne"),
             BinaryOp::Lt => self.generate_lt(&left_type, &left_val, &right_type, &right_val, &temp),
             BinaryOp::Le => self.generate_le(&left_type, &left_val, &right_type, &right_val, &temp),
             BinaryOp::Gt => self.generate_gt(&left_type, &left_val, &right_type, &right_val, &temp),
@@ -44,67 +69,48 @@ impl IRGenerator {
         }
     }
 
+    /// char 操作数零扩展为 i32，其余操作数原样返回
+    fn promote_char_operand(&mut self, source: &Expr, ty: String, val: String) -> (String, String) {
+        if ty != "i8" || self.byte_extension(source) != "zext" {
+            return (ty, val);
+        }
+        let temp = self.new_temp();
+        self.emit_line(&format!("  {} = zext i8 {} to i32", temp, val));
+        ("i32".to_string(), temp)
+    }
+
+    /// 折叠由字符串字面量组成的加法链（如 `"a" + "b" + "c"`）
+    fn fold_string_literals(bin: &BinaryExpr) -> Option<String> {
+        fn fold(expr: &Expr) -> Option<String> {
+            match expr {
+                Expr::Literal(LiteralValue::String(s)) => Some(s.clone()),
+                Expr::Binary(bin) => IRGenerator::fold_string_literals(bin),
+                _ => None,
+            }
+        }
+        if bin.op != BinaryOp::Add {
+            return None;
+        }
+        Some(fold(&bin.left)? + &fold(&bin.right)?)
+    }
+
     /// 生成加法表达式
-    fn generate_add(&mut self, left_type: &str, left_val: &str, right_type: &str, right_val: &str, temp: &str) -> cayResult<String> {
-        // 字符串拼接处理
-        if left_type == "i8*" && right_type == "i8*" {
+    fn generate_add(&mut self, bin: &BinaryExpr, left_type: &str, left_val: &str, right_type: &str, right_val: &str, temp: &str) -> cayResult<String> {
+        // 字符串拼接处理：另一侧为基本类型时先转换为字符串
+        let is_scalar = |t: &str| t == "float" || t == "double" || (t.starts_with('i') && !t.ends_with('*'));
+        if (left_type == "i8*" && (right_type == "i8*" || is_scalar(right_type)))
+            || (is_scalar(left_type) && right_type == "i8*") {
+            let left_str = self.concat_operand_to_string(left_type, left_val, &bin.left);
+            let right_str = self.concat_operand_to_string(right_type, right_val, &bin.right);
             // 调用内建的字符串拼接函数
             self.emit_line(&format!("  {} = call i8* @__cay_string_concat(i8* {}, i8* {})",
-                temp, left_val, right_val));
-            return Ok(format!("i8* {}", temp));
-        } else if left_type == "i8*" && right_type == "i8" {
-            // 字符串 + char：先将char转换为字符串，然后拼接
-            let char_as_string = self.new_temp();
-            self.emit_line(&format!("  {} = call i8* @__cay_char_to_string(i8 {})",
-                char_as_string, right_val));
-            self.emit_line(&format!("  {} = call i8* @__cay_string_concat(i8* {}, i8* {})",
-                temp, left_val, char_as_string));
-            return Ok(format!("i8* {}", temp));
-        } else if left_type == "i8" && right_type == "i8*" {
-            // char + 字符串：先将char转换为字符串，然后拼接
-            let char_as_string = self.new_temp();
-            self.emit_line(&format!("  {} = call i8* @__cay_char_to_string(i8 {})",
-                char_as_string, left_val));
-            self.emit_line(&format!("  {} = call i8* @__cay_string_concat(i8* {}, i8* {})",
-                temp, char_as_string, right_val));
-            return Ok(format!("i8* {}", temp));
-        } else if left_type == "i8*" && right_type.starts_with("i") {
-            // 字符串 + 整数：先将整数转换为字符串，然后拼接
-            let int_as_string = self.new_temp();
-            // 如果是 i32，先扩展到 i64
-            let int_val = if right_type == "i32" {
-                let extended = self.new_temp();
-                self.emit_line(&format!("  {} = sext i32 {} to i64", extended, right_val));
-                extended
-            } else {
-                right_val.to_string()
-            };
-            self.emit_line(&format!("  {} = call i8* @__cay_int_to_string(i64 {})",
-                int_as_string, int_val));
-            self.emit_line(&format!("  {} = call i8* @__cay_string_concat(i8* {}, i8* {})",
-                temp, left_val, int_as_string));
-            return Ok(format!("i8* {}", temp));
-        } else if left_type.starts_with("i") && right_type == "i8*" {
-            // 整数 + 字符串：先将整数转换为字符串，然后拼接
-            let int_as_string = self.new_temp();
-            // 如果是 i32，先扩展到 i64
-            let int_val = if left_type == "i32" {
-                let extended = self.new_temp();
-                self.emit_line(&format!("  {} = sext i32 {} to i64", extended, left_val));
-                extended
-            } else {
-                left_val.to_string()
-            };
-            self.emit_line(&format!("  {} = call i8* @__cay_int_to_string(i64 {})",
-                int_as_string, int_val));
-            self.emit_line(&format!("  {} = call i8* @__cay_string_concat(i8* {}, i8* {})",
-                temp, int_as_string, right_val));
+                temp, left_str, right_str));
             return Ok(format!("i8* {}", temp));
         } else if left_type.starts_with("i") && right_type.starts_with("i") {
             // 整数加法，需要类型提升
             let (promoted_type, promoted_left, promoted_right) = self.promote_integer_operands(left_type, left_val, right_type, right_val);
-            self.emit_line(&format!("  {} = add {} {}, {}",
-                temp, promoted_type, promoted_left, promoted_right));
+            self.emit_line(&format!("  {} = add{} {} {}, {}",
+                temp, Self::signed_overflow_flag(&promoted_type), promoted_type, promoted_left, promoted_right));
             return Ok(format!("{} {}", promoted_type, temp));
         } else if (left_type == "float" || left_type == "double") && (right_type == "float" || right_type == "double") {
             // 浮点数加法，需要类型提升
@@ -141,13 +147,36 @@ impl IRGenerator {
         }
     }
 
+    /// 将字符串拼接的操作数转换为字符串（i8*）
+    ///
+    /// char、bool、整数和浮点数分别调用对应的运行时转换函数；i8 按 `source` 的静态类型区分 char 和 int8。
+    fn concat_operand_to_string(&mut self, ty: &str, val: &str, source: &Expr) -> String {
+        let (func, arg_type, arg) = match ty {
+            "i8*" => return val.to_string(),
+            "i8" if self.expr_static_type(source) != Some(Type::Int8) => ("__cay_char_to_string", "i8", val.to_string()),
+            "i1" => ("__cay_bool_to_string", "i1", val.to_string()),
+            "double" => ("__cay_float_to_string", "double", val.to_string()),
+            "float" => ("__cay_float32_to_string", "float", val.to_string()),
+            "i64" => ("__cay_int_to_string", "i64", val.to_string()),
+            _ => {
+                // 其他整数先符号扩展到 i64
+                let extended = self.new_temp();
+                self.emit_line(&format!("  {} = sext {} {} to i64", extended, ty, val));
+                ("__cay_int_to_string", "i64", extended)
+            }
+        };
+        let result = self.new_temp();
+        self.emit_line(&format!("  {} = call i8* @{}({} {})", result, func, arg_type, arg));
+        result
+    }
+
     /// 生成减法表达式
     fn generate_sub(&mut self, left_type: &str, left_val: &str, right_type: &str, right_val: &str, temp: &str) -> cayResult<String> {
         if left_type.starts_with("i") && right_type.starts_with("i") {
             // 整数减法，需要类型提升
             let (promoted_type, promoted_left, promoted_right) = self.promote_integer_operands(left_type, left_val, right_type, right_val);
-            self.emit_line(&format!("  {} = sub {} {}, {}",
-                temp, promoted_type, promoted_left, promoted_right));
+            self.emit_line(&format!("  {} = sub{} {} {}, {}",
+                temp, Self::signed_overflow_flag(&promoted_type), promoted_type, promoted_left, promoted_right));
             return Ok(format!("{} {}", promoted_type, temp));
         } else if (left_type == "float" || left_type == "double") && (right_type == "float" || right_type == "double") {
             // 浮点数减法，需要类型提升
@@ -170,8 +199,8 @@ impl IRGenerator {
         if left_type.starts_with("i") && right_type.starts_with("i") {
             // 整数乘法，需要类型提升
             let (promoted_type, promoted_left, promoted_right) = self.promote_integer_operands(left_type, left_val, right_type, right_val);
-            self.emit_line(&format!("  {} = mul {} {}, {}",
-                temp, promoted_type, promoted_left, promoted_right));
+            self.emit_line(&format!("  {} = mul{} {} {}, {}",
+                temp, Self::signed_overflow_flag(&promoted_type), promoted_type, promoted_left, promoted_right));
             return Ok(format!("{} {}", promoted_type, temp));
         } else if (left_type == "float" || left_type == "double") && (right_type == "float" || right_type == "double") {
             // 浮点数乘法，需要类型提升
@@ -232,10 +261,9 @@ impl IRGenerator {
 
     /// 生成等于比较表达式
     fn generate_eq(&mut self, left_type: &str, left_val: &str, right_type: &str, right_val: &str, temp: &str) -> cayResult<String> {
-        if left_type == "i8*" && right_type == "i8*" {
-            // 字符串比较
-            self.emit_line(&format!("  {} = icmp eq i8* {}, {}", temp, left_val, right_val));
-            return Ok(format!("i1 {}", temp));
+        if left_type.ends_with('*') || right_type.ends_with('*') {
+            // 引用比较（对象、数组、与 null 比较）
+            return self.generate_identity(left_type, left_val, right_type, right_val, temp, "eq");
         } else if left_type.starts_with("i") && right_type.starts_with("i") {
             let (promoted_type, promoted_left, promoted_right) = self.promote_integer_operands(left_type, left_val, right_type, right_val);
             self.emit_line(&format!("  {} = icmp eq {} {}, {}", temp, promoted_type, promoted_left, promoted_right));
@@ -255,9 +283,8 @@ impl IRGenerator {
 
     /// 生成不等于比较表达式
     fn generate_ne(&mut self, left_type: &str, left_val: &str, right_type: &str, right_val: &str, temp: &str) -> cayResult<String> {
-        if left_type == "i8*" && right_type == "i8*" {
-            self.emit_line(&format!("  {} = icmp ne i8* {}, {}", temp, left_val, right_val));
-            return Ok(format!("i1 {}", temp));
+        if left_type.ends_with('*') || right_type.ends_with('*') {
+            return self.generate_identity(left_type, left_val, right_type, right_val, temp, "ne");
         } else if left_type.starts_with("i") && right_type.starts_with("i") {
             let (promoted_type, promoted_left, promoted_right) = self.promote_integer_operands(left_type, left_val, right_type, right_val);
             self.emit_line(&format!("  {} = icmp ne {} {}, {}", temp, promoted_type, promoted_left, promoted_right));
@@ -275,6 +302,43 @@ impl IRGenerator {
         }
     }
 
+    /// 生成引用同一性比较表达式（=== / !==）
+    ///
+    /// 只比较指针地址，不比较字符串内容或调用 equals()
+    pub fn generate_identity(&mut self, left_type: &str, left_val: &str, right_type: &str, right_val: &str, temp: &str, cond: &str) -> cayResult<String> {
+        let left_ptr = self.cast_to_i8_ptr(left_type, left_val);
+        let right_ptr = self.cast_to_i8_ptr(right_type, right_val);
+        self.emit_line(&format!("  {} = icmp {} i8* {}, {}", temp, cond, left_ptr, right_ptr));
+        Ok(format!("i1 {}", temp))
+    }
+
+    /// 生成字符串内容比较（== / !=），两个 null 相等，null 与非 null 字符串不等
+    fn generate_string_equality(&mut self, left_val: &str, right_val: &str, temp: &str, negate: bool) -> cayResult<String> {
+        if !negate {
+            self.emit_line(&format!("  {} = call i1 @__cay_string_equals(i8* {}, i8* {})", temp, left_val, right_val));
+            return Ok(format!("i1 {}", temp));
+        }
+        let equal = self.new_temp();
+        self.emit_line(&format!("  {} = call i1 @__cay_string_equals(i8* {}, i8* {})", equal, left_val, right_val));
+        self.emit_line(&format!("  {} = xor i1 {}, true", temp, equal));
+        Ok(format!("i1 {}", temp))
+    }
+
+    /// 将引用值统一转换为 i8*（null 字面量以 i64 0 表示）
+    fn cast_to_i8_ptr(&mut self, ty: &str, val: &str) -> String {
+        if ty == "i8*" {
+            val.to_string()
+        } else if ty.ends_with('*') {
+            let t = self.new_temp();
+            self.emit_line(&format!("  {} = bitcast {} {} to i8*", t, ty, val));
+            t
+        } else {
+            let t = self.new_temp();
+            self.emit_line(&format!("  {} = inttoptr {} {} to i8*", t, ty, val));
+            t
+        }
+    }
+
     /// 生成小于比较表达式
     fn generate_lt(&mut self, left_type: &str, left_val: &str, right_type: &str, right_val: &str, temp: &str) -> cayResult<String> {
         if left_type.starts_with("i") && right_type.starts_with("i") {
@@ -1,6 +1,6 @@
 //! 内置函数调用代码生成
 //!
-//! 处理 print/println/readInt/readFloat/readLine 等内置函数。
+//! 处理 print/println/readInt/readFloat/readLine/valueOf 等内置函数。
 
 use crate::codegen::context::IRGenerator;
 use crate::ast::*;
@@ -89,6 +89,32 @@ impl IRGenerator {
                         fmt_ptr, fmt_len, fmt_len, fmt_name));
                     self.emit_line(&format!("  call i32 (i8*, ...) @printf(i8* {}, i8* {})",
                         fmt_ptr, val));
+                } else if type_str == "%__cay_string_view" {
+                    // 字符串视图：使用 %.*s 按长度打印，不依赖 null 结尾
+                    let fmt_str = if newline { "%.*s\n" } else { "%.*s" };
+                    let fmt_name = self.get_or_create_string_constant(fmt_str);
+                    let fmt_len = fmt_str.len() + 1;
+                    let fmt_ptr = self.new_temp();
+                    self.emit_line(&format!("  {} = getelementptr [{} x i8], [{} x i8]* {}, i64 0, i64 0",
+                        fmt_ptr, fmt_len, fmt_len, fmt_name));
+                    let view_ptr = self.new_temp();
+                    let view_len = self.new_temp();
+                    self.emit_line(&format!("  {} = extractvalue %__cay_string_view {}, 0", view_ptr, val));
+                    self.emit_line(&format!("  {} = extractvalue %__cay_string_view {}, 1", view_len, val));
+                    self.emit_line(&format!("  call i32 (i8*, ...) @printf(i8* {}, i32 {}, i8* {})",
+                        fmt_ptr, view_len, view_ptr));
+                } else if type_str == "i1" {
+                    // 布尔类型：转换为 "true" / "false" 再按字符串打印
+                    let bool_str = self.new_temp();
+                    self.emit_line(&format!("  {} = call i8* @__cay_bool_to_string(i1 {})", bool_str, val));
+                    let fmt_str = if newline { "%s\n" } else { "%s" };
+                    let fmt_name = self.get_or_create_string_constant(fmt_str);
+                    let fmt_len = fmt_str.len() + 1;
+                    let fmt_ptr = self.new_temp();
+                    self.emit_line(&format!("  {} = getelementptr [{} x i8], [{} x i8]* {}, i64 0, i64 0",
+                        fmt_ptr, fmt_len, fmt_len, fmt_name));
+                    self.emit_line(&format!("  call i32 (i8*, ...) @printf(i8* {}, i8* {})",
+                        fmt_ptr, bool_str));
                 } else if type_str.starts_with("i") && type_str != "i8*" {
                     // 整数类型（排除i8*）
                     // 需要将整数扩展为 i64 以匹配格式
@@ -257,4 +283,403 @@ impl IRGenerator {
         Ok(format!("i8* {}", buffer_ptr))
     }
 
+    /// 生成 readLong / readDouble / readChar 调用代码
+    ///
+    /// 用 scanf 按给定格式读取一个值，读取失败时结果为 0。
+    ///
+    /// # Arguments
+    /// * `func_name` - 输入函数名，用于错误信息
+    /// * `args` - 参数列表（应该为空）
+    /// * `fmt_str` - scanf 格式字符串
+    /// * `llvm_type` - 读取的值的 LLVM 类型
+    pub fn generate_scanf_read_call(&mut self, func_name: &str, args: &[Expr], fmt_str: &str, llvm_type: &str) -> cayResult<String> {
+        if !args.is_empty() {
+            return Err(codegen_error(format!("{}() takes no arguments", func_name)));
+        }
+
+        let value_ptr = self.new_temp();
+        self.emit_line(&format!("  {} = alloca {}", value_ptr, llvm_type));
+        self.emit_line(&format!("  store {} zeroinitializer, {}* {}", llvm_type, llvm_type, value_ptr));
+
+        let fmt_name = self.get_or_create_string_constant(fmt_str);
+        let fmt_len = fmt_str.len() + 1;
+        let fmt_ptr = self.new_temp();
+        self.emit_line(&format!("  {} = getelementptr [{} x i8], [{} x i8]* {}, i64 0, i64 0",
+            fmt_ptr, fmt_len, fmt_len, fmt_name));
+        self.emit_line(&format!("  call i32 (i8*, ...) @scanf(i8* {}, {}* {})", fmt_ptr, llvm_type, value_ptr));
+
+        let result = self.new_temp();
+        self.emit_line(&format!("  {} = load {}, {}* {}", result, llvm_type, llvm_type, value_ptr));
+        Ok(format!("{} {}", llvm_type, result))
+    }
+
+    /// 生成 readBool 调用代码
+    ///
+    /// 读取一个单词，是 `true` 时结果为 true，其他输入为 false。
+    ///
+    /// # Arguments
+    /// * `args` - 参数列表（应该为空）
+    pub fn generate_read_bool_call(&mut self, args: &[Expr]) -> cayResult<String> {
+        if !args.is_empty() {
+            return Err(codegen_error("readBool() takes no arguments".to_string()));
+        }
+
+        // 只需区分 "true"，多余的字符截断
+        let buffer = self.new_temp();
+        self.emit_line(&format!("  {} = alloca [8 x i8]", buffer));
+        self.emit_line(&format!("  store [8 x i8] zeroinitializer, [8 x i8]* {}", buffer));
+        let buffer_ptr = self.new_temp();
+        self.emit_line(&format!("  {} = getelementptr [8 x i8], [8 x i8]* {}, i64 0, i64 0", buffer_ptr, buffer));
+
+        let fmt_name = self.get_or_create_string_constant("%7s");
+        let fmt_ptr = self.new_temp();
+        self.emit_line(&format!("  {} = getelementptr [4 x i8], [4 x i8]* {}, i64 0, i64 0", fmt_ptr, fmt_name));
+        let true_name = self.get_or_create_string_constant("true");
+        let true_ptr = self.new_temp();
+        self.emit_line(&format!("  {} = getelementptr [5 x i8], [5 x i8]* {}, i64 0, i64 0", true_ptr, true_name));
+        self.emit_line(&format!("  call i32 (i8*, ...) @scanf(i8* {}, i8* {})", fmt_ptr, buffer_ptr));
+
+        let cmp = self.new_temp();
+        self.emit_line(&format!("  {} = call i32 @strncmp(i8* {}, i8* {}, i64 5)", cmp, buffer_ptr, true_ptr));
+        let result = self.new_temp();
+        self.emit_line(&format!("  {} = icmp eq i32 {}, 0", result, cmp));
+        Ok(format!("i1 {}", result))
+    }
+
+    /// 生成 valueOf / String.valueOf 调用代码
+    ///
+    /// 各重载与 `(String)` 强制转换共用同一套 `__cay_*_to_string` 运行时函数。
+    ///
+    /// # Arguments
+    /// * `args` - 参数列表（应该只有一个基本类型参数）
+    /// * `loc` - 调用位置
+    pub fn generate_value_of_call(&mut self, args: &[Expr], loc: &crate::error::SourceLocation) -> cayResult<String> {
+        if args.len() != 1 {
+            return Err(codegen_error("String.valueOf() takes 1 argument".to_string()));
+        }
+
+        let cast = CastExpr {
+            expr: Box::new(args[0].clone()),
+            target_type: crate::types::Type::String,
+            loc: loc.clone(),
+        };
+        self.generate_cast_expression(&cast)
+    }
+
+    /// 生成 addressOf 调用代码
+    ///
+    /// 通过 `ptrtoint` 把引用转换为 long，便于调试对象同一性。
+    ///
+    /// # Arguments
+    /// * `args` - 参数列表（应该只有一个引用类型参数）
+    pub fn generate_address_of_call(&mut self, args: &[Expr]) -> cayResult<String> {
+        if args.len() != 1 {
+            return Err(codegen_error("addressOf() takes 1 argument".to_string()));
+        }
+
+        let value = self.generate_expression(&args[0])?;
+        let (value_type, val) = self.parse_typed_value(&value);
+        if !value_type.ends_with('*') {
+            return Err(codegen_error(format!("addressOf() requires a reference, got {}", value_type)));
+        }
+
+        let temp = self.new_temp();
+        self.emit_line(&format!("  {} = ptrtoint {} {} to i64", temp, value_type, val));
+        Ok(format!("i64 {}", temp))
+    }
+
+    /// 生成 print(value, x) / println(value, x) 调用代码
+    ///
+    /// 整数按进制格式化为字符串再输出；浮点数的第二个参数是小数位数，用 `%.*f` 输出。
+    pub fn generate_print_format_call(&mut self, args: &[Expr], newline: bool) -> cayResult<String> {
+        let func_name = if newline { "println" } else { "print" };
+        if args.len() != 2 {
+            return Err(codegen_error(format!("{}() takes 2 arguments (value, base), got {}", func_name, args.len())));
+        }
+
+        let value = self.generate_expression(&args[0])?;
+        let (value_type, val) = self.parse_typed_value(&value);
+        if value_type == "double" || value_type == "float" {
+            return self.generate_print_precision(func_name, &value_type, &val, &args[1], newline);
+        }
+
+        let str_val = self.format_int_with_base(func_name, &value_type, &val, &args[1])?;
+        let fmt_str = if newline { "%s\n" } else { "%s" };
+        let fmt_name = self.get_or_create_string_constant(fmt_str);
+        let fmt_len = fmt_str.len() + 1;
+        let fmt_ptr = self.new_temp();
+        self.emit_line(&format!("  {} = getelementptr [{} x i8], [{} x i8]* {}, i64 0, i64 0",
+            fmt_ptr, fmt_len, fmt_len, fmt_name));
+        self.emit_line(&format!("  call i32 (i8*, ...) @printf(i8* {}, i8* {})", fmt_ptr, str_val));
+        Ok("void".to_string())
+    }
+
+    /// 生成 printf(fmt, ...) 调用代码
+    ///
+    /// 直接调用 C 的 printf。实参按 C 可变参数的规则提升：float 扩展为 double，
+    /// bool/char 等窄整数扩展为 int；整数转换的长度修饰符按实参实际宽度改写（long 用 `ll`）。
+    pub fn generate_printf_call(&mut self, args: &[Expr]) -> cayResult<String> {
+        let format = match args.first() {
+            Some(Expr::Literal(LiteralValue::String(format))) => format,
+            _ => return Err(codegen_error("printf() format must be a String literal".to_string())),
+        };
+        let specs = crate::semantic::parse_format_specs(format)
+            .map_err(|msg| codegen_error(format!("printf(): {}", msg)))?;
+
+        let mut values = Vec::new();
+        for arg in &args[1..] {
+            let value = self.generate_expression(arg)?;
+            let (value_type, val) = self.parse_typed_value(&value);
+            let promoted = match value_type.as_str() {
+                "float" => "double",
+                "i1" | "i8" | "i16" => "i32",
+                _ => {
+                    values.push((value_type, val));
+                    continue;
+                }
+            };
+            let promoted = self.widen_value(&value, Some(arg), promoted);
+            values.push(self.parse_typed_value(&promoted));
+        }
+
+        // 整数转换的长度修饰符由实参宽度决定，用户写的 %d / %ld 对 int 和 long 都能正确输出
+        let mut rewritten = String::with_capacity(format.len());
+        let mut copied = 0;
+        let mut arg_index = 0;
+        for spec in &specs {
+            arg_index += spec.star_count;
+            if spec.is_integer() {
+                rewritten.push_str(&format[copied..spec.length_pos]);
+                if values.get(arg_index).is_some_and(|(ty, _)| ty == "i64") {
+                    rewritten.push_str("ll");
+                }
+                copied = spec.conversion_pos;
+            }
+            arg_index += 1;
+        }
+        rewritten.push_str(&format[copied..]);
+
+        let fmt_name = self.get_or_create_string_constant(&rewritten);
+        let fmt_len = rewritten.len() + 1;
+        let fmt_ptr = self.new_temp();
+        self.emit_line(&format!("  {} = getelementptr [{} x i8], [{} x i8]* {}, i64 0, i64 0",
+            fmt_ptr, fmt_len, fmt_len, fmt_name));
+        let mut call_args = vec![format!("i8* {}", fmt_ptr)];
+        call_args.extend(values.iter().map(|(ty, val)| format!("{} {}", ty, val)));
+        self.emit_line(&format!("  call i32 (i8*, ...) @printf({})", call_args.join(", ")));
+        Ok("void".to_string())
+    }
+
+    /// 按指定小数位数输出浮点数
+    ///
+    /// 小数位数可以是运行时的值，通过 `%.*f` 以 i32 参数传给 printf。
+    fn generate_print_precision(&mut self, func_name: &str, value_type: &str, val: &str, precision: &Expr, newline: bool) -> cayResult<String> {
+        if let Expr::Literal(LiteralValue::Int32(n @ i32::MIN..=-1)) = precision {
+            return Err(codegen_error(format!("The precision of {}() must not be negative, got {}", func_name, n)));
+        }
+
+        let precision = self.generate_expression(precision)?;
+        let (precision_type, precision_val) = self.parse_typed_value(&precision);
+        let precision_i32 = match precision_type.as_str() {
+            "i32" => precision_val,
+            "i64" => {
+                let truncated = self.new_temp();
+                self.emit_line(&format!("  {} = trunc i64 {} to i32", truncated, precision_val));
+                truncated
+            }
+            t if t.starts_with('i') && !t.ends_with('*') => {
+                let extended = self.new_temp();
+                self.emit_line(&format!("  {} = sext {} {} to i32", extended, t, precision_val));
+                extended
+            }
+            t => return Err(codegen_error(format!("The precision of {}() must be an int, got {}", func_name, t))),
+        };
+
+        // printf 的可变参数中浮点数总是以 double 传递
+        let double_val = if value_type == "float" {
+            let ext_temp = self.new_temp();
+            self.emit_line(&format!("  {} = fpext float {} to double", ext_temp, val));
+            ext_temp
+        } else {
+            val.to_string()
+        };
+
+        let fmt_str = if newline { "%.*f\n" } else { "%.*f" };
+        let fmt_name = self.get_or_create_string_constant(fmt_str);
+        let fmt_len = fmt_str.len() + 1;
+        let fmt_ptr = self.new_temp();
+        self.emit_line(&format!("  {} = getelementptr [{} x i8], [{} x i8]* {}, i64 0, i64 0",
+            fmt_ptr, fmt_len, fmt_len, fmt_name));
+        self.emit_line(&format!("  call i32 (i8*, ...) @printf(i8* {}, i32 {}, double {})",
+            fmt_ptr, precision_i32, double_val));
+        Ok("void".to_string())
+    }
+
+    /// 生成 toString(value, base) 调用代码
+    ///
+    /// # Arguments
+    /// * `func_name` - 调用的内置函数名（用于错误信息）
+    /// * `args` - 参数列表（整数值和进制）
+    pub fn generate_to_string_base_call(&mut self, func_name: &str, args: &[Expr]) -> cayResult<String> {
+        if args.len() != 2 {
+            return Err(codegen_error(format!("{}() takes 2 arguments (value, base), got {}", func_name, args.len())));
+        }
+
+        let value = self.generate_expression(&args[0])?;
+        let (value_type, val) = self.parse_typed_value(&value);
+        let result = self.format_int_with_base(func_name, &value_type, &val, &args[1])?;
+        Ok(format!("i8* {}", result))
+    }
+
+    /// 将已求值的整数按进制格式化为字符串，返回结果寄存器
+    ///
+    /// 格式化由 `__cay_int_to_string_base` 完成。print 的参数不经过语义分析的类型检查，
+    /// 因此这里再次确认进制是 2/8/10/16 的整数字面量。
+    fn format_int_with_base(&mut self, func_name: &str, value_type: &str, val: &str, base: &Expr) -> cayResult<String> {
+        let base = match base {
+            Expr::Literal(LiteralValue::Int32(n)) => *n as i64,
+            Expr::Literal(LiteralValue::Int64(n)) => *n,
+            _ => return Err(codegen_error(format!("The base of {}() must be an integer literal", func_name))),
+        };
+        if !matches!(base, 2 | 8 | 10 | 16) {
+            return Err(codegen_error(format!("Unsupported base {} for {}(): expected 2, 8, 10 or 16", base, func_name)));
+        }
+
+        let i64_val = if value_type == "i64" {
+            val.to_string()
+        } else {
+            let extended = self.new_temp();
+            self.emit_line(&format!("  {} = sext {} {} to i64", extended, value_type, val));
+            extended
+        };
+
+        let result = self.new_temp();
+        self.emit_line(&format!("  {} = call i8* @__cay_int_to_string_base(i64 {}, i32 {})",
+            result, i64_val, base));
+        Ok(result)
+    }
+
+    /// 生成 readFile(path) 调用代码
+    ///
+    /// 读取整个文件内容为字符串；文件无法打开时返回空字符串。
+    ///
+    /// # Arguments
+    /// * `args` - 参数列表（文件路径）
+    pub fn generate_read_file_call(&mut self, args: &[Expr]) -> cayResult<String> {
+        if args.len() != 1 {
+            return Err(codegen_error(format!("readFile() takes 1 argument (path), got {}", args.len())));
+        }
+
+        let path = self.generate_expression(&args[0])?;
+        let (path_type, path_val) = self.parse_typed_value(&path);
+        if path_type != "i8*" {
+            return Err(codegen_error("Argument 'path' of readFile() must be a String".to_string()));
+        }
+
+        let result = self.new_temp();
+        self.emit_line(&format!("  {} = call i8* @__cay_read_file(i8* {})", result, path_val));
+        Ok(format!("i8* {}", result))
+    }
+
+    /// 生成 floorMod(a, b) 调用代码
+    ///
+    /// `%` 按截断除法取余（结果与被除数同号），floorMod 的结果与除数同号：
+    /// 余数非零且与除数异号时加上除数，例如 floorMod(-7, 3) 为 2。
+    /// 用 select 而不是 `((a % b) + b) % b`，后者在操作数接近 MIN/MAX 时会溢出。
+    ///
+    /// # Arguments
+    /// * `args` - 参数列表（被除数和除数）
+    pub fn generate_floor_mod_call(&mut self, args: &[Expr]) -> cayResult<String> {
+        if args.len() != 2 {
+            return Err(codegen_error(format!("floorMod() takes 2 arguments (dividend, divisor), got {}", args.len())));
+        }
+
+        let dividend = self.generate_expression(&args[0])?;
+        let divisor = self.generate_expression(&args[1])?;
+        let (dividend_type, dividend_val) = self.parse_typed_value(&dividend);
+        let (divisor_type, divisor_val) = self.parse_typed_value(&divisor);
+        let (int_type, a, b) = self.promote_integer_operands(&dividend_type, &dividend_val, &divisor_type, &divisor_val);
+        self.generate_division_by_zero_check(&int_type, &b)?;
+
+        let rem = self.new_temp();
+        self.emit_line(&format!("  {} = srem {} {}, {}", rem, int_type, a, b));
+        let nonzero = self.new_temp();
+        self.emit_line(&format!("  {} = icmp ne {} {}, 0", nonzero, int_type, rem));
+        let sign_bits = self.new_temp();
+        self.emit_line(&format!("  {} = xor {} {}, {}", sign_bits, int_type, rem, b));
+        let signs_differ = self.new_temp();
+        self.emit_line(&format!("  {} = icmp slt {} {}, 0", signs_differ, int_type, sign_bits));
+        let needs_fixup = self.new_temp();
+        self.emit_line(&format!("  {} = and i1 {}, {}", needs_fixup, nonzero, signs_differ));
+        // 异号时 |rem| < |b|，相加不会溢出
+        let adjusted = self.new_temp();
+        self.emit_line(&format!("  {} = add {} {}, {}", adjusted, int_type, rem, b));
+        let result = self.new_temp();
+        self.emit_line(&format!("  {} = select i1 {}, {} {}, {} {}", result, needs_fixup, int_type, adjusted, int_type, rem));
+        Ok(format!("{} {}", int_type, result))
+    }
+
+    /// 生成 writeFile(path, content) 调用代码
+    ///
+    /// 覆盖写入文件，返回是否成功。
+    ///
+    /// # Arguments
+    /// * `args` - 参数列表（文件路径和内容）
+    pub fn generate_write_file_call(&mut self, args: &[Expr]) -> cayResult<String> {
+        if args.len() != 2 {
+            return Err(codegen_error(format!("writeFile() takes 2 arguments (path, content), got {}", args.len())));
+        }
+
+        let mut values = Vec::new();
+        for (name, arg) in ["path", "content"].iter().zip(args) {
+            let value = self.generate_expression(arg)?;
+            let (value_type, val) = self.parse_typed_value(&value);
+            if value_type != "i8*" {
+                return Err(codegen_error(format!("Argument '{}' of writeFile() must be a String", name)));
+            }
+            values.push(val);
+        }
+
+        let result = self.new_temp();
+        self.emit_line(&format!("  {} = call i1 @__cay_write_file(i8* {}, i8* {})", result, values[0], values[1]));
+        Ok(format!("i1 {}", result))
+    }
+
+    /// 生成宿主程序注册的内置函数调用
+    ///
+    /// 实参先按语义分析允许的拓宽规则转换为声明的参数类型，再交给注册的闭包输出调用。
+    pub fn generate_custom_builtin_call(&mut self, spec: &crate::builtins::BuiltinSpec, args: &[Expr]) -> cayResult<String> {
+        let mut converted = Vec::with_capacity(args.len());
+        for (arg, param_type) in args.iter().zip(&spec.params) {
+            let value = self.generate_expression(arg)?;
+            let param_type = self.type_to_llvm(param_type);
+            converted.push(self.widen_value(&value, Some(arg), &param_type));
+        }
+
+        if !self.used_builtins.contains(&spec.name) {
+            self.used_builtins.push(spec.name.clone());
+        }
+        (spec.emit)(self, &converted)
+    }
+
+    /// 输出已调用的自定义内置函数所需的模块级声明，重复的声明只输出一次
+    pub(crate) fn emit_builtin_declarations(&mut self) {
+        let mut emitted = Vec::new();
+        for name in &self.used_builtins {
+            for declaration in &self.builtins[name].declarations {
+                if !emitted.contains(declaration) && !self.output.contains(declaration.as_str()) {
+                    emitted.push(declaration.clone());
+                }
+            }
+        }
+        if emitted.is_empty() {
+            return;
+        }
+        self.output.push_str("; Host builtin declarations\n");
+        for declaration in emitted {
+            self.output.push_str(&declaration);
+            self.output.push('\n');
+        }
+        self.output.push('\n');
+    }
 }
@@ -5,6 +5,7 @@
 use crate::codegen::context::IRGenerator;
 use crate::ast::*;
 use crate::error::{cayResult, codegen_error};
+use crate::types::LIST_CLASS;
 
 impl IRGenerator {
     /// 生成函数调用表达式代码
@@ -14,18 +15,92 @@ impl IRGenerator {
     pub fn generate_call_expression(&mut self, call: &CallExpr) -> cayResult<String> {
         // 处理 print 和 println 函数
         if let Expr::Identifier(name) = call.callee.as_ref() {
+            if let Some(spec) = self.builtins.get(name).filter(|_| !self.current_class_has_method(name)).cloned() {
+                return self.generate_custom_builtin_call(&spec, &call.args);
+            }
             match name.as_str() {
+                "print" | "println" if call.args.len() == 2 && !self.current_class_has_method(name) => {
+                    return self.generate_print_format_call(&call.args, name == "println");
+                }
                 "print" => return self.generate_print_call(&call.args, false),
                 "println" => return self.generate_print_call(&call.args, true),
+                "printf" if !self.current_class_has_method("printf") => {
+                    return self.generate_printf_call(&call.args);
+                }
                 "readInt" => return self.generate_read_int_call(&call.args),
                 "readFloat" => return self.generate_read_float_call(&call.args),
                 "readLine" => return self.generate_read_line_call(&call.args),
+                "readLong" => {
+                    let fmt = self.get_i64_format_specifier();
+                    return self.generate_scanf_read_call(name, &call.args, fmt, "i64");
+                }
+                "readDouble" => return self.generate_scanf_read_call(name, &call.args, "%lf", "double"),
+                // 前导空格跳过空白，与其他输入函数一样读取下一个非空白字符
+                "readChar" => return self.generate_scanf_read_call(name, &call.args, " %c", "i8"),
+                "readBool" => return self.generate_read_bool_call(&call.args),
+                "valueOf" if !self.current_class_has_method("valueOf") => {
+                    return self.generate_value_of_call(&call.args, &call.loc);
+                }
+                "addressOf" if !self.current_class_has_method("addressOf") => {
+                    return self.generate_address_of_call(&call.args);
+                }
+                // static_assert 已在语义分析阶段求值，不生成运行时代码
+                "static_assert" if !self.current_class_has_method("static_assert") => {
+                    return Ok("void %dummy".to_string());
+                }
+                "readFile" if !self.current_class_has_method("readFile") => {
+                    return self.generate_read_file_call(&call.args);
+                }
+                "writeFile" if !self.current_class_has_method("writeFile") => {
+                    return self.generate_write_file_call(&call.args);
+                }
+                "arrayEquals" if !self.current_class_has_method("arrayEquals") => {
+                    return self.generate_array_equals_call(&call.args);
+                }
+                "toString" if !self.current_class_has_method("toString") => {
+                    return self.generate_to_string_base_call(name, &call.args);
+                }
+                "floorMod" if !self.current_class_has_method("floorMod") => {
+                    return self.generate_floor_mod_call(&call.args);
+                }
                 _ => {}
             }
         }
 
-        // 处理 String 方法调用: str.method(args)
+        // 处理 String.valueOf(...)
+        if matches!(call.callee.as_ref(), Expr::MemberAccess(member)
+            if member.member == "valueOf" && matches!(member.object.as_ref(), Expr::Identifier(name) if name == "String"))
+        {
+            return self.generate_value_of_call(&call.args, &call.loc);
+        }
+
+        // 处理 obj.is(other) 引用同一性比较
+        if let Expr::MemberAccess(member) = call.callee.as_ref() {
+            match member.member.as_str() {
+                "is" if call.args.len() == 1 && self.is_builtin_identity_call(&member.object) => {
+                    let left = self.generate_expression(&member.object)?;
+                    let right = self.generate_expression(&call.args[0])?;
+                    let (left_type, left_val) = self.parse_typed_value(&left);
+                    let (right_type, right_val) = self.parse_typed_value(&right);
+                    let temp = self.new_temp();
+                    return self.generate_identity(&left_type, &left_val, &right_type, &right_val, &temp, "eq");
+                }
+                _ => {}
+            }
+        }
+
+        // 处理数组和 String 的内置方法调用: arr.clone() / str.method(args)
         if let Expr::MemberAccess(member) = call.callee.as_ref() {
+            // 数组的 clone()：对象所属的类无法确定时，成员调用的对象只能是数组
+            if member.member == "clone" && call.args.is_empty() && self.resolve_object_class(&member.object).is_none() {
+                return self.generate_array_clone(&member.object);
+            }
+
+            // 内置 List 的 add/get/size
+            if self.resolve_object_class(&member.object).as_deref() == Some(LIST_CLASS) {
+                return self.generate_list_method_call(member, call);
+            }
+
             // 检查是否是 String 方法调用
             if let Some(method_result) = self.try_generate_string_method_call(member, &call.args)? {
                 return Ok(method_result);
@@ -45,9 +120,17 @@ impl IRGenerator {
             }
             Expr::MemberAccess(member) => {
                 if let Expr::Identifier(obj_name) = member.object.as_ref() {
-                    let class_name = self.var_class_map.get(obj_name)
-                        .cloned()
-                        .unwrap_or_else(|| obj_name.clone());
+                    // this.method() 在当前类中解析，即使有同名局部变量
+                    let class_name = if obj_name == "this" {
+                        self.current_class.clone()
+                    } else {
+                        self.var_class_map.get(obj_name)
+                            .cloned()
+                            .unwrap_or_else(|| obj_name.clone())
+                    };
+                    (class_name, member.member.clone(), Some(member.object.clone()))
+                } else if let Some(class_name) = self.resolve_object_class(&member.object) {
+                    // arr[i].method() / a.b.method()：由对象表达式的类型确定类名
                     (class_name, member.member.clone(), Some(member.object.clone()))
                 } else {
                     return Err(codegen_error("Invalid method call".to_string()));
@@ -78,7 +161,7 @@ impl IRGenerator {
             let has_array = arg_results.len() > fixed_count;
             (packed, has_array)
         } else {
-            (arg_results, false)
+            (self.widen_call_args(&class_name, &method_name, arg_results, &call.args), false)
         };
 
         // 检查是否是实例方法（需要传递 this）
@@ -219,7 +302,7 @@ impl IRGenerator {
     }
 
     /// 根据方法定义的参数类型构建函数名
-    fn build_function_name_from_method(&self, class_name: &str, method_name: &str, params: &[crate::types::ParameterInfo], has_varargs_array: bool) -> String {
+    pub(crate) fn build_function_name_from_method(&self, class_name: &str, method_name: &str, params: &[crate::types::ParameterInfo], has_varargs_array: bool) -> String {
         if params.is_empty() {
             return format!("{}.{}", class_name, method_name);
         }
@@ -242,19 +325,56 @@ impl IRGenerator {
         }
 
         match ty {
+            crate::types::Type::Int8 => "i8".to_string(),
+            crate::types::Type::Int16 => "i16".to_string(),
             crate::types::Type::Int32 => "i".to_string(),
             crate::types::Type::Int64 => "l".to_string(),
             crate::types::Type::Float32 => "f".to_string(),
             crate::types::Type::Float64 => "d".to_string(),
             crate::types::Type::Bool => "b".to_string(),
             crate::types::Type::String => "s".to_string(),
+            crate::types::Type::StringView => "sv".to_string(),
             crate::types::Type::Char => "c".to_string(),
             crate::types::Type::Object(name) => format!("o{}", name),
             crate::types::Type::Array(inner) => format!("a{}", self.param_type_to_signature(inner, false)),
+            crate::types::Type::Tuple(_) => self.type_to_signature(ty),
             _ => "x".to_string(),
         }
     }
 
+    /// 在生成代码之前确定方法调用表达式的返回类型
+    ///
+    /// 按与调用生成相同的规则解析类名（当前类、对象变量的类或静态调用的类名），
+    /// 再按实参数量在类型注册表中查找重载；无法确定时返回 None。
+    pub fn call_return_type(&self, call: &CallExpr) -> Option<crate::types::Type> {
+        let (class_name, method_name) = match call.callee.as_ref() {
+            Expr::Identifier(name) => (self.current_class.clone(), name),
+            Expr::MemberAccess(member) => {
+                let class_name = match member.object.as_ref() {
+                    Expr::Identifier(obj_name) if obj_name != "this" => self.var_class_map.get(obj_name)
+                        .cloned()
+                        .unwrap_or_else(|| obj_name.clone()),
+                    object => self.resolve_object_class(object)?,
+                };
+                (class_name, &member.member)
+            }
+            _ => return None,
+        };
+        let registry = self.type_registry.as_ref()?;
+        if class_name == LIST_CLASS {
+            return match (method_name.as_str(), call.callee.as_ref()) {
+                ("get", Expr::MemberAccess(member)) => registry.list_call_element(&call.loc).cloned()
+                    .or_else(|| self.list_element_type(&member.object)),
+                ("size", _) => Some(crate::types::Type::Int32),
+                _ => Some(crate::types::Type::Void),
+            };
+        }
+        let methods = registry.get_class(&class_name)?.methods.get(method_name)?;
+        methods.iter()
+            .find(|m| m.params.len() == call.args.len())
+            .map(|m| m.return_type.clone())
+    }
+
     /// 获取方法的返回类型
     fn get_method_return_type(&self, class_name: &str, method_name: &str, processed_args: &[String], has_varargs_array: bool) -> crate::types::Type {
         // 获取实际参数的类型签名
@@ -322,6 +442,49 @@ impl IRGenerator {
         crate::types::Type::Int64
     }
 
+    /// 没有签名完全一致的重载时，按拓宽规则选择重载并转换实参
+    ///
+    /// 选择规则与语义分析的重载解析一致：精确匹配优先，否则取最具体的可拓宽重载，
+    /// 例如只有 f(long) 时 f(5) 的实参先扩展为 i64。
+    /// 数组、对象和字符串参数保持声明的指针类型，null 实参转换为对应的空指针。
+    fn widen_call_args(&mut self, class_name: &str, method_name: &str, args: Vec<String>, arg_exprs: &[Expr]) -> Vec<String> {
+        let arg_types: Vec<String> = args.iter().map(|a| self.parse_typed_value(a).0).collect();
+        let Some(param_types) = self.select_widening_overload(class_name, method_name, &arg_types) else {
+            return args;
+        };
+
+        args.iter().zip(arg_exprs).zip(&param_types)
+            .map(|((arg, expr), param_type)| self.widen_value(arg, Some(expr), param_type))
+            .collect()
+    }
+
+    /// 查找需要拓宽实参才能调用的重载，返回其参数的 LLVM 类型；已有精确匹配时返回 None
+    fn select_widening_overload(&self, class_name: &str, method_name: &str, arg_types: &[String]) -> Option<Vec<String>> {
+        let registry = self.type_registry.as_ref()?;
+        let mut current = registry.get_class(class_name);
+        while let Some(class_info) = current {
+            let applicable: Vec<&crate::types::MethodInfo> = class_info.methods.get(method_name)
+                .map(|methods| methods.iter().filter(|m| {
+                    !m.params.last().is_some_and(|p| p.is_varargs)
+                        && m.params.len() == arg_types.len()
+                        && m.params.iter().zip(arg_types).all(|(p, a)| Self::llvm_widens_to(a, &self.type_to_llvm(&p.param_type)))
+                }).collect())
+                .unwrap_or_default();
+            if !applicable.is_empty() {
+                let is_exact = |m: &crate::types::MethodInfo| {
+                    m.params.iter().zip(arg_types).all(|(p, a)| self.type_to_llvm(&p.param_type) == *a)
+                };
+                if applicable.iter().any(|m| is_exact(m)) {
+                    return None;
+                }
+                let method = crate::types::ClassInfo::select_most_specific(applicable, |_| false).ok()??;
+                return Some(method.params.iter().map(|p| self.type_to_llvm(&p.param_type)).collect());
+            }
+            current = class_info.parent.as_deref().and_then(|parent| registry.get_class(parent));
+        }
+        None
+    }
+
     /// 检查方法是否是可变参数方法
     /// 查询类型注册表来确定方法是否真的是可变参数方法
     fn is_varargs_method(&self, class_name: &str, method_name: &str) -> bool {
@@ -342,6 +505,22 @@ impl IRGenerator {
         false
     }
 
+    /// 检查 obj.is(...) 是否应使用内置的引用同一性比较（类未自定义 is 方法）
+    fn is_builtin_identity_call(&self, object: &Expr) -> bool {
+        match self.resolve_object_class(object) {
+            Some(class_name) => self.type_registry.as_ref()
+                .is_some_and(|r| r.get_method(&class_name, "is").is_none()),
+            None => false,
+        }
+    }
+
+    /// 检查当前类是否定义了指定名称的方法
+    pub(crate) fn current_class_has_method(&self, method_name: &str) -> bool {
+        self.type_registry.as_ref()
+            .and_then(|r| r.get_class(&self.current_class))
+            .is_some_and(|c| c.methods.contains_key(method_name))
+    }
+
     /// 检查方法是否是实例方法（非静态方法）
     fn is_instance_method(&self, class_name: &str, method_name: &str) -> bool {
         // 查询类型注册表
@@ -386,10 +565,15 @@ impl IRGenerator {
         let array_type = "i32";  // 假设可变参数是 int 类型
         let array_ptr = self.new_temp();
 
-        // 分配数组内存
+        // 分配数组内存，与普通数组一样在数据前留 8 字节存放长度
         let elem_size = 4;  // i32 占 4 字节
-        let total_size = array_size * elem_size;
-        self.emit_line(&format!("  {} = call i8* @calloc(i64 1, i64 {})", array_ptr, total_size));
+        let total_size = array_size * elem_size + 8;
+        let block_ptr = self.new_temp();
+        self.emit_line(&format!("  {} = call i8* @calloc(i64 1, i64 {})", block_ptr, total_size));
+        let len_ptr = self.new_temp();
+        self.emit_line(&format!("  {} = bitcast i8* {} to i32*", len_ptr, block_ptr));
+        self.emit_line(&format!("  store i32 {}, i32* {}, align 4", array_size, len_ptr));
+        self.emit_line(&format!("  {} = getelementptr i8, i8* {}, i64 8", array_ptr, block_ptr));
 
         // 将可变参数存入数组
         for (i, arg_str) in varargs.iter().enumerate() {
@@ -4,6 +4,7 @@
 
 use crate::codegen::context::IRGenerator;
 use crate::ast::*;
+use crate::types::Type;
 use crate::error::{cayResult, codegen_error};
 
 impl IRGenerator {
@@ -36,9 +37,10 @@ impl IRGenerator {
             let to_bits: u32 = to_type.trim_start_matches('i').parse().unwrap_or(64);
             
             if to_bits > from_bits {
-                // 符号扩展
-                self.emit_line(&format!("  {} = sext {} {} to {}",
-                    temp, from_type, val, to_type));
+                // char 按无符号处理，零扩展；int8 和其余整数符号扩展
+                let ext = self.int_extension(&from_type, &cast.expr);
+                self.emit_line(&format!("  {} = {} {} {} to {}",
+                    temp, ext, from_type, val, to_type));
             } else {
                 // 截断
                 self.emit_line(&format!("  {} = trunc {} {} to {}",
@@ -47,11 +49,12 @@ impl IRGenerator {
             return Ok(format!("{} {}", to_type, temp));
         }
         
-        // 整数到浮点
+        // 整数到浮点（char 按无符号值转换）
         if from_type.starts_with("i") && !from_type.ends_with("*") && 
            (to_type == "float" || to_type == "double") {
-            self.emit_line(&format!("  {} = sitofp {} {} to {}",
-                temp, from_type, val, to_type));
+            let conv = self.int_to_float(&from_type, &cast.expr);
+            self.emit_line(&format!("  {} = {} {} {} to {}",
+                temp, conv, from_type, val, to_type));
             return Ok(format!("{} {}", to_type, temp));
         }
         
@@ -78,26 +81,20 @@ impl IRGenerator {
         
         // 浮点到字符串（float/double -> String）
         if (from_type == "float" || from_type == "double") && to_type == "i8*" {
-            // 关键修复：C 的可变参数函数中，float 会被提升为 double
-            // 所以即使原类型是 float，也必须 fpext 到 double 再传参
-            let arg_val = if from_type == "float" {
-                let promoted = self.new_temp();
-                self.emit_line(&format!("  {} = fpext float {} to double", promoted, val));
-                promoted
-            } else {
-                val.to_string()  // 已经是 double
-            };
-
-            // 调用专门的运行时函数来避免调用约定问题
+            // float 使用单精度版本，按 float 精度选择最短表示
+            let func = if from_type == "float" { "__cay_float32_to_string" } else { "__cay_float_to_string" };
             let result = self.new_temp();
-            self.emit_line(&format!("  {} = call i8* @__cay_float_to_string(double {})",
-                result, arg_val));
+            self.emit_line(&format!("  {} = call i8* @{}({} {})",
+                result, func, from_type, val));
 
             return Ok(format!("{} {}", to_type, result));
         }
         
+        // int8 与 char 同为 i8，按整数转换为字符串
+        let is_int8 = self.expr_static_type(&cast.expr) == Some(Type::Int8);
+
         // 字符到字符串（char -> String）- 必须在整数转字符串之前处理
-        if from_type == "i8" && to_type == "i8*" {
+        if from_type == "i8" && to_type == "i8*" && !is_int8 {
             let result = self.new_temp();
             self.emit_line(&format!("  {} = call i8* @__cay_char_to_string(i8 {})",
                 result, val));
@@ -106,7 +103,7 @@ impl IRGenerator {
         
         // 布尔到字符串（bool -> String）
         // 布尔可能是 i1 或 i8，需要处理两种情况
-        if (from_type == "i1" || from_type == "i8") && to_type == "i8*" {
+        if (from_type == "i1" || from_type == "i8") && to_type == "i8*" && !is_int8 {
             let result = self.new_temp();
             let bool_val = if from_type == "i1" {
                 val.to_string()
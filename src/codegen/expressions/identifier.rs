@@ -1,6 +1,6 @@
 //! 标识符表达式代码生成
 //!
-//! 处理变量访问、静态字段访问和隐式 this 访问。
+//! 处理变量访问、const 常量替换、静态字段访问和隐式 this 访问。
 
 use crate::codegen::context::IRGenerator;
 use crate::error::cayResult;
@@ -11,17 +11,36 @@ impl IRGenerator {
     /// # Arguments
     /// * `name` - 标识符名称
     pub fn generate_identifier(&mut self, name: &str) -> cayResult<String> {
-        // 检查是否是类名（静态成员访问的上下文）
+        // 实例方法中的 this 保存在 this_ptr 中（参数 %this 本身不是指针变量）
+        let this_ptr = self.scope_manager.get_llvm_name("this_ptr").filter(|_| name == "this");
+        if let Some(this_llvm_name) = this_ptr {
+            let temp = self.new_temp();
+            self.emit_line(&format!("  {} = load i8*, i8** %{}, align 8", temp, this_llvm_name));
+            return Ok(format!("i8* {}", temp));
+        }
+
+        // 检查是否是类名（静态成员访问的上下文），同名局部变量优先
+        let is_local_var = self.scope_manager.get_var_type(name).is_some()
+            || self.var_types.contains_key(name);
         if let Some(ref registry) = self.type_registry {
-            if registry.class_exists(name) {
+            if registry.class_exists(name) && !is_local_var {
                 // 类名不应该单独作为表达式使用
                 // 返回一个占位符，实际使用应该在 MemberAccess 中处理
                 return Ok(format!("i64 0"));
             }
         }
 
-        // 检查是否是当前类的静态字段
-        if !self.current_class.is_empty() {
+        // 当前类（含父类）的 const 常量直接替换为字面量，同名局部变量和参数优先
+        let class_constant = self.type_registry.as_ref()
+            .filter(|_| !self.current_class.is_empty() && !is_local_var)
+            .and_then(|registry| registry.get_class_constant(&self.current_class, name))
+            .map(|info| info.value.clone());
+        if let Some(value) = class_constant {
+            return self.generate_literal(&value);
+        }
+
+        // 检查是否是当前类的静态字段（同样被同名局部变量遮蔽）
+        if !self.current_class.is_empty() && !is_local_var {
             let static_key = format!("{}.{}", self.current_class, name);
             if let Some(field_info) = self.static_field_map.get(&static_key).cloned() {
                 let temp = self.new_temp();
@@ -33,11 +52,7 @@ impl IRGenerator {
         }
 
         // 检查是否是局部变量
-        let is_local_var = self.scope_manager.get_var_type(name).is_some() 
-            || self.var_types.contains_key(name);
-        
         if is_local_var {
-            let temp = self.new_temp();
             // 优先使用作用域管理器获取变量类型和 LLVM 名称
             let (var_type, llvm_name) = if let Some(scope_type) = self.scope_manager.get_var_type(name) {
                 let llvm_name = self.scope_manager.get_llvm_name(name).unwrap_or_else(|| name.to_string());
@@ -47,12 +62,25 @@ impl IRGenerator {
                 let var_type = self.var_types.get(name).cloned().unwrap_or_else(|| "i64".to_string());
                 (var_type, name.to_string())
             };
+            // SSA 循环计数器直接使用寄存器中的值
+            if let Some(value) = self.ssa_vars.get(&llvm_name) {
+                return Ok(value.clone());
+            }
+            let temp = self.new_temp();
             let align = self.get_type_align(&var_type);  // 获取正确的对齐
             self.emit_line(&format!("  {} = load {}, {}* %{}, align {}",
                 temp, var_type, var_type, llvm_name, align));
             return Ok(format!("{} {}", var_type, temp));
         }
 
+        // 顶层 const 常量
+        let global_constant = self.type_registry.as_ref()
+            .and_then(|registry| registry.constants.get(name))
+            .map(|info| info.value.clone());
+        if let Some(value) = global_constant {
+            return self.generate_literal(&value);
+        }
+
         // 尝试作为实例字段访问（隐式 this）
         if !self.current_class.is_empty() {
             if let Some(field_info) = self.get_instance_field(&self.current_class, name).cloned() {
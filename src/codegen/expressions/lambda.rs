@@ -24,9 +24,10 @@ impl IRGenerator {
         // 保存当前代码缓冲区
         let saved_code = std::mem::take(&mut self.code);
         let saved_temp_counter = self.temp_counter;
+        let saved_label_counter = self.label_counter;
 
-        // 重置临时变量计数器
-        self.temp_counter = 0;
+        // Lambda 是独立的函数，临时变量和标签重新编号
+        self.reset_function_counters();
 
         // 生成 Lambda 参数类型
         let mut param_types = Vec::new();
@@ -95,6 +96,7 @@ impl IRGenerator {
         // 恢复之前的代码缓冲区
         self.code = saved_code;
         self.temp_counter = saved_temp_counter;
+        self.label_counter = saved_label_counter;
 
         // 将 Lambda 函数代码存储到全局函数列表
         self.lambda_functions.push(lambda_code);
@@ -112,23 +114,39 @@ impl IRGenerator {
     /// # Arguments
     /// * `method_ref` - 方法引用表达式
     pub fn generate_method_ref(&mut self, method_ref: &MethodRefExpr) -> cayResult<String> {
-        // 方法引用在 cay 中暂时作为函数指针处理
-        // 返回函数指针（i8* 作为占位符）
+        // 方法引用在 cay 中暂时作为函数指针处理，统一以 i8* 表示
         let temp = self.new_temp();
 
-        if let Some(ref class_name) = method_ref.class_name {
-            // 静态方法引用: ClassName::methodName
-            // 生成函数名
-            let fn_name = format!("{}.{}", class_name, method_ref.method_name);
+        // 确定方法所属的类：ClassName::methodName 直接给出类名，obj::methodName 从对象推断
+        let class_name = match (&method_ref.class_name, &method_ref.object) {
+            // 与方法调用一致，局部对象变量优先于同名的类
+            (Some(name), _) => Some(self.var_class_map.get(name).cloned().unwrap_or_else(|| name.clone())),
+            (None, Some(object)) => self.resolve_object_class(object),
+            (None, None) => None,
+        };
+        let method_info = class_name.and_then(|class_name| {
+            self.type_registry.as_ref()?
+                .get_method(&class_name, &method_ref.method_name)
+                .cloned()
+        });
+
+        if let Some(method) = method_info {
+            // 按方法的实际签名构造函数类型，继承的方法使用定义它的类名
+            let fn_name = self.build_function_name_from_method(&method.class_name, &method.name, &method.params, false);
+            let ret_type = self.type_to_llvm(&method.return_type);
+            let mut param_types = Vec::new();
+            // 实例方法的第一个参数是 this
+            if !method.is_static {
+                param_types.push("i8*".to_string());
+            }
+            for param in &method.params {
+                param_types.push(self.type_to_llvm(&param.param_type));
+            }
 
-            // 使用 bitcast 获取函数指针
-            self.emit_line(&format!("  {} = bitcast void (i64)* @{} to i8*", temp, fn_name));
-        } else if let Some(_object) = &method_ref.object {
-            // 实例方法引用: obj::methodName
-            // 暂时不支持，返回空指针
-            self.emit_line(&format!("  {} = inttoptr i64 0 to i8*", temp));
+            self.emit_line(&format!("  {} = bitcast {} ({})* @{} to i8*",
+                temp, ret_type, param_types.join(", "), fn_name));
         } else {
-            // 未知类型，返回空指针
+            // 无法解析的方法引用，返回空指针
             self.emit_line(&format!("  {} = inttoptr i64 0 to i8*", temp));
         }
 
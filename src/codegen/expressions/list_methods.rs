@@ -0,0 +1,157 @@
+//! List 方法调用代码生成
+//!
+//! 处理内置 List 的 add/get/size。元素统一以 i64 存放在运行时缓冲区中：
+//! 整数符号扩展，布尔零扩展，浮点按位转换，指针转为整数。元素类型由语义分析确定，
+//! 按 add/get 的调用位置记录在类型注册表中，读取时据此转换回来；调用位置没有记录时，
+//! 按 List 所在的变量、字段、参数或返回值的记录查找。
+
+use crate::codegen::context::IRGenerator;
+use crate::ast::*;
+use crate::error::{cayResult, codegen_error};
+use crate::types::{Type, TypeRegistry};
+
+impl IRGenerator {
+    /// 生成 `new List()`
+    pub fn generate_list_new(&mut self) -> String {
+        let temp = self.new_temp();
+        self.emit_line(&format!("  {} = call i8* @__cay_list_new()", temp));
+        format!("i8* {}", temp)
+    }
+
+    /// 生成 List 方法调用
+    ///
+    /// # Arguments
+    /// * `member` - 成员访问表达式（对象为 List）
+    /// * `call` - 调用表达式
+    pub fn generate_list_method_call(&mut self, member: &MemberAccessExpr, call: &CallExpr) -> cayResult<String> {
+        let list = self.generate_expression(&member.object)?;
+        let (_, list_val) = self.parse_typed_value(&list);
+
+        match member.member.as_str() {
+            "size" => {
+                let temp = self.new_temp();
+                self.emit_line(&format!("  {} = call i32 @__cay_list_size(i8* {})", temp, list_val));
+                Ok(format!("i32 {}", temp))
+            }
+            "add" => {
+                let value = self.generate_expression(&call.args[0])?;
+                let element = self.list_call_element(member, call)?;
+                if let Some(slot) = self.list_slot(&member.object) {
+                    self.list_element_map.insert(slot, element.clone());
+                }
+                let element_type = self.type_to_llvm(&element);
+                let value = self.widen_value(&value, Some(&call.args[0]), &element_type);
+                let bits = self.list_value_to_bits(&value);
+                self.emit_line(&format!("  call void @__cay_list_add(i8* {}, i64 {})", list_val, bits));
+                Ok("void".to_string())
+            }
+            "get" => {
+                let element = self.list_call_element(member, call)?;
+                let index = self.generate_i32_argument(&call.args[0])?;
+                let bits = self.new_temp();
+                self.emit_line(&format!("  {} = call i64 @__cay_list_get(i8* {}, i32 {})", bits, list_val, index));
+                Ok(self.list_bits_to_value(&bits, &self.type_to_llvm(&element)))
+            }
+            other => Err(codegen_error(format!("Unknown method '{}' for class List", other))),
+        }
+    }
+
+    /// add/get 调用处 List 的元素类型：优先取语义分析在该调用位置的记录
+    fn list_call_element(&self, member: &MemberAccessExpr, call: &CallExpr) -> cayResult<Type> {
+        self.type_registry.as_ref()
+            .and_then(|registry| registry.list_call_element(&call.loc))
+            .cloned()
+            .or_else(|| self.list_element_type(&member.object))
+            .ok_or_else(|| codegen_error(format!(
+                "Cannot determine the element type of List at line {}: add an element to it first",
+                call.loc.line
+            )))
+    }
+
+    /// List 表达式的元素类型：变量和参数取当前方法中的记录，字段和方法返回值取语义分析的记录
+    pub fn list_element_type(&self, list: &Expr) -> Option<Type> {
+        let registry = self.type_registry.as_ref();
+        if let Expr::Call(call) = list {
+            let (class_name, method_name) = match call.callee.as_ref() {
+                Expr::Identifier(name) => (self.current_class.clone(), name.as_str()),
+                Expr::MemberAccess(member) => match (self.resolve_object_class(&member.object), member.object.as_ref()) {
+                    (Some(class_name), _) => (class_name, member.member.as_str()),
+                    (None, Expr::Identifier(class_name)) => (class_name.clone(), member.member.as_str()),
+                    _ => return None,
+                },
+                _ => return None,
+            };
+            return registry?.list_slot_element(&TypeRegistry::list_return_key(&class_name, method_name)).cloned();
+        }
+        let slot = self.list_slot(list)?;
+        self.list_element_map.get(&slot)
+            .or_else(|| registry?.list_slot_element(&slot))
+            .cloned()
+    }
+
+    /// List 变量声明或赋值时，目标沿用所赋 List 的元素类型；所赋的是新 List 时清除记录
+    pub fn track_list_assignment(&mut self, slot: String, value: &Expr) {
+        match self.list_element_type(value) {
+            Some(element) => self.list_element_map.insert(slot, element),
+            None => self.list_element_map.remove(&slot),
+        };
+    }
+
+    /// List 表达式在元素类型表中的键：局部变量取变量名，字段取 "类名.字段名"
+    pub fn list_slot(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Identifier(name) if self.scope_manager.get_var_type(name).is_some() => Some(name.clone()),
+            Expr::Identifier(name) if !self.current_class.is_empty() => Some(format!("{}.{}", self.current_class, name)),
+            Expr::MemberAccess(member) => {
+                let owner = match member.object.as_ref() {
+                    Expr::Identifier(class_name) if self.resolve_object_class(&member.object).is_none() => class_name.clone(),
+                    object => self.resolve_object_class(object)?,
+                };
+                Some(format!("{}.{}", owner, member.member))
+            }
+            _ => None,
+        }
+    }
+
+    /// 把元素值转换为 i64 存储
+    fn list_value_to_bits(&mut self, value: &str) -> String {
+        let (ty, val) = self.parse_typed_value(value);
+        let instr = match ty.as_str() {
+            "i64" => return val,
+            "i32" | "i16" | "i8" => "sext",
+            "i1" => "zext",
+            "double" => "bitcast",
+            "float" => {
+                let raw = self.new_temp();
+                self.emit_line(&format!("  {} = bitcast float {} to i32", raw, val));
+                let temp = self.new_temp();
+                self.emit_line(&format!("  {} = zext i32 {} to i64", temp, raw));
+                return temp;
+            }
+            _ => "ptrtoint",
+        };
+        let temp = self.new_temp();
+        self.emit_line(&format!("  {} = {} {} {} to i64", temp, instr, ty, val));
+        temp
+    }
+
+    /// 把存储的 i64 转换回元素类型
+    fn list_bits_to_value(&mut self, bits: &str, ty: &str) -> String {
+        let instr = match ty {
+            "i64" => return format!("i64 {}", bits),
+            "i32" | "i16" | "i8" | "i1" => "trunc",
+            "double" => "bitcast",
+            "float" => {
+                let raw = self.new_temp();
+                self.emit_line(&format!("  {} = trunc i64 {} to i32", raw, bits));
+                let temp = self.new_temp();
+                self.emit_line(&format!("  {} = bitcast i32 {} to float", temp, raw));
+                return format!("float {}", temp);
+            }
+            _ => "inttoptr",
+        };
+        let temp = self.new_temp();
+        self.emit_line(&format!("  {} = {} i64 {} to {}", temp, instr, bits, ty));
+        format!("{} {}", ty, temp)
+    }
+}
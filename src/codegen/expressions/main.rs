@@ -65,6 +65,9 @@ impl IRGenerator {
             
             // instanceof
             Expr::InstanceOf(instanceof) => self.generate_instanceof_expression(instanceof),
+
+            // 元组
+            Expr::Tuple(tuple) => self.generate_tuple_expression(tuple),
         }
     }
 }
@@ -1,9 +1,10 @@
 //! 成员访问表达式代码生成
 //!
-//! 处理静态字段访问、对象成员访问和数组 length 属性。
+//! 处理静态字段访问、对象成员访问（含 a.b.c 字段链）和数组 length 属性。
 
 use crate::codegen::context::IRGenerator;
 use crate::ast::*;
+use crate::types::Type;
 use crate::error::cayResult;
 
 impl IRGenerator {
@@ -14,6 +15,14 @@ impl IRGenerator {
     pub fn generate_member_access(&mut self, member: &MemberAccessExpr) -> cayResult<String> {
         // 检查是否是静态字段访问: ClassName.fieldName
         if let Expr::Identifier(class_name) = &*member.object {
+            // ClassName.CONST 直接替换为字面量
+            let constant = self.type_registry.as_ref()
+                .and_then(|registry| registry.get_class_constant(class_name, &member.member))
+                .map(|info| info.value.clone());
+            if let Some(value) = constant {
+                return self.generate_literal(&value);
+            }
+
             let static_key = format!("{}.{}", class_name, member.member);
             if let Some(field_info) = self.static_field_map.get(&static_key).cloned() {
                 // 静态字段访问 - 返回全局变量的指针
@@ -27,45 +36,29 @@ impl IRGenerator {
         
         // 特殊处理数组的 .length 属性
         if member.member == "length" {
+            // 由数组字面量初始化且从未重新赋值的数组，长度在编译期已知
+            let const_len = match member.object.as_ref() {
+                Expr::Identifier(name) => self.scope_manager.get_const_array_len(name),
+                _ => None,
+            };
+            if let Some(len) = const_len {
+                return Ok(format!("i32 {}", len));
+            }
+
             let obj = self.generate_expression(&member.object)?;
             let (obj_type, obj_val) = self.parse_typed_value(&obj);
             
             // 检查是否是数组类型（以 * 结尾）
             if obj_type.ends_with("*") {
-                // 首先将数组指针转换为 i8*
-                let obj_i8 = self.new_temp();
-                self.emit_line(&format!("  {} = bitcast {} {} to i8*", obj_i8, obj_type, obj_val));
-                
-                // 数组长度存储在数组指针前面的 8 字节中
-                // 计算长度地址：array_ptr - 8
-                let len_ptr_i8 = self.new_temp();
-                self.emit_line(&format!("  {} = getelementptr i8, i8* {}, i64 -8", len_ptr_i8, obj_i8));
-                
-                // 将长度指针转换为 i32*
-                let len_ptr = self.new_temp();
-                self.emit_line(&format!("  {} = bitcast i8* {} to i32*", len_ptr, len_ptr_i8));
-                
-                // 加载长度（作为 i32）
-                let len_val = self.new_temp();
-                self.emit_line(&format!("  {} = load i32, i32* {}, align 4", len_val, len_ptr));
-                
+                let len_val = self.load_array_length(&obj_type, &obj_val);
                 return Ok(format!("i32 {}", len_val));
             }
         }
         
         // 处理实例字段访问: this.fieldName 或 obj.fieldName
         
-        // 确定对象所属的类
-        let class_name_opt: Option<String> = if let Expr::Identifier(name) = &*member.object {
-            if name == "this" {
-                Some(self.current_class.clone())
-            } else {
-                // 尝试从变量类型推断类名
-                self.var_class_map.get(name).cloned()
-            }
-        } else {
-            None
-        };
+        // 确定对象所属的类（支持 a.b.c 这样的字段访问链）
+        let class_name_opt = self.resolve_object_class(&member.object);
         
         if let Some(class_name) = class_name_opt {
             if let Some(field_info) = self.get_instance_field(&class_name, &member.member).cloned() {
@@ -120,4 +113,83 @@ impl IRGenerator {
         let (_, obj_val) = self.parse_typed_value(&obj);
         Ok(format!("i8* {}", obj_val))
     }
+
+    /// 解析对象表达式所属的类名
+    ///
+    /// 支持 `this`、对象类型的局部变量、隐式 this 的对象字段，
+    /// 以及递归的实例字段访问链（如 `a.b` 的类型由 `a` 的类中字段 `b` 的类型决定）。
+    ///
+    /// # Arguments
+    /// * `expr` - 对象表达式
+    pub fn resolve_object_class(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Identifier(name) if name == "this" => Some(self.current_class.clone()),
+            Expr::Identifier(name) => {
+                if let Some(class_name) = self.var_class_map.get(name) {
+                    return Some(class_name.clone());
+                }
+                // 隐式 this 的对象字段或当前类的静态字段
+                if self.current_class.is_empty() {
+                    return None;
+                }
+                let static_field = self.static_field_map.get(&format!("{}.{}", self.current_class, name));
+                let field_type = match static_field {
+                    Some(field) => &field.field_type,
+                    None => &self.get_instance_field(&self.current_class, name)?.field_type,
+                };
+                match field_type {
+                    Type::Object(class_name) => Some(class_name.clone()),
+                    _ => None,
+                }
+            }
+            Expr::MemberAccess(inner) => {
+                let owner = self.resolve_object_class(&inner.object)?;
+                match &self.get_instance_field(&owner, &inner.member)?.field_type {
+                    Type::Object(class_name) => Some(class_name.clone()),
+                    _ => None,
+                }
+            }
+            Expr::ArrayAccess(access) => self.resolve_array_element_class(&access.array),
+            Expr::New(new_expr) => Some(new_expr.class_name.clone()),
+            Expr::Call(call) => match self.call_return_type(call)? {
+                Type::Object(class_name) => Some(class_name),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// 解析对象数组表达式的元素类名
+    ///
+    /// 支持对象数组类型的局部变量/参数、隐式 this 的数组字段和 `obj.arr` 形式的数组字段。
+    fn resolve_array_element_class(&self, expr: &Expr) -> Option<String> {
+        let field_type = match expr {
+            Expr::Identifier(name) => {
+                if let Some(class_name) = self.array_class_map.get(name) {
+                    return Some(class_name.clone());
+                }
+                if self.current_class.is_empty() {
+                    return None;
+                }
+                &self.get_instance_field(&self.current_class, name)?.field_type
+            }
+            Expr::MemberAccess(inner) => {
+                let owner = self.resolve_object_class(&inner.object)?;
+                &self.get_instance_field(&owner, &inner.member)?.field_type
+            }
+            _ => return None,
+        };
+        Self::object_array_class(field_type).cloned()
+    }
+
+    /// 对象数组类型的元素类名，其他类型返回 None
+    pub fn object_array_class(ty: &Type) -> Option<&String> {
+        match ty {
+            Type::Array(elem) => match elem.as_ref() {
+                Type::Object(class_name) => Some(class_name),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
 }
@@ -13,6 +13,7 @@
 //! - `call`: 函数/方法调用
 //! - `builtin`: 内置函数（print/read 等）
 //! - `string_methods`: String 方法调用
+//! - `list_methods`: 内置 List 方法调用
 //! - `array`: 数组创建、访问、初始化
 //! - `cast`: 类型转换
 //! - `member`: 成员访问
@@ -21,6 +22,7 @@
 //! - `lambda`: Lambda 表达式和方法引用
 //! - `ternary`: 三元运算符
 //! - `instanceof`: instanceof 表达式
+//! - `tuple`: 元组构造与解构
 
 // 工具模块（需要最先加载）
 mod utils;
@@ -38,6 +40,7 @@ mod unary;
 mod call;
 mod builtin;
 mod string_methods;
+mod list_methods;
 
 // 数组
 mod array;
@@ -52,3 +55,4 @@ mod new;
 mod lambda;
 mod ternary;
 mod instanceof;
+mod tuple;
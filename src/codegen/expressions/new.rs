@@ -4,7 +4,8 @@
 
 use crate::codegen::context::IRGenerator;
 use crate::ast::*;
-use crate::error::cayResult;
+use crate::error::{cayResult, codegen_error};
+use crate::types::LIST_CLASS;
 
 impl IRGenerator {
     /// 生成 new 表达式代码
@@ -13,6 +14,9 @@ impl IRGenerator {
     /// * `new_expr` - new 表达式
     pub fn generate_new_expression(&mut self, new_expr: &NewExpr) -> cayResult<String> {
         let class_name = &new_expr.class_name;
+        if class_name == LIST_CLASS {
+            return Ok(self.generate_list_new());
+        }
         let type_id_value = self.get_type_id_value(class_name).unwrap_or(0);
 
         // 获取类布局信息，确定对象大小
@@ -29,6 +33,55 @@ impl IRGenerator {
 
         let cast_temp = self.new_temp();
         self.emit_line(&format!("  {} = bitcast i8* {} to i8*", cast_temp, calloc_temp));
+
+        // 调用与实参匹配的构造函数
+        let mut args = Vec::with_capacity(new_expr.args.len());
+        for arg in &new_expr.args {
+            args.push(self.generate_expression(arg)?);
+        }
+        if let Some(param_types) = self.select_constructor(class_name, &args, new_expr.loc.line)? {
+            let mut call_args = vec![format!("i8* {}", cast_temp)];
+            for ((arg, expr), param) in args.iter().zip(&new_expr.args).zip(&param_types) {
+                let param_type = self.type_to_llvm(param);
+                call_args.push(self.widen_value(arg, Some(expr), &param_type));
+            }
+            let ctor_name = if param_types.is_empty() {
+                format!("{}.__ctor", class_name)
+            } else {
+                let sigs: Vec<String> = param_types.iter().map(|t| self.type_to_signature(t)).collect();
+                format!("{}.__ctor_{}", class_name, sigs.join("_"))
+            };
+            self.emit_line(&format!("  call void @{}({})", ctor_name, call_args.join(", ")));
+        }
+
         Ok(format!("i8* {}", cast_temp))
     }
+
+    /// 按实参类型选择构造函数，返回其参数类型
+    ///
+    /// 精确匹配优先，否则取第一个可通过拓宽接受实参的构造函数。
+    /// 类没有声明构造函数时，无参的 new 不调用构造函数；其余找不到匹配的情况报错。
+    fn select_constructor(&self, class_name: &str, args: &[String], line: usize) -> cayResult<Option<Vec<crate::types::Type>>> {
+        let Some(class_info) = self.type_registry.as_ref().and_then(|r| r.get_class(class_name)) else {
+            return Ok(None);
+        };
+        if class_info.constructors.is_empty() && args.is_empty() {
+            return Ok(None);
+        }
+        let arg_types: Vec<String> = args.iter().map(|a| self.parse_typed_value(a).0).collect();
+
+        let candidates: Vec<&crate::types::ConstructorInfo> = class_info.constructors.iter()
+            .filter(|c| c.params.len() == arg_types.len())
+            .collect();
+        let exact = candidates.iter().find(|c| {
+            c.params.iter().zip(&arg_types).all(|(p, a)| self.type_to_llvm(&p.param_type) == *a)
+        });
+        let ctor = exact.or_else(|| candidates.iter().find(|c| {
+            c.params.iter().zip(&arg_types).all(|(p, a)| Self::llvm_widens_to(a, &self.type_to_llvm(&p.param_type)))
+        })).ok_or_else(|| codegen_error(format!(
+            "No constructor of {} accepts arguments ({}) at line {}",
+            class_name, arg_types.join(", "), line
+        )))?;
+        Ok(Some(ctor.params.iter().map(|p| p.param_type.clone()).collect()))
+    }
 }
@@ -1,6 +1,7 @@
 //! String 方法调用代码生成
 //!
-//! 处理 String 类型的方法调用（length, substring, indexOf, charAt, replace）。
+//! 处理 String 类型的方法调用（length, substring, subview, indexOf, charAt, replace, compareTo），
+//! 以及 StringView 类型的方法调用（length, charAt, equals, toString）。
 
 use crate::codegen::context::IRGenerator;
 use crate::ast::*;
@@ -18,6 +19,11 @@ impl IRGenerator {
         let obj_result = self.generate_expression(&member.object)?;
         let (obj_type, obj_val) = self.parse_typed_value(&obj_result);
 
+        // StringView 方法
+        if obj_type == "%__cay_string_view" {
+            return self.generate_string_view_method_call(&obj_val, member, args);
+        }
+
         // 检查对象是否是字符串类型 (i8*)
         if obj_type != "i8*" {
             return Ok(None);
@@ -76,6 +82,19 @@ impl IRGenerator {
                     temp, obj_val, begin_i32, end_i32));
                 Ok(Some(format!("i8* {}", temp)))
             }
+            "subview" => {
+                // subview(beginIndex, endIndex) - 返回指向原字符串的视图，不分配内存
+                if args.len() != 2 {
+                    return Err(codegen_error("String.subview() takes 2 arguments".to_string()));
+                }
+
+                let begin_i32 = self.generate_i32_argument(&args[0])?;
+                let end_i32 = self.generate_i32_argument(&args[1])?;
+
+                self.emit_line(&format!("  {} = call %__cay_string_view @__cay_string_subview(i8* {}, i32 {}, i32 {})",
+                    temp, obj_val, begin_i32, end_i32));
+                Ok(Some(format!("%__cay_string_view {}", temp)))
+            }
             "indexOf" => {
                 // indexOf(substr) - 返回子串首次出现的位置
                 if args.len() != 1 {
@@ -132,7 +151,111 @@ impl IRGenerator {
                     temp, obj_val, old_val, new_val));
                 Ok(Some(format!("i8* {}", temp)))
             }
+            "compareTo" => {
+                // compareTo(other) - 字典序比较，返回负数、0 或正数
+                if args.len() != 1 {
+                    return Err(codegen_error("String.compareTo() takes 1 argument".to_string()));
+                }
+
+                let other_result = self.generate_expression(&args[0])?;
+                let (other_type, other_val) = self.parse_typed_value(&other_result);
+
+                if other_type != "i8*" {
+                    return Err(codegen_error("String.compareTo() argument must be a string".to_string()));
+                }
+
+                self.emit_line(&format!("  {} = call i32 @__cay_string_compare(i8* {}, i8* {})",
+                    temp, obj_val, other_val));
+                Ok(Some(format!("i32 {}", temp)))
+            }
+            "isEmpty" => {
+                // isEmpty() - 长度为 0（null 也视为空）
+                if !args.is_empty() {
+                    return Err(codegen_error("String.isEmpty() takes no arguments".to_string()));
+                }
+                let len_temp = self.new_temp();
+                self.emit_line(&format!("  {} = call i32 @__cay_string_length(i8* {})",
+                    len_temp, obj_val));
+                self.emit_line(&format!("  {} = icmp eq i32 {}, 0", temp, len_temp));
+                Ok(Some(format!("i1 {}", temp)))
+            }
+            "isBlank" => {
+                // isBlank() - 为空或只包含空白字符
+                if !args.is_empty() {
+                    return Err(codegen_error("String.isBlank() takes no arguments".to_string()));
+                }
+                self.emit_line(&format!("  {} = call i1 @__cay_string_is_blank(i8* {})",
+                    temp, obj_val));
+                Ok(Some(format!("i1 {}", temp)))
+            }
             _ => Ok(None), // 不是已知的 String 方法
         }
     }
+
+    /// 生成 StringView 方法调用代码
+    ///
+    /// # Arguments
+    /// * `view_val` - 视图值（%__cay_string_view）
+    /// * `member` - 成员访问表达式
+    /// * `args` - 参数列表
+    fn generate_string_view_method_call(&mut self, view_val: &str, member: &MemberAccessExpr, args: &[Expr]) -> cayResult<Option<String>> {
+        let temp = self.new_temp();
+
+        match member.member.as_str() {
+            "length" => {
+                // length() - 直接读取视图中保存的长度
+                if !args.is_empty() {
+                    return Err(codegen_error("StringView.length() takes no arguments".to_string()));
+                }
+                self.emit_line(&format!("  {} = extractvalue %__cay_string_view {}, 1",
+                    temp, view_val));
+                Ok(Some(format!("i32 {}", temp)))
+            }
+            "charAt" => {
+                if args.len() != 1 {
+                    return Err(codegen_error("StringView.charAt() takes 1 argument".to_string()));
+                }
+                let index_i32 = self.generate_i32_argument(&args[0])?;
+                self.emit_line(&format!("  {} = call i8 @__cay_string_view_charat(%__cay_string_view {}, i32 {})",
+                    temp, view_val, index_i32));
+                Ok(Some(format!("i8 {}", temp)))
+            }
+            "equals" => {
+                if args.len() != 1 {
+                    return Err(codegen_error("StringView.equals() takes 1 argument".to_string()));
+                }
+                let other_result = self.generate_expression(&args[0])?;
+                let (other_type, other_val) = self.parse_typed_value(&other_result);
+                if other_type != "i8*" {
+                    return Err(codegen_error("StringView.equals() argument must be a string".to_string()));
+                }
+                self.emit_line(&format!("  {} = call i1 @__cay_string_view_equals(%__cay_string_view {}, i8* {})",
+                    temp, view_val, other_val));
+                Ok(Some(format!("i1 {}", temp)))
+            }
+            "toString" => {
+                // toString() - 唯一会分配内存的操作
+                if !args.is_empty() {
+                    return Err(codegen_error("StringView.toString() takes no arguments".to_string()));
+                }
+                self.emit_line(&format!("  {} = call i8* @__cay_string_view_tostring(%__cay_string_view {})",
+                    temp, view_val));
+                Ok(Some(format!("i8* {}", temp)))
+            }
+            _ => Err(codegen_error(format!("Unknown StringView method '{}'", member.member))),
+        }
+    }
+
+    /// 生成索引参数并转换为 i32
+    pub(crate) fn generate_i32_argument(&mut self, arg: &Expr) -> cayResult<String> {
+        let result = self.generate_expression(arg)?;
+        let (arg_type, arg_val) = self.parse_typed_value(&result);
+        if arg_type == "i32" {
+            Ok(arg_val)
+        } else {
+            let t = self.new_temp();
+            self.emit_line(&format!("  {} = trunc {} {} to i32", t, arg_type, arg_val));
+            Ok(t)
+        }
+    }
 }
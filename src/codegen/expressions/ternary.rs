@@ -1,6 +1,10 @@
 //! 三元运算符表达式代码生成
 //!
 //! 处理条件表达式 ? :
+//!
+//! 一般情况下只求值被选中的分支：条件跳转到 then/else 块，
+//! 两个分支各自求值后在合并块中用 phi 汇合。
+//! 两个分支都是字面量或标识符（没有副作用）时，直接用 select。
 
 use crate::codegen::context::IRGenerator;
 use crate::ast::*;
@@ -12,49 +16,130 @@ impl IRGenerator {
     /// # Arguments
     /// * `ternary` - 三元表达式
     pub fn generate_ternary_expression(&mut self, ternary: &TernaryExpr) -> cayResult<String> {
-        // 创建标签
-        let then_label = self.new_label("ternary.then");
-        let else_label = self.new_label("ternary.else");
-        let end_label = self.new_label("ternary.end");
-
         // 生成条件表达式
         let cond_result = self.generate_expression(&ternary.condition)?;
-        let (cond_type, cond_val) = self.parse_typed_value(&cond_result);
-        let cond_reg = self.new_temp();
+        let cond_reg = self.ternary_condition(&cond_result);
 
-        // 将条件转换为 i1 类型
-        if cond_type == "i1" {
-            self.emit_line(&format!("  {} = icmp ne i1 {}, 0", cond_reg, cond_val));
-        } else {
-            // 对于整数类型，先与 0 比较
-            self.emit_line(&format!("  {} = icmp ne {} {}, 0", cond_reg, cond_type, cond_val));
+        if Self::is_side_effect_free(&ternary.true_branch) && Self::is_side_effect_free(&ternary.false_branch) {
+            return self.generate_ternary_select(ternary, &cond_reg);
         }
 
+        // 创建标签
+        let then_label = self.new_label("ternary.then");
+        let else_label = self.new_label("ternary.else");
+        let then_exit_label = self.new_label("ternary.then.exit");
+        let else_exit_label = self.new_label("ternary.else.exit");
+        let end_label = self.new_label("ternary.end");
+
         // 条件分支
         self.emit_line(&format!("  br i1 {}, label %{}, label %{}", cond_reg, then_label, else_label));
 
-        // then 分支
+        // then 分支：只有条件为真时才求值
         self.emit_line(&format!("\n{}:", then_label));
         let then_result = self.generate_expression(&ternary.true_branch)?;
         let (then_type, then_val) = self.parse_typed_value(&then_result);
-        let then_temp = self.new_temp();
-        self.emit_line(&format!("  {} = add {} {}, 0", then_temp, then_type, then_val));
-        self.emit_line(&format!("  br label %{}", end_label));
+        self.emit_line(&format!("  br label %{}", then_exit_label));
 
-        // else 分支
+        // else 分支：只有条件为假时才求值
         self.emit_line(&format!("\n{}:", else_label));
         let else_result = self.generate_expression(&ternary.false_branch)?;
         let (else_type, else_val) = self.parse_typed_value(&else_result);
-        let else_temp = self.new_temp();
-        self.emit_line(&format!("  {} = add {} {}, 0", else_temp, else_type, else_val));
+        self.emit_line(&format!("  br label %{}", else_exit_label));
+
+        // 分支表达式内部可能产生新的基本块（嵌套三元、除零检查等），
+        // 因此类型转换放在单独的出口块中，phi 的前驱也就是确定的
+        let result_type = Self::ternary_result_type(&then_type, &else_type);
+
+        self.emit_line(&format!("\n{}:", then_exit_label));
+        let then_val = self.convert_ternary_operand(&then_type, &then_val, &result_type, &ternary.true_branch);
+        self.emit_line(&format!("  br label %{}", end_label));
+
+        self.emit_line(&format!("\n{}:", else_exit_label));
+        let else_val = self.convert_ternary_operand(&else_type, &else_val, &result_type, &ternary.false_branch);
         self.emit_line(&format!("  br label %{}", end_label));
 
         // 合并点
         self.emit_line(&format!("\n{}:", end_label));
         let result_temp = self.new_temp();
         self.emit_line(&format!("  {} = phi {} [ {}, %{} ], [ {}, %{} ]",
-            result_temp, then_type, then_temp, then_label, else_temp, else_label));
+            result_temp, result_type, then_val, then_exit_label, else_val, else_exit_label));
+
+        Ok(format!("{} {}", result_type, result_temp))
+    }
+
+    /// 两个分支都没有副作用时，同时求值并用 select 选择结果
+    fn generate_ternary_select(&mut self, ternary: &TernaryExpr, cond_reg: &str) -> cayResult<String> {
+        let then_result = self.generate_expression(&ternary.true_branch)?;
+        let (then_type, then_val) = self.parse_typed_value(&then_result);
+        let else_result = self.generate_expression(&ternary.false_branch)?;
+        let (else_type, else_val) = self.parse_typed_value(&else_result);
+
+        let result_type = Self::ternary_result_type(&then_type, &else_type);
+        let then_val = self.convert_ternary_operand(&then_type, &then_val, &result_type, &ternary.true_branch);
+        let else_val = self.convert_ternary_operand(&else_type, &else_val, &result_type, &ternary.false_branch);
+
+        let result_temp = self.new_temp();
+        self.emit_line(&format!("  {} = select i1 {}, {} {}, {} {}",
+            result_temp, cond_reg, result_type, then_val, result_type, else_val));
+
+        Ok(format!("{} {}", result_type, result_temp))
+    }
+
+    /// 将条件转换为 i1 类型
+    fn ternary_condition(&mut self, cond_result: &str) -> String {
+        let (cond_type, cond_val) = self.parse_typed_value(cond_result);
+        if cond_type == "i1" {
+            return cond_val;
+        }
+
+        // 对于整数类型，先与 0 比较
+        let cond_reg = self.new_temp();
+        self.emit_line(&format!("  {} = icmp ne {} {}, 0", cond_reg, cond_type, cond_val));
+        cond_reg
+    }
+
+    /// 字面量和标识符求值没有副作用，可以无条件求值
+    fn is_side_effect_free(expr: &Expr) -> bool {
+        matches!(expr, Expr::Literal(_) | Expr::Identifier(_))
+    }
+
+    /// 计算两个分支的公共类型（与语义分析的数值提升规则一致）
+    fn ternary_result_type(then_type: &str, else_type: &str) -> String {
+        if then_type == else_type {
+            return then_type.to_string();
+        }
+
+        let is_float = |t: &str| t == "float" || t == "double";
+        let is_int = |t: &str| t.starts_with('i') && !t.ends_with('*');
+
+        if then_type == "double" || else_type == "double" {
+            "double".to_string()
+        } else if is_float(then_type) || is_float(else_type) {
+            "float".to_string()
+        } else if is_int(then_type) && is_int(else_type) {
+            let bits = |t: &str| t.trim_start_matches('i').parse::<u32>().unwrap_or(64);
+            if bits(then_type) >= bits(else_type) { then_type.to_string() } else { else_type.to_string() }
+        } else {
+            // 引用类型统一按 then 分支的类型处理
+            then_type.to_string()
+        }
+    }
+
+    /// 将分支结果转换为公共类型（char 分支按无符号值转换）
+    fn convert_ternary_operand(&mut self, ty: &str, val: &str, target: &str, source: &Expr) -> String {
+        if ty == target {
+            return val.to_string();
+        }
+
+        let instr = match (ty, target) {
+            ("float", "double") => "fpext",
+            (_, "float") | (_, "double") => self.int_to_float(ty, source),
+            _ if ty.ends_with('*') || target.ends_with('*') => "bitcast",
+            _ => self.int_extension(ty, source),
+        };
 
-        Ok(format!("{} {}", then_type, result_temp))
+        let temp = self.new_temp();
+        self.emit_line(&format!("  {} = {} {} {} to {}", temp, instr, ty, val, target));
+        temp
     }
 }
@@ -0,0 +1,103 @@
+//! 元组表达式代码生成
+//!
+//! 元组降级为 LLVM 字面结构体并按值传递：`(a, b)` 用 insertvalue 逐个填充元素，
+//! 解构时用 extractvalue 取出。元素类型不一致时（如 int 元素赋给 long 元素）逐元素转换。
+
+use crate::codegen::context::IRGenerator;
+use crate::ast::*;
+use crate::types::Type;
+use crate::error::cayResult;
+
+impl IRGenerator {
+    /// 生成元组表达式代码
+    pub fn generate_tuple_expression(&mut self, tuple: &TupleExpr) -> cayResult<String> {
+        let mut elements = Vec::with_capacity(tuple.elements.len());
+        for element in &tuple.elements {
+            let value = self.generate_expression(element)?;
+            elements.push(self.parse_typed_value(&value));
+        }
+
+        let tuple_type = format!("{{{}}}", elements.iter()
+            .map(|(ty, _)| ty.as_str())
+            .collect::<Vec<_>>()
+            .join(","));
+
+        let mut current = "undef".to_string();
+        for (index, (ty, val)) in elements.iter().enumerate() {
+            let temp = self.new_temp();
+            self.emit_line(&format!("  {} = insertvalue {} {}, {} {}, {}",
+                temp, tuple_type, current, ty, val, index));
+            current = temp;
+        }
+
+        Ok(format!("{} {}", tuple_type, current))
+    }
+
+    /// 取出元组的第 index 个元素，返回 "type value"
+    pub fn extract_tuple_element(&mut self, tuple_type: &str, val: &str, index: usize) -> String {
+        let element_type = self.tuple_element_llvm_types(tuple_type)
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| "i64".to_string());
+        let temp = self.new_temp();
+        self.emit_line(&format!("  {} = extractvalue {} {}, {}", temp, tuple_type, val, index));
+        format!("{} {}", element_type, temp)
+    }
+
+    /// 将元组值逐元素转换为目标元组类型，返回转换后的值
+    ///
+    /// `source` 是元组值的表达式，由其静态类型区分 char 和 int8 元素（都是 i8）。
+    pub fn convert_tuple_value(&mut self, value_type: &str, val: &str, target_type: &str, source: Option<&Expr>) -> String {
+        if value_type == target_type {
+            return val.to_string();
+        }
+        let element_types = match source.and_then(|expr| self.expr_static_type(expr)) {
+            Some(Type::Tuple(types)) => types,
+            _ => Vec::new(),
+        };
+
+        let target_elements = self.tuple_element_llvm_types(target_type);
+        let mut current = "undef".to_string();
+        for (index, target_element) in target_elements.iter().enumerate() {
+            let element = self.extract_tuple_element(value_type, val, index);
+            let (element_type, element_val) = self.parse_typed_value(&element);
+            let converted = self.convert_tuple_element(&element_type, &element_val, target_element, element_types.get(index));
+            let temp = self.new_temp();
+            self.emit_line(&format!("  {} = insertvalue {} {}, {} {}, {}",
+                temp, target_type, current, target_element, converted, index));
+            current = temp;
+        }
+        current
+    }
+
+    /// 转换单个元组元素（与变量初始化的隐式转换规则一致，char 元素按无符号值转换）
+    fn convert_tuple_element(&mut self, ty: &str, val: &str, target: &str, static_type: Option<&Type>) -> String {
+        if ty == target {
+            return val.to_string();
+        }
+        if self.is_tuple_type(ty) && self.is_tuple_type(target) {
+            return self.convert_tuple_value(ty, val, target, None);
+        }
+
+        let signed = ty != "i8" || static_type == Some(&Type::Int8);
+        let instr = match (ty, target) {
+            ("float", "double") => "fpext",
+            ("double", "float") => "fptrunc",
+            (_, "float") | (_, "double") => if signed { "sitofp" } else { "uitofp" },
+            ("float", _) | ("double", _) => "fptosi",
+            _ if ty.ends_with('*') || target.ends_with('*') => "bitcast",
+            _ => {
+                let bits = |t: &str| t.trim_start_matches('i').parse::<u32>().unwrap_or(64);
+                match (bits(target) > bits(ty), signed) {
+                    (true, true) => "sext",
+                    (true, false) => "zext",
+                    (false, _) => "trunc",
+                }
+            }
+        };
+
+        let temp = self.new_temp();
+        self.emit_line(&format!("  {} = {} {} {} to {}", temp, instr, ty, val, target));
+        temp
+    }
+}
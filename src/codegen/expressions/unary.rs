@@ -1,6 +1,6 @@
 //! 一元表达式代码生成
 //!
-//! 处理取负、逻辑非、位取反和自增/自减操作。
+//! 处理取负、取正、逻辑非、位取反和自增/自减操作。
 
 use crate::codegen::context::IRGenerator;
 use crate::ast::*;
@@ -13,19 +13,33 @@ impl IRGenerator {
     /// * `unary` - 一元表达式
     pub fn generate_unary_expression(&mut self, unary: &UnaryExpr) -> cayResult<String> {
         let operand = self.generate_expression(&unary.operand)?;
-        let (op_type, op_val) = self.parse_typed_value(&operand);
+        let (mut op_type, mut op_val) = self.parse_typed_value(&operand);
+
+        // 与二元运算一致，char/int8（i8）和 int16（i16）取正负前先提升为 i32
+        if matches!(unary.op, UnaryOp::Neg | UnaryOp::Plus) && (op_type == "i8" || op_type == "i16") {
+            let ext = self.int_extension(&op_type, &unary.operand);
+            let promoted = self.new_temp();
+            self.emit_line(&format!("  {} = {} {} {} to i32", promoted, ext, op_type, op_val));
+            op_type = "i32".to_string();
+            op_val = promoted;
+        }
+        if unary.op == UnaryOp::Plus {
+            return Ok(format!("{} {}", op_type, op_val));
+        }
+
         let temp = self.new_temp();
         
         match unary.op {
             UnaryOp::Neg => {
                 if op_type.starts_with("i") {
-                    self.emit_line(&format!("  {} = sub {} 0, {}",
-                        temp, op_type, op_val));
+                    self.emit_line(&format!("  {} = sub{} {} 0, {}",
+                        temp, Self::signed_overflow_flag(&op_type), op_type, op_val));
                 } else {
                     self.emit_line(&format!("  {} = fneg {} {}",
                         temp, op_type, op_val));
                 }
             }
+            UnaryOp::Plus => unreachable!("unary + returns its promoted operand above"),
             UnaryOp::Not => {
                 self.emit_line(&format!("  {} = xor {} {}, 1",
                     temp, op_type, op_val));
@@ -70,6 +84,7 @@ impl IRGenerator {
         
         // 计算新值
         let new_temp = self.new_temp();
+        // 常量的类型由指令上的操作数类型决定；1.0 在 float 和 double 中都能精确表示
         let one = if llvm_type == "float" || llvm_type == "double" { "1.0" } else { "1" };
         if llvm_type == "float" || llvm_type == "double" {
             if is_inc {
@@ -81,11 +96,11 @@ impl IRGenerator {
             }
         } else {
             if is_inc {
-                self.emit_line(&format!("  {} = add {} {}, {}",
-                    new_temp, llvm_type, load_temp, one));
+                self.emit_line(&format!("  {} = add{} {} {}, {}",
+                    new_temp, Self::signed_overflow_flag(&llvm_type), llvm_type, load_temp, one));
             } else {
-                self.emit_line(&format!("  {} = sub {} {}, {}",
-                    new_temp, llvm_type, load_temp, one));
+                self.emit_line(&format!("  {} = sub{} {} {}, {}",
+                    new_temp, Self::signed_overflow_flag(&llvm_type), llvm_type, load_temp, one));
             }
         }
         
@@ -4,9 +4,220 @@
 
 use crate::codegen::context::IRGenerator;
 use crate::ast::*;
+use crate::types::{Type, LIST_CLASS};
 use crate::error::{cayResult, codegen_error};
 
 impl IRGenerator {
+    /// 有符号整数算术（add/sub/mul）的溢出标志
+    ///
+    /// Cavvy 的 int/long 都是有符号整数，默认的非检查模式下加上 nsw，
+    /// 让 LLVM 可以利用"有符号溢出未定义"做优化。Cavvy 没有无符号整数类型，
+    /// 因此不会生成 nuw；char（i8）运算已在提升后进行，这里不加标志。
+    pub fn signed_overflow_flag(llvm_type: &str) -> &'static str {
+        match llvm_type {
+            "i32" | "i64" => " nsw",
+            _ => "",
+        }
+    }
+
+    /// i8 值扩展为更宽整数时使用的指令
+    ///
+    /// char 和 int8 在 LLVM 中都是 i8：char 取字符码，按无符号零扩展；int8 按有符号符号扩展。
+    pub fn byte_extension(&self, expr: &Expr) -> &'static str {
+        if self.expr_static_type(expr) == Some(Type::Int8) { "sext" } else { "zext" }
+    }
+
+    /// LLVM 类型为 `from` 的整数值扩展为更宽整数时使用的指令：i8 见 `byte_extension`，其余整数符号扩展
+    pub fn int_extension(&self, from: &str, source: &Expr) -> &'static str {
+        if from == "i8" { self.byte_extension(source) } else { "sext" }
+    }
+
+    /// LLVM 类型为 `from` 的整数值转换为浮点数时使用的指令：char 按无符号值转换
+    pub fn int_to_float(&self, from: &str, source: &Expr) -> &'static str {
+        if self.int_extension(from, source) == "zext" { "uitofp" } else { "sitofp" }
+    }
+
+    /// LLVM 类型为 `from` 的实参能否通过拓宽传给 `to` 类型的参数
+    ///
+    /// 与语义分析的 `ClassInfo::types_match` 对应；i8 同时代表 char 和 int8，null 字面量是 i64 0。
+    pub fn llvm_widens_to(from: &str, to: &str) -> bool {
+        from == to || matches!((from, to),
+            ("i8", "i16" | "i32" | "i64" | "float" | "double")
+            | ("i16", "i32" | "i64" | "float" | "double")
+            | ("i32", "i64" | "float" | "double")
+            | ("i64", "double")
+            | ("float", "double"))
+            || (from == "i64" && to.ends_with('*'))
+    }
+
+    /// 把值转换为参数类型 `target`：整数扩展、整数转浮点、float/double 互转，null 转为指针
+    ///
+    /// 方法调用、构造函数、printf、List 元素和自定义内置函数的实参共用这套规则。
+    /// i8 按 `source` 的静态类型扩展（int8 有符号，char 无符号）；无需或无法转换时原样返回。
+    pub fn widen_value(&mut self, value: &str, source: Option<&Expr>, target: &str) -> String {
+        let (from, val) = self.parse_typed_value(value);
+        let signed_byte = source.is_some_and(|expr| self.byte_extension(expr) == "sext");
+        let instr = match (from.as_str(), target) {
+            (f, t) if f == t => return value.to_string(),
+            ("i1", "i8" | "i16" | "i32" | "i64") => "zext",
+            ("i8", "i16" | "i32" | "i64") => if signed_byte { "sext" } else { "zext" },
+            ("i8", "float" | "double") => if signed_byte { "sitofp" } else { "uitofp" },
+            ("i16", "i32" | "i64") | ("i32", "i64") => "sext",
+            ("i16" | "i32" | "i64", "float" | "double") => "sitofp",
+            ("float", "double") => "fpext",
+            ("double", "float") => "fptrunc",
+            ("i64", t) if t.ends_with('*') => "inttoptr",
+            (f, t) if f.ends_with('*') && t.ends_with('*') => "bitcast",
+            _ => return value.to_string(),
+        };
+        let temp = self.new_temp();
+        self.emit_line(&format!("  {} = {} {} {} to {}", temp, instr, from, val, target));
+        format!("{} {}", target, temp)
+    }
+
+    /// 表达式的静态类型是否为 String
+    ///
+    /// String 和对象引用在 LLVM 中都是 i8*，`==` 需要据此区分内容比较和引用比较。
+    /// 无法确定类型的表达式视为非字符串，按引用比较。
+    pub fn is_string_expr(&self, expr: &Expr) -> bool {
+        self.expr_static_type(expr) == Some(Type::String)
+    }
+
+    /// 表达式的静态类型（Cavvy 类型），无法确定时返回 None
+    ///
+    /// 由变量、参数、字段、数组元素、方法和内置函数声明的类型推导，
+    /// 用于 LLVM 类型相同而语义不同的情况（char/int8 都是 i8，String/对象引用都是 i8*）。
+    pub fn expr_static_type(&self, expr: &Expr) -> Option<Type> {
+        match expr {
+            Expr::Literal(lit) => match lit {
+                LiteralValue::Int32(_) => Some(Type::Int32),
+                LiteralValue::Int64(_) => Some(Type::Int64),
+                LiteralValue::Float32(_) => Some(Type::Float32),
+                LiteralValue::Float64(_) => Some(Type::Float64),
+                LiteralValue::String(_) => Some(Type::String),
+                LiteralValue::Bool(_) => Some(Type::Bool),
+                LiteralValue::Char(_) => Some(Type::Char),
+                LiteralValue::Null => None,
+            },
+            Expr::Identifier(name) if name == "this" => {
+                Some(Type::Object(self.current_class.clone())).filter(|_| !self.current_class.is_empty())
+            }
+            Expr::Identifier(name) => match self.scope_manager.lookup_var(name) {
+                Some(var) => var.cay_type.clone(),
+                // 隐式 this 的实例字段或当前类的静态字段
+                None => self.field_static_type(&self.current_class, name),
+            },
+            Expr::MemberAccess(member) => {
+                if member.member == "length" && matches!(self.expr_static_type(&member.object), Some(Type::Array(_))) {
+                    return Some(Type::Int32);
+                }
+                let owner = match (self.resolve_object_class(&member.object), member.object.as_ref()) {
+                    (Some(class_name), _) => class_name,
+                    (None, Expr::Identifier(class_name)) => class_name.clone(),
+                    _ => return None,
+                };
+                self.field_static_type(&owner, &member.member)
+            }
+            Expr::ArrayAccess(access) => match self.expr_static_type(&access.array)? {
+                Type::Array(element) => Some(*element),
+                _ => None,
+            },
+            Expr::Cast(cast) => Some(cast.target_type.clone()),
+            Expr::Call(call) => self.call_static_type(call),
+            Expr::New(new_expr) => Some(Type::Object(new_expr.class_name.clone())),
+            Expr::Tuple(tuple) => tuple.elements.iter()
+                .map(|element| self.expr_static_type(element))
+                .collect::<Option<Vec<_>>>()
+                .map(Type::Tuple),
+            Expr::Assignment(assign) => self.expr_static_type(&assign.target),
+            Expr::Ternary(ternary) => {
+                let ty = self.expr_static_type(&ternary.true_branch)?;
+                (self.expr_static_type(&ternary.false_branch)? == ty).then_some(ty)
+            }
+            Expr::Unary(unary) => {
+                let operand = self.expr_static_type(&unary.operand)?;
+                match unary.op {
+                    UnaryOp::Not => Some(Type::Bool),
+                    // 与二元运算一致，char/int8/int16 取正负时提升为 int
+                    UnaryOp::Neg | UnaryOp::Plus if matches!(operand, Type::Char | Type::Int8 | Type::Int16) => Some(Type::Int32),
+                    _ => Some(operand),
+                }
+            }
+            Expr::Binary(bin) => match bin.op {
+                BinaryOp::Eq | BinaryOp::Ne | BinaryOp::Is | BinaryOp::IsNot
+                | BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge
+                | BinaryOp::And | BinaryOp::Or => Some(Type::Bool),
+                _ => {
+                    let left = self.expr_static_type(&bin.left);
+                    let right = self.expr_static_type(&bin.right);
+                    if bin.op == BinaryOp::Add && (left == Some(Type::String) || right == Some(Type::String)) {
+                        return Some(Type::String);
+                    }
+                    match (left?, right?) {
+                        (Type::Bool, Type::Bool) => Some(Type::Bool),
+                        (left, right) => [Type::Float64, Type::Float32, Type::Int64].into_iter()
+                            .find(|ty| left == *ty || right == *ty)
+                            .or(Some(Type::Int32)),
+                    }
+                }
+            },
+            _ => None,
+        }
+    }
+
+    /// 字段的声明类型：类（含父类）的实例字段或该类的静态字段
+    fn field_static_type(&self, class_name: &str, field_name: &str) -> Option<Type> {
+        if class_name.is_empty() {
+            return None;
+        }
+        self.get_instance_field(class_name, field_name)
+            .map(|field| field.field_type.clone())
+            .or_else(|| self.static_field_map.get(&format!("{}.{}", class_name, field_name)).map(|f| f.field_type.clone()))
+    }
+
+    /// 调用的返回类型：内置函数、String/StringView 方法、数组 clone()、List 方法和类方法
+    fn call_static_type(&self, call: &CallExpr) -> Option<Type> {
+        let (class_name, method_name) = match call.callee.as_ref() {
+            Expr::Identifier(name) if !self.current_class_has_method(name) => {
+                return match name.as_str() {
+                    "toString" | "valueOf" | "readLine" | "readFile" => Some(Type::String),
+                    "readChar" => Some(Type::Char),
+                    "readBool" | "writeFile" | "arrayEquals" => Some(Type::Bool),
+                    _ => None,
+                };
+            }
+            Expr::Identifier(name) => (self.current_class.clone(), name.as_str()),
+            Expr::MemberAccess(member) => {
+                let class_name = match (self.expr_static_type(&member.object), member.object.as_ref()) {
+                    (Some(Type::String), _) => "String".to_string(),
+                    (Some(Type::StringView), _) => "StringView".to_string(),
+                    (Some(array @ Type::Array(_)), _) if member.member == "clone" => return Some(array),
+                    (Some(Type::Object(class_name)), _) if class_name == LIST_CLASS => return self.call_return_type(call),
+                    (Some(Type::Object(class_name)), _) => class_name,
+                    (None, Expr::Identifier(class_name)) if class_name == "String" && member.member == "valueOf" => {
+                        return Some(Type::String);
+                    }
+                    // 静态方法调用
+                    (None, Expr::Identifier(class_name)) => class_name.clone(),
+                    _ => return None,
+                };
+                (class_name, member.member.as_str())
+            }
+            _ => return None,
+        };
+        let registry = self.type_registry.as_ref()?;
+        let arg_types: Option<Vec<Type>> = call.args.iter().map(|arg| self.expr_static_type(arg)).collect();
+        if let Some(method) = arg_types.and_then(|types| registry.find_method(&class_name, method_name, &types)) {
+            return Some(method.return_type.clone());
+        }
+        // 实参类型无法全部确定时，取参数个数相同且返回类型一致的重载
+        let mut return_types = registry.method_candidates(&class_name, method_name).into_iter()
+            .filter(|method| method.params.len() == call.args.len())
+            .map(|method| &method.return_type);
+        let first = return_types.next()?;
+        return_types.all(|ty| ty == first).then(|| first.clone())
+    }
+
     /// 提升整数操作数到相同类型
     ///
     /// # Arguments
@@ -27,17 +238,15 @@ impl IRGenerator {
             return (left_type.to_string(), left_val.to_string(), right_val.to_string());
         }
         
-        // char (i8) 类型在算术运算中需要提升到 i32
-        let target_type = if left_type == "i8" || right_type == "i8" {
-            "i32"
-        } else if left_type == right_type {
+        // char/int8 (i8) 和 int16 (i16) 在算术运算中至少提升到 i32
+        let is_narrow = |ty: &str| ty == "i8" || ty == "i16";
+        if left_type == right_type && !is_narrow(left_type) {
             return (left_type.to_string(), left_val.to_string(), right_val.to_string());
-        } else {
-            // 确定提升后的类型（选择位数更大的类型）
-            let left_bits: u32 = left_type.trim_start_matches('i').parse().unwrap_or(64);
-            let right_bits: u32 = right_type.trim_start_matches('i').parse().unwrap_or(64);
-            if left_bits >= right_bits { left_type } else { right_type }
-        };
+        }
+        // 确定提升后的类型（选择位数更大的类型）
+        let left_bits: u32 = left_type.trim_start_matches('i').parse().unwrap_or(64);
+        let right_bits: u32 = right_type.trim_start_matches('i').parse().unwrap_or(64);
+        let target_type = format!("i{}", left_bits.max(right_bits).max(32));
         
         // 提升左操作数
         let promoted_left = if left_type != target_type {
@@ -57,7 +266,7 @@ impl IRGenerator {
             right_val.to_string()
         };
         
-        (target_type.to_string(), promoted_left, promoted_right)
+        (target_type, promoted_left, promoted_right)
     }
     
     /// 提升浮点操作数到相同类型
@@ -199,8 +408,13 @@ impl IRGenerator {
         // 错误处理块
         self.emit_line(&format!("{}:", error_label));
         // 输出错误信息到 stderr
-        let error_msg = self.get_or_create_string_constant("Error: Division by zero\n");
-        self.emit_line(&format!("  call i32 (i8*, ...) @printf(i8* {})", error_msg));
+        let message = "Error: Division by zero\n";
+        let error_msg = self.get_or_create_string_constant(message);
+        let msg_len = message.len() + 1;
+        let msg_ptr = self.new_temp();
+        self.emit_line(&format!("  {} = getelementptr [{} x i8], [{} x i8]* {}, i64 0, i64 0",
+            msg_ptr, msg_len, msg_len, error_msg));
+        self.emit_line(&format!("  call i32 (i8*, ...) @printf(i8* {})", msg_ptr));
         // 调用 exit 退出程序
         self.emit_line("  call void @exit(i32 1)");
         self.emit_line("  unreachable");
@@ -220,8 +434,16 @@ impl IRGenerator {
             "double" => "d".to_string(),
             "i1" => "b".to_string(),
             "i8*" => "s".to_string(),
+            "%__cay_string_view" => "sv".to_string(),
             "i8" => "c".to_string(),
             t if t.ends_with("*") => "o".to_string(), // 对象/数组指针
+            t if self.is_tuple_type(t) => {
+                let elements = self.tuple_element_llvm_types(t);
+                format!("t{}_{}", elements.len(), elements.iter()
+                    .map(|element| self.llvm_type_to_signature(element))
+                    .collect::<Vec<_>>()
+                    .join("_"))
+            }
             _ => "x".to_string(), // 未知类型
         }
     }
@@ -81,6 +81,7 @@ impl PlatformAbstraction {
 impl IRGenerator {
     pub fn generate(&mut self, program: &Program) -> cayResult<String> {
         self.emit_header();
+        let header_end = self.output.len();
 
         let mut main_class = None;
         let mut main_method = None;
@@ -96,15 +97,29 @@ impl IRGenerator {
             }
         }
 
-        // 首先计算所有类的实例布局
-        for class in &program.classes {
-            let instance_fields: Vec<_> = class.members.iter()
-                .filter_map(|m| match m {
-                    ClassMember::Field(f) => Some(f.clone()),
-                    _ => None,
-                })
-                .collect();
-            self.compute_class_layout(&class.name, &instance_fields);
+        // 首先计算所有类的实例布局（父类先于子类计算，子类布局以父类字段开头）
+        let mut pending: Vec<&ClassDecl> = program.classes.iter().collect();
+        while !pending.is_empty() {
+            let before = pending.len();
+            pending.retain(|class| {
+                let parent = class.parent.as_deref()
+                    .filter(|p| program.classes.iter().any(|c| c.name == *p));
+                if parent.is_some_and(|p| self.get_class_layout(p).is_none()) {
+                    return true;
+                }
+                let instance_fields: Vec<_> = class.members.iter()
+                    .filter_map(|m| match m {
+                        ClassMember::Field(f) => Some(f.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                self.compute_class_layout(&class.name, parent, &instance_fields);
+                false
+            });
+            // 继承关系成环时语义分析已经报错，这里只防止死循环
+            if pending.len() == before {
+                break;
+            }
         }
 
         for class in &program.classes {
@@ -143,20 +158,28 @@ impl IRGenerator {
             self.generate_top_level_function(func)?;
         }
 
+        // 先登记所有类的方法签名，方法体之间（包括跨类）可以任意前向引用
         for class in &program.classes {
-            self.generate_class(class)?;
+            self.emit_method_declarations(class)?;
+        }
+
+        for class in &program.classes {
+            self.generate_method_bodies(class)?;
         }
 
         self.output.push_str(&self.code);
 
-        // 生成跨平台 C entry point
-        if use_top_level_main {
+        // 生成跨平台 C entry point（库模式下由宿主程序提供入口）
+        if self.library_mode {
+            self.emit_library_initializer();
+        } else if use_top_level_main {
             // 使用顶层 main 函数
             let func = top_level_main.unwrap();
             self.output.push_str("; Cross-platform C entry point\n");
             self.output.push_str(&format!("define i32 @main() {{
 "));
             self.output.push_str("entry:\n");
+            self.reset_function_counters();
             
             // 使用平台配置生成初始化代码
             let platform_init = self.generate_platform_init();
@@ -179,6 +202,7 @@ impl IRGenerator {
             self.output.push_str("; C entry point\n");
             self.output.push_str(&format!("define i32 @main() {{\n"));
             self.output.push_str("entry:\n");
+            self.reset_function_counters();
             // 只在 Windows 目标平台上设置控制台代码页
             if self.is_windows_target() {
                 self.output.push_str("  call void @SetConsoleOutputCP(i32 65001)\n");
@@ -195,14 +219,21 @@ impl IRGenerator {
             self.output.push_str(lambda_code);
         }
 
+        // 所有调用都已生成，此时才知道需要哪些运行时函数
+        self.emit_runtime_functions()?;
+        self.emit_builtin_declarations();
+
         let string_decls = self.get_string_declarations();
         let type_id_decls = self.emit_type_id_declarations();
 
         let mut output = self.output.clone();
-        let insert_pos = output.find("define i8* @__cay_string_concat")
-            .unwrap_or(output.len());
+        let insert_pos = header_end;
 
         let mut decls = String::new();
+        if !self.method_declarations.is_empty() {
+            decls.push_str(&self.method_declarations.concat());
+            decls.push('\n');
+        }
         if !type_id_decls.is_empty() {
             decls.push_str(&type_id_decls);
             decls.push_str("\n");
@@ -342,6 +373,25 @@ impl IRGenerator {
         }
     }
 
+    /// 库模式下没有 main 负责初始化静态数组字段，改为通过 llvm.global_ctors 在加载时初始化
+    fn emit_library_initializer(&mut self) {
+        self.reset_function_counters();
+        let init_start = self.output.len();
+        self.generate_static_array_initialization();
+        if self.output.len() == init_start {
+            return;
+        }
+        let body = self.output.split_off(init_start);
+        self.output.push_str("; Library initializer\n");
+        self.output.push_str("define internal void @__cay_library_init() {\n");
+        self.output.push_str("entry:\n");
+        self.output.push_str(&body);
+        self.output.push_str("  ret void\n");
+        self.output.push_str("}\n");
+        self.output.push_str("@llvm.global_ctors = appending global [1 x { i32, void ()*, i8* }] [{ i32, void ()*, i8* } { i32 65535, void ()* @__cay_library_init, i8* null }]\n");
+        self.output.push('\n');
+    }
+
     fn generate_static_array_initialization(&mut self) {
         let fields: Vec<_> = self.static_fields.clone();
         for field in fields {
@@ -352,7 +402,8 @@ impl IRGenerator {
                             if let Some(size_val) = self.evaluate_const_int(&array_creation.sizes[0]) {
                                 let elem_llvm_type = self.type_to_llvm(elem_type);
                                 let elem_size = self.get_type_size(&elem_llvm_type);
-                                let total_size = size_val as i64 * elem_size;
+                                // 数据前留 8 字节存放长度，与局部数组的布局一致
+                                let total_size = size_val as i64 * elem_size + 8;
 
                                 let calloc_temp = self.new_temp();
                                 self.output.push_str(&format!(
@@ -360,10 +411,22 @@ impl IRGenerator {
                                     calloc_temp, total_size
                                 ));
 
+                                let len_ptr = self.new_temp();
+                                self.output.push_str(&format!(
+                                    "  {} = bitcast i8* {} to i32*\n  store i32 {}, i32* {}, align 4\n",
+                                    len_ptr, calloc_temp, size_val, len_ptr
+                                ));
+
+                                let data_ptr = self.new_temp();
+                                self.output.push_str(&format!(
+                                    "  {} = getelementptr i8, i8* {}, i64 8\n",
+                                    data_ptr, calloc_temp
+                                ));
+
                                 let cast_temp = self.new_temp();
                                 self.output.push_str(&format!(
                                     "  {} = bitcast i8* {} to {}*\n",
-                                    cast_temp, calloc_temp, elem_llvm_type
+                                    cast_temp, data_ptr, elem_llvm_type
                                 ));
 
                                 self.output.push_str(&format!(
@@ -378,10 +441,34 @@ impl IRGenerator {
         }
     }
 
-    fn evaluate_const_int(&self, expr: &Expr) -> Option<i64> {
+    /// 在编译期求值整数表达式（字面量、const 常量及其算术组合）
+    pub(crate) fn evaluate_const_int(&self, expr: &Expr) -> Option<i64> {
+        let constant_value = |info: &crate::types::ConstantInfo| match info.value {
+            crate::ast::LiteralValue::Int32(n) => Some(n as i64),
+            crate::ast::LiteralValue::Int64(n) => Some(n),
+            crate::ast::LiteralValue::Char(c) => Some(c as i64),
+            _ => None,
+        };
         match expr {
             Expr::Literal(crate::ast::LiteralValue::Int32(n)) => Some(*n as i64),
             Expr::Literal(crate::ast::LiteralValue::Int64(n)) => Some(*n),
+            Expr::Literal(crate::ast::LiteralValue::Char(c)) => Some(*c as i64),
+            // 同名局部变量和参数遮蔽常量，不是编译期常量
+            Expr::Identifier(name) if self.scope_manager.get_var_type(name).is_some() => None,
+            Expr::Identifier(name) => {
+                let registry = self.type_registry.as_ref()?;
+                let class_name = Some(self.current_class.as_str()).filter(|c| !c.is_empty());
+                registry.resolve_constant(class_name, name).and_then(constant_value)
+            }
+            Expr::MemberAccess(member) => match member.object.as_ref() {
+                Expr::Identifier(owner) => self.type_registry.as_ref()?
+                    .get_class_constant(owner, &member.member)
+                    .and_then(constant_value),
+                _ => None,
+            },
+            Expr::Unary(unary) if matches!(unary.op, crate::ast::UnaryOp::Neg) => {
+                self.evaluate_const_int(&unary.operand).map(|n| -n)
+            }
             Expr::Binary(binary) => {
                 let left = self.evaluate_const_int(&binary.left)?;
                 let right = self.evaluate_const_int(&binary.right)?;
@@ -409,37 +496,49 @@ impl IRGenerator {
         }
     }
 
-    fn generate_class_declarations(&mut self, class: &ClassDecl) -> cayResult<()> {
+    /// 在生成任何方法体之前登记类中所有方法的签名
+    ///
+    /// LLVM 模块内函数的定义顺序无关紧要，但同一函数不能既 declare 又 define，
+    /// 因此只有在本模块中没有函数体的方法（native 方法）才输出 declare。
+    fn emit_method_declarations(&mut self, class: &ClassDecl) -> cayResult<()> {
         for member in &class.members {
-            if let ClassMember::Method(method) = member {
-                if !method.modifiers.contains(&Modifier::Native) {
-                    self.generate_method_declaration(&class.name, method)?;
-                }
+            let ClassMember::Method(method) = member else {
+                continue;
+            };
+            if method.body.is_some() && !method.modifiers.contains(&Modifier::Native) {
+                continue;
+            }
+            let (ret_type, fn_name, params) = self.method_signature(&class.name, method);
+            let param_types: Vec<&str> = params.iter().map(|(ty, _)| ty.as_str()).collect();
+            let decl = format!("declare {} @{}({})\n", ret_type, fn_name, param_types.join(", "));
+            if !self.method_declarations.contains(&decl) {
+                self.method_declarations.push(decl);
             }
         }
         Ok(())
     }
 
-    fn generate_method_declaration(&mut self, class_name: &str, method: &MethodDecl) -> cayResult<()> {
+    /// 方法的返回类型、修饰后的函数名和形参列表（LLVM 类型, 参数名）
+    fn method_signature(&self, class_name: &str, method: &MethodDecl) -> (String, String, Vec<(String, String)>) {
         let fn_name = self.generate_method_name(class_name, method);
         let ret_type = self.type_to_llvm(&method.return_type);
 
-        let decl = if method.params.is_empty() {
-            format!("declare {} @{}()\n", ret_type, fn_name)
-        } else {
-            let params: Vec<String> = method.params.iter()
-                .map(|p| self.type_to_llvm(&p.param_type))
-                .collect();
-            format!("declare {} @{}({})\n", ret_type, fn_name, params.join(", "))
-        };
-        
-        if !self.method_declarations.contains(&decl) {
-            self.method_declarations.push(decl);
+        let mut params = Vec::new();
+
+        // 实例方法添加 this 参数
+        if !method.modifiers.contains(&Modifier::Static) {
+            params.push(("i8*".to_string(), "%this".to_string()));
         }
-        Ok(())
+
+        for param in &method.params {
+            params.push((self.type_to_llvm(&param.param_type), format!("%{}.{}", class_name, param.name)));
+        }
+
+        (ret_type, fn_name, params)
     }
 
-    fn generate_class(&mut self, class: &ClassDecl) -> cayResult<()> {
+    /// 生成类中所有方法、构造函数、析构函数和静态初始化块的函数体
+    fn generate_method_bodies(&mut self, class: &ClassDecl) -> cayResult<()> {
         for member in &class.members {
             match member {
                 ClassMember::Method(method) => {
@@ -468,32 +567,28 @@ impl IRGenerator {
     }
 
     fn generate_method(&mut self, class_name: &str, method: &MethodDecl) -> cayResult<()> {
-        let fn_name = self.generate_method_name(class_name, method);
+        let (ret_type, fn_name, params) = self.method_signature(class_name, method);
         self.current_function = fn_name.clone();
         self.current_class = class_name.to_string();
-        self.current_return_type = self.type_to_llvm(&method.return_type);
+        self.current_return_type = ret_type.clone();
 
-        self.temp_counter = 0;
+        self.reset_function_counters();
         self.var_types.clear();
+        self.array_class_map.clear();
         self.scope_manager.reset();
         self.loop_stack.clear();
+        self.label_stack.clear();
 
-        let ret_type = self.current_return_type.clone();
         let is_static = method.modifiers.contains(&Modifier::Static);
-        
-        let mut params: Vec<String> = Vec::new();
-        
-        // 实例方法添加 this 参数
-        if !is_static {
-            params.push("i8* %this".to_string());
-        }
-        
-        for param in &method.params {
-            params.push(format!("{} %{}.{}", self.type_to_llvm(&param.param_type), class_name, param.name));
-        }
+        // @inline 方法加上 alwaysinline 属性
+        let attrs = if method.modifiers.contains(&Modifier::Inline) { " alwaysinline" } else { "" };
 
-        self.emit_line(&format!("define {} @{}({}) {{",
-            ret_type, fn_name, params.join(", ")));
+        // 库模式下只导出 public 方法，其余方法仅在模块内部可见
+        let linkage = if self.library_mode && !method.modifiers.contains(&Modifier::Public) { "internal " } else { "" };
+
+        let params: Vec<String> = params.iter().map(|(ty, name)| format!("{} {}", ty, name)).collect();
+        self.emit_line(&format!("define {}{} @{}({}){} {{",
+            linkage, ret_type, fn_name, params.join(", "), attrs));
         self.indent += 1;
 
         self.emit_line("entry:");
@@ -510,10 +605,18 @@ impl IRGenerator {
         for param in &method.params {
             let param_type = self.type_to_llvm(&param.param_type);
             let llvm_name = self.scope_manager.declare_var(&param.name, &param_type);
+            self.scope_manager.set_cay_type(&param.name, &param.param_type);
             self.emit_line(&format!("  %{} = alloca {}", llvm_name, param_type));
             self.emit_line(&format!("  store {} %{}.{}, {}* %{}",
                 param_type, class_name, param.name, param_type, llvm_name));
             self.var_types.insert(param.name.clone(), param_type);
+            self.register_param_class(param);
+            // List 参数沿用语义分析从调用处记录的元素类型，并覆盖其他方法中同名变量的记录
+            let list_key = crate::types::TypeRegistry::list_param_key(class_name, &method.name, &param.name);
+            match self.type_registry.as_ref().and_then(|r| r.list_slot_element(&list_key)).cloned() {
+                Some(element) => { self.list_element_map.insert(param.name.clone(), element); }
+                None => { self.list_element_map.remove(&param.name); }
+            }
         }
 
         if let Some(body) = method.body.as_ref() {
@@ -531,16 +634,28 @@ impl IRGenerator {
         Ok(())
     }
 
+    /// 对象参数记录类名、对象数组参数记录元素类名，以便访问其实例字段
+    fn register_param_class(&mut self, param: &crate::types::ParameterInfo) {
+        if let Type::Object(param_class) = &param.param_type {
+            self.var_class_map.insert(param.name.clone(), param_class.clone());
+        }
+        if let Some(elem_class) = Self::object_array_class(&param.param_type) {
+            self.array_class_map.insert(param.name.clone(), elem_class.clone());
+        }
+    }
+
     fn generate_constructor(&mut self, class_name: &str, ctor: &crate::ast::ConstructorDecl) -> cayResult<()> {
         let fn_name = self.generate_constructor_name(class_name, ctor);
         self.current_function = fn_name.clone();
         self.current_class = class_name.to_string();
         self.current_return_type = "void".to_string();
 
-        self.temp_counter = 0;
+        self.reset_function_counters();
         self.var_types.clear();
+        self.array_class_map.clear();
         self.scope_manager.reset();
         self.loop_stack.clear();
+        self.label_stack.clear();
 
         let params: Vec<String> = ctor.params.iter()
             .map(|p| format!("{} %{}.{}_param", self.type_to_llvm(&p.param_type), class_name, p.name))
@@ -555,7 +670,7 @@ impl IRGenerator {
 
         self.emit_line("entry:");
 
-        let this_llvm_name = self.scope_manager.declare_var("this", "i8*");
+        let this_llvm_name = self.scope_manager.declare_var("this_ptr", "i8*");
         self.emit_line(&format!("  %{} = alloca i8*", this_llvm_name));
         self.emit_line(&format!("  store i8* %this, i8** %{}", this_llvm_name));
         self.var_types.insert("this".to_string(), "i8*".to_string());
@@ -563,10 +678,12 @@ impl IRGenerator {
         for param in &ctor.params {
             let param_type = self.type_to_llvm(&param.param_type);
             let llvm_name = self.scope_manager.declare_var(&param.name, &param_type);
+            self.scope_manager.set_cay_type(&param.name, &param.param_type);
             self.emit_line(&format!("  %{} = alloca {}", llvm_name, param_type));
             self.emit_line(&format!("  store {} %{}.{}_param, {}* %{}",
                 param_type, class_name, param.name, param_type, llvm_name));
             self.var_types.insert(param.name.clone(), param_type);
+            self.register_param_class(param);
         }
 
         if let Some(ref call) = ctor.constructor_call {
@@ -617,17 +734,19 @@ impl IRGenerator {
         self.current_class = class_name.to_string();
         self.current_return_type = "void".to_string();
 
-        self.temp_counter = 0;
+        self.reset_function_counters();
         self.var_types.clear();
+        self.array_class_map.clear();
         self.scope_manager.reset();
         self.loop_stack.clear();
+        self.label_stack.clear();
 
         self.emit_line(&format!("define void @{}(i8* %this) {{", fn_name));
         self.indent += 1;
 
         self.emit_line("entry:");
 
-        let this_llvm_name = self.scope_manager.declare_var("this", "i8*");
+        let this_llvm_name = self.scope_manager.declare_var("this_ptr", "i8*");
         self.emit_line(&format!("  %{} = alloca i8*", this_llvm_name));
         self.emit_line(&format!("  store i8* %this, i8** %{}", this_llvm_name));
         self.var_types.insert("this".to_string(), "i8*".to_string());
@@ -649,10 +768,12 @@ impl IRGenerator {
         self.current_class = class_name.to_string();
         self.current_return_type = "void".to_string();
 
-        self.temp_counter = 0;
+        self.reset_function_counters();
         self.var_types.clear();
+        self.array_class_map.clear();
         self.scope_manager.reset();
         self.loop_stack.clear();
+        self.label_stack.clear();
 
         self.emit_line(&format!("define void @{}() {{", fn_name));
         self.indent += 1;
@@ -697,10 +818,12 @@ impl IRGenerator {
         self.current_class = String::new(); // 顶层函数没有类
         self.current_return_type = self.type_to_llvm(&func.return_type);
 
-        self.temp_counter = 0;
+        self.reset_function_counters();
         self.var_types.clear();
+        self.array_class_map.clear();
         self.scope_manager.reset();
         self.loop_stack.clear();
+        self.label_stack.clear();
 
         let ret_type = self.current_return_type.clone();
         let params: Vec<String> = func.params.iter()
@@ -716,10 +839,12 @@ impl IRGenerator {
         for param in &func.params {
             let param_type = self.type_to_llvm(&param.param_type);
             let llvm_name = self.scope_manager.declare_var(&param.name, &param_type);
+            self.scope_manager.set_cay_type(&param.name, &param.param_type);
             self.emit_line(&format!("  %{} = alloca {}", llvm_name, param_type));
             self.emit_line(&format!("  store {} %{}.param, {}* %{}",
                 param_type, param.name, param_type, llvm_name));
             self.var_types.insert(param.name.clone(), param_type);
+            self.register_param_class(param);
         }
 
         self.generate_block(&func.body)?;
@@ -0,0 +1,311 @@
+//! 小型静态方法内联
+//!
+//! 在生成的 IR 文本上运行（--inline-small）：只有一个基本块、指令数不超过阈值、
+//! 在整个模块中只被直接调用一次的静态方法，其函数体被复制到调用处。
+//! 被内联方法中的 alloca 移到调用者的 entry 块，避免在循环中反复分配栈空间。
+//! 原函数定义保留（库模式下可能被外部调用），由 LLVM 优化时删除。
+
+use std::collections::HashMap;
+
+/// 最多重复内联的轮数
+///
+/// 被内联的方法本身又调用了其他小方法时，需要再做一轮才能把嵌套的调用也展开。
+const MAX_PASSES: usize = 8;
+
+/// 可以内联的函数
+struct Candidate {
+    /// 参数的 (类型, 寄存器名)，寄存器名不含 `%`
+    params: Vec<(String, String)>,
+    /// 函数体中除 ret 之外的指令
+    body: Vec<String>,
+    /// ret 的类型和值，`ret void` 时为 None
+    ret: Option<(String, String)>,
+}
+
+/// 小型静态方法内联器
+pub struct SmallFunctionInliner {
+    threshold: usize,
+    counter: usize,
+}
+
+impl SmallFunctionInliner {
+    /// 创建内联器，指令数（不含标签）不超过 `threshold` 的函数才会被内联
+    pub fn new(threshold: usize) -> Self {
+        Self { threshold, counter: 0 }
+    }
+
+    /// 内联整个 IR 模块中符合条件的调用
+    pub fn inline_ir(&mut self, ir: &str) -> String {
+        let mut ir = ir.to_string();
+        for _ in 0..MAX_PASSES {
+            let before = self.counter;
+            ir = self.inline_pass(&ir);
+            if self.counter == before {
+                break;
+            }
+        }
+        ir
+    }
+
+    /// 一轮内联：每个候选函数在其唯一的调用点展开一次
+    fn inline_pass(&mut self, ir: &str) -> String {
+        let lines: Vec<&str> = ir.lines().collect();
+        let candidates = self.find_candidates(&lines);
+
+        let mut output: Vec<String> = Vec::with_capacity(lines.len());
+        let mut in_function = false;
+        // 当前函数 entry 标签之后的位置，以及需要移到那里的 alloca
+        let mut entry_pos: Option<usize> = None;
+        let mut hoisted: Vec<String> = Vec::new();
+
+        for line in &lines {
+            let trimmed = line.trim();
+            if trimmed.starts_with("define ") {
+                in_function = true;
+            } else if in_function && trimmed == "}" {
+                in_function = false;
+                if let Some(pos) = entry_pos.take() {
+                    output.splice(pos..pos, hoisted.drain(..));
+                }
+            }
+
+            let expanded = entry_pos
+                .and_then(|_| Self::parse_call(trimmed))
+                .and_then(|(result, name, args)| {
+                    let callee = candidates.get(name)?;
+                    self.expand(name, callee, result, &args)
+                });
+
+            match expanded {
+                Some((allocas, body)) => {
+                    let indent = &line[..line.len() - line.trim_start().len()];
+                    hoisted.extend(allocas.into_iter().map(|l| format!("{}{}", indent, l)));
+                    output.extend(body.into_iter().map(|l| format!("{}{}", indent, l)));
+                }
+                None => {
+                    output.push(line.to_string());
+                    if in_function && entry_pos.is_none() && trimmed.ends_with(':') {
+                        entry_pos = Some(output.len());
+                    }
+                }
+            }
+        }
+
+        let mut result = output.join("\n");
+        if ir.ends_with('\n') {
+            result.push('\n');
+        }
+        result
+    }
+
+    /// 找出所有可以内联的函数，按函数名索引
+    fn find_candidates(&self, lines: &[&str]) -> HashMap<String, Candidate> {
+        let mut candidates = HashMap::new();
+        let mut i = 0;
+        while i < lines.len() {
+            let header = lines[i].trim();
+            i += 1;
+            if !(header.starts_with("define ") && header.ends_with('{')) {
+                continue;
+            }
+            let body: Vec<&str> = lines[i..].iter()
+                .map(|l| l.trim())
+                .take_while(|l| *l != "}")
+                .collect();
+            i += body.len();
+            if let Some((name, candidate)) = self.candidate(header, &body)
+                .filter(|(name, _)| Self::single_call_site(lines, name))
+            {
+                candidates.insert(name, candidate);
+            }
+        }
+        candidates
+    }
+
+    /// 判断函数是否可以内联
+    ///
+    /// 只考虑类的静态方法（名称形如 `Class.method`，没有 `%this` 参数），
+    /// 函数体必须只有一个基本块并以唯一的 ret 结束，且不调用自身。
+    fn candidate(&self, header: &str, body: &[&str]) -> Option<(String, Candidate)> {
+        let name_start = header.find('@')? + 1;
+        let open = name_start + header[name_start..].find('(')?;
+        let close = header.rfind(')')?;
+        let name = header[name_start..open].to_string();
+        let ret_type = header["define ".len()..name_start - 1].split_whitespace().last()?;
+        if !name.contains('.') || ret_type.starts_with(['{', '[', '%']) {
+            return None;
+        }
+
+        let mut params = Vec::new();
+        for param in split_args(&header[open + 1..close]) {
+            let (ty, reg) = param.rsplit_once(' ')?;
+            let reg = reg.strip_prefix('%')?;
+            if reg == "this" {
+                return None;
+            }
+            params.push((ty.trim().to_string(), reg.to_string()));
+        }
+
+        let instructions: Vec<&str> = body.iter()
+            .map(|l| l.split(';').next().unwrap_or("").trim())
+            .filter(|l| !l.is_empty())
+            .collect();
+        let labels = instructions.iter().filter(|l| l.ends_with(':')).count();
+        let (last, rest) = instructions.split_last()?;
+        let rest: Vec<&str> = rest.iter().copied().filter(|l| !l.ends_with(':')).collect();
+        let self_call = format!("@{}(", name);
+        if labels != 1
+            || rest.len() + 1 > self.threshold
+            || !last.starts_with("ret ")
+            || rest.iter().any(|l| is_control_flow(l) || l.contains(&self_call))
+        {
+            return None;
+        }
+
+        let ret = match last["ret ".len()..].trim() {
+            "void" => None,
+            typed => {
+                let (ty, value) = typed.rsplit_once(' ')?;
+                Some((ty.to_string(), value.to_string()))
+            }
+        };
+
+        let body = rest.iter().map(|l| l.to_string()).collect();
+        Some((name, Candidate { params, body, ret }))
+    }
+
+    /// 函数在整个模块中只被引用一次，且这次引用是直接调用
+    fn single_call_site(lines: &[&str], name: &str) -> bool {
+        let mut references = lines.iter()
+            .filter(|l| !l.trim_start().starts_with("define "))
+            .map(|l| l.split(';').next().unwrap_or(""))
+            .flat_map(|l| std::iter::repeat_n(l, count_references(l, name)));
+        match (references.next(), references.next()) {
+            (Some(line), None) => Self::parse_call(line.trim()).is_some_and(|(_, callee, _)| callee == name),
+            _ => false,
+        }
+    }
+
+    /// 解析直接调用指令，返回 (结果寄存器, 被调用函数名, 实参列表)
+    fn parse_call(line: &str) -> Option<(Option<&str>, &str, Vec<&str>)> {
+        let (result, call) = match line.split_once(" = ") {
+            Some((lhs, rhs)) if lhs.starts_with('%') => (Some(lhs), rhs),
+            _ => (None, line),
+        };
+        let call = call.strip_prefix("call ")?;
+        let name_start = call.find('@')? + 1;
+        let open = name_start + call[name_start..].find('(')?;
+        let close = call.rfind(')')?;
+        // 可变参数函数的调用形如 `call i32 (i8*, ...) @printf(...)`，不会是内联候选
+        if call[..name_start].contains('(') || close + 1 != call.len() {
+            return None;
+        }
+        Some((result, &call[name_start..open], split_args(&call[open + 1..close])))
+    }
+
+    /// 展开一次调用，返回 (需要移到 entry 块的 alloca, 替换调用指令的代码)
+    ///
+    /// 实参与形参的类型不一致时不展开。
+    fn expand(&mut self, name: &str, callee: &Candidate, result: Option<&str>, args: &[&str]) -> Option<(Vec<String>, Vec<String>)> {
+        if args.len() != callee.params.len() {
+            return None;
+        }
+        // 形参替换为实参的值，函数内定义的寄存器加前缀避免与调用者冲突
+        let mut renames = HashMap::new();
+        for ((ty, reg), arg) in callee.params.iter().zip(args) {
+            let value = arg.strip_prefix(ty.as_str())?.trim();
+            renames.insert(reg.clone(), value.to_string());
+        }
+        let prefix = format!("inl{}.", self.counter);
+        self.counter += 1;
+        for line in &callee.body {
+            if let Some(reg) = line.split_once(" = ").and_then(|(lhs, _)| lhs.strip_prefix('%')) {
+                renames.insert(reg.to_string(), format!("%{}{}", prefix, reg));
+            }
+        }
+
+        let mut allocas = Vec::new();
+        let mut body = vec![format!("; 内联 @{}", name)];
+        for line in &callee.body {
+            let renamed = rename_registers(line, &renames);
+            if renamed.contains(" = alloca ") {
+                allocas.push(renamed);
+            } else {
+                body.push(renamed);
+            }
+        }
+        if let (Some(result), Some((ty, value))) = (result, &callee.ret) {
+            let value = rename_registers(value, &renames);
+            body.push(format!("{} = bitcast {} {} to {}", result, ty, value, ty));
+        }
+        Some((allocas, body))
+    }
+}
+
+/// 改变控制流的指令，出现在函数体中时不内联
+fn is_control_flow(line: &str) -> bool {
+    let op = line.split_once(" = ").map_or(line, |(_, rhs)| rhs);
+    ["br ", "switch ", "indirectbr ", "invoke ", "resume ", "unreachable", "phi ", "ret "]
+        .iter()
+        .any(|kw| op.starts_with(kw))
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '$')
+}
+
+/// 行中对 `@name` 的完整引用次数（不计以 name 为前缀的其他函数）
+fn count_references(line: &str, name: &str) -> usize {
+    let pattern = format!("@{}", name);
+    line.match_indices(&pattern)
+        .filter(|(pos, _)| !line[pos + pattern.len()..].starts_with(is_ident_char))
+        .count()
+}
+
+/// 按顶层逗号拆分参数列表，忽略括号内的逗号
+fn split_args(list: &str) -> Vec<&str> {
+    let mut args = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, c) in list.char_indices() {
+        match c {
+            '(' | '[' | '{' | '<' => depth += 1,
+            ')' | ']' | '}' | '>' => depth -= 1,
+            ',' if depth == 0 => {
+                args.push(list[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    let last = list[start..].trim();
+    if !last.is_empty() {
+        args.push(last);
+    }
+    args
+}
+
+/// 按映射替换行中的寄存器名，未出现在映射中的寄存器（以及 `%` 开头的类型名）保持不变
+fn rename_registers(line: &str, renames: &HashMap<String, String>) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        let mut end = i + 1;
+        while let Some(&(j, next)) = chars.peek() {
+            if !is_ident_char(next) {
+                break;
+            }
+            end = j + next.len_utf8();
+            chars.next();
+        }
+        match renames.get(&line[i + 1..end]) {
+            Some(replacement) => out.push_str(replacement),
+            None => out.push_str(&line[i..end]),
+        }
+    }
+    out
+}
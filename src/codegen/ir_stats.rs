@@ -0,0 +1,84 @@
+//! LLVM IR 指令统计
+//!
+//! 解析生成的 IR 文本，统计每个函数体内的指令条数，
+//! 供 `--dump-ir-stats` 模式输出。
+
+use std::fmt;
+
+/// 单个函数的指令统计
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionStats {
+    pub name: String,
+    pub instructions: usize,
+}
+
+/// 整个模块的指令统计
+#[derive(Debug, Clone, Default)]
+pub struct IrStats {
+    pub functions: Vec<FunctionStats>,
+}
+
+impl IrStats {
+    /// 从 IR 文本中收集统计信息
+    ///
+    /// 只统计 `define` 函数体内的指令行，标签、注释和空行不计入。
+    pub fn from_ir(ir: &str) -> Self {
+        let mut functions = Vec::new();
+        let mut current: Option<FunctionStats> = None;
+
+        for line in ir.lines() {
+            let trimmed = line.trim();
+
+            match current.as_mut() {
+                None => {
+                    if trimmed.starts_with("define ") {
+                        current = Self::function_name(trimmed)
+                            .map(|name| FunctionStats { name, instructions: 0 });
+                    }
+                }
+                Some(_) if trimmed == "}" => functions.extend(current.take()),
+                Some(func) => {
+                    if Self::is_instruction(trimmed) {
+                        func.instructions += 1;
+                    }
+                }
+            }
+        }
+
+        Self { functions }
+    }
+
+    /// 所有函数的指令总数
+    pub fn total(&self) -> usize {
+        self.functions.iter().map(|f| f.instructions).sum()
+    }
+
+    /// 按函数名查找统计
+    pub fn get(&self, name: &str) -> Option<&FunctionStats> {
+        self.functions.iter().find(|f| f.name == name)
+    }
+
+    /// 从 `define ... @name(...)` 行中提取函数名
+    fn function_name(line: &str) -> Option<String> {
+        let start = line.find('@')? + 1;
+        let rest = &line[start..];
+        let end = rest.find('(')?;
+        Some(rest[..end].trim_matches('"').to_string())
+    }
+
+    /// 判断函数体内的一行是否为指令
+    fn is_instruction(line: &str) -> bool {
+        !(line.is_empty() || line.starts_with(';') || line.ends_with(':'))
+    }
+}
+
+impl fmt::Display for IrStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "IR 指令统计:")?;
+        let width = self.functions.iter().map(|func| func.name.len()).max().unwrap_or(0);
+        for func in &self.functions {
+            writeln!(f, "  {:<width$}  {}", func.name, func.instructions, width = width)?;
+        }
+        write!(f, "  共 {} 个函数，{} 条指令", self.functions.len(), self.total())
+    }
+}
@@ -11,6 +11,9 @@ pub mod runtime;
 mod generator;
 mod platform;
 pub mod obfuscator;
+pub mod ir_stats;
+pub mod inliner;
+pub mod source_map;
 
 // 公开 IRGenerator 作为代码生成器的入口
 pub use context::IRGenerator;
\ No newline at end of file
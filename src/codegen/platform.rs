@@ -2,6 +2,7 @@
 #[derive(Debug, Clone)]
 pub struct PlatformConfig {
     pub target_os: String,
+    pub target_triple: Option<String>,  // 显式指定的目标三元组
     pub features: Vec<String>,
     pub no_features: Vec<String>,
     pub defines: Vec<String>,
@@ -13,6 +14,7 @@ impl PlatformConfig {
     pub fn new(target_os: &str) -> Self {
         Self {
             target_os: target_os.to_string(),
+            target_triple: None,
             features: Vec::new(),
             no_features: Vec::new(),
             defines: Vec::new(),
@@ -0,0 +1,92 @@
+//! 文件读写运行时函数
+
+use crate::codegen::context::IRGenerator;
+
+impl IRGenerator {
+    /// 生成文件读写相关的运行时函数
+    ///
+    /// C 库的文件函数只在程序用到 readFile / writeFile 时才声明。
+    pub(super) fn emit_file_io_runtime(&mut self) {
+        self.emit_raw("declare i8* @fopen(i8*, i8*)");
+        self.emit_raw("declare i32 @fclose(i8*)");
+        self.emit_raw("declare i64 @fread(i8*, i64, i64, i8*)");
+        self.emit_raw("declare i64 @fwrite(i8*, i64, i64, i8*)");
+        self.emit_raw("@.str.file_mode_read = private unnamed_addr constant [3 x i8] c\"rb\\00\", align 1");
+        self.emit_raw("@.str.file_mode_write = private unnamed_addr constant [3 x i8] c\"wb\\00\", align 1");
+        self.emit_raw("");
+        self.emit_read_file_runtime();
+        self.emit_write_file_runtime();
+    }
+
+    /// 生成读取整个文件内容的运行时函数
+    ///
+    /// 按块读取到可增长的缓冲区中（容量不足时翻倍），不依赖 fseek/ftell，
+    /// 因此也适用于管道等无法定位的文件。文件无法打开时返回空字符串。
+    fn emit_read_file_runtime(&mut self) {
+        self.emit_raw("define i8* @__cay_read_file(i8* %path) {");
+        self.emit_raw("entry:");
+        self.emit_raw("  %mode = getelementptr [3 x i8], [3 x i8]* @.str.file_mode_read, i64 0, i64 0");
+        self.emit_raw("  %file = call i8* @fopen(i8* %path, i8* %mode)");
+        self.emit_raw("  %missing = icmp eq i8* %file, null");
+        self.emit_raw("  br i1 %missing, label %not_found, label %read_start");
+        self.emit_raw("");
+        self.emit_raw("not_found:");
+        self.emit_raw("  ret i8* getelementptr ([1 x i8], [1 x i8]* @.cay_empty_str, i64 0, i64 0)");
+        self.emit_raw("");
+        self.emit_raw("read_start:");
+        self.emit_raw("  %init_buf = call i8* @calloc(i64 1, i64 4096)");
+        self.emit_raw("  br label %read_loop");
+        self.emit_raw("");
+        self.emit_raw("read_loop:");
+        self.emit_raw("  %buf = phi i8* [ %init_buf, %read_start ], [ %grown, %grow ]");
+        self.emit_raw("  %cap = phi i64 [ 4096, %read_start ], [ %new_cap, %grow ]");
+        self.emit_raw("  %len = phi i64 [ 0, %read_start ], [ %new_len, %grow ]");
+        self.emit_raw("  ; 留出一个字节给结尾的 0");
+        self.emit_raw("  %dest = getelementptr i8, i8* %buf, i64 %len");
+        self.emit_raw("  %free = sub i64 %cap, %len");
+        self.emit_raw("  %room = sub i64 %free, 1");
+        self.emit_raw("  %count = call i64 @fread(i8* %dest, i64 1, i64 %room, i8* %file)");
+        self.emit_raw("  %new_len = add i64 %len, %count");
+        self.emit_raw("  %at_end = icmp ult i64 %count, %room");
+        self.emit_raw("  br i1 %at_end, label %done, label %grow");
+        self.emit_raw("");
+        self.emit_raw("grow:");
+        self.emit_raw("  %new_cap = mul i64 %cap, 2");
+        self.emit_raw("  %grown = call i8* @realloc(i8* %buf, i64 %new_cap)");
+        self.emit_raw("  br label %read_loop");
+        self.emit_raw("");
+        self.emit_raw("done:");
+        self.emit_raw("  %end = getelementptr i8, i8* %buf, i64 %new_len");
+        self.emit_raw("  store i8 0, i8* %end");
+        self.emit_raw("  call i32 @fclose(i8* %file)");
+        self.emit_raw("  ret i8* %buf");
+        self.emit_raw("}");
+        self.emit_raw("");
+    }
+
+    /// 生成把字符串写入文件的运行时函数
+    ///
+    /// 覆盖写入整个字符串；打开失败、写入不完整或关闭失败时返回 false。
+    fn emit_write_file_runtime(&mut self) {
+        self.emit_raw("define i1 @__cay_write_file(i8* %path, i8* %content) {");
+        self.emit_raw("entry:");
+        self.emit_raw("  %mode = getelementptr [3 x i8], [3 x i8]* @.str.file_mode_write, i64 0, i64 0");
+        self.emit_raw("  %file = call i8* @fopen(i8* %path, i8* %mode)");
+        self.emit_raw("  %failed = icmp eq i8* %file, null");
+        self.emit_raw("  br i1 %failed, label %open_failed, label %write");
+        self.emit_raw("");
+        self.emit_raw("open_failed:");
+        self.emit_raw("  ret i1 false");
+        self.emit_raw("");
+        self.emit_raw("write:");
+        self.emit_raw("  %len = call i64 @strlen(i8* %content)");
+        self.emit_raw("  %written = call i64 @fwrite(i8* %content, i64 1, i64 %len, i8* %file)");
+        self.emit_raw("  %close_rc = call i32 @fclose(i8* %file)");
+        self.emit_raw("  %complete = icmp eq i64 %written, %len");
+        self.emit_raw("  %closed = icmp eq i32 %close_rc, 0");
+        self.emit_raw("  %ok = and i1 %complete, %closed");
+        self.emit_raw("  ret i1 %ok");
+        self.emit_raw("}");
+        self.emit_raw("");
+    }
+}
@@ -4,18 +4,96 @@ use crate::codegen::context::IRGenerator;
 
 impl IRGenerator {
     /// 生成浮点数转字符串运行时函数
+    ///
+    /// 输出能精确还原该值的最短十进制表示：`3.14` 为 "3.14"，整数值保留一位小数
+    /// （`3.0` 为 "3.0"）。绝对值小于 1e-4 或不小于 1e16 的值使用指数形式（如 "1e+20"）。
+    /// float 按单精度判断是否还原，因此 `3.14f` 也输出 "3.14"。
     pub(super) fn emit_float_to_string_runtime(&mut self) {
-        // 使用一个包装函数来确保正确的调用约定
-        // 注意：使用 calloc 分配堆内存（自动零初始化），而不是 alloca 分配栈内存
-        self.emit_raw("define i8* @__cay_float_to_string(double %value) {");
+        self.emit_raw("declare double @strtod(i8*, i8**)");
+        self.emit_raw("declare i8* @strpbrk(i8*, i8*)");
+        self.emit_raw("declare double @llvm.fabs.f64(double)");
+        self.emit_raw("@.str.float_fixed_fmt = private unnamed_addr constant [5 x i8] c\"%.*f\\00\", align 1");
+        self.emit_raw("@.str.float_sci_fmt = private unnamed_addr constant [5 x i8] c\"%.*g\\00\", align 1");
+        self.emit_raw("@.str.float_marks = private unnamed_addr constant [5 x i8] c\".eni\\00\", align 1");
+        self.emit_raw("");
+        self.emit_shortest_float_to_string("__cay_float_to_string", "double");
+        self.emit_shortest_float_to_string("__cay_float32_to_string", "float");
+        self.emit_float_point_suffix_runtime();
+    }
+
+    /// 生成逐步增加精度、直到 strtod 能还原原值的转换函数
+    ///
+    /// 分配堆内存（calloc）作为 64 字节的结果缓冲区，最长输出（`%.24f`）也不会超出。
+    fn emit_shortest_float_to_string(&mut self, name: &str, ty: &str) {
+        self.emit_raw(&format!("define i8* @{}({} %value) {{", name, ty));
         self.emit_raw("entry:");
-        self.emit_raw("  ; 分配堆内存缓冲区（64字节，8字节对齐，使用 calloc 自动零初始化）");
+        if ty == "float" {
+            self.emit_raw("  %wide = fpext float %value to double");
+        }
+        let wide = if ty == "float" { "%wide" } else { "%value" };
         self.emit_raw("  %buf = call i8* @calloc(i64 1, i64 64)");
-        self.emit_raw("  %fmt_ptr = getelementptr [3 x i8], [3 x i8]* @.str.float_fmt, i64 0, i64 0");
-        self.emit_raw("  ; 调用 snprintf（指定缓冲区大小）");
-        self.emit_raw("  call i32 (i8*, i64, i8*, ...) @snprintf(i8* %buf, i64 64, i8* %fmt_ptr, double %value)");
+        self.emit_raw(&format!("  %abs = call double @llvm.fabs.f64(double {})", wide));
+        self.emit_raw("  ; 过小（非零）、过大或 NaN 使用指数形式，其余使用定点形式");
+        self.emit_raw("  %tiny = fcmp olt double %abs, 0x3F1A36E2EB1C432D");
+        self.emit_raw("  %nonzero = fcmp one double %abs, 0.0");
+        self.emit_raw("  %small = and i1 %tiny, %nonzero");
+        self.emit_raw("  %large = fcmp uge double %abs, 1.000000e+16");
+        self.emit_raw("  %sci = or i1 %small, %large");
+        self.emit_raw("  %fixed_fmt = getelementptr [5 x i8], [5 x i8]* @.str.float_fixed_fmt, i64 0, i64 0");
+        self.emit_raw("  %sci_fmt = getelementptr [5 x i8], [5 x i8]* @.str.float_sci_fmt, i64 0, i64 0");
+        self.emit_raw("  %fmt = select i1 %sci, i8* %sci_fmt, i8* %fixed_fmt");
+        self.emit_raw("  ; %g 的精度是有效数字位数（至少 1），%f 的精度是小数位数（可以为 0）");
+        self.emit_raw("  %start = zext i1 %sci to i32");
+        self.emit_raw("  br label %try");
+        self.emit_raw("");
+        self.emit_raw("try:");
+        self.emit_raw("  %prec = phi i32 [%start, %entry], [%next, %retry]");
+        self.emit_raw(&format!("  call i32 (i8*, i64, i8*, ...) @snprintf(i8* %buf, i64 64, i8* %fmt, i32 %prec, double {})", wide));
+        self.emit_raw("  %parsed = call double @strtod(i8* %buf, i8** null)");
+        if ty == "float" {
+            self.emit_raw("  %narrow = fptrunc double %parsed to float");
+            self.emit_raw("  %exact = fcmp oeq float %narrow, %value");
+        } else {
+            self.emit_raw("  %exact = fcmp oeq double %parsed, %value");
+        }
+        self.emit_raw("  %exhausted = icmp sge i32 %prec, 24");
+        self.emit_raw("  %done = or i1 %exact, %exhausted");
+        self.emit_raw("  br i1 %done, label %finish, label %retry");
+        self.emit_raw("");
+        self.emit_raw("retry:");
+        self.emit_raw("  %next = add i32 %prec, 1");
+        self.emit_raw("  br label %try");
+        self.emit_raw("");
+        self.emit_raw("finish:");
+        self.emit_raw("  call void @__cay_float_point_suffix(i8* %buf)");
         self.emit_raw("  ret i8* %buf");
         self.emit_raw("}");
         self.emit_raw("");
     }
+
+    /// 生成为整数形式的结果追加 ".0" 的辅助函数
+    ///
+    /// 已包含小数点、指数或 nan/inf 时保持不变。
+    fn emit_float_point_suffix_runtime(&mut self) {
+        self.emit_raw("define private void @__cay_float_point_suffix(i8* %buf) {");
+        self.emit_raw("entry:");
+        self.emit_raw("  %marks = getelementptr [5 x i8], [5 x i8]* @.str.float_marks, i64 0, i64 0");
+        self.emit_raw("  %found = call i8* @strpbrk(i8* %buf, i8* %marks)");
+        self.emit_raw("  %plain = icmp eq i8* %found, null");
+        self.emit_raw("  br i1 %plain, label %append, label %done");
+        self.emit_raw("");
+        self.emit_raw("append:");
+        self.emit_raw("  %len = call i64 @strlen(i8* %buf)");
+        self.emit_raw("  %point = getelementptr i8, i8* %buf, i64 %len");
+        self.emit_raw("  store i8 46, i8* %point");
+        self.emit_raw("  %zero_pos = add i64 %len, 1");
+        self.emit_raw("  %zero = getelementptr i8, i8* %buf, i64 %zero_pos");
+        self.emit_raw("  store i8 48, i8* %zero");
+        self.emit_raw("  br label %done");
+        self.emit_raw("");
+        self.emit_raw("done:");
+        self.emit_raw("  ret void");
+        self.emit_raw("}");
+        self.emit_raw("");
+    }
 }
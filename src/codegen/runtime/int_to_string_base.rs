@@ -0,0 +1,54 @@
+//! 按进制将整数转字符串的运行时函数
+
+use crate::codegen::context::IRGenerator;
+
+impl IRGenerator {
+    /// 生成按指定进制（2~16）将整数转换为字符串的运行时函数
+    ///
+    /// 负数输出为 `-` 加绝对值（与 Java 的 `Long.toString(v, radix)` 一致），
+    /// 数字从缓冲区末尾向前写入，返回指向首个字符的指针。
+    pub(super) fn emit_int_to_string_base_runtime(&mut self) {
+        self.emit_raw("define i8* @__cay_int_to_string_base(i64 %value, i32 %base) {");
+        self.emit_raw("entry:");
+        self.emit_raw("  ; 64 位二进制数字 + 符号 + 结尾的 0，calloc 保证结尾为 0");
+        self.emit_raw("  %buf = call i8* @calloc(i64 1, i64 72)");
+        self.emit_raw("  %radix = sext i32 %base to i64");
+        self.emit_raw("  %is_neg = icmp slt i64 %value, 0");
+        self.emit_raw("  ; 取反后按无符号处理，最小值的绝对值也能正确表示");
+        self.emit_raw("  %negated = sub i64 0, %value");
+        self.emit_raw("  %magnitude = select i1 %is_neg, i64 %negated, i64 %value");
+        self.emit_raw("  br label %digit_loop");
+        self.emit_raw("");
+        self.emit_raw("digit_loop:");
+        self.emit_raw("  %n = phi i64 [ %magnitude, %entry ], [ %rest, %digit_loop ]");
+        self.emit_raw("  %pos = phi i64 [ 70, %entry ], [ %prev_pos, %digit_loop ]");
+        self.emit_raw("  %prev_pos = sub i64 %pos, 1");
+        self.emit_raw("  %digit = urem i64 %n, %radix");
+        self.emit_raw("  %rest = udiv i64 %n, %radix");
+        self.emit_raw("  %is_letter = icmp uge i64 %digit, 10");
+        self.emit_raw("  %letter = add i64 %digit, 87");
+        self.emit_raw("  %number = add i64 %digit, 48");
+        self.emit_raw("  %code = select i1 %is_letter, i64 %letter, i64 %number");
+        self.emit_raw("  %ch = trunc i64 %code to i8");
+        self.emit_raw("  %slot = getelementptr i8, i8* %buf, i64 %prev_pos");
+        self.emit_raw("  store i8 %ch, i8* %slot");
+        self.emit_raw("  %more = icmp ne i64 %rest, 0");
+        self.emit_raw("  br i1 %more, label %digit_loop, label %check_sign");
+        self.emit_raw("");
+        self.emit_raw("check_sign:");
+        self.emit_raw("  br i1 %is_neg, label %add_sign, label %done");
+        self.emit_raw("");
+        self.emit_raw("add_sign:");
+        self.emit_raw("  %sign_pos = sub i64 %prev_pos, 1");
+        self.emit_raw("  %sign_slot = getelementptr i8, i8* %buf, i64 %sign_pos");
+        self.emit_raw("  store i8 45, i8* %sign_slot");
+        self.emit_raw("  br label %done");
+        self.emit_raw("");
+        self.emit_raw("done:");
+        self.emit_raw("  %start = phi i64 [ %prev_pos, %check_sign ], [ %sign_pos, %add_sign ]");
+        self.emit_raw("  %result = getelementptr i8, i8* %buf, i64 %start");
+        self.emit_raw("  ret i8* %result");
+        self.emit_raw("}");
+        self.emit_raw("");
+    }
+}
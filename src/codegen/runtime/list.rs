@@ -0,0 +1,113 @@
+//! 内置 List 运行时函数
+
+use crate::codegen::context::IRGenerator;
+
+/// List 头部的结构类型：数据缓冲区、元素个数、容量
+const LIST_HEADER: &str = "{ i64*, i32, i32 }";
+
+/// 第一次扩容时分配的元素个数
+const INITIAL_CAPACITY: i32 = 8;
+
+impl IRGenerator {
+    /// 生成 List 运行时函数
+    ///
+    /// List 是 16 字节的头部 `{ i64* data, i32 size, i32 capacity }`，元素以 i64 存放在
+    /// 单独的堆缓冲区中，缓冲区满时容量翻倍（realloc）。越界读取打印错误并以状态码 1 退出。
+    pub(super) fn emit_list_runtime(&mut self) {
+        self.emit_raw("@.str.list_bounds_fmt = private unnamed_addr constant [48 x i8] c\"Error: List index %d out of bounds for size %d\\0A\\00\", align 1");
+        self.emit_raw("");
+        self.emit_list_new_runtime();
+        self.emit_list_add_runtime();
+        self.emit_list_get_runtime();
+        self.emit_list_size_runtime();
+    }
+
+    /// 分配空 List，缓冲区在第一次 add 时分配
+    fn emit_list_new_runtime(&mut self) {
+        self.emit_raw("define i8* @__cay_list_new() {");
+        self.emit_raw("entry:");
+        self.emit_raw("  %list = call i8* @calloc(i64 1, i64 16)");
+        self.emit_raw("  ret i8* %list");
+        self.emit_raw("}");
+        self.emit_raw("");
+    }
+
+    /// 追加元素，必要时扩容
+    fn emit_list_add_runtime(&mut self) {
+        self.emit_raw("define void @__cay_list_add(i8* %list, i64 %value) {");
+        self.emit_raw("entry:");
+        self.emit_raw(&format!("  %header = bitcast i8* %list to {}*", LIST_HEADER));
+        self.emit_raw(&format!("  %data_ptr = getelementptr {}, {}* %header, i32 0, i32 0", LIST_HEADER, LIST_HEADER));
+        self.emit_raw(&format!("  %size_ptr = getelementptr {}, {}* %header, i32 0, i32 1", LIST_HEADER, LIST_HEADER));
+        self.emit_raw(&format!("  %cap_ptr = getelementptr {}, {}* %header, i32 0, i32 2", LIST_HEADER, LIST_HEADER));
+        self.emit_raw("  %size = load i32, i32* %size_ptr");
+        self.emit_raw("  %cap = load i32, i32* %cap_ptr");
+        self.emit_raw("  %full = icmp sge i32 %size, %cap");
+        self.emit_raw("  br i1 %full, label %grow, label %store");
+        self.emit_raw("");
+        self.emit_raw("grow:");
+        self.emit_raw("  %is_empty = icmp eq i32 %cap, 0");
+        self.emit_raw("  %doubled = shl i32 %cap, 1");
+        self.emit_raw(&format!("  %new_cap = select i1 %is_empty, i32 {}, i32 %doubled", INITIAL_CAPACITY));
+        self.emit_raw("  %new_cap_wide = sext i32 %new_cap to i64");
+        self.emit_raw("  %bytes = mul i64 %new_cap_wide, 8");
+        self.emit_raw("  %old_data = load i64*, i64** %data_ptr");
+        self.emit_raw("  %old_raw = bitcast i64* %old_data to i8*");
+        self.emit_raw("  %new_raw = call i8* @realloc(i8* %old_raw, i64 %bytes)");
+        self.emit_raw("  %new_data = bitcast i8* %new_raw to i64*");
+        self.emit_raw("  store i64* %new_data, i64** %data_ptr");
+        self.emit_raw("  store i32 %new_cap, i32* %cap_ptr");
+        self.emit_raw("  br label %store");
+        self.emit_raw("");
+        self.emit_raw("store:");
+        self.emit_raw("  %data = load i64*, i64** %data_ptr");
+        self.emit_raw("  %index = sext i32 %size to i64");
+        self.emit_raw("  %slot = getelementptr i64, i64* %data, i64 %index");
+        self.emit_raw("  store i64 %value, i64* %slot");
+        self.emit_raw("  %next_size = add i32 %size, 1");
+        self.emit_raw("  store i32 %next_size, i32* %size_ptr");
+        self.emit_raw("  ret void");
+        self.emit_raw("}");
+        self.emit_raw("");
+    }
+
+    /// 读取元素，越界时报错退出
+    fn emit_list_get_runtime(&mut self) {
+        self.emit_raw("define i64 @__cay_list_get(i8* %list, i32 %index) {");
+        self.emit_raw("entry:");
+        self.emit_raw(&format!("  %header = bitcast i8* %list to {}*", LIST_HEADER));
+        self.emit_raw(&format!("  %size_ptr = getelementptr {}, {}* %header, i32 0, i32 1", LIST_HEADER, LIST_HEADER));
+        self.emit_raw("  %size = load i32, i32* %size_ptr");
+        self.emit_raw("  ; 无符号比较同时排除负数下标");
+        self.emit_raw("  %in_bounds = icmp ult i32 %index, %size");
+        self.emit_raw("  br i1 %in_bounds, label %load, label %out_of_bounds");
+        self.emit_raw("");
+        self.emit_raw("out_of_bounds:");
+        self.emit_raw("  %fmt = getelementptr [48 x i8], [48 x i8]* @.str.list_bounds_fmt, i64 0, i64 0");
+        self.emit_raw("  call i32 (i8*, ...) @printf(i8* %fmt, i32 %index, i32 %size)");
+        self.emit_raw("  call void @exit(i32 1)");
+        self.emit_raw("  unreachable");
+        self.emit_raw("");
+        self.emit_raw("load:");
+        self.emit_raw(&format!("  %data_ptr = getelementptr {}, {}* %header, i32 0, i32 0", LIST_HEADER, LIST_HEADER));
+        self.emit_raw("  %data = load i64*, i64** %data_ptr");
+        self.emit_raw("  %wide_index = sext i32 %index to i64");
+        self.emit_raw("  %slot = getelementptr i64, i64* %data, i64 %wide_index");
+        self.emit_raw("  %value = load i64, i64* %slot");
+        self.emit_raw("  ret i64 %value");
+        self.emit_raw("}");
+        self.emit_raw("");
+    }
+
+    /// 元素个数
+    fn emit_list_size_runtime(&mut self) {
+        self.emit_raw("define i32 @__cay_list_size(i8* %list) {");
+        self.emit_raw("entry:");
+        self.emit_raw(&format!("  %header = bitcast i8* %list to {}*", LIST_HEADER));
+        self.emit_raw(&format!("  %size_ptr = getelementptr {}, {}* %header, i32 0, i32 1", LIST_HEADER, LIST_HEADER));
+        self.emit_raw("  %size = load i32, i32* %size_ptr");
+        self.emit_raw("  ret i32 %size");
+        self.emit_raw("}");
+        self.emit_raw("");
+    }
+}
@@ -1,21 +1,30 @@
 //! 运行时支持函数生成模块
 //!
 //! 本模块包含所有 cay 运行时支持函数的 LLVM IR 生成。
-//! 每个运行时函数都有独立的子模块。
+//! 每个运行时函数都有独立的子模块，默认只输出程序实际调用到的函数。
 
 use crate::codegen::context::IRGenerator;
+use crate::error::{cayResult, codegen_error};
+use crate::RuntimeMode;
 
 // 子模块声明
 mod string_concat;
 mod float_to_string;
 mod int_to_string;
+mod int_to_string_base;
 mod bool_to_string;
 mod char_to_string;
 mod string_length;
+mod string_equals;
+mod string_compare;
+mod string_blank;
 mod string_substring;
 mod string_indexof;
 mod string_charat;
 mod string_replace;
+mod string_view;
+mod file_io;
+mod list;
 
 impl IRGenerator {
     /// 发射IR头部（外部声明和运行时函数）
@@ -24,10 +33,11 @@ impl IRGenerator {
         
         // 根据目标平台设置目标三元组
         let target_triple = if let Some(config) = &self.platform_config {
-            match config.target_os.as_str() {
-                "windows" => "x86_64-w64-mingw32",
-                "linux" => "x86_64-unknown-linux-gnu",
-                "macos" => "x86_64-apple-darwin",
+            match (config.target_triple.as_deref(), config.target_os.as_str()) {
+                (Some(triple), _) => triple,
+                (None, "windows") => "x86_64-w64-mingw32",
+                (None, "linux") => "x86_64-unknown-linux-gnu",
+                (None, "macos") => "x86_64-apple-darwin",
                 _ => "x86_64-unknown-linux-gnu"
             }
         } else if cfg!(target_os = "windows") {
@@ -39,6 +49,7 @@ impl IRGenerator {
         } else {
             "x86_64-unknown-linux-gnu"
         };
+        let target_triple = target_triple.to_string();
         self.emit_raw(&format!("target triple = \"{}\"", target_triple));
         self.emit_raw("");
 
@@ -102,30 +113,82 @@ impl IRGenerator {
         }
         
         self.emit_raw("declare i64 @strlen(i8*)");
+        self.emit_raw("declare i32 @strncmp(i8*, i8*, i64)");
         self.emit_raw("declare i8* @calloc(i64, i64)");
+        self.emit_raw("declare i8* @realloc(i8*, i64)");
         self.emit_raw("declare void @exit(i32)");
         self.emit_raw("declare void @llvm.memcpy.p0i8.p0i8.i64(i8* noalias nocapture writeonly, i8* noalias nocapture readonly, i64, i1 immarg)");
         self.emit_raw("declare i32 @snprintf(i8*, i64, i8*, ...)");
-        self.emit_raw("@.str.float_fmt = private unnamed_addr constant [3 x i8] c\"%f\\00\", align 1");
         self.emit_raw("@.str.int_fmt = private unnamed_addr constant [5 x i8] c\"%lld\\00\", align 1");
         self.emit_raw("@.str.true_str = private unnamed_addr constant [5 x i8] c\"true\\00\", align 1");
         self.emit_raw("@.str.false_str = private unnamed_addr constant [6 x i8] c\"false\\00\", align 1");
         self.emit_raw("");
 
+        // 字符串视图类型：指向原字符串的指针 + 长度
+        self.emit_raw("%__cay_string_view = type { i8*, i32 }");
+        self.emit_raw("");
+
         // 空字符串常量（用于 null 安全）
         self.emit_raw("@.cay_empty_str = private unnamed_addr constant [1 x i8] c\"\\00\", align 1");
         self.emit_raw("");
 
-        // 生成运行时函数
-        self.emit_string_concat_runtime();
-        self.emit_float_to_string_runtime();
-        self.emit_int_to_string_runtime();
-        self.emit_bool_to_string_runtime();
-        self.emit_char_to_string_runtime();
-        self.emit_string_length_runtime();
-        self.emit_string_substring_runtime();
-        self.emit_string_indexof_runtime();
-        self.emit_string_charat_runtime();
-        self.emit_string_replace_runtime();
     }
-}
\ No newline at end of file
+
+    /// 按运行时模式输出运行时函数
+    ///
+    /// 在所有函数体生成之后调用，此时 `used_runtime_functions` 已记录了全部调用。
+    pub fn emit_runtime_functions(&mut self) -> cayResult<()> {
+        match self.runtime_mode {
+            RuntimeMode::Always => {
+                for (_, emit) in RUNTIME_MODULES {
+                    emit(self);
+                }
+            }
+            RuntimeMode::OnDemand => {
+                for (functions, emit) in RUNTIME_MODULES {
+                    if functions.iter().any(|f| self.used_runtime_functions.contains(*f)) {
+                        emit(self);
+                    }
+                }
+            }
+            RuntimeMode::Never => {
+                let mut used: Vec<&String> = self.used_runtime_functions.iter().collect();
+                used.sort();
+                if let Some(name) = used.first() {
+                    return Err(codegen_error(format!(
+                        "Runtime function '{}' is required but runtime emission is disabled (--no-runtime)",
+                        name
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 运行时子模块的生成函数
+type RuntimeEmitter = fn(&mut IRGenerator);
+
+/// 运行时子模块：(模块中定义的函数, 生成函数)
+const RUNTIME_MODULES: &[(&[&str], RuntimeEmitter)] = &[
+    (&["__cay_string_concat"], IRGenerator::emit_string_concat_runtime),
+    (&["__cay_float_to_string", "__cay_float32_to_string"], IRGenerator::emit_float_to_string_runtime),
+    (&["__cay_int_to_string"], IRGenerator::emit_int_to_string_runtime),
+    (&["__cay_int_to_string_base"], IRGenerator::emit_int_to_string_base_runtime),
+    (&["__cay_bool_to_string"], IRGenerator::emit_bool_to_string_runtime),
+    (&["__cay_char_to_string"], IRGenerator::emit_char_to_string_runtime),
+    (&["__cay_string_length"], IRGenerator::emit_string_length_runtime),
+    (&["__cay_string_equals"], IRGenerator::emit_string_equals_runtime),
+    (&["__cay_string_compare"], IRGenerator::emit_string_compare_runtime),
+    (&["__cay_string_is_blank"], IRGenerator::emit_string_blank_runtime),
+    (&["__cay_string_substring"], IRGenerator::emit_string_substring_runtime),
+    (&["__cay_string_indexof"], IRGenerator::emit_string_indexof_runtime),
+    (&["__cay_string_charat"], IRGenerator::emit_string_charat_runtime),
+    (&["__cay_string_replace"], IRGenerator::emit_string_replace_runtime),
+    (
+        &["__cay_string_subview", "__cay_string_view_charat", "__cay_string_view_equals", "__cay_string_view_tostring"],
+        IRGenerator::emit_string_view_runtime,
+    ),
+    (&["__cay_read_file", "__cay_write_file"], IRGenerator::emit_file_io_runtime),
+    (&["__cay_list_new", "__cay_list_add", "__cay_list_get", "__cay_list_size"], IRGenerator::emit_list_runtime),
+];
@@ -0,0 +1,42 @@
+//! 字符串空白检查运行时函数
+
+use crate::codegen::context::IRGenerator;
+
+impl IRGenerator {
+    /// 生成字符串空白检查运行时函数（String.isBlank）
+    ///
+    /// 所有字符都是空白（空格、\t、\n、\v、\f、\r）时返回 true，空字符串和 null 也返回 true。
+    pub(super) fn emit_string_blank_runtime(&mut self) {
+        self.emit_raw("define i1 @__cay_string_is_blank(i8* %str) {");
+        self.emit_raw("entry:");
+        self.emit_raw("  %is_null = icmp eq i8* %str, null");
+        self.emit_raw("  br i1 %is_null, label %blank, label %loop");
+        self.emit_raw("");
+        self.emit_raw("loop:");
+        self.emit_raw("  %i = phi i64 [0, %entry], [%next, %whitespace]");
+        self.emit_raw("  %ptr = getelementptr i8, i8* %str, i64 %i");
+        self.emit_raw("  %byte = load i8, i8* %ptr");
+        self.emit_raw("  %at_end = icmp eq i8 %byte, 0");
+        self.emit_raw("  br i1 %at_end, label %blank, label %check");
+        self.emit_raw("");
+        self.emit_raw("check:");
+        self.emit_raw("  ; \\t \\n \\v \\f \\r 的编码是 9..=13");
+        self.emit_raw("  %is_space = icmp eq i8 %byte, 32");
+        self.emit_raw("  %offset = sub i8 %byte, 9");
+        self.emit_raw("  %is_control = icmp ult i8 %offset, 5");
+        self.emit_raw("  %is_ws = or i1 %is_space, %is_control");
+        self.emit_raw("  br i1 %is_ws, label %whitespace, label %not_blank");
+        self.emit_raw("");
+        self.emit_raw("whitespace:");
+        self.emit_raw("  %next = add i64 %i, 1");
+        self.emit_raw("  br label %loop");
+        self.emit_raw("");
+        self.emit_raw("blank:");
+        self.emit_raw("  ret i1 true");
+        self.emit_raw("");
+        self.emit_raw("not_blank:");
+        self.emit_raw("  ret i1 false");
+        self.emit_raw("}");
+        self.emit_raw("");
+    }
+}
@@ -0,0 +1,56 @@
+//! 字符串字典序比较运行时函数
+
+use crate::codegen::context::IRGenerator;
+
+impl IRGenerator {
+    /// 生成字符串字典序比较运行时函数（String.compareTo）
+    ///
+    /// 逐字节按无符号值比较，返回第一个不同字节之差；一个字符串是另一个的前缀时较短的更小。
+    /// null 小于任何非 null 字符串，两个 null 相等。
+    pub(super) fn emit_string_compare_runtime(&mut self) {
+        self.emit_raw("define i32 @__cay_string_compare(i8* %a, i8* %b) {");
+        self.emit_raw("entry:");
+        self.emit_raw("  %same_ptr = icmp eq i8* %a, %b");
+        self.emit_raw("  br i1 %same_ptr, label %equal, label %check_a_null");
+        self.emit_raw("");
+        self.emit_raw("check_a_null:");
+        self.emit_raw("  %a_null = icmp eq i8* %a, null");
+        self.emit_raw("  br i1 %a_null, label %less, label %check_b_null");
+        self.emit_raw("");
+        self.emit_raw("check_b_null:");
+        self.emit_raw("  %b_null = icmp eq i8* %b, null");
+        self.emit_raw("  br i1 %b_null, label %greater, label %loop");
+        self.emit_raw("");
+        self.emit_raw("loop:");
+        self.emit_raw("  %i = phi i64 [0, %check_b_null], [%next, %same_byte]");
+        self.emit_raw("  %a_ptr = getelementptr i8, i8* %a, i64 %i");
+        self.emit_raw("  %b_ptr = getelementptr i8, i8* %b, i64 %i");
+        self.emit_raw("  %a_byte = load i8, i8* %a_ptr");
+        self.emit_raw("  %b_byte = load i8, i8* %b_ptr");
+        self.emit_raw("  %a_val = zext i8 %a_byte to i32");
+        self.emit_raw("  %b_val = zext i8 %b_byte to i32");
+        self.emit_raw("  %diff = sub i32 %a_val, %b_val");
+        self.emit_raw("  %differs = icmp ne i32 %diff, 0");
+        self.emit_raw("  br i1 %differs, label %done, label %same_byte");
+        self.emit_raw("");
+        self.emit_raw("same_byte:");
+        self.emit_raw("  ; 两个字符串同时结束");
+        self.emit_raw("  %at_end = icmp eq i8 %a_byte, 0");
+        self.emit_raw("  %next = add i64 %i, 1");
+        self.emit_raw("  br i1 %at_end, label %equal, label %loop");
+        self.emit_raw("");
+        self.emit_raw("done:");
+        self.emit_raw("  ret i32 %diff");
+        self.emit_raw("");
+        self.emit_raw("equal:");
+        self.emit_raw("  ret i32 0");
+        self.emit_raw("");
+        self.emit_raw("less:");
+        self.emit_raw("  ret i32 -1");
+        self.emit_raw("");
+        self.emit_raw("greater:");
+        self.emit_raw("  ret i32 1");
+        self.emit_raw("}");
+        self.emit_raw("");
+    }
+}
@@ -0,0 +1,39 @@
+//! 字符串内容比较运行时函数
+
+use crate::codegen::context::IRGenerator;
+
+impl IRGenerator {
+    /// 生成字符串内容比较运行时函数（String 的 == / !=）
+    pub(super) fn emit_string_equals_runtime(&mut self) {
+        self.emit_raw("define i1 @__cay_string_equals(i8* %a, i8* %b) {");
+        self.emit_raw("entry:");
+        self.emit_raw("  ; 同一指针（包括两个 null）直接相等");
+        self.emit_raw("  %same_ptr = icmp eq i8* %a, %b");
+        self.emit_raw("  br i1 %same_ptr, label %equal, label %check_null");
+        self.emit_raw("");
+        self.emit_raw("check_null:");
+        self.emit_raw("  %a_null = icmp eq i8* %a, null");
+        self.emit_raw("  %b_null = icmp eq i8* %b, null");
+        self.emit_raw("  %any_null = or i1 %a_null, %b_null");
+        self.emit_raw("  br i1 %any_null, label %not_equal, label %check_len");
+        self.emit_raw("");
+        self.emit_raw("check_len:");
+        self.emit_raw("  %a_len = call i64 @strlen(i8* %a)");
+        self.emit_raw("  %b_len = call i64 @strlen(i8* %b)");
+        self.emit_raw("  %same_len = icmp eq i64 %a_len, %b_len");
+        self.emit_raw("  br i1 %same_len, label %compare, label %not_equal");
+        self.emit_raw("");
+        self.emit_raw("compare:");
+        self.emit_raw("  %cmp = call i32 @strncmp(i8* %a, i8* %b, i64 %a_len)");
+        self.emit_raw("  %content_equal = icmp eq i32 %cmp, 0");
+        self.emit_raw("  ret i1 %content_equal");
+        self.emit_raw("");
+        self.emit_raw("equal:");
+        self.emit_raw("  ret i1 1");
+        self.emit_raw("");
+        self.emit_raw("not_equal:");
+        self.emit_raw("  ret i1 0");
+        self.emit_raw("}");
+        self.emit_raw("");
+    }
+}
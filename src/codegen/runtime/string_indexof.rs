@@ -55,7 +55,5 @@ impl IRGenerator {
         self.emit_raw("  br label %loop_check");
         self.emit_raw("}");
         self.emit_raw("");
-        self.emit_raw("declare i32 @strncmp(i8*, i8*, i64)");
-        self.emit_raw("");
     }
 }
@@ -0,0 +1,102 @@
+//! 字符串视图运行时函数
+//!
+//! StringView 是指向原字符串内部的胖指针 `{ i8*, i32 }`，
+//! 创建视图不分配内存，只有 toString() 才会复制出新字符串。
+
+use crate::codegen::context::IRGenerator;
+
+impl IRGenerator {
+    /// 生成字符串视图运行时函数
+    pub(super) fn emit_string_view_runtime(&mut self) {
+        // subview(beginIndex, endIndex) - 边界处理与 substring 保持一致
+        self.emit_raw("define %__cay_string_view @__cay_string_subview(i8* %str, i32 %begin, i32 %end) {");
+        self.emit_raw("entry:");
+        self.emit_raw("  ; 空指针安全检查");
+        self.emit_raw("  %is_null = icmp eq i8* %str, null");
+        self.emit_raw("  br i1 %is_null, label %null_case, label %check_bounds");
+        self.emit_raw("");
+        self.emit_raw("null_case:");
+        self.emit_raw("  %empty0 = insertvalue %__cay_string_view undef, i8* getelementptr ([1 x i8], [1 x i8]* @.cay_empty_str, i64 0, i64 0), 0");
+        self.emit_raw("  %empty1 = insertvalue %__cay_string_view %empty0, i32 0, 1");
+        self.emit_raw("  ret %__cay_string_view %empty1");
+        self.emit_raw("");
+        self.emit_raw("check_bounds:");
+        self.emit_raw("  %total_len = call i64 @strlen(i8* %str)");
+        self.emit_raw("  %total_len_i32 = trunc i64 %total_len to i32");
+        self.emit_raw("  %begin_neg = icmp slt i32 %begin, 0");
+        self.emit_raw("  %begin_final = select i1 %begin_neg, i32 0, i32 %begin");
+        self.emit_raw("  %end_too_large = icmp sgt i32 %end, %total_len_i32");
+        self.emit_raw("  %end_final = select i1 %end_too_large, i32 %total_len_i32, i32 %end");
+        self.emit_raw("  %begin_gt_end = icmp sgt i32 %begin_final, %end_final");
+        self.emit_raw("  %begin_clamped = select i1 %begin_gt_end, i32 %end_final, i32 %begin_final");
+        self.emit_raw("  %view_len = sub i32 %end_final, %begin_clamped");
+        self.emit_raw("  ; 直接指向原字符串，不复制");
+        self.emit_raw("  %begin_i64 = sext i32 %begin_clamped to i64");
+        self.emit_raw("  %view_ptr = getelementptr i8, i8* %str, i64 %begin_i64");
+        self.emit_raw("  %view0 = insertvalue %__cay_string_view undef, i8* %view_ptr, 0");
+        self.emit_raw("  %view1 = insertvalue %__cay_string_view %view0, i32 %view_len, 1");
+        self.emit_raw("  ret %__cay_string_view %view1");
+        self.emit_raw("}");
+        self.emit_raw("");
+
+        // charAt(index) - 越界返回 0
+        self.emit_raw("define i8 @__cay_string_view_charat(%__cay_string_view %view, i32 %index) {");
+        self.emit_raw("entry:");
+        self.emit_raw("  %ptr = extractvalue %__cay_string_view %view, 0");
+        self.emit_raw("  %len = extractvalue %__cay_string_view %view, 1");
+        self.emit_raw("  %index_neg = icmp slt i32 %index, 0");
+        self.emit_raw("  %index_too_large = icmp sge i32 %index, %len");
+        self.emit_raw("  %out_of_range = or i1 %index_neg, %index_too_large");
+        self.emit_raw("  br i1 %out_of_range, label %out_of_bounds, label %get_char");
+        self.emit_raw("");
+        self.emit_raw("out_of_bounds:");
+        self.emit_raw("  ret i8 0");
+        self.emit_raw("");
+        self.emit_raw("get_char:");
+        self.emit_raw("  %idx_i64 = sext i32 %index to i64");
+        self.emit_raw("  %char_ptr = getelementptr i8, i8* %ptr, i64 %idx_i64");
+        self.emit_raw("  %char_val = load i8, i8* %char_ptr");
+        self.emit_raw("  ret i8 %char_val");
+        self.emit_raw("}");
+        self.emit_raw("");
+
+        // equals(String) - 长度相同且内容一致
+        self.emit_raw("define i1 @__cay_string_view_equals(%__cay_string_view %view, i8* %other) {");
+        self.emit_raw("entry:");
+        self.emit_raw("  %is_null = icmp eq i8* %other, null");
+        self.emit_raw("  br i1 %is_null, label %not_equal, label %check_len");
+        self.emit_raw("");
+        self.emit_raw("check_len:");
+        self.emit_raw("  %ptr = extractvalue %__cay_string_view %view, 0");
+        self.emit_raw("  %len = extractvalue %__cay_string_view %view, 1");
+        self.emit_raw("  %len_i64 = sext i32 %len to i64");
+        self.emit_raw("  %other_len = call i64 @strlen(i8* %other)");
+        self.emit_raw("  %same_len = icmp eq i64 %len_i64, %other_len");
+        self.emit_raw("  br i1 %same_len, label %compare, label %not_equal");
+        self.emit_raw("");
+        self.emit_raw("compare:");
+        self.emit_raw("  %cmp = call i32 @strncmp(i8* %ptr, i8* %other, i64 %len_i64)");
+        self.emit_raw("  %equal = icmp eq i32 %cmp, 0");
+        self.emit_raw("  ret i1 %equal");
+        self.emit_raw("");
+        self.emit_raw("not_equal:");
+        self.emit_raw("  ret i1 0");
+        self.emit_raw("}");
+        self.emit_raw("");
+
+        // toString() - 复制为独立的 null 结尾字符串
+        self.emit_raw("define i8* @__cay_string_view_tostring(%__cay_string_view %view) {");
+        self.emit_raw("entry:");
+        self.emit_raw("  %ptr = extractvalue %__cay_string_view %view, 0");
+        self.emit_raw("  %len = extractvalue %__cay_string_view %view, 1");
+        self.emit_raw("  %len_i64 = sext i32 %len to i64");
+        self.emit_raw("  %buf_size = add i64 %len_i64, 1");
+        self.emit_raw("  %result = call i8* @calloc(i64 1, i64 %buf_size)");
+        self.emit_raw("  call void @llvm.memcpy.p0i8.p0i8.i64(i8* %result, i8* %ptr, i64 %len_i64, i1 false)");
+        self.emit_raw("  %end_ptr = getelementptr i8, i8* %result, i64 %len_i64");
+        self.emit_raw("  store i8 0, i8* %end_ptr");
+        self.emit_raw("  ret i8* %result");
+        self.emit_raw("}");
+        self.emit_raw("");
+    }
+}
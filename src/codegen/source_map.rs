@@ -0,0 +1,121 @@
+//! IR 到源代码的位置映射
+//!
+//! 代码生成器在启用源码映射时（[`IRGenerator::set_source_markers`]），在每条语句的代码前输出
+//! `; @cay 行:列` 标记，之后输出 `; @cay end`。[`SourceMap::extract`] 删除这些标记，
+//! 把标记之间的 IR 行记录为对应源代码位置的映射，供调试器和覆盖率工具使用。
+//! 嵌套语句（如 if 的分支）覆盖外层语句的位置，结束后恢复外层位置。
+//!
+//! [`IRGenerator::set_source_markers`]: crate::codegen::IRGenerator::set_source_markers
+
+use crate::error::SourceLocation;
+
+/// 语句开始标记的前缀
+const MARKER_PREFIX: &str = "; @cay ";
+
+/// 语句结束标记
+pub const END_MARKER: &str = "  ; @cay end";
+
+/// 语句开始标记
+pub fn start_marker(loc: &SourceLocation) -> String {
+    format!("  {}{}:{}", MARKER_PREFIX, loc.line, loc.column)
+}
+
+/// 一段连续的 IR 行及其对应的源代码位置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceMapEntry {
+    /// 所在的 IR 函数
+    pub function: String,
+    /// 起始 IR 行号（从 1 开始）
+    pub ir_start: usize,
+    /// 结束 IR 行号（包含）
+    pub ir_end: usize,
+    pub loc: SourceLocation,
+}
+
+/// IR 行号到源代码位置的映射，按 IR 行号排序
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    pub entries: Vec<SourceMapEntry>,
+}
+
+impl SourceMap {
+    /// 删除 IR 中的位置标记，返回清理后的 IR 和映射
+    pub fn extract(ir: &str) -> (String, SourceMap) {
+        let mut output = String::with_capacity(ir.len());
+        let mut entries: Vec<SourceMapEntry> = Vec::new();
+        let mut function: Option<String> = None;
+        let mut stack: Vec<SourceLocation> = Vec::new();
+        let mut line_no = 0;
+
+        for line in ir.lines() {
+            let trimmed = line.trim();
+            if let Some(marker) = trimmed.strip_prefix(MARKER_PREFIX) {
+                if marker == "end" {
+                    stack.pop();
+                } else if let Some(loc) = parse_location(marker) {
+                    stack.push(loc);
+                }
+                continue;
+            }
+
+            output.push_str(line);
+            output.push('\n');
+            line_no += 1;
+
+            if trimmed.starts_with("define ") {
+                function = trimmed.find('@')
+                    .map(|start| &trimmed[start + 1..])
+                    .and_then(|rest| rest.find('(').map(|end| rest[..end].to_string()));
+                stack.clear();
+                continue;
+            }
+            if trimmed == "}" {
+                function = None;
+                stack.clear();
+                continue;
+            }
+
+            let (Some(func), Some(loc)) = (&function, stack.last()) else { continue };
+            if trimmed.is_empty() {
+                continue;
+            }
+            match entries.last_mut() {
+                Some(last) if last.ir_end + 1 == line_no && last.loc == *loc && last.function == *func => {
+                    last.ir_end = line_no;
+                }
+                _ => entries.push(SourceMapEntry {
+                    function: func.clone(),
+                    ir_start: line_no,
+                    ir_end: line_no,
+                    loc: loc.clone(),
+                }),
+            }
+        }
+
+        if !ir.ends_with('\n') {
+            output.pop();
+        }
+        (output, SourceMap { entries })
+    }
+
+    /// 查找 IR 行（从 1 开始）对应的源代码位置
+    pub fn lookup(&self, ir_line: usize) -> Option<&SourceLocation> {
+        self.entries.iter()
+            .find(|e| (e.ir_start..=e.ir_end).contains(&ir_line))
+            .map(|e| &e.loc)
+    }
+
+    /// 源代码某一行生成的所有 IR 行段
+    pub fn entries_for_line(&self, source_line: usize) -> impl Iterator<Item = &SourceMapEntry> {
+        self.entries.iter().filter(move |e| e.loc.line == source_line)
+    }
+}
+
+/// 解析 `行:列`
+fn parse_location(text: &str) -> Option<SourceLocation> {
+    let (line, column) = text.split_once(':')?;
+    Some(SourceLocation {
+        line: line.trim().parse().ok()?,
+        column: column.trim().parse().ok()?,
+    })
+}
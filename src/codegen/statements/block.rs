@@ -1,30 +1,71 @@
 //! 语句块代码生成
 //!
-//! 处理语句块（带作用域管理）的代码生成。
+//! 处理语句块（带作用域管理）的代码生成，包括带标签的语句块 `name: { ... }`。
 
-use crate::codegen::context::IRGenerator;
+use crate::codegen::context::{IRGenerator, LabelContext};
 use crate::ast::*;
 use crate::error::cayResult;
+use super::induction::stmt_writes_var;
 
 impl IRGenerator {
     /// 生成语句块代码（带作用域管理）
     pub fn generate_block(&mut self, block: &Block) -> cayResult<()> {
+        if let Some(label) = &block.label {
+            return self.generate_labeled_block(label, block);
+        }
+
         // 进入新作用域
         self.scope_manager.enter_scope();
 
-        for stmt in &block.statements {
-            self.generate_statement(stmt)?;
-        }
+        self.generate_statements(&block.statements)?;
 
         // 退出作用域
         self.scope_manager.exit_scope();
         Ok(())
     }
 
+    /// 生成带标签的语句块代码
+    ///
+    /// 块内的 break label 跳转到块后的结束标签；正常执行完毕时也落到这里。
+    fn generate_labeled_block(&mut self, label: &str, block: &Block) -> cayResult<()> {
+        let end_label = self.new_label("block.end");
+        self.label_stack.push(LabelContext { label: label.to_string(), end_label: end_label.clone() });
+        self.scope_manager.enter_scope();
+
+        let body_start = self.code.len();
+        self.generate_statements(&block.statements)?;
+        if !self.ends_with_terminator(body_start) {
+            self.emit_line(&format!("  br label %{}", end_label));
+        }
+
+        self.scope_manager.exit_scope();
+        self.label_stack.pop();
+
+        self.emit_line(&format!("{}:", end_label));
+        Ok(())
+    }
+
     /// 生成语句块代码（不带新作用域，用于函数体等已有作用域的场景）
     pub fn generate_block_without_scope(&mut self, block: &Block) -> cayResult<()> {
-        for stmt in &block.statements {
+        self.generate_statements(&block.statements)
+    }
+
+    /// 依次生成语句
+    ///
+    /// 由数组字面量初始化、且在其作用域（块内后续语句）中从不被重新赋值的数组变量，
+    /// 记录其长度，`arr.length` 可以直接折叠为常量。
+    fn generate_statements(&mut self, statements: &[Stmt]) -> cayResult<()> {
+        for (i, stmt) in statements.iter().enumerate() {
             self.generate_statement(stmt)?;
+
+            if let Stmt::VarDecl(var) = stmt {
+                match &var.initializer {
+                    Some(Expr::ArrayInit(init)) if !statements[i + 1..].iter().any(|s| stmt_writes_var(s, &var.name)) => {
+                        self.scope_manager.set_const_array_len(&var.name, init.elements.len());
+                    }
+                    _ => {}
+                }
+            }
         }
         Ok(())
     }
@@ -39,24 +39,10 @@ impl IRGenerator {
         self.emit_line(&format!("{}:", then_label));
         let then_code_before = self.code.len();
         self.generate_statement(&if_stmt.then_branch)?;
-        let then_code_after = self.code.len();
 
         // 检查 then 块是否以终止指令结束
-        let mut then_terminates = false;
-        if then_code_after > then_code_before {
-            let then_code = &self.code[then_code_before..then_code_after];
-            let then_lines: Vec<&str> = then_code.trim().lines().collect();
-            if let Some(last_line) = then_lines.last() {
-                let trimmed = last_line.trim();
-                if trimmed.starts_with("ret") || trimmed.starts_with("br") || trimmed.starts_with("switch") || trimmed.starts_with("unreachable") {
-                    then_terminates = true;
-                } else {
-                    self.emit_line(&format!("  br label %{}", merge_label));
-                }
-            } else {
-                self.emit_line(&format!("  br label %{}", merge_label));
-            }
-        } else {
+        let then_terminates = self.ends_with_terminator(then_code_before);
+        if !then_terminates {
             self.emit_line(&format!("  br label %{}", merge_label));
         }
 
@@ -66,23 +52,10 @@ impl IRGenerator {
             self.emit_line(&format!("{}:", else_label));
             let else_code_before = self.code.len();
             self.generate_statement(else_branch)?;
-            let else_code_after = self.code.len();
 
             // 检查 else 块是否以终止指令结束
-            if else_code_after > else_code_before {
-                let else_code = &self.code[else_code_before..else_code_after];
-                let else_lines: Vec<&str> = else_code.trim().lines().collect();
-                if let Some(last_line) = else_lines.last() {
-                    let trimmed = last_line.trim();
-                    if trimmed.starts_with("ret") || trimmed.starts_with("br") || trimmed.starts_with("switch") || trimmed.starts_with("unreachable") {
-                        else_terminates = true;
-                    } else {
-                        self.emit_line(&format!("  br label %{}", merge_label));
-                    }
-                } else {
-                    self.emit_line(&format!("  br label %{}", merge_label));
-                }
-            } else {
+            else_terminates = self.ends_with_terminator(else_code_before);
+            if !else_terminates {
                 self.emit_line(&format!("  br label %{}", merge_label));
             }
         }
@@ -0,0 +1,143 @@
+//! for 循环计数器的 SSA 优化
+//!
+//! 形如 `for (int i = a; cond; i++)` 的简单计数器，如果循环体和条件中
+//! 没有对计数器的写入或捕获，就不为它分配栈空间，而是在条件块头部
+//! 用 `phi` 节点维护其值，避免每次迭代都从内存重新加载。
+
+use crate::ast::*;
+use crate::types::Type;
+
+/// 可提升为 SSA 寄存器的循环计数器
+pub(crate) struct InductionVar<'a> {
+    pub decl: &'a VarDecl,
+    pub init: &'a Expr,
+    pub op: &'static str,  // "add" 或 "sub"
+    pub step: i64,
+}
+
+/// 判断 for 循环的计数器能否保存在 SSA 寄存器中
+pub(crate) fn find_induction_var(for_stmt: &ForStmt) -> Option<InductionVar<'_>> {
+    let decl = match for_stmt.init.as_deref() {
+        Some(Stmt::VarDecl(decl)) if matches!(decl.var_type, Type::Int32 | Type::Int64) => decl,
+        _ => return None,
+    };
+    let init = decl.initializer.as_ref()?;
+    let (op, step) = match_update(for_stmt.update.as_ref()?, &decl.name)?;
+
+    if expr_writes_var(init, &decl.name)
+        || for_stmt.condition.as_ref().is_some_and(|c| expr_writes_var(c, &decl.name))
+        || stmt_writes_var(&for_stmt.body, &decl.name)
+    {
+        return None;
+    }
+
+    Some(InductionVar { decl, init, op, step })
+}
+
+/// 识别 `i++`、`++i`、`i--`、`--i`、`i += c`、`i -= c` 形式的更新表达式
+fn match_update(update: &Expr, name: &str) -> Option<(&'static str, i64)> {
+    match update {
+        Expr::Unary(unary) if is_var(&unary.operand, name) => match unary.op {
+            UnaryOp::PreInc | UnaryOp::PostInc => Some(("add", 1)),
+            UnaryOp::PreDec | UnaryOp::PostDec => Some(("sub", 1)),
+            _ => None,
+        },
+        Expr::Assignment(assign) if is_var(&assign.target, name) => {
+            let step = match assign.value.as_ref() {
+                Expr::Literal(LiteralValue::Int32(v)) => *v as i64,
+                Expr::Literal(LiteralValue::Int64(v)) => *v,
+                _ => return None,
+            };
+            match assign.op {
+                AssignOp::AddAssign => Some(("add", step)),
+                AssignOp::SubAssign => Some(("sub", step)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn is_var(expr: &Expr, name: &str) -> bool {
+    matches!(expr, Expr::Identifier(n) if n == name)
+}
+
+/// 语句中是否写入、重新声明或可能捕获指定变量（保守判断）
+pub(super) fn stmt_writes_var(stmt: &Stmt, name: &str) -> bool {
+    match stmt {
+        Stmt::Expr(expr) => expr_writes_var(expr, name),
+        Stmt::VarDecl(decl) => {
+            decl.name == name
+                || decl.initializer.as_ref().is_some_and(|init| expr_writes_var(init, name))
+        }
+        Stmt::Return(expr) => expr.as_ref().is_some_and(|e| expr_writes_var(e, name)),
+        Stmt::If(if_stmt) => {
+            expr_writes_var(&if_stmt.condition, name)
+                || stmt_writes_var(&if_stmt.then_branch, name)
+                || if_stmt.else_branch.as_ref().is_some_and(|s| stmt_writes_var(s, name))
+        }
+        Stmt::While(while_stmt) => {
+            expr_writes_var(&while_stmt.condition, name) || stmt_writes_var(&while_stmt.body, name)
+        }
+        Stmt::DoWhile(do_while) => {
+            expr_writes_var(&do_while.condition, name) || stmt_writes_var(&do_while.body, name)
+        }
+        Stmt::Repeat(repeat) => {
+            expr_writes_var(&repeat.count, name) || stmt_writes_var(&repeat.body, name)
+        }
+        Stmt::For(inner) => {
+            inner.init.as_ref().is_some_and(|s| stmt_writes_var(s, name))
+                || inner.condition.as_ref().is_some_and(|e| expr_writes_var(e, name))
+                || inner.update.as_ref().is_some_and(|e| expr_writes_var(e, name))
+                || stmt_writes_var(&inner.body, name)
+        }
+        Stmt::Switch(switch) => {
+            expr_writes_var(&switch.expr, name)
+                || switch.cases.iter().flat_map(|c| &c.body).any(|s| stmt_writes_var(s, name))
+                || switch.default.iter().flatten().any(|s| stmt_writes_var(s, name))
+        }
+        Stmt::Block(block) => block.statements.iter().any(|s| stmt_writes_var(s, name)),
+        Stmt::TupleDestructure(destructure) => {
+            destructure.names.iter().any(|n| n == name) || expr_writes_var(&destructure.initializer, name)
+        }
+        Stmt::Break(_) | Stmt::Continue | Stmt::Empty => false,
+    }
+}
+
+/// 表达式中是否写入或可能捕获指定变量（保守判断）
+fn expr_writes_var(expr: &Expr, name: &str) -> bool {
+    match expr {
+        Expr::Literal(_) | Expr::Identifier(_) | Expr::MethodRef(_) => false,
+        // Lambda 可能捕获计数器，保守地放弃优化
+        Expr::Lambda(_) => true,
+        Expr::Assignment(assign) => {
+            is_var(&assign.target, name)
+                || expr_writes_var(&assign.target, name)
+                || expr_writes_var(&assign.value, name)
+        }
+        Expr::Unary(unary) => {
+            let is_inc_dec = matches!(unary.op,
+                UnaryOp::PreInc | UnaryOp::PreDec | UnaryOp::PostInc | UnaryOp::PostDec);
+            (is_inc_dec && is_var(&unary.operand, name)) || expr_writes_var(&unary.operand, name)
+        }
+        Expr::Binary(bin) => expr_writes_var(&bin.left, name) || expr_writes_var(&bin.right, name),
+        Expr::Call(call) => {
+            expr_writes_var(&call.callee, name) || call.args.iter().any(|a| expr_writes_var(a, name))
+        }
+        Expr::MemberAccess(member) => expr_writes_var(&member.object, name),
+        Expr::New(new_expr) => new_expr.args.iter().any(|a| expr_writes_var(a, name)),
+        Expr::Cast(cast) => expr_writes_var(&cast.expr, name),
+        Expr::ArrayCreation(creation) => creation.sizes.iter().any(|s| expr_writes_var(s, name)),
+        Expr::ArrayAccess(access) => {
+            expr_writes_var(&access.array, name) || expr_writes_var(&access.index, name)
+        }
+        Expr::ArrayInit(init) => init.elements.iter().any(|e| expr_writes_var(e, name)),
+        Expr::Tuple(tuple) => tuple.elements.iter().any(|e| expr_writes_var(e, name)),
+        Expr::Ternary(ternary) => {
+            expr_writes_var(&ternary.condition, name)
+                || expr_writes_var(&ternary.true_branch, name)
+                || expr_writes_var(&ternary.false_branch, name)
+        }
+        Expr::InstanceOf(instance_of) => expr_writes_var(&instance_of.expr, name),
+    }
+}
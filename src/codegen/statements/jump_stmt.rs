@@ -1,14 +1,22 @@
 //! 跳转语句代码生成
 //!
-//! 处理break和continue语句的代码生成。
+//! 处理break（包括 break label）和continue语句的代码生成。
 
 use crate::codegen::context::IRGenerator;
 use crate::error::{cayResult, codegen_error};
 
 impl IRGenerator {
     /// 生成 break 语句代码
-    pub fn generate_break_statement(&mut self) -> cayResult<()> {
-        if let Some(loop_ctx) = self.current_loop() {
+    ///
+    /// `break label;` 跳转到对应带标签语句块的结束位置。
+    pub fn generate_break_statement(&mut self, label: Option<&str>) -> cayResult<()> {
+        if let Some(label) = label {
+            let end_label = match self.find_label(label) {
+                Some(ctx) => ctx.end_label.clone(),
+                None => return Err(codegen_error(format!("Undefined label '{}' in break statement", label))),
+            };
+            self.emit_line(&format!("  br label %{}", end_label));
+        } else if let Some(loop_ctx) = self.current_loop() {
             self.emit_line(&format!("  br label %{}", loop_ctx.end_label));
         } else {
             return Err(codegen_error("break statement outside of loop".to_string()));
@@ -1,10 +1,11 @@
 //! 循环语句代码生成
 //!
-//! 处理while、for、do-while循环的代码生成。
+//! 处理while、for、do-while、repeat循环的代码生成。
 
 use crate::codegen::context::IRGenerator;
 use crate::ast::*;
 use crate::error::cayResult;
+use super::induction::{find_induction_var, InductionVar};
 
 impl IRGenerator {
     /// 生成 while 语句代码
@@ -33,8 +34,11 @@ impl IRGenerator {
 
         // 循环体
         self.emit_line(&format!("{}:", body_label));
+        let body_start = self.code.len();
         self.generate_statement(&while_stmt.body)?;
-        self.emit_line(&format!("  br label %{}", cond_label));
+        if !self.ends_with_terminator(body_start) {
+            self.emit_line(&format!("  br label %{}", cond_label));
+        }
 
         // 结束块
         self.emit_line(&format!("{}:", end_label));
@@ -46,16 +50,34 @@ impl IRGenerator {
     }
 
     /// 生成 for 语句代码
+    ///
+    /// 初始化部分声明的变量只在循环内可见，因此整个 for 语句处于独立作用域中。
+    /// 无论生成成功与否都会退出该作用域，break/continue 仅生成跳转指令，不影响作用域栈。
     pub fn generate_for_statement(&mut self, for_stmt: &ForStmt) -> cayResult<()> {
+        self.scope_manager.enter_scope();
+        let result = self.generate_for_loop(for_stmt);
+        self.scope_manager.exit_scope();
+        result
+    }
+
+    /// 生成 for 循环的初始化、条件、循环体和更新块（作用域由调用者管理）
+    fn generate_for_loop(&mut self, for_stmt: &ForStmt) -> cayResult<()> {
         let cond_label = self.new_label("for.cond");
         let body_label = self.new_label("for.body");
         let update_label = self.new_label("for.update");
         let end_label = self.new_label("for.end");
 
-        // 初始化部分
-        if let Some(init) = for_stmt.init.as_ref() {
-            self.generate_statement(init)?;
-        }
+        // 简单计数器保存在 SSA 寄存器中，否则按普通变量声明处理
+        let induction = find_induction_var(for_stmt);
+        let counter = match &induction {
+            Some(iv) => Some(self.generate_induction_init(iv)?),
+            None => {
+                if let Some(init) = for_stmt.init.as_ref() {
+                    self.generate_statement(init)?;
+                }
+                None
+            }
+        };
 
         // 进入循环上下文（continue 跳转到 update 标签）
         self.enter_loop(update_label.clone(), end_label.clone());
@@ -64,6 +86,11 @@ impl IRGenerator {
 
         // 条件块
         self.emit_line(&format!("{}:", cond_label));
+        let next_reg = counter.as_ref().map(|_| self.new_temp()).unwrap_or_default();
+        if let Some((llvm_type, llvm_name, init_val, preheader)) = &counter {
+            self.emit_line(&format!("  %{} = phi {} [{}, %{}], [{}, %{}]",
+                llvm_name, llvm_type, init_val, preheader, next_reg, update_label));
+        }
         if let Some(condition) = for_stmt.condition.as_ref() {
             let cond = self.generate_expression(condition)?;
             let (cond_type, cond_val) = self.parse_typed_value(&cond);
@@ -82,13 +109,24 @@ impl IRGenerator {
 
         // 循环体
         self.emit_line(&format!("{}:", body_label));
+        let body_start = self.code.len();
         self.generate_statement(&for_stmt.body)?;
-        self.emit_line(&format!("  br label %{}", update_label));
+        if !self.ends_with_terminator(body_start) {
+            self.emit_line(&format!("  br label %{}", update_label));
+        }
 
         // 更新块
         self.emit_line(&format!("{}:", update_label));
-        if let Some(update) = for_stmt.update.as_ref() {
-            self.generate_expression(update)?;
+        match (&induction, &counter) {
+            (Some(iv), Some((llvm_type, llvm_name, _, _))) => {
+                self.emit_line(&format!("  {} = {}{} {} %{}, {}",
+                    next_reg, iv.op, Self::signed_overflow_flag(llvm_type), llvm_type, llvm_name, iv.step));
+            }
+            _ => {
+                if let Some(update) = for_stmt.update.as_ref() {
+                    self.generate_expression(update)?;
+                }
+            }
         }
         self.emit_line(&format!("  br label %{}", cond_label));
 
@@ -97,10 +135,43 @@ impl IRGenerator {
 
         // 退出循环上下文
         self.exit_loop();
+        if let Some((_, llvm_name, _, _)) = &counter {
+            self.ssa_vars.remove(llvm_name);
+        }
 
         Ok(())
     }
 
+    /// 声明 SSA 循环计数器并计算其初值
+    ///
+    /// 返回 (LLVM 类型, LLVM 名称, 初值, 前置块标签)，初值在前置块中可用，
+    /// 供条件块头部的 phi 节点引用。
+    fn generate_induction_init(&mut self, iv: &InductionVar) -> cayResult<(String, String, String, String)> {
+        let llvm_type = self.type_to_llvm(&iv.decl.var_type);
+        let value = self.generate_expression(iv.init)?;
+        let (value_type, mut init_val) = self.parse_typed_value(&value);
+        if value_type != llvm_type {
+            let from_bits: u32 = value_type.trim_start_matches('i').parse().unwrap_or(64);
+            let to_bits: u32 = llvm_type.trim_start_matches('i').parse().unwrap_or(64);
+            let conv = if to_bits > from_bits { "sext" } else { "trunc" };
+            let temp = self.new_temp();
+            self.emit_line(&format!("  {} = {} {} {} to {}", temp, conv, value_type, init_val, llvm_type));
+            init_val = temp;
+        }
+
+        let llvm_name = self.scope_manager.declare_var(&iv.decl.name, &llvm_type);
+        self.scope_manager.set_cay_type(&iv.decl.name, &iv.decl.var_type);
+        self.var_types.insert(iv.decl.name.clone(), llvm_type.clone());
+        self.ssa_vars.insert(llvm_name.clone(), format!("{} %{}", llvm_type, llvm_name));
+
+        // 独立的前置块保证 phi 的来源标签确定
+        let preheader = self.new_label("for.preheader");
+        self.emit_line(&format!("  br label %{}", preheader));
+        self.emit_line(&format!("{}:", preheader));
+
+        Ok((llvm_type, llvm_name, init_val, preheader))
+    }
+
     /// 生成 do-while 语句代码
     pub fn generate_do_while_statement(&mut self, do_while_stmt: &DoWhileStmt) -> cayResult<()> {
         let body_label = self.new_label("dowhile.body");
@@ -110,25 +181,88 @@ impl IRGenerator {
         // 进入循环上下文
         self.enter_loop(cond_label.clone(), end_label.clone());
 
-        // 先执行循环体
+        // 先无条件执行一次循环体；循环体内的 break 直接跳到结束块，不求值条件
         self.emit_line(&format!("  br label %{}", body_label));
         self.emit_line(&format!("{}:", body_label));
+        let body_start = self.code.len();
         self.generate_statement(&do_while_stmt.body)?;
-        self.emit_line(&format!("  br label %{}", cond_label));
+        if !self.ends_with_terminator(body_start) {
+            self.emit_line(&format!("  br label %{}", cond_label));
+        }
 
-        // 条件检查
+        // 条件检查；do { ... } while (false) 是只执行一次的块，continue 和 break 都直接结束
         self.emit_line(&format!("{}:", cond_label));
-        let cond = self.generate_expression(&do_while_stmt.condition)?;
-        let (cond_type, cond_val) = self.parse_typed_value(&cond);
-        let cond_reg = self.new_temp();
-        if cond_type == "i1" {
-            self.emit_line(&format!("  {} = icmp ne i1 {}, 0", cond_reg, cond_val));
+        if matches!(do_while_stmt.condition, Expr::Literal(LiteralValue::Bool(false))) {
+            self.emit_line(&format!("  br label %{}", end_label));
         } else {
-            self.emit_line(&format!("  {} = icmp ne {} {}, 0", cond_reg, cond_type, cond_val));
+            let cond = self.generate_expression(&do_while_stmt.condition)?;
+            let (cond_type, cond_val) = self.parse_typed_value(&cond);
+            let cond_reg = self.new_temp();
+            if cond_type == "i1" {
+                self.emit_line(&format!("  {} = icmp ne i1 {}, 0", cond_reg, cond_val));
+            } else {
+                self.emit_line(&format!("  {} = icmp ne {} {}, 0", cond_reg, cond_type, cond_val));
+            }
+            self.emit_line(&format!("  br i1 {}, label %{}, label %{}",
+                cond_reg, body_label, end_label));
         }
+
+        // 结束块
+        self.emit_line(&format!("{}:", end_label));
+
+        // 退出循环上下文
+        self.exit_loop();
+
+        Ok(())
+    }
+
+    /// 生成 repeat 语句代码
+    ///
+    /// 次数只在进入循环前求值一次；隐藏的计数器保存在 SSA 寄存器中，
+    /// continue 跳转到计数器递增块。次数不大于 0 时循环体不执行。
+    pub fn generate_repeat_statement(&mut self, repeat_stmt: &RepeatStmt) -> cayResult<()> {
+        let preheader_label = self.new_label("repeat.preheader");
+        let cond_label = self.new_label("repeat.cond");
+        let body_label = self.new_label("repeat.body");
+        let step_label = self.new_label("repeat.step");
+        let end_label = self.new_label("repeat.end");
+
+        let count = self.generate_expression(&repeat_stmt.count)?;
+        let (count_type, count_val) = self.parse_typed_value(&count);
+
+        // 独立的前置块保证 phi 的来源标签确定
+        self.emit_line(&format!("  br label %{}", preheader_label));
+        self.emit_line(&format!("{}:", preheader_label));
+        self.emit_line(&format!("  br label %{}", cond_label));
+
+        // 进入循环上下文（continue 跳转到 step 标签）
+        self.enter_loop(step_label.clone(), end_label.clone());
+
+        // 条件块
+        self.emit_line(&format!("{}:", cond_label));
+        let counter = self.new_temp();
+        let next = self.new_temp();
+        self.emit_line(&format!("  {} = phi {} [0, %{}], [{}, %{}]",
+            counter, count_type, preheader_label, next, step_label));
+        let cond_reg = self.new_temp();
+        self.emit_line(&format!("  {} = icmp slt {} {}, {}", cond_reg, count_type, counter, count_val));
         self.emit_line(&format!("  br i1 {}, label %{}, label %{}",
             cond_reg, body_label, end_label));
 
+        // 循环体
+        self.emit_line(&format!("{}:", body_label));
+        let body_start = self.code.len();
+        self.generate_statement(&repeat_stmt.body)?;
+        if !self.ends_with_terminator(body_start) {
+            self.emit_line(&format!("  br label %{}", step_label));
+        }
+
+        // 计数器递增
+        self.emit_line(&format!("{}:", step_label));
+        self.emit_line(&format!("  {} = add{} {} {}, 1",
+            next, Self::signed_overflow_flag(&count_type), count_type, counter));
+        self.emit_line(&format!("  br label %{}", cond_label));
+
         // 结束块
         self.emit_line(&format!("{}:", end_label));
 
@@ -7,6 +7,7 @@ mod var_decl;
 mod return_stmt;
 mod if_stmt;
 mod loops;
+mod induction;
 mod switch_stmt;
 mod jump_stmt;
 mod statement;
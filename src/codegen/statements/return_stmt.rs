@@ -17,6 +17,10 @@ impl IRGenerator {
             // 如果返回类型是 void，但表达式非空，这是错误（但由语义分析处理）
             if ret_type == "void" {
                 self.emit_line("  ret void");
+            } else if value_type != ret_type && self.is_tuple_type(&value_type) && self.is_tuple_type(&ret_type) {
+                // 元组逐元素转换
+                let converted = self.convert_tuple_value(&value_type, &val, &ret_type, Some(e));
+                self.emit_line(&format!("  ret {} {}", ret_type, converted));
             } else if value_type != ret_type {
                 // 需要类型转换
                 let temp = self.new_temp();
@@ -39,9 +43,10 @@ impl IRGenerator {
                     let to_bits: u32 = ret_type.trim_start_matches('i').parse().unwrap_or(64);
 
                     if to_bits > from_bits {
-                        // 符号扩展
-                        self.emit_line(&format!("  {} = sext {} {} to {}",
-                            temp, value_type, val, ret_type));
+                        // 符号扩展（char 零扩展）
+                        let ext = self.int_extension(&value_type, e);
+                        self.emit_line(&format!("  {} = {} {} {} to {}",
+                            temp, ext, value_type, val, ret_type));
                     } else {
                         // 截断
                         self.emit_line(&format!("  {} = trunc {} {} to {}",
@@ -51,8 +56,9 @@ impl IRGenerator {
                 }
                 // 整数到浮点数转换
                 else if value_type.starts_with("i") && (ret_type == "float" || ret_type == "double") {
-                    self.emit_line(&format!("  {} = sitofp {} {} to {}",
-                        temp, value_type, val, ret_type));
+                    let conv = self.int_to_float(&value_type, e);
+                    self.emit_line(&format!("  {} = {} {} {} to {}",
+                        temp, conv, value_type, val, ret_type));
                     self.emit_line(&format!("  ret {} {}", ret_type, temp));
                 }
                 // 浮点数到整数转换
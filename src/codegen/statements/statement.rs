@@ -3,12 +3,27 @@
 //! 处理语句类型的分发。
 
 use crate::codegen::context::IRGenerator;
+use crate::codegen::source_map;
 use crate::ast::*;
 use crate::error::cayResult;
 
 impl IRGenerator {
     /// 生成单个语句代码
+    ///
+    /// 启用源码映射时，语句的代码前后分别输出位置标记和结束标记。
     pub fn generate_statement(&mut self, stmt: &Stmt) -> cayResult<()> {
+        let marker = stmt.loc().filter(|_| self.source_markers).cloned();
+        if let Some(loc) = &marker {
+            self.emit_line(&source_map::start_marker(loc));
+        }
+        self.generate_statement_code(stmt)?;
+        if marker.is_some() {
+            self.emit_line(source_map::END_MARKER);
+        }
+        Ok(())
+    }
+
+    fn generate_statement_code(&mut self, stmt: &Stmt) -> cayResult<()> {
         match stmt {
             Stmt::Expr(expr) => {
                 self.generate_expression(expr)?;
@@ -34,16 +49,41 @@ impl IRGenerator {
             Stmt::DoWhile(do_while_stmt) => {
                 self.generate_do_while_statement(do_while_stmt)?;
             }
+            Stmt::Repeat(repeat_stmt) => {
+                self.generate_repeat_statement(repeat_stmt)?;
+            }
             Stmt::Switch(switch_stmt) => {
                 self.generate_switch_statement(switch_stmt)?;
             }
-            Stmt::Break => {
-                self.generate_break_statement()?;
+            Stmt::TupleDestructure(destructure) => {
+                self.generate_tuple_destructure(destructure)?;
+            }
+            Stmt::Break(label) => {
+                self.generate_break_statement(label.as_deref())?;
             }
             Stmt::Continue => {
                 self.generate_continue_statement()?;
             }
+            Stmt::Empty => {}
         }
         Ok(())
     }
+
+    /// 检查从 `start` 开始生成的代码是否以终止指令结束
+    ///
+    /// 以 break/continue/return 结尾的语句块已经终结了当前基本块，
+    /// 调用方不能再在其后追加跳转指令。
+    /// 以标签结尾（如 switch 的 `switch.end.N:`）说明开始了新的基本块，尚未终结。
+    /// 注释行（包括源码位置标记）不影响判断。
+    pub(super) fn ends_with_terminator(&self, start: usize) -> bool {
+        self.code[start..].lines()
+            .map(str::trim)
+            .rfind(|line| !line.is_empty() && !line.starts_with(';'))
+            .is_some_and(|line| {
+                if line.ends_with(':') {
+                    return false;
+                }
+                line.starts_with("ret") || line.starts_with("br") || line.starts_with("switch") || line.starts_with("unreachable")
+            })
+    }
 }
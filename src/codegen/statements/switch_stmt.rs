@@ -4,7 +4,7 @@
 
 use crate::codegen::context::IRGenerator;
 use crate::ast::*;
-use crate::error::cayResult;
+use crate::error::{cayResult, codegen_error};
 
 impl IRGenerator {
     /// 生成 switch 语句代码
@@ -23,16 +23,22 @@ impl IRGenerator {
         // 创建 case 标签
         let mut case_labels: Vec<(i64, String, usize)> = Vec::new();
         for (idx, case) in switch_stmt.cases.iter().enumerate() {
-            let label = self.new_label(&format!("switch.case.{}", case.value));
-            case_labels.push((case.value, label, idx));
+            // case 标签可以是整数/字符字面量或 const 常量，必须能在编译期求值
+            let value = self.evaluate_const_int(&case.value).ok_or_else(|| {
+                codegen_error("case label must be a compile-time integer constant")
+            })?;
+            let label = self.new_label(&format!("switch.case.{}", value));
+            case_labels.push((value, label, idx));
         }
 
         // 将表达式值转换为 i64（如果还不是的话）
+        // char 的 case 标签取字符码（0..=255），所以 char 按无符号扩展，int8 按有符号扩展
         let switch_val = if expr_type == "i64" {
             expr_val.to_string()
         } else {
+            let ext = self.int_extension(&expr_type, &switch_stmt.expr);
             let temp = self.new_temp();
-            self.emit_line(&format!("  {} = sext {} {} to i64", temp, expr_type, expr_val));
+            self.emit_line(&format!("  {} = {} {} {} to i64", temp, ext, expr_type, expr_val));
             temp
         };
 
@@ -43,70 +49,47 @@ impl IRGenerator {
         }
         self.emit_line("  ]");
 
-        // 生成 case 块
-        let mut fallthrough = false;
-        for i in 0..case_labels.len() {
-            let (value, label, case_idx) = &case_labels[i];
-            let case = &switch_stmt.cases[*case_idx];
-            self.emit_line(&format!("{}:", label));
+        // 按源代码顺序排列各分支块：(标签, 语句, 是否穿透到下一块)
+        // default 可以出现在任意位置，只有没有 case 匹配时才由 switch 指令跳入，
+        // 但穿透时与 case 一样进入源代码中的下一块
+        let mut blocks: Vec<(String, &[Stmt], bool)> = case_labels.iter()
+            .map(|(_, label, idx)| {
+                let case = &switch_stmt.cases[*idx];
+                (label.clone(), case.body.as_slice(), case.body.is_empty() || case.fallthrough)
+            })
+            .collect();
+        if let Some(default_body) = switch_stmt.default.as_ref() {
+            let position = switch_stmt.default_index.min(blocks.len());
+            blocks.insert(position, (default_label.clone(), default_body.as_slice(), default_body.is_empty()));
+        }
 
-            // 执行 case 体
-            if case.body.is_empty() {
-                // 空的 case 体，直接穿透到下一个 case
-                fallthrough = true;
-            } else {
-                for (j, stmt) in case.body.iter().enumerate() {
-                    match stmt {
-                        Stmt::Break => {
-                            // 遇到 break，跳转到 switch 结束
-                            self.emit_line(&format!("  br label %{}", end_label));
-                            fallthrough = false;
-                            break;
-                        }
-                        _ => {
-                            self.generate_statement(stmt)?;
-                            // 如果不是最后一条，继续执行
-                            if j == case.body.len() - 1 {
-                                // 最后一条语句，检查是否需要穿透
-                                fallthrough = true;
-                            }
-                        }
-                    }
-                }
-            }
+        // 生成分支块
+        // 分支末尾默认隐式 break；空分支体（多个标签共用一段代码）
+        // 和以 fallthrough; 结尾的 case 继续执行下一块，最后一块穿透到结束
+        for i in 0..blocks.len() {
+            let (label, body, falls_through) = &blocks[i];
+            self.emit_line(&format!("{}:", label));
 
-            // 如果不是 break，穿透到下一个 case
-            if fallthrough && i < case_labels.len() - 1 {
-                let (_, next_label, _) = &case_labels[i + 1];
-                self.emit_line(&format!("  br label %{}", next_label));
-                fallthrough = false;
-            } else if fallthrough {
-                // 最后一个 case 没有 break，穿透到 default 或结束
-                if switch_stmt.default.is_some() {
-                    self.emit_line(&format!("  br label %{}", default_label));
-                } else {
+            let body_start = self.code.len();
+            for stmt in *body {
+                if let Stmt::Break(None) = stmt {
+                    // 遇到 break，跳转到 switch 结束，之后的语句不可达
                     self.emit_line(&format!("  br label %{}", end_label));
+                    break;
                 }
-                fallthrough = false;
+                self.generate_statement(stmt)?;
             }
-        }
 
-        // 生成 default 块
-        if let Some(default_body) = switch_stmt.default.as_ref() {
-            self.emit_line(&format!("{}:", default_label));
-            for stmt in default_body {
-                match stmt {
-                    Stmt::Break => {
-                        self.emit_line(&format!("  br label %{}", end_label));
-                        break;
-                    }
-                    _ => {
-                        self.generate_statement(stmt)?;
-                    }
-                }
+            // break / return 等已经终结了当前块
+            if self.ends_with_terminator(body_start) {
+                continue;
             }
-            // 确保 default 最后跳转到结束
-            self.emit_line(&format!("  br label %{}", end_label));
+
+            let target = match blocks.get(i + 1) {
+                Some((next_label, _, _)) if *falls_through => next_label,
+                _ => &end_label,
+            };
+            self.emit_line(&format!("  br label %{}", target));
         }
 
         // 结束块
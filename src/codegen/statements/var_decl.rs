@@ -4,7 +4,7 @@
 
 use crate::codegen::context::IRGenerator;
 use crate::ast::*;
-use crate::types::Type;
+use crate::types::{Type, LIST_CLASS};
 use crate::error::cayResult;
 
 impl IRGenerator {
@@ -34,10 +34,14 @@ impl IRGenerator {
             Expr::Unary(unary) => {
                 self.infer_type_from_expr(&unary.operand)
             },
+            Expr::Tuple(tuple) => tuple.elements.iter()
+                .map(|element| self.infer_type_from_expr(element))
+                .collect::<Option<Vec<_>>>()
+                .map(Type::Tuple),
+            Expr::New(new_expr) => Some(Type::Object(new_expr.class_name.clone())),
             Expr::Call(call) => {
-                // 对于函数调用，需要知道函数的返回类型
-                // 这里简化处理，返回 int 作为默认值
-                Some(Type::Int32)
+                // 方法调用取注册表中的返回类型，无法确定时按 int 处理
+                Some(self.call_return_type(call).unwrap_or(Type::Int32))
             },
             _ => Some(Type::Int32), // 默认返回 int
         }
@@ -46,6 +50,7 @@ impl IRGenerator {
     /// 将 LLVM 类型转换为 Cayvy 类型
     fn llvm_type_to_cay_type(&self, llvm_type: &str) -> Option<Type> {
         match llvm_type {
+            "i16" => Some(Type::Int16),
             "i32" => Some(Type::Int32),
             "i64" => Some(Type::Int64),
             "float" => Some(Type::Float32),
@@ -69,8 +74,13 @@ impl IRGenerator {
     pub fn generate_var_decl(&mut self, var: &VarDecl) -> cayResult<()> {
         // 处理 auto 类型推断
         let actual_type = if var.var_type == Type::Auto {
-            // 从初始化器推断类型
-            if let Some(init) = &var.initializer {
+            // 优先使用语义分析推断出的类型，其次从初始化器推断
+            let inferred = self.type_registry.as_ref()
+                .and_then(|registry| registry.inferred_local(&var.loc))
+                .cloned();
+            if let Some(ty) = inferred {
+                ty
+            } else if let Some(init) = &var.initializer {
                 self.infer_type_from_expr(init).unwrap_or(Type::Int32)
             } else {
                 return Err(crate::error::semantic_error(
@@ -87,6 +97,7 @@ impl IRGenerator {
 
         // 使用作用域管理器生成唯一的 LLVM 变量名
         let llvm_name = self.scope_manager.declare_var(&var.name, &var_type);
+        self.scope_manager.set_cay_type(&var.name, &actual_type);
 
         self.emit_line(&format!("  %{} = alloca {}, align {}", llvm_name, var_type, align));
         // 同时存储到旧系统以保持兼容性
@@ -95,6 +106,16 @@ impl IRGenerator {
         if let Type::Object(class_name) = &actual_type {
             self.var_class_map.insert(var.name.clone(), class_name.clone());
         }
+        // 对象数组记录元素的类名，以便解析 arr[i].field 和 arr[i].method()
+        if let Some(class_name) = Self::object_array_class(&actual_type) {
+            self.array_class_map.insert(var.name.clone(), class_name.clone());
+        }
+        if matches!(&actual_type, Type::Object(class_name) if class_name == LIST_CLASS) {
+            match &var.initializer {
+                Some(init) => self.track_list_assignment(var.name.clone(), init),
+                None => { self.list_element_map.remove(&var.name); }
+            }
+        }
 
         if let Some(init) = var.initializer.as_ref() {
             // 特殊处理数组初始化，传递目标类型信息
@@ -107,7 +128,11 @@ impl IRGenerator {
                 let (value_type, val) = self.parse_typed_value(&value);
 
                 // 如果值类型与变量类型不匹配，需要转换
-                if value_type != var_type {
+                if value_type != var_type && self.is_tuple_type(&value_type) && self.is_tuple_type(&var_type) {
+                    let converted = self.convert_tuple_value(&value_type, &val, &var_type, Some(init));
+                    self.emit_line(&format!("  store {} {}, {}* %{}",
+                        var_type, converted, var_type, llvm_name));
+                } else if value_type != var_type {
                     let temp = self.new_temp();
 
                     // 浮点类型转换
@@ -128,15 +153,22 @@ impl IRGenerator {
                             temp, value_type, val, var_type));
                         self.emit_line(&format!("  store {} {}, {}* %{}, align {}", var_type, temp, var_type, llvm_name, align));
                     }
+                    // null（i64 0）赋给引用类型
+                    else if var_type.ends_with("*") && value_type.starts_with("i") {
+                        self.emit_line(&format!("  {} = inttoptr {} {} to {}",
+                            temp, value_type, val, var_type));
+                        self.emit_line(&format!("  store {} {}, {}* %{}, align {}", var_type, temp, var_type, llvm_name, align));
+                    }
                     // 整数类型转换
                     else if value_type.starts_with("i") && var_type.starts_with("i") && !value_type.ends_with("*") && !var_type.ends_with("*") {
                         let from_bits: u32 = value_type.trim_start_matches('i').parse().unwrap_or(64);
                         let to_bits: u32 = var_type.trim_start_matches('i').parse().unwrap_or(64);
 
                         if to_bits > from_bits {
-                            // 符号扩展
-                            self.emit_line(&format!("  {} = sext {} {} to {}",
-                                temp, value_type, val, var_type));
+                            // 符号扩展（char 零扩展）
+                            let ext = self.int_extension(&value_type, init);
+                            self.emit_line(&format!("  {} = {} {} {} to {}",
+                                temp, ext, value_type, val, var_type));
                         } else {
                             // 截断
                             self.emit_line(&format!("  {} = trunc {} {} to {}",
@@ -146,8 +178,9 @@ impl IRGenerator {
                     }
                     // 整数到浮点数转换
                     else if value_type.starts_with("i") && (var_type == "float" || var_type == "double") {
-                        self.emit_line(&format!("  {} = sitofp {} {} to {}",
-                            temp, value_type, val, var_type));
+                        let conv = self.int_to_float(&value_type, init);
+                        self.emit_line(&format!("  {} = {} {} {} to {}",
+                            temp, conv, value_type, val, var_type));
                         self.emit_line(&format!("  store {} {}, {}* %{}, align {}", var_type, temp, var_type, llvm_name, align));
                     }
                     // 浮点数到整数转换
@@ -171,4 +204,25 @@ impl IRGenerator {
 
         Ok(())
     }
+
+    /// 生成元组解构声明代码: var (x, y) = expr;
+    ///
+    /// 每个名字声明为一个局部变量，类型取自元组对应元素的类型。
+    pub fn generate_tuple_destructure(&mut self, destructure: &TupleDestructure) -> cayResult<()> {
+        let value = self.generate_expression(&destructure.initializer)?;
+        let (tuple_type, tuple_val) = self.parse_typed_value(&value);
+
+        for (index, name) in destructure.names.iter().enumerate() {
+            let element = self.extract_tuple_element(&tuple_type, &tuple_val, index);
+            let (element_type, _) = self.parse_typed_value(&element);
+            let align = self.get_type_align(&element_type);
+
+            let llvm_name = self.scope_manager.declare_var(name, &element_type);
+            self.emit_line(&format!("  %{} = alloca {}, align {}", llvm_name, element_type, align));
+            self.emit_line(&format!("  store {}, {}* %{}", element, element_type, llvm_name));
+            self.var_types.insert(name.clone(), element_type);
+        }
+
+        Ok(())
+    }
 }
@@ -7,16 +7,24 @@ impl IRGenerator {
     pub fn type_to_llvm(&self, ty: &Type) -> String {
         match ty {
             Type::Void => "void".to_string(),
+            Type::Int8 => "i8".to_string(),
+            Type::Int16 => "i16".to_string(),
             Type::Int32 => "i32".to_string(),
             Type::Int64 => "i64".to_string(),
             Type::Float32 => "float".to_string(),
             Type::Float64 => "double".to_string(),
             Type::Bool => "i1".to_string(),
             Type::String => "i8*".to_string(),
+            Type::StringView => "%__cay_string_view".to_string(),
             Type::Char => "i8".to_string(),
             Type::Object(_) => "i8*".to_string(),
             Type::Array(inner) => format!("{}*", self.type_to_llvm(inner)),
             Type::Function(_) => "i8*".to_string(),
+            // 元组降级为字面结构体；元素之间不留空格，保证 "类型 值" 形式能按首个空格切分
+            Type::Tuple(elements) => format!("{{{}}}", elements.iter()
+                .map(|element| self.type_to_llvm(element))
+                .collect::<Vec<_>>()
+                .join(",")),
             Type::Auto => panic!("Type::Auto should have been resolved before code generation"),
         }
     }
@@ -31,6 +39,36 @@ impl IRGenerator {
         }
     }
 
+    /// 拆分元组结构体类型 `{i32,i8*}` 的元素类型（支持嵌套元组）
+    pub fn tuple_element_llvm_types(&self, llvm_type: &str) -> Vec<String> {
+        let inner = llvm_type.strip_prefix('{')
+            .and_then(|rest| rest.strip_suffix('}'))
+            .unwrap_or("");
+        let mut elements = Vec::new();
+        let mut depth = 0;
+        let mut start = 0;
+        for (i, ch) in inner.char_indices() {
+            match ch {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                ',' if depth == 0 => {
+                    elements.push(inner[start..i].to_string());
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        if !inner.is_empty() {
+            elements.push(inner[start..].to_string());
+        }
+        elements
+    }
+
+    /// 判断是否为元组（字面结构体）类型
+    pub fn is_tuple_type(&self, ty: &str) -> bool {
+        ty.starts_with('{')
+    }
+
     /// 判断是否为整数类型
     pub fn is_integer_type(&self, ty: &str) -> bool {
         ty.starts_with("i") && !ty.ends_with("*")
@@ -73,11 +73,19 @@ pub enum cayError {
         message: String,
         suggestion: String,
     },
+
+    #[error("警告被视为错误 [{line}:{column}]: {message}\n  提示: {suggestion}")]
+    Warning {
+        line: usize,
+        column: usize,
+        message: String,
+        suggestion: String,
+    },
 }
 
 pub type cayResult<T> = Result<T, cayError>;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SourceLocation {
     pub line: usize,
     pub column: usize,
@@ -89,6 +97,64 @@ impl fmt::Display for SourceLocation {
     }
 }
 
+/// 警告来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarningKind {
+    /// 预处理器 #warning 指令
+    Preprocessor,
+    /// 使用了已弃用的特性
+    Deprecated,
+    /// 不可达代码
+    Unreachable,
+    /// 使用了高于目标版本的特性
+    Version,
+    /// 其他语义警告
+    Semantic,
+}
+
+/// 编译警告
+///
+/// 各编译阶段不直接打印警告，而是收集为 `cayWarning`，
+/// 由 `Compiler` 统一输出，或在启用 `--werror` 时转换为错误。
+#[derive(Debug, Clone)]
+pub struct cayWarning {
+    pub kind: WarningKind,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl cayWarning {
+    pub fn new(kind: WarningKind, line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// 将警告转换为错误（用于 --werror 模式）
+    pub fn into_error(self) -> cayError {
+        cayError::Warning {
+            line: self.line,
+            column: self.column,
+            message: self.message,
+            suggestion: "已启用 --werror，所有警告都会导致编译失败".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for cayWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line > 0 {
+            write!(f, "warning [{}:{}]: {}", self.line, self.column, self.message)
+        } else {
+            write!(f, "warning: {}", self.message)
+        }
+    }
+}
+
 // 词法错误
 pub fn lexer_error(line: usize, column: usize, message: impl Into<String>) -> cayError {
     let msg = message.into();
@@ -212,8 +278,10 @@ fn get_parser_suggestion(message: &str) -> String {
         "请检查语法结构，可能是关键字拼写错误或缺少必要的符号".to_string()
     } else if message.contains("Expected identifier") {
         "此处需要一个标识符（变量名或函数名）".to_string()
+    } else if message.contains("'void' is not a valid") {
+        "void 只能用作方法的返回类型，变量、字段和参数需要具体的类型，如: int, long, String".to_string()
     } else if message.contains("Expected type") {
-        "变量声明需要指定类型，如: int, long, String, void".to_string()
+        "变量声明需要指定类型，如: int, long, String".to_string()
     } else {
         "请检查代码语法结构".to_string()
     }
@@ -266,6 +334,8 @@ pub fn print_error_with_context(error: &cayError, source: &str, filename: &str)
         cayError::TypeMismatch { line, column, .. } => (*line, *column),
         cayError::UndefinedIdentifier { line, column, .. } => (*line, *column),
         cayError::DuplicateDefinition { line, column, .. } => (*line, *column),
+        cayError::Preprocessor { line, column, .. } => (*line, *column),
+        cayError::Warning { line, column, .. } => (*line, *column),
         _ => (0, 0),
     };
     
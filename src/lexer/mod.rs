@@ -4,8 +4,6 @@ use crate::error::SourceLocation;
 
 #[derive(Logos, Debug, Clone, PartialEq)]
 #[logos(skip r"[ \t\f]+")]
-#[logos(skip r"//[^\n]*")]
-#[logos(skip r"/\*([^*]|\*[^/])*\*\*/")]
 pub enum Token {
     // 关键字
     #[token("public")]
@@ -18,22 +16,35 @@ pub enum Token {
     Static,
     #[token("final")]
     Final,
+    #[token("const")]
+    Const,
     #[token("abstract")]
     Abstract,
     #[token("native")]
     Native,
-    // 注解 - 注意：@main 和 @Override 是完整的令牌，不是 @ + 标识符
+    // 注解 - 注意：@main、@Override 和 @SinceVersion 是完整的令牌，不是 @ + 标识符
     #[token("@main")]
+    #[token("@entry")]  // @main 的别名
     AtMain,
     #[token("@Override")]
     AtOverride,
+    #[token("@SinceVersion")]
+    AtSinceVersion,
+    #[token("@inline")]
+    AtInline,
     #[token("class")]
     Class,
     #[token("void")]
     Void,
+    #[token("int8")]
+    Int8,
+    #[token("int16")]
+    Int16,
     #[token("int")]
+    #[token("int32")]
     Int,
     #[token("long")]
+    #[token("int64")]
     Long,
     #[token("float")]
     Float,
@@ -63,6 +74,10 @@ pub enum Token {
     For,
     #[token("do")]
     Do,
+    #[token("loop")]
+    Loop,
+    #[token("repeat")]
+    Repeat,
     #[token("switch")]
     Switch,
     #[token("case")]
@@ -75,6 +90,8 @@ pub enum Token {
     Break,
     #[token("continue")]
     Continue,
+    #[token("fallthrough")]
+    Fallthrough,
     #[token("new")]
     New,
     #[token("this")]
@@ -155,7 +172,7 @@ pub enum Token {
     })]
     StringLiteral(Option<String>),
     
-    #[regex(r"'([^'\\]|\\.)'", |lex| {
+    #[regex(r"'([^'\\]|\\.|\\x[0-9a-fA-F]{2}|\\u[0-9a-fA-F]{4})'", |lex| {
         let s = lex.slice();
         let content = &s[1..s.len()-1];
         process_char_escape(content)
@@ -177,6 +194,10 @@ pub enum Token {
     EqEq,
     #[token("!=")]
     NotEq,
+    #[token("===")]
+    TripleEq,
+    #[token("!==")]
+    NotTripleEq,
     #[token("<")]
     Lt,
     #[token("<=")]
@@ -259,6 +280,11 @@ pub enum Token {
     // 换行（用于跟踪行号）- 支持 Windows \r\n 和 Unix \n
     #[regex(r"\r?\n")]
     Newline,
+
+    // 注释（保留原始文本，包括 // 或 /* */ 定界符）- 默认由 Lexer 丢弃
+    #[regex(r"//[^\r\n]*", |lex| lex.slice().to_string())]
+    #[token("/*", lex_block_comment)]
+    Comment(String),
 }
 
 #[derive(Debug, Clone)]
@@ -272,15 +298,25 @@ pub struct Lexer<'a> {
     inner: logos::Lexer<'a, Token>,
     line: usize,
     column: usize,
+    line_start: usize,  // 当前行首的字节偏移，用于计算列号
+    preserve_comments: bool,  // 是否将注释作为 Token::Comment 输出
 }
 
 impl<'a> Lexer<'a> {
     pub fn new(source: &'a str) -> Self {
+        Self::with_comments(source, false)
+    }
+
+    /// 创建词法分析器，`preserve_comments` 为 true 时注释作为 `Token::Comment` 输出
+    /// （供格式化、文档工具使用），否则跳过注释
+    pub fn with_comments(source: &'a str, preserve_comments: bool) -> Self {
         Self {
             source,
             inner: Token::lexer(source),
             line: 1,
             column: 1,
+            line_start: 0,
+            preserve_comments,
         }
     }
 
@@ -291,6 +327,8 @@ impl<'a> Lexer<'a> {
             match token_result {
                 Ok(token) => {
                     let span = self.inner.span();
+                    // 列号按行首偏移计算，被跳过的空白同样计入
+                    self.column = span.start - self.line_start + 1;
                     let loc = SourceLocation {
                         line: self.line,
                         column: self.column,
@@ -299,10 +337,30 @@ impl<'a> Lexer<'a> {
                     // 更新行号和列号
                     if token == Token::Newline {
                         self.line += 1;
+                        self.line_start = span.end;
                         self.column = 1;
                         continue; // 不保留换行token
-                    } else {
-                        self.column += span.end - span.start;
+                    }
+
+                    if token == Token::CharLiteral(None) {
+                        return Err(lexer_error(
+                            loc.line,
+                            loc.column,
+                            format!("Invalid escape sequence in character literal: {}", &self.source[span])
+                        ));
+                    }
+
+                    self.column += span.end - span.start;
+                    if let Token::Comment(text) = &token {
+                        // 块注释可能跨行
+                        if let Some(last_newline) = text.rfind('\n') {
+                            self.line += text.matches('\n').count();
+                            self.line_start = span.start + last_newline + 1;
+                            self.column = span.end - self.line_start + 1;
+                        }
+                        if !self.preserve_comments {
+                            continue;
+                        }
                     }
                     
                     tokens.push(TokenWithLocation { token, loc });
@@ -312,7 +370,7 @@ impl<'a> Lexer<'a> {
                     let error_char = &self.source[span.clone()];
                     return Err(lexer_error(
                         self.line,
-                        self.column,
+                        span.start - self.line_start + 1,
                         format!("Unexpected character: '{}'", error_char)
                     ));
                 }
@@ -333,10 +391,22 @@ impl<'a> Lexer<'a> {
 }
 
 pub fn lex(source: &str) -> cayResult<Vec<TokenWithLocation>> {
-    let mut lexer = Lexer::new(source);
+    lex_with_comments(source, false)
+}
+
+/// 词法分析，`preserve_comments` 为 true 时保留注释令牌
+pub fn lex_with_comments(source: &str, preserve_comments: bool) -> cayResult<Vec<TokenWithLocation>> {
+    let mut lexer = Lexer::with_comments(source, preserve_comments);
     lexer.tokenize()
 }
 
+/// 读取块注释剩余部分直到 `*/`，未闭合的块注释视为词法错误
+fn lex_block_comment(lex: &mut logos::Lexer<Token>) -> Option<String> {
+    let end = lex.remainder().find("*/")?;
+    lex.bump(end + 2);
+    Some(lex.slice().to_string())
+}
+
 /// 处理字符串中的转义序列
 fn process_escape_sequences(s: &str) -> String {
     let mut result = String::with_capacity(s.len());
@@ -367,20 +437,28 @@ fn process_escape_sequences(s: &str) -> String {
     result
 }
 
-/// 处理字符字面量的转义序列
+/// 处理字符字面量的转义序列，无效的转义返回 None
+///
+/// 除常见转义外支持 `\xHH` 和 `\uHHHH`，码点必须是 ASCII（不超过 0x7F）：
+/// char 按无符号的字符码参与运算，而 i8 超过 0x7F 的值在扩展时会被当成负数。
 fn process_char_escape(s: &str) -> Option<char> {
-    if s.starts_with("\\") {
-        match s.chars().nth(1) {
-            Some('n') => Some('\n'),
-            Some('t') => Some('\t'),
-            Some('r') => Some('\r'),
-            Some('\\') => Some('\\'),
-            Some('\'') => Some('\''),
-            Some('"') => Some('"'),
-            Some('0') => Some('\0'),
-            _ => s.chars().nth(1),
+    let escape = match s.strip_prefix('\\') {
+        Some(escape) => escape,
+        None => return s.chars().next(),
+    };
+    match escape {
+        "n" => Some('\n'),
+        "t" => Some('\t'),
+        "r" => Some('\r'),
+        "\\" => Some('\\'),
+        "'" => Some('\''),
+        "\"" => Some('"'),
+        "0" => Some('\0'),
+        _ => {
+            let digits = escape.strip_prefix('x').filter(|d| d.len() == 2)
+                .or_else(|| escape.strip_prefix('u').filter(|d| d.len() == 4))?;
+            let code = u32::from_str_radix(digits, 16).ok().filter(|&code| code <= 0x7F)?;
+            char::from_u32(code)
         }
-    } else {
-        s.chars().next()
     }
 }
@@ -6,47 +6,170 @@ pub mod lexer;
 pub mod parser;
 pub mod semantic;
 pub mod codegen;
+pub mod prelude;
+pub mod pipeline;
+pub mod builtins;
 
 use std::path::{Path, PathBuf};
-use error::cayResult;
+
+pub use ast::Program;
+pub use pipeline::Pipeline;
+pub use builtins::BuiltinSpec;
+pub use error::{cayError, cayResult, cayWarning};
+pub use types::Type;
+pub use codegen::source_map::SourceMap;
+
+/// 运行时函数（字符串拼接、数值转字符串等）的输出方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RuntimeMode {
+    /// 总是输出全部运行时函数
+    Always,
+    /// 不输出运行时函数，程序需要时报错（--no-runtime）
+    Never,
+    /// 只输出程序实际调用的运行时函数
+    #[default]
+    OnDemand,
+}
+
+/// 符号表的输出格式（--dump-symbols）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SymbolFormat {
+    /// 纯文本
+    #[default]
+    Text,
+    /// JSON（--dump-symbols=json）
+    Json,
+}
 
 /// 编译器配置选项
 #[derive(Debug, Clone)]
 pub struct CompilerOptions {
     pub target_os: String,
+    /// 显式指定目标三元组，覆盖由 target_os 推导的默认值（--target-triple）
+    pub target_triple: Option<String>,
     pub features: Vec<String>,
     pub no_features: Vec<String>,
     pub defines: Vec<String>,
     pub undefines: Vec<String>,
     pub obfuscate: bool,
+    /// 将所有警告视为错误（--werror）
+    pub warnings_as_errors: bool,
+    /// 生成后输出每个函数的 IR 指令统计（--dump-ir-stats）
+    pub dump_ir_stats: bool,
+    /// 语义分析后输出所有类的字段和方法（--dump-symbols）
+    pub dump_symbols: Option<SymbolFormat>,
+    /// 目标 Cavvy 版本，使用更高版本引入的方法时发出警告（--target-version）
+    pub target_version: Option<String>,
+    /// 运行时函数的输出方式（--no-runtime 对应 Never）
+    pub emit_runtime: RuntimeMode,
+    /// 允许 `string + 基本类型` 隐式转换为字符串拼接（--implicit-string-concat），默认严格模式
+    pub implicit_string_concat: bool,
+    /// 局部变量遮蔽方法参数时发出警告（--warn-shadow）
+    pub warn_shadowed_params: bool,
+    /// 循环条件恒为真且循环体中没有可达的 break/return 时发出警告（--warn-infinite-loop）
+    pub warn_infinite_loops: bool,
+    /// 没有 `f` 后缀的浮点字面量赋给 float 并丢失精度时发出警告（--warn-float-precision）
+    pub warn_float_precision: bool,
+    /// final 数组的元素也不可修改，对其元素赋值报错（--immutable-final-arrays）
+    pub immutable_final_arrays: bool,
+    /// 数组访问时检查下标是否在 [0, length) 内，越界时终止程序（--bounds-check）
+    pub bounds_check: bool,
+    /// 语义错误达到该数量后停止分析并报告 "too many errors"，0 表示不限制（--max-errors）
+    pub max_errors: usize,
+    /// 编译为库模块：不生成 C `main` 入口，只导出 public 方法（--library）
+    pub library: bool,
+    /// 只有一个调用点、指令数不超过该值的小型静态方法内联到调用处，None 表示关闭（--inline-small）
+    pub inline_threshold: Option<usize>,
+    /// 交给 clang 优化生成的 IR 时使用的优化级别（-O0 ~ -O3、-Os、-Oz），None 表示不优化（--opt-ir）
+    ///
+    /// 优化由命令行工具在生成 IR 之后调用 clang 完成，`compile_to_ir` 返回的始终是未优化的 IR。
+    pub optimization: Option<String>,
 }
 
 impl Default for CompilerOptions {
     fn default() -> Self {
         Self {
             target_os: std::env::consts::OS.to_string(),
+            target_triple: None,
             features: Vec::new(),
             no_features: Vec::new(),
             defines: Vec::new(),
             undefines: Vec::new(),
             obfuscate: false,
+            warnings_as_errors: false,
+            dump_ir_stats: false,
+            dump_symbols: None,
+            target_version: None,
+            emit_runtime: RuntimeMode::default(),
+            implicit_string_concat: false,
+            warn_shadowed_params: false,
+            warn_infinite_loops: false,
+            warn_float_precision: false,
+            immutable_final_arrays: false,
+            bounds_check: false,
+            max_errors: 100,
+            library: false,
+            inline_threshold: None,
+            optimization: None,
         }
     }
 }
 
+/// Cavvy 编译器的主入口
+///
+/// 使用 [`Compiler::new`] 获得默认配置，或通过 [`Compiler::builder`] 逐项配置：
+///
+/// ```
+/// use cavvy::prelude::*;
+///
+/// let compiler = Compiler::builder()
+///     .target("linux")
+///     .optimize("-O2")
+///     .implicit_string_concat(true)
+///     .build();
+///
+/// let ir = compiler.compile_to_ir(r#"
+/// public class Main {
+///     public static void main() {
+///         println("answer: " + 42);
+///     }
+/// }
+/// "#)?;
+/// assert!(ir.contains("x86_64-unknown-linux-gnu"));
+/// # Ok::<(), cayError>(())
+/// ```
 pub struct Compiler {
     options: CompilerOptions,
+    builtins: builtins::BuiltinRegistry,
 }
 
 impl Compiler {
     pub fn new() -> Self {
-        Self {
-            options: CompilerOptions::default(),
-        }
+        Self::with_options(CompilerOptions::default())
     }
     
     pub fn with_options(options: CompilerOptions) -> Self {
-        Self { options }
+        Self { options, builtins: builtins::BuiltinRegistry::new() }
+    }
+
+    /// 创建编译器构建器
+    pub fn builder() -> CompilerBuilder {
+        CompilerBuilder::new()
+    }
+
+    /// 获取编译器配置
+    pub fn options(&self) -> &CompilerOptions {
+        &self.options
+    }
+
+    /// 注册自定义内置函数，同名的函数会被替换
+    pub fn register_builtin(&mut self, spec: BuiltinSpec) {
+        self.builtins.insert(spec.name.clone(), spec);
+    }
+
+    /// 获取已注册的自定义内置函数
+    pub fn builtins(&self) -> &builtins::BuiltinRegistry {
+        &self.builtins
     }
 
     /// 编译源代码为 LLVM IR
@@ -58,8 +181,24 @@ impl Compiler {
     /// # Returns
     /// 编译成功返回 Ok(())
     pub fn compile(&self, source: &str, output_path: &str) -> cayResult<()> {
+        let ir = self.compile_to_ir(source)?;
+
+        // 输出到文件
+        std::fs::write(output_path, ir)
+            .map_err(|e| error::cayError::Io(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 编译源代码，直接返回生成的 LLVM IR 文本
+    ///
+    /// # Arguments
+    /// * `source` - 原始源代码（已预处理）
+    pub fn compile_to_ir(&self, source: &str) -> cayResult<String> {
+        let pipeline = self.pipeline();
+
         // 1. 词法分析
-        let tokens = lexer::lex(source)?;
+        let tokens = pipeline.lex(source)?;
         
         // 调试：打印所有token
         #[cfg(debug_assertions)]
@@ -72,32 +211,28 @@ impl Compiler {
         }
         
         // 2. 语法分析
-        let ast = parser::parse(tokens)?;
+        let ast = pipeline.parse(tokens)?;
         
         // 3. 语义分析
-        let mut analyzer = semantic::SemanticAnalyzer::new();
-        analyzer.analyze(&ast)?;
-
-        // 4. 代码生成 - 生成LLVM IR（字符串常量已在生成器内处理）
-        let mut ir_gen = codegen::IRGenerator::new();
-        // 传递多平台配置
-        ir_gen.set_platform_config(&self.options);
-        // 传递类型注册表以支持正确的方法名生成
-        ir_gen.set_type_registry(analyzer.get_type_registry().clone());
-        let mut ir = ir_gen.generate(&ast)?;
-        
-        // 5. 如果启用了混淆，应用IR混淆
-        if self.options.obfuscate {
-            use codegen::obfuscator::IRObfuscator;
-            let mut obfuscator = IRObfuscator::new();
-            ir = obfuscator.obfuscate_ir(&ir);
-        }
-        
-        // 输出到文件
-        std::fs::write(output_path, ir)
-            .map_err(|e| error::cayError::Io(e.to_string()))?;
-        
-        Ok(())
+        let registry = pipeline.analyze(&ast)?;
+
+        // 4. 代码生成 - 生成LLVM IR（含调试构建下的 IR 检查、混淆和指令统计）
+        pipeline.generate(&ast, &registry)
+    }
+
+    /// 编译源代码，返回 LLVM IR 以及 IR 行到源代码位置的映射
+    ///
+    /// 映射以语句为单位，供调试器、覆盖率等工具把 IR 指令关联回 `.cay` 源代码。
+    pub fn compile_with_sourcemap(&self, source: &str) -> cayResult<(String, SourceMap)> {
+        let pipeline = self.pipeline();
+        let ast = pipeline.parse(pipeline.lex(source)?)?;
+        let registry = pipeline.analyze(&ast)?;
+        pipeline.generate_with_source_map(&ast, &registry)
+    }
+
+    /// 获取分阶段执行编译流程的 [`Pipeline`]
+    pub fn pipeline(&self) -> Pipeline<'_> {
+        Pipeline::new(self)
     }
 
     /// 从文件编译，自动执行预处理
@@ -122,11 +257,31 @@ impl Compiler {
             .unwrap_or_else(|| PathBuf::from("."));
         
         // 预处理
-        let preprocessed = preprocessor::preprocess(&source, input_path, base_dir)?;
+        let mut preprocessor = preprocessor::Preprocessor::new(base_dir);
+        let preprocessed = preprocessor.process(&source, input_path)?;
+        self.report_warnings(preprocessor.take_warnings())?;
         
         // 编译预处理后的代码
         self.compile(&preprocessed, output_path)
     }
+
+    /// 统一处理各阶段收集到的警告
+    ///
+    /// 默认输出到 stderr；启用 `warnings_as_errors` 时，
+    /// 第一条警告会转换为错误并使编译失败。
+    pub(crate) fn report_warnings(&self, warnings: Vec<cayWarning>) -> cayResult<()> {
+        if self.options.warnings_as_errors {
+            if let Some(warning) = warnings.into_iter().next() {
+                return Err(warning.into_error());
+            }
+            return Ok(());
+        }
+
+        for warning in &warnings {
+            eprintln!("{}", warning);
+        }
+        Ok(())
+    }
 }
 
 impl Default for Compiler {
@@ -135,6 +290,166 @@ impl Default for Compiler {
     }
 }
 
+/// 编译器构建器，逐项设置 [`CompilerOptions`] 后生成 [`Compiler`]
+#[derive(Debug, Clone, Default)]
+pub struct CompilerBuilder {
+    options: CompilerOptions,
+    builtins: Vec<BuiltinSpec>,
+}
+
+impl CompilerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 目标操作系统（windows / linux / macos），决定目标三元组和平台声明
+    pub fn target(mut self, target_os: impl Into<String>) -> Self {
+        self.options.target_os = target_os.into();
+        self
+    }
+
+    /// 显式指定目标三元组（如 `aarch64-unknown-linux-gnu`），覆盖 target 推导的默认值
+    pub fn target_triple(mut self, triple: impl Into<String>) -> Self {
+        self.options.target_triple = Some(triple.into());
+        self
+    }
+
+    /// 启用一个平台特性
+    pub fn feature(mut self, feature: impl Into<String>) -> Self {
+        self.options.features.push(feature.into());
+        self
+    }
+
+    /// 禁用一个平台特性
+    pub fn no_feature(mut self, feature: impl Into<String>) -> Self {
+        self.options.no_features.push(feature.into());
+        self
+    }
+
+    /// 定义一个宏
+    pub fn define(mut self, name: impl Into<String>) -> Self {
+        self.options.defines.push(name.into());
+        self
+    }
+
+    /// 取消定义一个宏
+    pub fn undefine(mut self, name: impl Into<String>) -> Self {
+        self.options.undefines.push(name.into());
+        self
+    }
+
+    /// 混淆生成的 IR
+    pub fn obfuscate(mut self, enabled: bool) -> Self {
+        self.options.obfuscate = enabled;
+        self
+    }
+
+    /// 将所有警告视为错误
+    pub fn warnings_as_errors(mut self, enabled: bool) -> Self {
+        self.options.warnings_as_errors = enabled;
+        self
+    }
+
+    /// 生成后输出每个函数的 IR 指令统计
+    pub fn dump_ir_stats(mut self, enabled: bool) -> Self {
+        self.options.dump_ir_stats = enabled;
+        self
+    }
+
+    /// 语义分析后按指定格式输出符号表
+    pub fn dump_symbols(mut self, format: SymbolFormat) -> Self {
+        self.options.dump_symbols = Some(format);
+        self
+    }
+
+    /// 目标 Cavvy 版本
+    pub fn target_version(mut self, version: impl Into<String>) -> Self {
+        self.options.target_version = Some(version.into());
+        self
+    }
+
+    /// 运行时函数的输出方式
+    pub fn emit_runtime(mut self, mode: RuntimeMode) -> Self {
+        self.options.emit_runtime = mode;
+        self
+    }
+
+    /// 允许 `string + 基本类型` 隐式拼接
+    pub fn implicit_string_concat(mut self, enabled: bool) -> Self {
+        self.options.implicit_string_concat = enabled;
+        self
+    }
+
+    /// 局部变量遮蔽方法参数时发出警告
+    pub fn warn_shadowed_params(mut self, enabled: bool) -> Self {
+        self.options.warn_shadowed_params = enabled;
+        self
+    }
+
+    /// 条件恒为真且无法退出的循环发出警告
+    pub fn warn_infinite_loops(mut self, enabled: bool) -> Self {
+        self.options.warn_infinite_loops = enabled;
+        self
+    }
+
+    /// double 字面量赋给 float 丢失精度时发出警告
+    pub fn warn_float_precision(mut self, enabled: bool) -> Self {
+        self.options.warn_float_precision = enabled;
+        self
+    }
+
+    /// final 数组的元素不可修改
+    pub fn immutable_final_arrays(mut self, enabled: bool) -> Self {
+        self.options.immutable_final_arrays = enabled;
+        self
+    }
+
+    /// 数组访问时检查下标范围
+    pub fn bounds_check(mut self, enabled: bool) -> Self {
+        self.options.bounds_check = enabled;
+        self
+    }
+
+    /// 设置语义错误数量上限，0 表示不限制
+    pub fn max_errors(mut self, limit: usize) -> Self {
+        self.options.max_errors = limit;
+        self
+    }
+
+    /// 编译为不含 C 入口的库模块
+    pub fn library(mut self, enabled: bool) -> Self {
+        self.options.library = enabled;
+        self
+    }
+
+    /// 内联指令数不超过 `threshold` 且只有一个调用点的小型静态方法
+    pub fn inline_threshold(mut self, threshold: usize) -> Self {
+        self.options.inline_threshold = Some(threshold);
+        self
+    }
+
+    /// 生成 IR 后交给 clang 按指定级别（如 `-O2`）优化
+    pub fn optimize(mut self, level: impl Into<String>) -> Self {
+        self.options.optimization = Some(level.into());
+        self
+    }
+
+    /// 注册一个自定义内置函数
+    pub fn builtin(mut self, spec: BuiltinSpec) -> Self {
+        self.builtins.push(spec);
+        self
+    }
+
+    /// 生成配置好的编译器
+    pub fn build(self) -> Compiler {
+        let mut compiler = Compiler::with_options(self.options);
+        for spec in self.builtins {
+            compiler.register_builtin(spec);
+        }
+        compiler
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,6 +468,69 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_char_literal_escapes() {
+        let chars: Vec<Option<char>> = lexer::lex(r"'\u0041' '\t' '\0' '\x7a' 'q'").unwrap().iter()
+            .filter_map(|t| match t.token {
+                lexer::Token::CharLiteral(c) => Some(c),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(chars, vec![Some('A'), Some('\t'), Some('\0'), Some('z'), Some('q')]);
+
+        for invalid in [r"'\x'", r"'\q'", r"'\u4e2d'", r"'\xE9'", r"'\u0080'"] {
+            let err = lexer::lex(invalid).unwrap_err().to_string();
+            assert!(err.contains("Invalid escape sequence in character literal"), "{}: {}", invalid, err);
+        }
+    }
+
+    #[test]
+    fn test_lexer_preserves_comments() {
+        let source = "// header\nint a = 1; /* inline */ int b;\n/* multi\n   line */ int c; // tail\n";
+
+        // 默认跳过注释，块注释跨行后的位置仍然正确
+        let tokens = lexer::lex(source).unwrap();
+        assert!(!tokens.iter().any(|t| matches!(t.token, lexer::Token::Comment(_))));
+        let c = tokens.iter().find(|t| t.token == lexer::Token::Identifier("c".to_string())).unwrap();
+        assert_eq!((c.loc.line, c.loc.column), (4, 16));
+
+        let tokens = lexer::lex_with_comments(source, true).unwrap();
+        let comments: Vec<(String, usize, usize)> = tokens.iter()
+            .filter_map(|t| match &t.token {
+                lexer::Token::Comment(text) => Some((text.clone(), t.loc.line, t.loc.column)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(comments, vec![
+            ("// header".to_string(), 1, 1),
+            ("/* inline */".to_string(), 2, 12),
+            ("/* multi\n   line */".to_string(), 3, 1),
+            ("// tail".to_string(), 4, 19),
+        ]);
+
+        // 保留注释不影响其他令牌的位置
+        let c = tokens.iter().find(|t| t.token == lexer::Token::Identifier("c".to_string())).unwrap();
+        assert_eq!((c.loc.line, c.loc.column), (4, 16));
+    }
+
+    #[test]
+    fn test_lexer_shift_and_comparison() {
+        use lexer::Token::*;
+        let kinds = |source: &str| -> Vec<lexer::Token> {
+            lexer::lex(source).unwrap().into_iter()
+                .map(|t| t.token)
+                .filter(|t| !matches!(t, IntegerLiteral(_) | Identifier(_) | Newline))
+                .collect()
+        };
+
+        assert_eq!(kinds("x >> 2 > 1"), vec![Shr, Gt]);
+        assert_eq!(kinds("x > 2"), vec![Gt]);
+        assert_eq!(kinds("x>>1>x>>2"), vec![Shr, Gt, Shr]);
+        assert_eq!(kinds("x >>> 3 >= 8"), vec![UnsignedShr, Ge]);
+        assert_eq!(kinds("x>>>2>=16"), vec![UnsignedShr, Ge]);
+        assert_eq!(kinds("a > > b"), vec![Gt, Gt]);
+    }
+
     #[test]
     fn test_hello_parser() {
         let source = r#"public class hello {
@@ -165,6 +543,63 @@ mod tests {
         println!("AST: {:?}", ast);
     }
 
+    #[test]
+    fn test_nested_ternary_is_right_associative() {
+        use ast::{ClassMember, Expr, Stmt};
+        let source = r#"public class Test {
+    public static void main() {
+        int r = a ? b : c ? d : e;
+        int s = a ? b ? c : d : e;
+    }
+}"#;
+        let ast = parser::parse(lexer::lex(source).unwrap()).unwrap();
+        let body = match &ast.classes[0].members[0] {
+            ClassMember::Method(method) => &method.body.as_ref().unwrap().statements,
+            _ => panic!("expected main method"),
+        };
+        let ternary = |stmt: &Stmt| match stmt {
+            Stmt::VarDecl(var) => match var.initializer.as_ref().unwrap() {
+                Expr::Ternary(t) => t.clone(),
+                other => panic!("expected ternary, got {:?}", other),
+            },
+            other => panic!("expected variable declaration, got {:?}", other),
+        };
+        let is_ident = |expr: &Expr, name: &str| matches!(expr, Expr::Identifier(n) if n == name);
+
+        // a ? b : (c ? d : e)
+        let r = ternary(&body[0]);
+        assert!(is_ident(&r.condition, "a") && is_ident(&r.true_branch, "b"));
+        match r.false_branch.as_ref() {
+            Expr::Ternary(inner) => {
+                assert!(is_ident(&inner.condition, "c"));
+                assert!(is_ident(&inner.true_branch, "d"));
+                assert!(is_ident(&inner.false_branch, "e"));
+            }
+            other => panic!("else branch should be nested ternary, got {:?}", other),
+        }
+
+        // a ? (b ? c : d) : e
+        let s = ternary(&body[1]);
+        assert!(matches!(s.true_branch.as_ref(), Expr::Ternary(_)));
+        assert!(is_ident(&s.false_branch, "e"));
+    }
+
+    #[test]
+    fn test_unbalanced_delimiters_point_at_opener() {
+        let parse_err = |source: &str| parser::parse(lexer::lex(source).unwrap()).unwrap_err().to_string();
+
+        // 文件末尾仍未闭合
+        let err = parse_err("public class A {\n    public static void main() {\n        f(1;\n    }\n}");
+        assert!(err.contains("Mismatched '}': unmatched '(' opened at line 3, column 10"), "got: {}", err);
+
+        // 缺少 } 时按缩进找到真正未闭合的开括号
+        let err = parse_err("public class A {\n    void f() {\n        if (x) {\n            g();\n    }\n}");
+        assert!(err.contains("Unmatched '{' opened at line 3, column 16"), "got: {}", err);
+
+        let err = parse_err("public class A {\n}\n}");
+        assert!(err.contains("Unexpected '}' without a matching '{'"), "got: {}", err);
+    }
+
     #[test]
     fn test_preprocessor_define() {
         let source = r#"
@@ -179,6 +614,1694 @@ public class Test {
         assert!(preprocessed.contains("int x = 1;"));
     }
 
+    #[test]
+    fn test_predefined_macros() {
+        let source = r#"public class Test {
+    public static void main() {
+        int line = __LINE__;
+        String file = __FILE__;
+        String version = __CAVVY_VERSION__;
+#ifdef __LINE__
+        int after = __LINE__;
+#endif
+    }
+}"#;
+        let preprocessed = preprocessor::preprocess(source, "dir\\test.cay", ".").unwrap();
+        assert!(preprocessed.contains("int line = 3;"));
+        assert!(preprocessed.contains("String file = \"dir\\\\test.cay\";"));
+        assert!(preprocessed.contains(&format!("String version = \"{}\";", env!("CARGO_PKG_VERSION"))));
+        assert!(preprocessed.contains("int after = 7;"));
+    }
+
+    #[test]
+    fn test_error_and_warning_messages_process_escapes() {
+        let source = "#error \"line1\\nline2\\tend\"\npublic class Test {}";
+        let err = preprocessor::Preprocessor::new(".").process(source, "test.cay").unwrap_err();
+        assert!(err.to_string().contains("#error: line1\nline2\tend"), "got: {}", err);
+
+        let source = "#warning \"say \\\"hi\\\" at C:\\\\tmp\\q\"\npublic class Test {}";
+        let mut preprocessor = preprocessor::Preprocessor::new(".");
+        preprocessor.process(source, "test.cay").unwrap();
+        let warnings = preprocessor.take_warnings();
+        assert!(warnings[0].to_string().contains(r#"#warning: say "hi" at C:\tmp\q"#), "got: {}", warnings[0]);
+
+        // #include 路径不处理转义
+        let source = "#include \"dir\\new\\file.cay\"\npublic class Test {}";
+        let err = preprocessor::Preprocessor::new(".").process(source, "test.cay").unwrap_err();
+        assert!(err.to_string().contains(r"dir\new\file.cay"), "got: {}", err);
+    }
+
+    #[test]
+    fn test_pragma_directives() {
+        let source = "#pragma once\n#pragma pack(1)\npublic class Test {}";
+        let mut preprocessor = preprocessor::Preprocessor::new(".");
+        let preprocessed = preprocessor.process(source, "test.cay").unwrap();
+        assert!(preprocessed.contains("public class Test {}"));
+
+        // 未知的 #pragma 只产生警告，不中断预处理
+        let warnings = preprocessor.take_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].to_string().contains("pack(1)"));
+    }
+
+    #[test]
+    fn test_include_not_found_lists_searched_dirs() {
+        let source = "#include \"missing_header.cay\"\npublic class Test {}";
+        let err = preprocessor::preprocess_with_system_paths(
+            source,
+            "project/src/main.cay",
+            "project",
+            vec![PathBuf::from("sys_include"), PathBuf::from("vendor/include")],
+        ).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("missing_header.cay"), "got: {}", message);
+        for dir in ["project/src", "project", "sys_include", "vendor/include"] {
+            assert!(message.contains(dir), "searched dir '{}' should be listed, got: {}", dir, message);
+        }
+    }
+
+    #[test]
+    fn test_include_relative_to_immediate_includer() {
+        let root = std::env::temp_dir().join("cavvy_include_chain_test");
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("a/b/c")).unwrap();
+        std::fs::write(root.join("a/one.cay"), "#include \"b/two.cay\"\n").unwrap();
+        std::fs::write(root.join("a/b/two.cay"), "#include \"c/three.cay\"\n").unwrap();
+        std::fs::write(root.join("a/b/c/three.cay"), "#include \"../../leaf.cay\"\n").unwrap();
+        std::fs::write(root.join("a/leaf.cay"), "#define CHAIN_DEPTH 3\n").unwrap();
+
+        // 入口文件与 base_dir 都在 root，只有按直接包含者的目录解析才能找到各级文件
+        let main = root.join("main.cay");
+        let source = "#include \"a/one.cay\"\nint x = CHAIN_DEPTH;";
+        let result = preprocessor::preprocess(
+            source,
+            &main.to_string_lossy(),
+            &root,
+        ).unwrap();
+        assert!(result.contains("int x = 3;"), "got: {}", result);
+    }
+
+    #[test]
+    fn test_werror_preprocessor_warning() {
+        let dir = std::env::temp_dir().join("cavvy_werror_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("werror.cay");
+        let output = dir.join("werror.ll");
+        std::fs::write(&input, r#"
+#warning "this feature is experimental"
+public class Test {
+    public static void main() {
+        println("hi");
+    }
+}
+"#).unwrap();
+        let input = input.to_string_lossy().to_string();
+        let output = output.to_string_lossy().to_string();
+
+        // 默认模式下警告不会中断编译
+        assert!(Compiler::new().compile_file(&input, &output).is_ok());
+
+        // --werror 模式下 #warning 导致编译失败
+        let options = CompilerOptions {
+            warnings_as_errors: true,
+            ..CompilerOptions::default()
+        };
+        let err = Compiler::with_options(options).compile_file(&input, &output).unwrap_err();
+        assert!(matches!(err, error::cayError::Warning { .. }));
+        assert!(err.to_string().contains("this feature is experimental"));
+    }
+
+    #[test]
+    fn test_dump_ir_stats_hello() {
+        let source = r#"public class hello {
+    public static void main() {
+        print("Hello, World");
+    }
+}"#;
+        let options = CompilerOptions {
+            dump_ir_stats: true,
+            ..CompilerOptions::default()
+        };
+        let ir = Compiler::with_options(options).compile_to_ir(source).unwrap();
+        let stats = codegen::ir_stats::IrStats::from_ir(&ir);
+        let main = stats.get("main").expect("main 函数应出现在统计中");
+        assert!(main.instructions > 0);
+        assert!(stats.total() >= main.instructions);
+        assert!(stats.to_string().contains("main"));
+    }
+
+    #[test]
+    fn test_target_version_warns_on_newer_method() {
+        let source = r#"public class Test {
+    public static void main() {
+        String s = "hello world";
+        String r = s.replace("world", "cavvy");
+        println(r);
+    }
+}"#;
+        // 目标版本足够新时不产生警告
+        let options = CompilerOptions {
+            target_version: Some("0.4.4".to_string()),
+            warnings_as_errors: true,
+            ..CompilerOptions::default()
+        };
+        assert!(Compiler::with_options(options).compile_to_ir(source).is_ok());
+
+        // 目标版本低于 String.replace 的引入版本时发出警告
+        let options = CompilerOptions {
+            target_version: Some("0.3.0".to_string()),
+            warnings_as_errors: true,
+            ..CompilerOptions::default()
+        };
+        let err = Compiler::with_options(options).compile_to_ir(source).unwrap_err();
+        assert!(err.to_string().contains("Method 'replace' was added in version 0.3.4, but target version is 0.3.0"));
+    }
+
+    #[test]
+    fn test_since_version_on_user_method() {
+        let source = r#"public class Test {
+    @SinceVersion("0.5.0")
+    public static int answer() {
+        return 42;
+    }
+
+    public static void main() {
+        int x = answer();
+        println(x);
+    }
+}"#;
+        let tokens = lexer::lex(source).unwrap();
+        let ast = parser::parse(tokens).unwrap();
+        let mut analyzer = semantic::SemanticAnalyzer::new();
+        analyzer.set_target_version(Some("0.4.4".to_string()));
+        analyzer.analyze(&ast).unwrap();
+        let warnings = analyzer.take_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("Method 'answer' was added in version 0.5.0, but target version is 0.4.4"));
+    }
+
+    #[test]
+    fn test_since_version_in_println_and_nested_bodies() {
+        let source = r#"public class Test {
+    public static void main() {
+        String s = "abc";
+        println(s.replace("a", "b"));
+        if (s.length() > 0) {
+            String t = s.replace("b", "c");
+        }
+        int i = 0;
+        while (i < 1) {
+            s = s.replace("c", "d");
+            i = i + 1;
+        }
+    }
+}"#;
+        let tokens = lexer::lex(source).unwrap();
+        let ast = parser::parse(tokens).unwrap();
+        let mut analyzer = semantic::SemanticAnalyzer::new();
+        analyzer.set_target_version(Some("0.3.0".to_string()));
+        analyzer.analyze(&ast).unwrap();
+        let lines: Vec<usize> = analyzer.take_warnings().iter()
+            .filter(|w| w.message.contains("Method 'replace' was added in version 0.3.4"))
+            .map(|w| w.line)
+            .collect();
+        assert_eq!(lines, vec![4, 6, 10]);
+    }
+
+    #[test]
+    fn test_warn_shadowed_params() {
+        let source = r#"public class Test {
+    public static int scale(int value, int factor) {
+        int factor = 3;
+        return value * factor;
+    }
+
+    public static void resize(int size) {
+        {
+            int size = 1;
+        }
+    }
+
+    public static void main() {
+        int other = scale(2, 5);
+        println(other);
+    }
+}"#;
+        let analyze = |enabled: bool| {
+            let tokens = lexer::lex(source).unwrap();
+            let ast = parser::parse(tokens).unwrap();
+            let mut analyzer = semantic::SemanticAnalyzer::new();
+            analyzer.set_warn_shadowed_params(enabled);
+            analyzer.analyze(&ast).unwrap();
+            analyzer.take_warnings()
+        };
+
+        // 默认不检查
+        assert!(analyze(false).is_empty());
+
+        let warnings = analyze(true);
+        assert_eq!(warnings.len(), 2, "got: {:?}", warnings);
+        assert!(warnings[0].message.contains("Local variable 'factor' shadows a parameter"));
+        assert_eq!(warnings[0].line, 3);
+        assert!(warnings[1].message.contains("Local variable 'size' shadows a parameter"));
+    }
+
+    #[test]
+    fn test_inc_dec_uses_operand_type() {
+        let source = r#"public class Test {
+    public static void main() {
+        long l = 1L;
+        l++;
+        float f = 1.5f;
+        ++f;
+        double d = 2.5;
+        d--;
+        println(l);
+    }
+}"#;
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        assert!(ir.contains("add nsw i64"), "long increment should add an i64, got:\n{}", ir);
+        assert!(ir.contains("fadd float"), "float increment should stay float, got:\n{}", ir);
+        assert!(ir.contains("fsub double"), "double decrement should stay double, got:\n{}", ir);
+        assert!(validate(&ir).is_empty(), "{:?}", validate(&ir));
+    }
+
+    #[test]
+    fn test_overload_mismatch_lists_candidates() {
+        let source = r#"public class Shape {
+    public int area(int side) { return side * side; }
+    public int area(int w, int h) { return w * h; }
+}
+public class Test {
+    public static void main() {
+        Shape s = new Shape();
+        int a = s.area("wide", 2);
+    }
+}"#;
+        let tokens = lexer::lex(source).unwrap();
+        let ast = parser::parse(tokens).unwrap();
+        let err = semantic::SemanticAnalyzer::new().analyze(&ast).unwrap_err().to_string();
+        assert!(err.contains("Method 'area' in class 'Shape' cannot be applied to given types (string, int)"), "got: {}", err);
+        assert!(err.contains("int area(int)\n  int area(int, int)"), "got: {}", err);
+    }
+
+    #[test]
+    fn test_overload_resolution_prefers_most_specific() {
+        let source = r#"public class Test {
+    public static String f(double x) { return "double"; }
+    public static String f(long x) { return "long"; }
+    public static String f(int x) { return "int"; }
+    public static String g(double x) { return "double"; }
+    public static String g(long x) { return "long"; }
+    public static void main() {}
+}"#;
+        let tokens = lexer::lex(source).unwrap();
+        let ast = parser::parse(tokens).unwrap();
+        let mut analyzer = semantic::SemanticAnalyzer::new();
+        analyzer.analyze(&ast).unwrap();
+        let registry = analyzer.get_type_registry();
+        let pick = |name: &str, arg: Type| registry.find_method("Test", name, &[arg]).map(|m| m.params[0].param_type.clone());
+
+        // 精确匹配
+        assert_eq!(pick("f", Type::Int32), Some(Type::Int32));
+        assert_eq!(pick("f", Type::Int64), Some(Type::Int64));
+        // 拓宽时选择最具体的重载，与声明顺序无关
+        assert_eq!(pick("g", Type::Int32), Some(Type::Int64));
+        assert_eq!(pick("g", Type::Float32), Some(Type::Float64));
+        assert_eq!(pick("g", Type::Bool), None);
+
+        // 构造生成的 IR 中 int 实参被扩展为 long
+        let ir = Compiler::new().compile_to_ir(&source.replace("public static void main() {}",
+            "public static void main() { String s = g(3); println(s); }")).unwrap();
+        assert!(ir.contains("sext i32 3 to i64"), "got:\n{}", ir);
+        assert!(ir.contains("@Test.__g_l("), "got:\n{}", ir);
+    }
+
+    #[test]
+    fn test_printf_format_checks() {
+        let specs = semantic::parse_format_specs("%-5d|%*.*f|%%|%lld|%s").unwrap();
+        let conversions: Vec<char> = specs.iter().map(|s| s.conversion).collect();
+        assert_eq!(conversions, vec!['d', 'f', 'd', 's']);
+        assert_eq!(specs[1].star_count, 2);
+        assert_eq!(specs[2].conversion_pos - specs[2].length_pos, 2);
+        assert!(semantic::parse_format_specs("%q").is_err());
+        assert!(semantic::parse_format_specs("100%").is_err());
+
+        let check = |body: &str| {
+            let source = format!("public class Test {{ public static void main() {{ {} }} }}", body);
+            let tokens = lexer::lex(&source).unwrap();
+            let ast = parser::parse(tokens).unwrap();
+            semantic::SemanticAnalyzer::new().analyze(&ast).map_err(|e| e.to_string())
+        };
+        assert!(check(r#"printf("%d and %s\n", 5, "hi");"#).is_ok());
+        assert!(check(r#"String f = "%d"; printf(f, 1);"#).unwrap_err().contains("format must be a String literal"));
+        assert!(check(r#"printf("%d %d", 1);"#).unwrap_err().contains("expects 2 arguments, got 1"));
+        assert!(check(r#"printf("%s", 1);"#).unwrap_err().contains("conversion '%s' does not accept an argument of type int"));
+        assert!(check(r#"printf("%f", 1);"#).unwrap_err().contains("conversion '%f'"));
+    }
+
+    #[test]
+    fn test_bounds_check_flag() {
+        let source = r#"public class Test {
+    public static void main() {
+        int[] arr = new int[3];
+        int[][] grid = new int[2][2];
+        int i = 1;
+        arr[i] = 5;
+        println(arr[i]);
+        println(grid[i][0]);
+    }
+}"#;
+        // 默认不生成检查
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        assert!(!ir.contains("bounds.error"));
+
+        let ir = Compiler::builder().bounds_check(true).build().compile_to_ir(source).unwrap();
+        assert!(ir.contains("icmp ult i64"), "index should be compared unsigned against the length:\n{}", ir);
+        assert!(ir.contains("out of bounds for length"), "got:\n{}", ir);
+        assert!(validate(&ir).is_empty(), "{:?}", validate(&ir));
+    }
+
+    #[test]
+    fn test_this_member_access_with_shadowing_param() {
+        let source = r#"public class Box {
+    private int size;
+    public int size() { return this.size; }
+    public int grow(int size) {
+        this.size = size;
+        return this.size() + size;
+    }
+    public static void main() {
+        Box b = new Box();
+        println(b.grow(3));
+    }
+}"#;
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        assert!(ir.contains("call i32 @Box.size(i8*"), "this.size() should call the current class's method, got:\n{}", ir);
+        assert!(!ir.contains("@this."), "this must not be treated as a class name, got:\n{}", ir);
+        assert!(validate(&ir).is_empty(), "{:?}", validate(&ir));
+    }
+
+    #[test]
+    fn test_static_method_cannot_call_instance_method() {
+        let source = r#"public class Test {
+    public void greet() { println("hi"); }
+    public static void helper() { }
+    public static void main() {
+        helper();
+        greet();
+    }
+}"#;
+        let err = Compiler::new().compile_to_ir(source).unwrap_err().to_string();
+        assert!(err.contains("non-static method void greet() cannot be referenced from a static context"), "got: {}", err);
+
+        let source = r#"public class Test {
+    public void greet() { println("hi"); }
+    public void twice() { greet(); greet(); }
+    public static void main() {
+        Test t = new Test();
+        t.twice();
+    }
+}"#;
+        assert!(Compiler::new().compile_to_ir(source).is_ok(), "instance methods may call each other without an object");
+    }
+
+    #[test]
+    fn test_string_literal_concat_folded() {
+        let source = r#"public class Test {
+    public static void main() {
+        String s = "Hello, " + "World";
+        println(s);
+    }
+}"#;
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        assert!(ir.contains(r#"c"Hello, World\00""#), "literals should fold into one constant, got:\n{}", ir);
+        assert!(!ir.contains("call i8* @__cay_string_concat"), "folded literals must not be concatenated at runtime, got:\n{}", ir);
+        assert!(validate(&ir).is_empty(), "{:?}", validate(&ir));
+    }
+
+    #[test]
+    fn test_long_for_counter_uses_i64() {
+        let source = r#"public class Test {
+    public static void main() {
+        for (long i = 3000000000L; i < 3000000002L; i++) {
+            println(i);
+        }
+    }
+}"#;
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        assert!(ir.contains("phi i64 [3000000000"), "long counter should be an i64, got:\n{}", ir);
+        assert!(ir.contains("icmp slt i64"), "long counter should be compared as i64, got:\n{}", ir);
+        assert!(!ir.contains("trunc i64"), "long counter must not be truncated, got:\n{}", ir);
+        assert!(validate(&ir).is_empty(), "{:?}", validate(&ir));
+    }
+
+    #[test]
+    fn test_pipeline_runs_phases_step_by_step() {
+        let source = r#"public class Test {
+    public static int twice(int x) { return x * 2; }
+    public static void main() {
+        println(twice(21));
+    }
+}"#;
+        let compiler = Compiler::new();
+        let pipeline = compiler.pipeline();
+
+        let tokens = pipeline.lex(source).unwrap();
+        assert!(!tokens.is_empty());
+
+        let mut program = pipeline.parse(tokens).unwrap();
+        assert_eq!(program.classes.len(), 1);
+        let methods: Vec<&str> = program.classes[0].members.iter()
+            .filter_map(|m| match m {
+                ast::ClassMember::Method(method) => Some(method.name.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(methods, vec!["twice", "main"]);
+
+        // 阶段之间可以修改 AST
+        if let ast::ClassMember::Method(method) = &mut program.classes[0].members[0] {
+            method.name = "doubled".to_string();
+        }
+        let renamed = pipeline.analyze(&program).unwrap();
+        assert!(renamed.get_method("Test", "doubled").is_some());
+        assert!(renamed.get_method("Test", "twice").is_none());
+
+        let program = pipeline.parse(pipeline.lex(source).unwrap()).unwrap();
+        let registry = pipeline.analyze(&program).unwrap();
+        assert!(registry.get_method("Test", "twice").is_some());
+
+        let ir = pipeline.generate(&program, &registry).unwrap();
+        assert_eq!(ir, compiler.compile_to_ir(source).unwrap());
+    }
+
+    #[test]
+    fn test_max_errors_stops_analysis() {
+        let source = r#"public class Test {
+    public static void main() {
+        int a = "one";
+        int b = "two";
+        int c = "three";
+        int d = "four";
+        int e = "five";
+    }
+}"#;
+        let err = Compiler::builder().max_errors(3).build().compile_to_ir(source).unwrap_err().to_string();
+        assert_eq!(err.matches("Cannot assign").count(), 3, "got: {}", err);
+        assert!(err.contains("too many errors (3), stopping analysis"), "got: {}", err);
+        assert!(!err.contains("line 6"), "analysis should stop at the limit, got: {}", err);
+
+        // 默认上限足够大，所有错误都会报告
+        let err = Compiler::new().compile_to_ir(source).unwrap_err().to_string();
+        assert_eq!(err.matches("Cannot assign").count(), 5, "got: {}", err);
+        assert!(!err.contains("too many errors"), "got: {}", err);
+    }
+
+    #[test]
+    fn test_library_mode_skips_entry_point() {
+        let source = r#"public class MathLib {
+    public static int[] table = new int[4];
+    public static int square(int x) { return helper(x) * x; }
+    static int helper(int x) { return x; }
+    public static void main() { println(square(3)); }
+}"#;
+        let ir = Compiler::builder().library(true).build().compile_to_ir(source).unwrap();
+        assert!(!ir.contains("@main()"), "library mode must not emit a C entry point, got:\n{}", ir);
+        assert!(ir.contains("define i32 @MathLib.__square_i(i32"), "public methods keep their mangled names, got:\n{}", ir);
+        assert!(ir.contains("define internal i32 @MathLib.__helper_i(i32"), "non-public methods stay internal, got:\n{}", ir);
+        assert!(ir.contains("@llvm.global_ctors"), "static arrays are initialized by a module constructor, got:\n{}", ir);
+        assert!(validate(&ir).is_empty(), "{:?}", validate(&ir));
+
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        assert!(ir.contains("define i32 @main()"));
+        assert!(!ir.contains("define internal"));
+    }
+
+    #[test]
+    fn test_println_string_ternary_uses_string_format() {
+        let source = r#"public class Test {
+    public static void main() {
+        int x = 5;
+        println(x > 0 ? "pos" : "neg");
+    }
+}"#;
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        let select = ir.lines().find(|l| l.contains("= select i1")).expect("string ternary should select between the literals");
+        assert!(select.contains("i8* %"), "the selected value should be a string, got: {}", select);
+        let result = select.trim().split(' ').next().unwrap();
+        assert!(ir.contains(&format!("i8* {})", result)), "the selected string should be passed to printf, got:\n{}", ir);
+        assert!(ir.contains(r#"c"%s\0A\00""#), "string ternary should be printed with %s, got:\n{}", ir);
+        assert!(validate(&ir).is_empty(), "{:?}", validate(&ir));
+    }
+
+    #[test]
+    fn test_switch_on_char_uses_code_values() {
+        let source = r#"public class Test {
+    public static void main() {
+        char c = 'b';
+        switch (c) {
+            case 'a':
+                println(1);
+            case 'b':
+                println(2);
+        }
+    }
+}"#;
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        assert!(ir.contains("zext i8"), "char subject should be zero-extended, got:\n{}", ir);
+        assert!(ir.contains("i64 97, label") && ir.contains("i64 98, label"), "char labels should use their code values, got:\n{}", ir);
+        assert!(validate(&ir).is_empty(), "{:?}", validate(&ir));
+
+        // switch 作为循环体的最后一条语句时，结束块仍需跳转到循环更新块
+        let source = r#"public class Test {
+    public static void main() {
+        String word = "cavvy";
+        int vowels = 0;
+        for (int i = 0; i < word.length(); i++) {
+            switch (word.charAt(i)) {
+                case 'a':
+                    vowels++;
+            }
+        }
+        println(vowels);
+    }
+}"#;
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        assert!(validate(&ir).is_empty(), "{:?}", validate(&ir));
+    }
+
+    #[test]
+    fn test_empty_body_warning() {
+        let source = r#"public class Test {
+    public static void main() {
+        int i = 0;
+        while (i > 10);
+        if (i == 0) {
+            for (int j = 0; j < 3; j++);
+        }
+        if (i == 0) ; else { i = 1; }
+        while (i < 3) { i++; }
+        ;
+        println(i);
+    }
+}"#;
+        let tokens = lexer::lex(source).unwrap();
+        let ast = parser::parse(tokens).unwrap();
+        let mut analyzer = semantic::SemanticAnalyzer::new();
+        analyzer.analyze(&ast).unwrap();
+        let warnings: Vec<String> = analyzer.take_warnings().iter().map(|w| w.to_string()).collect();
+        assert_eq!(warnings.len(), 3, "got: {:?}", warnings);
+        assert!(warnings[0].contains("Empty body after 'while' (misplaced ';'?)") && warnings[0].contains("4:9"), "got: {:?}", warnings);
+        assert!(warnings[1].contains("'for'"), "nested loops should be checked, got: {:?}", warnings);
+        assert!(warnings[2].contains("'if'"), "got: {:?}", warnings);
+
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        assert!(validate(&ir).is_empty(), "{:?}", validate(&ir));
+    }
+
+    #[test]
+    fn test_custom_builtin_hypot() {
+        let hypot = BuiltinSpec::new("hypot", vec![Type::Float64, Type::Float64], Type::Float64, |ir_gen, args| {
+            let result = ir_gen.new_temp();
+            ir_gen.emit_line(&format!("  {} = call double @hypot({}, {})", result, args[0], args[1]));
+            Ok(format!("double {}", result))
+        })
+        .declare("declare double @hypot(double, double)");
+        let compiler = Compiler::builder().builtin(hypot).build();
+
+        let ir = compiler.compile_to_ir(r#"public class Main {
+    public static void main() {
+        int a = 3;
+        double c = hypot(a, 4.0);
+        println(c);
+    }
+}"#).unwrap();
+        assert!(ir.contains("declare double @hypot(double, double)"));
+        assert!(ir.contains("sitofp i32"), "int 实参应转换为 double: {}", ir);
+        assert!(ir.contains("call double @hypot(double %"));
+
+        // 语义分析按注册的签名检查实参
+        let err = compiler.compile_to_ir(r#"public class Main {
+    public static void main() {
+        double c = hypot(3.0);
+    }
+}"#).unwrap_err().to_string();
+        assert!(err.contains("hypot() takes 2 arguments, got 1"), "got: {}", err);
+        let err = compiler.compile_to_ir(r#"public class Main {
+    public static void main() {
+        double c = hypot("3", 4.0);
+    }
+}"#).unwrap_err().to_string();
+        assert!(err.contains("Argument 1 of hypot() must be double, got string"), "got: {}", err);
+
+        // 未调用时不输出声明
+        let ir = compiler.compile_to_ir("public class Main {\n    public static void main() {\n    }\n}").unwrap();
+        assert!(!ir.contains("@hypot"));
+    }
+
+    #[test]
+    fn test_sized_int_types() {
+        let ir = Compiler::new().compile_to_ir(r#"public class Main {
+    static int16 widen(int8 b) {
+        return b;
+    }
+    public static void main() {
+        int8 b = -5;
+        int16 s = widen(b);
+        int32 i = s;
+        int64 l = i;
+        println(l);
+    }
+}"#).unwrap();
+        assert!(ir.contains("define i16 @Main.__widen_i8(i8 %Main.b)"), "{}", ir);
+        assert!(ir.contains("alloca i8, align 1"));
+        assert!(ir.contains("alloca i16, align 2"));
+        assert!(ir.contains("sext i8 %"), "int8 应按有符号扩展: {}", ir);
+
+        // 超出范围的常量和非常量的 int 都需要显式转换
+        for init in ["128", "-129", "i"] {
+            let source = format!("public class Main {{\n    public static void main() {{\n        int i = 1;\n        int8 b = {};\n    }}\n}}", init);
+            let err = Compiler::new().compile_to_ir(&source).unwrap_err().to_string();
+            assert!(err.contains("Cannot assign int to int8"), "{}: {}", init, err);
+        }
+    }
+
+    #[test]
+    fn test_warn_infinite_loops() {
+        let warnings = |body: &str, enabled: bool| {
+            let source = format!("public class Main {{\n    public static void main() {{\n        int i = 0;\n{}\n    }}\n}}", body);
+            let ast = parser::parse(lexer::lex(&source).unwrap()).unwrap();
+            let mut analyzer = semantic::SemanticAnalyzer::new();
+            analyzer.set_warn_infinite_loops(enabled);
+            analyzer.analyze(&ast).unwrap();
+            analyzer.take_warnings().iter().map(|w| w.to_string()).collect::<Vec<_>>()
+        };
+
+        let found = warnings("        while (true) {\n            i++;\n        }", true);
+        assert_eq!(found.len(), 1, "{:?}", found);
+        assert!(found[0].contains("'while' loop condition is always true"), "{:?}", found);
+        assert_eq!(warnings("        for (;;) {\n            i++;\n        }", true).len(), 1);
+        // 内层循环的 break 不会离开外层循环
+        assert_eq!(warnings("        while (1 < 2) {\n            while (i > 3) {\n                break;\n            }\n        }", true).len(), 1);
+        // 带标签的 break 指向循环体内的语句块时只离开该语句块
+        assert_eq!(warnings("        while (true) {\n            inner: {\n                break inner;\n            }\n        }", true).len(), 1);
+        assert!(warnings("        outer: {\n            while (true) {\n                break outer;\n            }\n        }", true).is_empty());
+
+        assert!(warnings("        while (true) {\n            i++;\n            if (i > 10) {\n                break;\n            }\n        }", true).is_empty());
+        assert!(warnings("        while (true) {\n            return;\n        }", true).is_empty());
+        assert!(warnings("        while (i < 10) {\n            i++;\n        }", true).is_empty());
+        // 默认不启用
+        assert!(warnings("        while (true) {\n            i++;\n        }", false).is_empty());
+    }
+
+    #[test]
+    fn test_immutable_final_arrays() {
+        let compile = |body: &str, enabled: bool| {
+            let source = format!("public class Main {{\n    public static void main() {{\n        final int[] arr = {{1, 2, 3}};\n        final int[][] grid = new int[2][2];\n{}\n    }}\n}}", body);
+            Compiler::builder().immutable_final_arrays(enabled).build().compile_to_ir(&source)
+        };
+
+        for body in ["        arr[0] = 9;", "        arr[1]++;", "        grid[1][0] = 4;"] {
+            let err = compile(body, true).unwrap_err().to_string();
+            assert!(err.contains("Cannot modify an element of final array"), "{}: {}", body, err);
+        }
+        // 非 final 的别名不受限制，默认也不启用检查
+        assert!(compile("        int[] alias = arr;\n        alias[0] = 9;", true).is_ok());
+        assert!(compile("        arr[0] = 9;", false).is_ok());
+    }
+
+    #[test]
+    fn test_do_while_false_block() {
+        let source = r#"public class Main {
+    public static void main() {
+        int i = 0;
+        do {
+            i++;
+            break;
+        } while (false);
+    }
+}"#;
+        let ast = parser::parse(lexer::lex(source).unwrap()).unwrap();
+        let mut analyzer = semantic::SemanticAnalyzer::new();
+        analyzer.set_warn_infinite_loops(true);
+        analyzer.analyze(&ast).unwrap();
+        assert!(analyzer.take_warnings().is_empty());
+
+        // 条件块直接跳到结束块，不求值条件也不回跳到循环体
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        let cond_block = ir.split("dowhile.cond.1:").nth(1).unwrap();
+        assert!(cond_block.trim_start().starts_with("br label %dowhile.end.2"), "{}", ir);
+        assert!(!ir.contains("br i1"), "{}", ir);
+    }
+
+    #[test]
+    fn test_equality_distinguishes_strings_from_objects() {
+        let ir = Compiler::new().compile_to_ir(r#"public class Point {
+    public int x;
+}
+public class Main {
+    public static void main() {
+        Point p = new Point();
+        Point q = new Point();
+        String s = "a";
+        boolean same = p == q;
+        boolean equal = s == "a0";
+        boolean missing = s != null;
+    }
+}"#).unwrap();
+        // 只有两侧都是 String 的比较调用内容比较，对象和 null 比较仍是指针比较
+        assert_eq!(ir.matches("call i1 @__cay_string_equals").count(), 1, "{}", ir);
+        let main_body = ir.split("define i32 @main").next().unwrap();
+        assert!(main_body.contains("icmp eq i8* "), "{}", ir);
+        assert!(main_body.contains("icmp ne i8* "), "{}", ir);
+    }
+
+    #[test]
+    fn test_final_field_requires_constructor_assignment() {
+        let compiler = Compiler::new();
+        let unassigned = r#"
+public class Point {
+    public final int x;
+    public Point(int a) {
+    }
+}
+public class Main {
+    public static void main() {
+        Point p = new Point(1);
+    }
+}
+"#;
+        let err = compiler.compile_to_ir(unassigned).unwrap_err().to_string();
+        assert!(err.contains("Final field 'x' is not assigned in constructor of 'Point'"), "{}", err);
+
+        let in_method = r#"
+public class Point {
+    public final int x;
+    public Point(int a) {
+        x = a;
+    }
+    public void reset() {
+        this.x = 0;
+    }
+}
+public class Main {
+    public static void main() {
+        Point p = new Point(1);
+    }
+}
+"#;
+        let err = compiler.compile_to_ir(in_method).unwrap_err().to_string();
+        assert!(err.contains("Cannot assign a value to final field 'x'"), "{}", err);
+
+        let ok = r#"
+public class Point {
+    public final int x;
+    public Point(int a) {
+        if (a > 0) {
+            this.x = a;
+        } else {
+            this.x = 0;
+        }
+    }
+}
+public class Main {
+    public static void main() {
+        Point p = new Point(1);
+        println(p.x);
+    }
+}
+"#;
+        let ir = compiler.compile_to_ir(ok).expect("branches may each assign the final field once");
+        assert!(ir.contains("call void @Point.__ctor_i(i8* "), "new should call the constructor:\n{}", ir);
+    }
+
+    #[test]
+    fn test_string_compare_to() {
+        let compiler = Compiler::new();
+        let source = r#"
+public class Main {
+    public static void main() {
+        int r = "a".compareTo("b");
+        println(r < 0);
+    }
+}
+"#;
+        let ir = compiler.compile_to_ir(source).expect("compareTo should compile");
+        assert!(ir.contains("define i32 @__cay_string_compare(i8* %a, i8* %b)"), "runtime should be emitted:\n{}", ir);
+        assert!(ir.contains("call i32 @__cay_string_compare(i8* "), "compareTo should call the runtime:\n{}", ir);
+
+        let bad = r#"
+public class Main {
+    public static void main() {
+        int r = "a".compareTo(1);
+    }
+}
+"#;
+        assert!(compiler.compile_to_ir(bad).is_err(), "compareTo should only accept a String");
+    }
+
+    #[test]
+    fn test_identical_methods_share_register_names() {
+        let compiler = Compiler::new();
+        let source = r#"
+public class Main {
+    public static int first(int n) {
+        int s = 0;
+        for (int i = 0; i < n; i++) {
+            if (i > 2) {
+                s = s + i;
+            }
+        }
+        return s;
+    }
+    public static int second(int n) {
+        int s = 0;
+        for (int i = 0; i < n; i++) {
+            if (i > 2) {
+                s = s + i;
+            }
+        }
+        return s;
+    }
+    public static void main() {
+        println(first(5) + second(5));
+    }
+}
+"#;
+        let ir = compiler.compile_to_ir(source).expect("should compile");
+        let body = |name: &str| -> String {
+            let start = ir.find(&format!("@Main.__{}_i(", name)).expect("method should be defined");
+            let rest = &ir[start..];
+            let header_end = rest.find('\n').unwrap();
+            rest[header_end..rest.find("\n}").unwrap()].to_string()
+        };
+        let first = body("first");
+        assert!(first.contains("for.cond.0"), "labels should be numbered from 0 in each function:\n{}", first);
+        assert_eq!(first, body("second"), "structurally identical methods should produce identical IR");
+    }
+
+    #[test]
+    fn test_arrow_method_body() {
+        use ast::{ClassMember, Expr, Stmt};
+        let source = r#"public class Test {
+    public static int square(int x) -> x * x;
+    public static void show(int x) -> println(x);
+}"#;
+        let ast = parser::parse(lexer::lex(source).unwrap()).unwrap();
+        let body = |index: usize| match &ast.classes[0].members[index] {
+            ClassMember::Method(method) => method.body.as_ref().unwrap().statements.clone(),
+            other => panic!("expected method, got {:?}", other),
+        };
+        let square = body(0);
+        assert_eq!(square.len(), 1);
+        assert!(matches!(&square[0], Stmt::Return(Some(Expr::Binary(_)))), "should desugar to return: {:?}", square);
+        let show = body(1);
+        assert!(matches!(&show[0], Stmt::Expr(Expr::Call(_))), "void methods should desugar to an expression statement: {:?}", show);
+
+        let compiler = Compiler::new();
+        let mismatch = r#"
+public class Main {
+    public static int name() -> "cay";
+    public static void main() {
+        println(name());
+    }
+}
+"#;
+        let err = compiler.compile_to_ir(mismatch).unwrap_err().to_string();
+        assert!(err.contains("Return type mismatch"), "{}", err);
+    }
+
+    #[test]
+    fn test_floor_mod_builtin() {
+        let compiler = Compiler::new();
+        let source = r#"
+public class Main {
+    public static void main() {
+        int a = floorMod(-7, 3);
+        long b = floorMod(-7L, 3);
+        println(a + b);
+    }
+}
+"#;
+        let ir = compiler.compile_to_ir(source).expect("floorMod should compile");
+        assert!(ir.contains("srem i32") && ir.contains("srem i64"), "int and long operands should keep their width:\n{}", ir);
+
+        let int_result_from_long = r#"
+public class Main {
+    public static void main() {
+        int a = floorMod(-7L, 3);
+    }
+}
+"#;
+        let err = compiler.compile_to_ir(int_result_from_long).unwrap_err().to_string();
+        assert!(err.contains("Cannot assign long to int"), "{}", err);
+
+        let not_integer = r#"
+public class Main {
+    public static void main() {
+        double d = floorMod(-7.5, 3);
+    }
+}
+"#;
+        let err = compiler.compile_to_ir(not_integer).unwrap_err().to_string();
+        assert!(err.contains("floorMod() requires int or long arguments, got double and int"), "{}", err);
+    }
+
+    #[test]
+    fn test_unary_minus_promotes_char() {
+        let compiler = Compiler::new();
+        let source = r#"
+public class Main {
+    public static void main() {
+        char c = 'A';
+        int n = -c;
+        println(n);
+    }
+}
+"#;
+        let ir = compiler.compile_to_ir(source).expect("negating a char should compile");
+        assert!(ir.contains("zext i8"), "char should be zero-extended before negation:\n{}", ir);
+        assert!(ir.contains("sub nsw i32 0,"), "negation should happen in i32:\n{}", ir);
+
+        let narrowing = r#"
+public class Main {
+    public static void main() {
+        char c = 'A';
+        char d = -c;
+    }
+}
+"#;
+        let err = compiler.compile_to_ir(narrowing).unwrap_err().to_string();
+        assert!(err.contains("Cannot assign int to char"), "-c should have type int: {}", err);
+    }
+
+    #[test]
+    fn test_dump_symbols_lists_method_signatures() {
+        let source = r#"public class Calc {
+    private int total;
+
+    public int add(int a, int b) {
+        return a + b;
+    }
+
+    public static long scale(long value, int factor) {
+        return value * factor;
+    }
+}
+
+@main
+public class Main {
+    public static void main() {
+        println(Calc.scale(2L, 3));
+    }
+}"#;
+        let compiler = Compiler::builder().dump_symbols(SymbolFormat::Json).build();
+        let pipeline = compiler.pipeline();
+        let program = pipeline.parse(pipeline.lex(source).unwrap()).unwrap();
+        let registry = pipeline.analyze(&program).unwrap();
+
+        let symbols = semantic::SymbolDump::collect(&program, &registry);
+        let calc = symbols.get("Calc").expect("Calc 应出现在符号表中");
+        let signatures: Vec<&str> = calc.methods.iter().map(|m| m.signature.as_str()).collect();
+        assert_eq!(signatures, vec!["int add(int, int)", "long scale(long, int)"]);
+        assert_eq!(calc.fields[0].modifiers, vec!["private"]);
+        // 内置的 String 类不出现在符号表中
+        assert!(symbols.get("String").is_none());
+
+        let text = symbols.to_string();
+        assert!(text.contains("method public int add(int, int)"));
+        assert!(text.contains("method public static long scale(long, int)"));
+        let json = symbols.to_json();
+        assert!(json.contains("\"signature\": \"int add(int, int)\""));
+        assert!(json.contains("\"signature\": \"long scale(long, int)\""));
+    }
+
+    #[test]
+    fn test_float_to_string_uses_shortest_form() {
+        let compiler = Compiler::new();
+        let source = r#"
+public class Main {
+    public static void main() {
+        double d = 3.14;
+        float f = 3.14f;
+        println((string)d + " " + f);
+    }
+}
+"#;
+        let ir = compiler.compile_to_ir(source).expect("float to string should compile");
+        assert!(ir.contains("call i8* @__cay_float_to_string(double %"), "double 使用双精度版本:\n{}", ir);
+        assert!(ir.contains("call i8* @__cay_float32_to_string(float %"), "float 不应先扩展为 double:\n{}", ir);
+        // 不再使用固定 6 位小数的 %f
+        assert!(ir.contains("c\"%.*f\\00\"") && ir.contains("c\"%.*g\\00\""));
+        assert!(!ir.contains("c\"%f\\00\""));
+    }
+
+    #[test]
+    fn test_inline_small_static_methods() {
+        let source = r#"
+public class Main {
+    public static int square(int x) {
+        return x * x;
+    }
+
+    public static int twice(int x) {
+        return x + x;
+    }
+
+    public static void main() {
+        int a = square(7);
+        int b = twice(a) + twice(1);
+        println(a + b);
+    }
+}
+"#;
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        assert!(ir.contains("call i32 @Main.__square_i("), "默认不内联:\n{}", ir);
+
+        let ir = Compiler::builder().inline_threshold(16).build().compile_to_ir(source).unwrap();
+        assert!(!ir.contains("call i32 @Main.__square_i("), "只有一个调用点的小方法应被内联:\n{}", ir);
+        // 有两个调用点的方法保持调用
+        assert!(ir.contains("call i32 @Main.__twice_i("), "有多个调用点的方法不内联:\n{}", ir);
+        assert!(codegen::IRGenerator::validate_ir(&ir).is_ok());
+
+        let ir = Compiler::builder().inline_threshold(2).build().compile_to_ir(source).unwrap();
+        assert!(ir.contains("call i32 @Main.__square_i("), "超过阈值的方法不内联:\n{}", ir);
+    }
+
+    #[test]
+    fn test_read_builtin_type_mismatch() {
+        let compile = |body: &str| {
+            let source = format!("public class Main {{\n    public static void main() {{\n{}\n    }}\n}}", body);
+            Compiler::new().compile_to_ir(&source)
+        };
+
+        let err = compile("        long n = 0L;\n        n = readLine();").unwrap_err().to_string();
+        assert!(err.contains("Cannot assign string to long: readLine() returns string, use readLong() instead"), "{}", err);
+
+        let err = compile("        int x = readInt(10);").unwrap_err().to_string();
+        assert!(err.contains("readInt() takes no arguments, got 1"), "{}", err);
+
+        // 拓宽转换仍然允许
+        assert!(compile("        double d = readInt();\n        long l = readInt();").is_ok());
+
+        // 提示中建议的输入函数都有代码生成
+        let ir = compile("        long l = readLong();\n        double d = readDouble();\n        char c = readChar();\n        boolean b = readBool();").unwrap();
+        assert!(!ir.contains("@Main.read"), "{}", ir);
+    }
+
+    #[test]
+    fn test_compile_with_sourcemap_hello() {
+        let source = r#"public class hello {
+    public static void main() {
+        int n = 1;
+        print("Hello, World");
+        if (n > 0) {
+            println(n);
+        }
+    }
+}"#;
+        let (ir, map) = Compiler::new().compile_with_sourcemap(source).unwrap();
+        assert!(!ir.contains("; @cay"), "位置标记应从 IR 中删除:\n{}", ir);
+        let plain = Compiler::new().compile_to_ir(source).unwrap();
+        assert_eq!(ir.lines().count(), plain.lines().count(), "映射不应改变生成的 IR");
+
+        let lines: Vec<&str> = ir.lines().collect();
+        let printf_line = lines.iter()
+            .position(|l| l.contains("call i32 (i8*, ...) @printf"))
+            .map(|i| i + 1)
+            .expect("print 应生成 printf 调用");
+        assert_eq!(map.lookup(printf_line).map(|loc| loc.line), Some(4), "{:?}", map.entries);
+
+        // if 内的 println 映射到第 6 行，条件判断映射到 if 所在的第 5 行
+        assert!(map.entries_for_line(6).any(|e| lines[e.ir_start - 1..e.ir_end].iter().any(|l| l.contains("@printf"))));
+        assert!(map.entries_for_line(5).any(|e| lines[e.ir_start - 1..e.ir_end].iter().any(|l| l.contains("icmp"))));
+        assert!(map.entries.iter().all(|e| e.function == "hello.main"));
+    }
+
+    #[test]
+    fn test_auto_object_from_method_call() {
+        let source = r#"
+public class Counter {
+    public int value;
+
+    public int get() {
+        return value;
+    }
+}
+
+public class Main {
+    public static Counter make() {
+        return new Counter();
+    }
+
+    public static void main() {
+        auto c = make();
+        println(c.get());
+    }
+}
+"#;
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        assert!(ir.contains("alloca i8*"), "auto 变量应按方法返回的对象类型分配:\n{}", ir);
+        assert!(ir.contains("call i32 @Counter.get(i8* "), "方法调用应解析到对象所属的类:\n{}", ir);
+        assert!(codegen::IRGenerator::validate_ir(&ir).is_ok());
+    }
+
+    #[test]
+    fn test_switch_default_first_keeps_source_order() {
+        let source = r#"
+public class Main {
+    public static void main() {
+        int n = 1;
+        switch (n) {
+            default:
+                println("default");
+            case 1:
+                println("one");
+        }
+    }
+}
+"#;
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        let default_block = ir.find("switch.default.1:").expect("default 块");
+        let case_block = ir.find("switch.case.1.2:").expect("case 块");
+        assert!(default_block < case_block, "default 块应按源代码顺序排在 case 之前:\n{}", ir);
+        // default 隐式 break，不会进入后面的 case
+        let default_body = &ir[default_block..case_block];
+        assert!(default_body.contains("br label %switch.end.0"), "default 末尾应跳转到结束:\n{}", ir);
+        assert!(codegen::IRGenerator::validate_ir(&ir).is_ok());
+    }
+
+    #[test]
+    fn test_var_infers_type_from_initializer() {
+        let source = r#"
+public class Main {
+    public static void main() {
+        var count = 5;
+        var ratio = 2.5;
+        var name = "abc";
+        var numbers = {1, 2, 3};
+        println(count);
+        println(ratio);
+        println(name);
+        println(numbers.length);
+    }
+}
+"#;
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        assert!(ir.contains("%count_s1 = alloca i32,"), "var count 应推断为 int:\n{}", ir);
+        assert!(ir.contains("%ratio_s1 = alloca double,"), "var ratio 应推断为 double:\n{}", ir);
+        assert!(ir.contains("%name_s1 = alloca i8*,"), "var name 应推断为 String:\n{}", ir);
+        assert!(ir.contains("%numbers_s1 = alloca i32*,"), "var numbers 应推断为 int[]:\n{}", ir);
+        assert!(codegen::IRGenerator::validate_ir(&ir).is_ok());
+
+        let err = Compiler::new().compile_to_ir(&source.replace("var count = 5;", "var count;")).unwrap_err();
+        assert!(err.to_string().contains("declared without a type requires an initializer"), "{}", err);
+    }
+
+    #[test]
+    fn test_warn_float_precision() {
+        let warnings = |body: &str, enabled: bool| {
+            let source = format!("public class Main {{\n    static float scale = 0.123456789;\n    public static void main() {{\n{}\n    }}\n}}", body);
+            let ast = parser::parse(lexer::lex(&source).unwrap()).unwrap();
+            let mut analyzer = semantic::SemanticAnalyzer::new();
+            analyzer.set_warn_float_precision(enabled);
+            analyzer.analyze(&ast).unwrap();
+            analyzer.take_warnings().iter().map(|w| w.to_string()).collect::<Vec<_>>()
+        };
+
+        let found = warnings("        float f = 3.141592653589793;", true);
+        assert_eq!(found.len(), 2, "{:?}", found);
+        assert!(found.iter().any(|w| w.contains("Double literal 0.123456789 loses precision")), "字段初始化值也应检查: {:?}", found);
+        assert!(found.iter().any(|w| w.contains("Double literal 3.141592653589793 loses precision when assigned to float (becomes 3.1415927)")), "{:?}", found);
+        assert_eq!(warnings("        float f = 1.0f;\n        f = -2.718281828459045;", true).len(), 2);
+
+        // 能用 float 精确表示的字面量、带 f 后缀的字面量和赋给 double 的值不警告
+        assert_eq!(warnings("        float a = 3.14;\n        float b = 3.141592653589793f;\n        double c = 3.141592653589793;\n        double d = 0.1 + 0.2;", true).len(), 1);
+        // 默认不启用
+        assert!(warnings("        float f = 3.141592653589793;", false).is_empty());
+    }
+
+    #[test]
+    fn test_string_is_empty_and_blank() {
+        let source = r#"
+public class Main {
+    public static void main() {
+        String s = "  ";
+        boolean empty = s.isEmpty();
+        boolean blank = s.isBlank();
+        println(empty);
+        println(blank);
+    }
+}
+"#;
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        assert!(ir.contains("call i32 @__cay_string_length("), "isEmpty 应复用字符串长度函数:\n{}", ir);
+        assert!(ir.contains("define i1 @__cay_string_is_blank(i8* %str)"), "isBlank 需要输出运行时函数:\n{}", ir);
+        assert!(codegen::IRGenerator::validate_ir(&ir).is_ok());
+
+        let err = Compiler::new().compile_to_ir(&source.replace("s.isBlank()", "s.isBlank(1)")).unwrap_err();
+        assert!(err.to_string().contains("isBlank"), "{}", err);
+    }
+
+    #[test]
+    fn test_main_marker_requires_main_method() {
+        let source = r#"
+@main
+public class Tool {
+    static void main() {
+        println("not public");
+    }
+}
+
+public class App {
+    public static void main() {
+        println("app");
+    }
+}
+"#;
+        let err = Compiler::new().compile_to_ir(source).unwrap_err();
+        assert!(err.to_string().contains("Class 'Tool' is marked @main but has no 'public static main' method"), "{}", err);
+
+        let fixed = source.replace("    static void main()", "    public static void main()");
+        assert!(Compiler::new().compile_to_ir(&fixed).is_ok());
+    }
+
+    #[test]
+    fn test_list_add_get_size() {
+        let source = r#"
+public class Main {
+    public static void main() {
+        List values = new List();
+        values.add(1.5);
+        values.add(2);
+        double first = values.get(0);
+        println(first + values.size());
+    }
+}
+"#;
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        assert!(ir.contains("call i8* @__cay_list_new()"), "{}", ir);
+        assert!(ir.contains("sitofp i32 2 to double"), "int 应转换为 List 的元素类型 double:\n{}", ir);
+        assert!(ir.contains("bitcast i64 %"), "读取的元素应按 double 转换回来:\n{}", ir);
+        assert!(ir.contains("define i32 @__cay_list_size(i8* %list)"), "{}", ir);
+        assert!(codegen::IRGenerator::validate_ir(&ir).is_ok());
+
+        let err = Compiler::new().compile_to_ir(&source.replace("values.add(2);", "values.add(\"2\");")).unwrap_err();
+        assert!(err.to_string().contains("Cannot add string to a List of double"), "{}", err);
+    }
+
+    #[test]
+    fn test_list_param_and_return_elements() {
+        let source = r#"
+public class Main {
+    public static void main() {
+        List values = make();
+        long first = values.get(0);
+        println(first + sum(values));
+    }
+
+    static long sum(List l) {
+        long total = 0;
+        for (int i = 0; i < l.size(); i++) {
+            total = total + l.get(i);
+        }
+        return total;
+    }
+
+    static List make() {
+        List l = new List();
+        l.add(3000000000L);
+        return l;
+    }
+}
+"#;
+        // 元素类型来自语义分析：返回值和参数沿用 make() 中 add 的 long
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        assert!(!ir.contains("trunc i64"), "long 元素读取时不应截断:\n{}", ir);
+        assert!(codegen::IRGenerator::validate_ir(&ir).is_ok());
+    }
+
+    #[test]
+    fn test_for_counter_kept_in_register() {
+        let source = r#"public class Test {
+    public static void main() {
+        int sum = 0;
+        for (int i = 0; i < 10; i++) {
+            sum = sum + i;
+        }
+        println(sum);
+    }
+}"#;
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        let start = ir.find("define void @Test.main()").unwrap();
+        let end = start + ir[start..].find("\n}").unwrap();
+        let main = &ir[start..end];
+        assert!(main.contains(" = phi i32 [0, %for.preheader"), "计数器应由 phi 节点维护");
+        assert!(!main.contains("%i_s2 = alloca"), "计数器不应分配栈空间");
+        assert!(!main.contains("i32* %i_"), "循环体中不应从内存加载计数器");
+    }
+
+    #[test]
+    fn test_inline_method_has_alwaysinline() {
+        let source = r#"public class Test {
+    @inline
+    public static int square(int x) {
+        return x * x;
+    }
+
+    public static int cube(int x) {
+        return x * square(x);
+    }
+
+    public static void main() {
+        println(cube(3));
+    }
+}"#;
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        let square = ir.lines().find(|l| l.starts_with("define i32 @Test.__square_i")).unwrap();
+        assert!(square.ends_with("alwaysinline {"), "@inline 方法应带 alwaysinline 属性: {}", square);
+        let cube = ir.lines().find(|l| l.starts_with("define i32 @Test.__cube_i")).unwrap();
+        assert!(!cube.contains("alwaysinline"), "普通方法不应带 alwaysinline 属性: {}", cube);
+    }
+
+    #[test]
+    fn test_var_decl_uses_declared_width() {
+        let source = r#"public class Test {
+    public static void main() {
+        int x;
+        x = 2147483647;
+        long y = 5L;
+        println(x);
+        println(y);
+    }
+}"#;
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        assert!(ir.contains("%x_s1 = alloca i32"), "int 变量应分配 i32");
+        assert!(ir.contains("store i32 2147483647, i32* %x_s1"), "int 变量应以 i32 存储");
+        assert!(ir.contains("%y_s1 = alloca i64"), "long 变量应分配 i64");
+    }
+
+    #[test]
+    fn test_array_literal_length_folded() {
+        let source = r#"public class Test {
+    public static void main() {
+        int[] a = {1, 2, 3};
+        int n = a.length;
+        int[] b = {4, 5};
+        b = new int[7];
+        int m = b.length;
+        println(n + m);
+    }
+}"#;
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        let start = ir.find("define void @Test.main()").unwrap();
+        let end = start + ir[start..].find("\n}").unwrap();
+        let main = &ir[start..end];
+        assert!(main.contains("store i32 3, i32* %n_s1"), "字面量数组的长度应折叠为常量");
+        assert_eq!(main.matches("i64 -8").count(), 1, "只有被重新赋值的数组需要读取长度头");
+    }
+
+    #[test]
+    fn test_const_substituted_as_literal() {
+        let source = r#"const int N = 4;
+
+public class Test {
+    const int M = N * 4;
+
+    public static void main() {
+        int x = M;
+        int[] a = new int[N];
+        println(x + a.length);
+    }
+}"#;
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        let start = ir.find("define void @Test.main()").unwrap();
+        let end = start + ir[start..].find("\n}").unwrap();
+        let main = &ir[start..end];
+        assert!(main.contains("store i32 16, i32* %x_s1"), "const 常量应替换为折叠后的字面量");
+        assert!(!main.contains("@Test.M_s"), "const 常量不应从全局变量加载");
+    }
+
+    #[test]
+    fn test_native_method_declared_before_bodies() {
+        let source = r#"public class Test {
+    public static void main() {
+        println(helper());
+    }
+
+    public static int helper() {
+        return 1;
+    }
+
+    public native int nativeAdd(int a, long b);
+}"#;
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        assert!(ir.contains("declare i32 @Test.__nativeAdd_i_l(i8*, i32, i64)"), "native 方法应声明完整签名");
+        assert!(!ir.contains("declare i32 @Test.__helper"), "有函数体的方法不能再 declare");
+        assert!(ir.find("declare i32 @Test.__nativeAdd").unwrap() < ir.find("define void @Test.main()").unwrap());
+    }
+
+    #[test]
+    fn test_runtime_emitted_on_demand() {
+        let compile = |source: &str, mode: RuntimeMode| {
+            let options = CompilerOptions {
+                emit_runtime: mode,
+                ..CompilerOptions::default()
+            };
+            Compiler::with_options(options).compile_to_ir(source)
+        };
+        let int_only = r#"public class Test {
+    public static void main() {
+        int sum = 0;
+        for (int i = 0; i < 10; i++) {
+            sum = sum + i;
+        }
+        println(sum);
+    }
+}"#;
+        let with_concat = r#"public class Test {
+    public static void main() {
+        String name = "world";
+        println("hello " + name);
+    }
+}"#;
+
+        // 纯整数程序不需要任何运行时函数
+        let ir = compile(int_only, RuntimeMode::OnDemand).unwrap();
+        assert!(!ir.contains("define i8* @__cay_"), "未使用的运行时函数不应输出");
+        assert!(compile(int_only, RuntimeMode::Never).is_ok());
+
+        // 只输出实际调用的运行时函数
+        let ir = compile(with_concat, RuntimeMode::OnDemand).unwrap();
+        assert!(ir.contains("define i8* @__cay_string_concat"));
+        assert!(!ir.contains("define i8* @__cay_string_replace"));
+
+        let ir = compile(int_only, RuntimeMode::Always).unwrap();
+        assert!(ir.contains("define i8* @__cay_string_replace"));
+        assert!(ir.contains("define %__cay_string_view @__cay_string_subview"));
+
+        let err = compile(with_concat, RuntimeMode::Never).unwrap_err();
+        assert!(err.to_string().contains("Runtime function '__cay_string_concat' is required"));
+    }
+
+    #[test]
+    fn test_signed_arithmetic_uses_nsw() {
+        let source = r#"public class Test {
+    public static void main() {
+        int a = 3;
+        long b = 4L;
+        int c = a + 1;
+        long d = b * 2L;
+        int e = -a;
+        double f = 1.5 + 2.0;
+        println(c + d + e + f);
+    }
+}"#;
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        assert!(ir.contains("= add nsw i32 %"), "有符号 int 加法应带 nsw");
+        assert!(ir.contains("= mul nsw i64 %"), "有符号 long 乘法应带 nsw");
+        assert!(ir.contains("= sub nsw i32 0, %"), "取负应带 nsw");
+        assert!(!ir.contains("fadd nsw"), "浮点运算不带 nsw");
+    }
+
+    #[test]
+    fn test_tuple_lowered_to_struct() {
+        let source = r#"public class Test {
+    public static (int, String) pair() {
+        return (1, "one");
+    }
+
+    public static void main() {
+        var (n, s) = pair();
+        println(s);
+    }
+}"#;
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        assert!(ir.contains("define {i32,i8*} @Test.pair()"), "元组返回值应降级为结构体");
+        assert!(ir.contains("insertvalue {i32,i8*} undef, i32 1, 0"), "元组元素应逐个 insertvalue");
+        assert!(ir.contains("extractvalue {i32,i8*}"), "解构应使用 extractvalue");
+    }
+
+    #[test]
+    fn test_implicit_string_concat_mode() {
+        let source = r#"public class Test {
+    public static String describe(int n, double d, bool b) {
+        return "n=" + n + " d=" + d + " b=" + b;
+    }
+
+    public static void main() {
+        println(describe(1, 2.5, true));
+    }
+}"#;
+        // 默认严格模式：string + int 是语义错误
+        let err = Compiler::new().compile_to_ir(source).unwrap_err();
+        assert!(err.to_string().contains("Cannot add string and int"));
+
+        // 隐式拼接模式：基本类型先转换为字符串
+        let options = CompilerOptions {
+            implicit_string_concat: true,
+            ..CompilerOptions::default()
+        };
+        let ir = Compiler::with_options(options).compile_to_ir(source).unwrap();
+        assert!(ir.contains("call i8* @__cay_int_to_string(i64 %"), "int 应转换为字符串");
+        assert!(ir.contains("call i8* @__cay_float_to_string(double %"), "double 应转换为字符串");
+        assert!(ir.contains("call i8* @__cay_bool_to_string(i1 %"), "bool 应转换为字符串");
+        assert!(validate(&ir).is_empty());
+    }
+
+    #[test]
+    fn test_compiler_builder_flags() {
+        let source = r#"public class Test {
+    public static String describe(int n) {
+        return "n=" + n;
+    }
+
+    public static void main() {
+        println(describe(7));
+    }
+}"#;
+        // 默认配置：严格模式下 string + int 是语义错误
+        assert!(Compiler::new().compile_to_ir(source).is_err());
+
+        let compiler = Compiler::builder()
+            .target("linux")
+            .target_triple("aarch64-unknown-linux-gnu")
+            .implicit_string_concat(true)
+            .emit_runtime(RuntimeMode::Always)
+            .define("BUILDER_TEST")
+            .optimize("-O3")
+            .build();
+        assert_eq!(compiler.options().target_os, "linux");
+        assert_eq!(compiler.options().optimization.as_deref(), Some("-O3"));
+
+        let ir = compiler.compile_to_ir(source).unwrap();
+        assert!(ir.contains("target triple = \"aarch64-unknown-linux-gnu\""), "自定义三元组应覆盖默认值");
+        assert!(ir.contains("call i8* @__cay_int_to_string(i64 %"), "隐式拼接应传递给语义分析和代码生成");
+        assert!(ir.contains("define i8* @__cay_string_replace"), "Always 模式应输出全部运行时函数");
+        assert!(ir.contains("BUILDER_TEST"), "宏定义应传递给代码生成器");
+
+        // 只指定 target 时仍使用推导出的三元组
+        let ir = Compiler::builder().target("macos").implicit_string_concat(true).build()
+            .compile_to_ir(source).unwrap();
+        assert!(ir.contains("target triple = \"x86_64-apple-darwin\""));
+    }
+
+    #[test]
+    fn test_int_to_string_format_matches_constant() {
+        let source = r#"public class Test {
+    public static void main() {
+        long big = -9000000000L;
+        println("big=" + (String)big);
+    }
+}"#;
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        // "%lld" 加结尾的 0 共 5 字节，取址时的数组类型必须与常量声明一致
+        assert!(ir.contains("@.str.int_fmt = private unnamed_addr constant [5 x i8]"));
+        assert!(ir.contains("getelementptr ([5 x i8], [5 x i8]* @.str.int_fmt"));
+        assert!(!ir.contains("[4 x i8]* @.str.int_fmt"));
+    }
+
+    #[test]
+    fn test_type_display_in_error_messages() {
+        let int_array = Type::Array(Box::new(Type::Int32));
+        assert_eq!(int_array.to_string(), "int[]");
+        assert_eq!(Type::Array(Box::new(int_array.clone())).to_string(), "int[][]");
+        assert_eq!(Type::Object("Foo".to_string()).to_string(), "Foo");
+        assert_eq!(Type::Array(Box::new(Type::Object("Foo".to_string()))).to_string(), "Foo[]");
+
+        let source = r#"public class Foo {
+    public int value;
+}
+
+public class Test {
+    public static void main() {
+        int[][] grid = new int[2][2];
+        Foo foo = new Foo();
+        int x = grid + foo;
+    }
+}"#;
+        let err = Compiler::new().compile_to_ir(source).unwrap_err();
+        assert!(err.to_string().contains("Cannot add int[][] and Foo"), "got: {}", err);
+    }
+
+    fn validate(ir: &str) -> Vec<String> {
+        codegen::IRGenerator::validate_ir(ir).err().unwrap_or_default()
+    }
+
+    #[test]
+    fn test_validate_ir_accepts_generated_hello() {
+        let source = r#"public class hello {
+    public static void main() {
+        for (int i = 0; i < 3; i++) {
+            print("Hello, World");
+        }
+    }
+}"#;
+        let ir = Compiler::new().compile_to_ir(source).unwrap();
+        assert_eq!(validate(&ir), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_validate_ir_undefined_label() {
+        let errors = validate("define void @f() {\nentry:\n  br label %missing\n}\n");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("'f'") && errors[0].contains("undefined label 'missing'"));
+
+        // switch 的多行分支列表同样检查
+        let errors = validate("define void @g(i64 %x) {\nentry:\n  switch i64 %x, label %done [\n    i64 1, label %nowhere\n  ]\ndone:\n  ret void\n}\n");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("'g'") && errors[0].contains("undefined label 'nowhere'"));
+    }
+
+    #[test]
+    fn test_validate_ir_terminators() {
+        let errors = validate("define void @f() {\nentry:\n  %a = add i32 1, 2\nnext:\n  ret void\n}\n");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("block 'entry' does not end with a terminator"));
+
+        let errors = validate("define void @f() {\nentry:\n  br label %next\n  br label %next\nnext:\n  ret void\n}\n");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("block 'entry' has instructions after its terminator"));
+    }
+
+    #[test]
+    fn test_validate_ir_phi_predecessors() {
+        let ir = "define i32 @f(i1 %c) {\nentry:\n  br i1 %c, label %a, label %b\na:\n  br label %join\nb:\n  ret i32 0\njoin:\n  %v = phi i32 [1, %a], [2, %b]\n  ret i32 %v\n}\n";
+        let errors = validate(ir);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("phi '%v' in block 'join' lists 'b' which is not a predecessor"));
+    }
+
+    #[test]
+    fn test_validate_ir_duplicate_register() {
+        let ir = "define i32 @f(i32 %x) {\nentry:\n  %t0 = add i32 %x, 1\n  %t0 = add i32 %x, 2\n  %x = add i32 1, 1\n  ret i32 %t0\n}\n";
+        let errors = validate(ir);
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("'f'") && errors[0].contains("register '%t0' is defined more than once"));
+        assert!(errors[1].contains("register '%x' is defined more than once"));
+    }
+
     #[test]
     fn test_preprocessor_ifdef() {
         let source = r#"
@@ -152,8 +152,12 @@ pub fn parse_class_member(parser: &mut Parser) -> cayResult<ClassMember> {
         return Ok(ClassMember::Method(parse_method(parser)?));
     }
     
-    // 如果是类型关键字，可能是字段或方法
-    if is_type_token(parser) {
+    // 类名后直接跟 '(' 是构造函数，不是以类名为类型的字段或方法
+    let is_constructor = matches!(parser.current_token(), Token::Identifier(_))
+        && super::utils::check_next(parser, &Token::LParen);
+
+    // 如果是类型关键字（或元组类型），可能是字段或方法
+    if (is_type_token(parser) && !is_constructor) || parser.check(&Token::LParen) {
         // 读取类型
         let member_type = parse_type(parser)?;
         let member_name = parser.consume_identifier("Expected member name")?;
@@ -240,6 +244,7 @@ pub fn parse_method(parser: &mut Parser) -> cayResult<MethodDecl> {
     let loc = parser.current_loc();
     let modifiers = parse_modifiers(parser)?;
     
+    let return_type_loc = parser.current_loc();
     let return_type = if parser.check(&Token::Void) {
         parser.advance();
         Type::Void
@@ -248,6 +253,15 @@ pub fn parse_method(parser: &mut Parser) -> cayResult<MethodDecl> {
     };
     
     let name = parser.consume_identifier("Expected method name")?;
+
+    // void count; 这样的声明是字段而不是方法
+    if return_type == Type::Void && !parser.check(&Token::LParen) {
+        return Err(crate::error::parser_error(
+            return_type_loc.line,
+            return_type_loc.column,
+            "'void' is not a valid variable type",
+        ));
+    }
     
     parser.consume(&Token::LParen, "Expected '(' after method name")?;
     let params = parse_parameters(parser)?;
@@ -259,6 +273,8 @@ pub fn parse_method(parser: &mut Parser) -> cayResult<MethodDecl> {
     let body = if is_native {
         parser.consume(&Token::Semicolon, "Expected ';' after native method declaration")?;
         None
+    } else if parser.check(&Token::Arrow) {
+        Some(parse_arrow_body(parser, &return_type)?)
     } else {
         Some(parse_block(parser)?)
     };
@@ -273,6 +289,24 @@ pub fn parse_method(parser: &mut Parser) -> cayResult<MethodDecl> {
     })
 }
 
+/// 解析单表达式方法体 `-> expression ;`
+///
+/// 脱糖为 `{ return expression; }`，返回值由 return 语句的类型检查对照返回类型；
+/// void 方法脱糖为 `{ expression; }`。
+fn parse_arrow_body(parser: &mut Parser, return_type: &Type) -> cayResult<Block> {
+    let loc = parser.current_loc();
+    parser.consume(&Token::Arrow, "Expected '->'")?;
+    let expr = parse_expression(parser)?;
+    parser.consume(&Token::Semicolon, "Expected ';' after '->' method body")?;
+
+    let stmt = if *return_type == Type::Void {
+        Stmt::Expr(expr)
+    } else {
+        Stmt::Return(Some(expr))
+    };
+    Ok(Block { statements: vec![stmt], label: None, loc })
+}
+
 /// 解析构造函数声明
 /// 格式: [modifiers] ClassName([params]) [throws ...] { body }
 /// 或: [modifiers] ClassName([params]) : this(args) { body }
@@ -408,6 +442,16 @@ pub fn parse_modifiers(parser: &mut Parser) -> cayResult<Vec<Modifier>> {
                 modifiers.push(Modifier::Final);
                 parser.advance();
             }
+            Token::Const => {
+                // const 隐含 static final，字段的存储和访问规则与静态常量字段一致
+                modifiers.push(Modifier::Const);
+                for implied in [Modifier::Static, Modifier::Final] {
+                    if !modifiers.contains(&implied) {
+                        modifiers.push(implied);
+                    }
+                }
+                parser.advance();
+            }
             Token::Abstract => {
                 modifiers.push(Modifier::Abstract);
                 parser.advance();
@@ -424,6 +468,21 @@ pub fn parse_modifiers(parser: &mut Parser) -> cayResult<Vec<Modifier>> {
                 modifiers.push(Modifier::Main);
                 parser.advance();
             }
+            Token::AtSinceVersion => {
+                parser.advance();
+                parser.consume(&Token::LParen, "Expected '(' after @SinceVersion")?;
+                let version = match parser.current_token() {
+                    Token::StringLiteral(Some(version)) => version.clone(),
+                    _ => return Err(parser.error("Expected version string in @SinceVersion, e.g. @SinceVersion(\"0.3.5\")")),
+                };
+                parser.advance();
+                parser.consume(&Token::RParen, "Expected ')' after @SinceVersion version")?;
+                modifiers.push(Modifier::SinceVersion(version));
+            }
+            Token::AtInline => {
+                modifiers.push(Modifier::Inline);
+                parser.advance();
+            }
             _ => break,
         }
     }
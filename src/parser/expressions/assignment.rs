@@ -38,7 +38,7 @@ fn parse_ternary(parser: &mut Parser) -> cayResult<Expr> {
 
     // 检查是否有 ? 标记
     if parser.match_token(&crate::lexer::Token::Question) {
-        let true_branch = Box::new(parse_or(parser)?);
+        let true_branch = Box::new(parse_ternary(parser)?); // 中间分支也可以是三元表达式
         parser.consume(&crate::lexer::Token::Colon, "Expected ':' after '?' in ternary expression")?;
         let false_branch = Box::new(parse_ternary(parser)?); // 右结合
 
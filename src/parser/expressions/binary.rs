@@ -119,6 +119,22 @@ pub fn parse_equality(parser: &mut Parser) -> cayResult<Expr> {
                 right: Box::new(right),
                 loc,
             });
+        } else if parser.match_token(&crate::lexer::Token::TripleEq) {
+            let right = parse_comparison(parser)?;
+            left = Expr::Binary(BinaryExpr {
+                left: Box::new(left),
+                op: BinaryOp::Is,
+                right: Box::new(right),
+                loc,
+            });
+        } else if parser.match_token(&crate::lexer::Token::NotTripleEq) {
+            let right = parse_comparison(parser)?;
+            left = Expr::Binary(BinaryExpr {
+                left: Box::new(left),
+                op: BinaryOp::IsNot,
+                right: Box::new(right),
+                loc,
+            });
         } else {
             break;
         }
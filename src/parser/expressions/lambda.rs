@@ -113,6 +113,7 @@ fn parse_lambda_block(parser: &mut Parser) -> cayResult<Block> {
 
     Ok(Block {
         statements,
+        label: None,
         loc: crate::error::SourceLocation { line: 0, column: 0 },
     })
 }
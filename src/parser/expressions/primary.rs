@@ -61,15 +61,32 @@ pub fn parse_primary(parser: &mut Parser) -> cayResult<Expr> {
             parser.advance();
             Ok(Expr::Literal(LiteralValue::Null))
         }
+        crate::lexer::Token::String if parser.check_next(&crate::lexer::Token::Dot) => {
+            // String.valueOf(...) 等静态调用，String 作为类名使用
+            parser.advance();
+            Ok(Expr::Identifier("String".to_string()))
+        }
         crate::lexer::Token::This => {
             parser.advance();
+
+            // 当前对象的方法引用: this::methodName
+            if parser.match_token(&crate::lexer::Token::DoubleColon) {
+                let method_name = parser.consume_identifier("Expected method name after '::'")?;
+                return Ok(Expr::MethodRef(MethodRefExpr {
+                    class_name: None,
+                    object: Some(Box::new(Expr::Identifier("this".to_string()))),
+                    method_name,
+                    loc,
+                }));
+            }
+
             Ok(Expr::Identifier("this".to_string()))
         }
         crate::lexer::Token::Identifier(name) => {
             let name = name.clone();
             parser.advance();
 
-            // 检查是否是方法引用: ClassName::methodName
+            // 检查是否是方法引用: ClassName::methodName 或 obj::methodName，由语义分析区分
             if parser.match_token(&crate::lexer::Token::DoubleColon) {
                 let method_name = parser.consume_identifier("Expected method name after '::'")?;
                 return Ok(Expr::MethodRef(MethodRefExpr {
@@ -101,6 +118,17 @@ pub fn parse_primary(parser: &mut Parser) -> cayResult<Expr> {
             parser.pos = checkpoint;
             parser.advance(); // 跳过 '('
             let expr = parse_expression(parser)?;
+
+            // 逗号分隔的多个表达式构成元组: (a, b)
+            if parser.check(&crate::lexer::Token::Comma) {
+                let mut elements = vec![expr];
+                while parser.match_token(&crate::lexer::Token::Comma) {
+                    elements.push(parse_expression(parser)?);
+                }
+                parser.consume(&crate::lexer::Token::RParen, "Expected ')' after tuple elements")?;
+                return Ok(Expr::Tuple(TupleExpr { elements, loc }));
+            }
+
             parser.consume(&crate::lexer::Token::RParen, "Expected ')' after expression")?;
             Ok(expr)
         }
@@ -178,6 +206,8 @@ pub fn parse_new_expression(parser: &mut Parser, loc: crate::error::SourceLocati
 /// 解析基本类型（不包含数组维度）
 pub fn parse_base_type(parser: &mut Parser) -> cayResult<Type> {
     match parser.current_token() {
+        crate::lexer::Token::Int8 => { parser.advance(); Ok(Type::Int8) }
+        crate::lexer::Token::Int16 => { parser.advance(); Ok(Type::Int16) }
         crate::lexer::Token::Int => { parser.advance(); Ok(Type::Int32) }
         crate::lexer::Token::Long => { parser.advance(); Ok(Type::Int64) }
         crate::lexer::Token::Float => { parser.advance(); Ok(Type::Float32) }
@@ -1,6 +1,6 @@
 //! 一元表达式解析
 //!
-//! 处理一元运算符（-、!、~）和类型转换表达式。
+//! 处理一元运算符（-、+、!、~）和类型转换表达式。
 
 use crate::ast::*;
 use crate::error::cayResult;
@@ -21,6 +21,15 @@ pub fn parse_unary(parser: &mut Parser) -> cayResult<Expr> {
         }));
     }
 
+    if parser.match_token(&crate::lexer::Token::Plus) {
+        let operand = parse_unary(parser)?;
+        return Ok(Expr::Unary(UnaryExpr {
+            op: UnaryOp::Plus,
+            operand: Box::new(operand),
+            loc,
+        }));
+    }
+
     if parser.match_token(&crate::lexer::Token::Bang) {
         let operand = parse_unary(parser)?;
         return Ok(Expr::Unary(UnaryExpr {
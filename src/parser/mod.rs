@@ -32,6 +32,11 @@ impl Parser {
         let mut classes = Vec::new();
         let mut interfaces = Vec::new();
         let mut top_level_functions = Vec::new();
+        let mut constants = Vec::new();
+        let mut static_asserts = Vec::new();
+
+        // 先检查括号配对，未闭合的括号直接指向开括号的位置
+        utils::check_delimiters(self)?;
 
         while !self.is_at_end() {
             if self.check(&crate::lexer::Token::Interface)
@@ -44,6 +49,12 @@ impl Parser {
                 || self.check(&crate::lexer::Token::AtMain)
             {
                 classes.push(self.parse_class()?);
+            } else if self.check(&crate::lexer::Token::Const) {
+                // 顶层常量: const int MAX = 100;
+                constants.push(self.parse_field()?);
+            } else if matches!(self.current_token(), crate::lexer::Token::Identifier(name) if name == "static_assert") {
+                // 顶层编译期断言: static_assert(MAX > 0, "message");
+                static_asserts.push(self.parse_top_level_static_assert()?);
             } else if self.check(&crate::lexer::Token::Public) {
                 // 检查是否是顶层 main 函数: public int main() 或 public int main(String[] args)
                 if self.check_top_level_main() {
@@ -57,7 +68,7 @@ impl Parser {
             }
         }
 
-        Ok(Program { classes, interfaces, top_level_functions })
+        Ok(Program { classes, interfaces, top_level_functions, constants, static_asserts })
     }
 
     // 类解析方法
@@ -287,6 +298,16 @@ impl Parser {
         }
     }
 
+    /// 解析顶层 static_assert(条件, "消息");
+    fn parse_top_level_static_assert(&mut self) -> cayResult<crate::ast::CallExpr> {
+        let expr = self.parse_expression()?;
+        self.consume(&crate::lexer::Token::Semicolon, "Expected ';' after static_assert")?;
+        match expr {
+            crate::ast::Expr::Call(call) => Ok(call),
+            _ => Err(self.error("Expected static_assert(condition, \"message\")")),
+        }
+    }
+
     /// 解析顶层函数
     fn parse_top_level_function(&mut self) -> cayResult<crate::ast::TopLevelFunction> {
         let loc = self.current_loc();
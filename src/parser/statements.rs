@@ -1,7 +1,7 @@
 //! 语句解析
 
 use crate::ast::*;
-use crate::error::cayResult;
+use crate::error::{cayResult, SourceLocation};
 use super::Parser;
 use super::types::{parse_type, is_primitive_type_token};
 use super::expressions::parse_expression;
@@ -18,7 +18,25 @@ pub fn parse_block(parser: &mut Parser) -> cayResult<Block> {
     
     parser.consume(&crate::lexer::Token::RBrace, "Expected '}' to end block")?;
     
-    Ok(Block { statements, loc })
+    Ok(Block { statements, label: None, loc })
+}
+
+/// 解析带标签的语句块: label: { ... }
+fn parse_labeled_block(parser: &mut Parser) -> cayResult<Stmt> {
+    let label = match parser.current_token() {
+        crate::lexer::Token::Identifier(name) => name.clone(),
+        _ => return Err(parser.error("Expected label name")),
+    };
+    parser.advance();
+    parser.consume(&crate::lexer::Token::Colon, "Expected ':' after label")?;
+
+    if !parser.check(&crate::lexer::Token::LBrace) {
+        return Err(parser.error(&format!("Label '{}' must be followed by a block", label)));
+    }
+
+    let mut block = parse_block(parser)?;
+    block.label = Some(label);
+    Ok(Stmt::Block(block))
 }
 
 /// 解析语句
@@ -29,13 +47,23 @@ pub fn parse_statement(parser: &mut Parser) -> cayResult<Stmt> {
         crate::lexer::Token::While => parse_while_statement(parser),
         crate::lexer::Token::For => parse_for_statement(parser),
         crate::lexer::Token::Do => parse_do_while_statement(parser),
+        crate::lexer::Token::Loop => parse_loop_statement(parser),
+        crate::lexer::Token::Repeat => parse_repeat_statement(parser),
         crate::lexer::Token::Switch => parse_switch_statement(parser),
         crate::lexer::Token::Return => parse_return_statement(parser),
         crate::lexer::Token::Break => {
             let _loc = parser.current_loc();
             parser.advance();
+            let label = match parser.current_token() {
+                crate::lexer::Token::Identifier(name) => {
+                    let name = name.clone();
+                    parser.advance();
+                    Some(name)
+                }
+                _ => None,
+            };
             parser.consume(&crate::lexer::Token::Semicolon, "Expected ';' after break")?;
-            Ok(Stmt::Break)
+            Ok(Stmt::Break(label))
         }
         crate::lexer::Token::Continue => {
             let _loc = parser.current_loc();
@@ -43,6 +71,21 @@ pub fn parse_statement(parser: &mut Parser) -> cayResult<Stmt> {
             parser.consume(&crate::lexer::Token::Semicolon, "Expected ';' after continue")?;
             Ok(Stmt::Continue)
         }
+        crate::lexer::Token::Semicolon => {
+            // 空语句
+            parser.advance();
+            Ok(Stmt::Empty)
+        }
+        crate::lexer::Token::Void => {
+            // void x; —— 语句不能以 void 开头，按变量声明报告更清楚的错误
+            Err(parser.error("'void' is not a valid variable type"))
+        }
+        crate::lexer::Token::Fallthrough => {
+            Err(parser.error("'fallthrough' can only be used as the last statement of a switch case"))
+        }
+        crate::lexer::Token::Identifier(_) if parser.check_next(&crate::lexer::Token::Colon) => {
+            parse_labeled_block(parser)
+        }
         crate::lexer::Token::Var | crate::lexer::Token::Let | crate::lexer::Token::Auto => {
             // 后置类型声明或自动类型推断
             parse_modern_var_decl(parser)
@@ -60,7 +103,7 @@ pub fn parse_statement(parser: &mut Parser) -> cayResult<Stmt> {
                 return parse_var_decl(parser);
             }
 
-            if super::types::is_type_token(parser) {
+            if super::types::is_type_token(parser) || parser.check(&crate::lexer::Token::LParen) {
                 // 尝试解析类型（不消耗最终位置）以判断是否紧跟变量名。
                 let checkpoint = parser.pos;
                 if super::types::parse_type(parser).is_ok() {
@@ -114,6 +157,7 @@ pub fn parse_var_decl(parser: &mut Parser) -> cayResult<Stmt> {
 /// 支持语法：
 /// - var x: int = 10;      // var 声明，类型后置
 /// - let y: String = "a";  // let 声明，类型后置
+/// - var w = 10;           // 省略类型注解时从初始化器推断类型
 /// - auto z = 10;          // 自动类型推断
 /// - final var x: int = 10; // final 修饰
 pub fn parse_modern_var_decl(parser: &mut Parser) -> cayResult<Stmt> {
@@ -126,6 +170,11 @@ pub fn parse_modern_var_decl(parser: &mut Parser) -> cayResult<Stmt> {
     let keyword = parser.current_token().clone();
     parser.advance(); // consume var/let/auto
     
+    // 元组解构：var (x, y) = expr;
+    if parser.check(&crate::lexer::Token::LParen) {
+        return parse_tuple_destructure(parser, is_final, loc);
+    }
+    
     let name = parser.consume_identifier("Expected variable name after var/let/auto")?;
     
     // 解析可选的类型注解（: Type）
@@ -133,13 +182,9 @@ pub fn parse_modern_var_decl(parser: &mut Parser) -> cayResult<Stmt> {
         // 有类型注解：var x: int
         parse_type(parser)?
     } else {
-        // 无类型注解，必须是 auto 或必须有初始化器
+        // 无类型注解：类型由语义分析从初始化器推断（var x = 5; / auto z = 10;）
         match keyword {
-            crate::lexer::Token::Auto => crate::types::Type::Auto,
-            crate::lexer::Token::Var | crate::lexer::Token::Let => {
-                // var/let 必须有类型注解（暂时如此，之后可以实现类型推断）
-                return Err(parser.error("var/let declaration requires type annotation (: Type) or use 'auto' for type inference"));
-            }
+            crate::lexer::Token::Auto | crate::lexer::Token::Var | crate::lexer::Token::Let => crate::types::Type::Auto,
             _ => unreachable!()
         }
     };
@@ -167,6 +212,31 @@ pub fn parse_modern_var_decl(parser: &mut Parser) -> cayResult<Stmt> {
     }))
 }
 
+/// 解析元组解构声明的剩余部分: (x, y) = expr;
+fn parse_tuple_destructure(parser: &mut Parser, is_final: bool, loc: SourceLocation) -> cayResult<Stmt> {
+    parser.consume(&crate::lexer::Token::LParen, "Expected '(' to start tuple destructuring")?;
+    let mut names = vec![parser.consume_identifier("Expected variable name in tuple destructuring")?];
+    while parser.match_token(&crate::lexer::Token::Comma) {
+        names.push(parser.consume_identifier("Expected variable name in tuple destructuring")?);
+    }
+    parser.consume(&crate::lexer::Token::RParen, "Expected ')' after tuple destructuring names")?;
+
+    if names.len() < 2 {
+        return Err(parser.error("Tuple destructuring requires at least two names"));
+    }
+
+    parser.consume(&crate::lexer::Token::Assign, "Tuple destructuring requires an initializer")?;
+    let initializer = parse_expression(parser)?;
+    parser.consume(&crate::lexer::Token::Semicolon, "Expected ';' after tuple destructuring")?;
+
+    Ok(Stmt::TupleDestructure(TupleDestructure {
+        names,
+        initializer,
+        is_final,
+        loc,
+    }))
+}
+
 /// 解析数组初始化表达式: {1, 2, 3}
 fn parse_array_initializer(parser: &mut Parser) -> cayResult<Expr> {
     let loc = parser.current_loc();
@@ -244,7 +314,7 @@ pub fn parse_for_statement(parser: &mut Parser) -> cayResult<Stmt> {
     
     parser.consume(&crate::lexer::Token::LParen, "Expected '(' after 'for'")?;
     
-    let init = if parser.check(&crate::lexer::Token::Semicolon) {
+    let init = if parser.match_token(&crate::lexer::Token::Semicolon) {
         None
     } else {
         Some(Box::new(parse_statement(parser)?))
@@ -267,6 +337,11 @@ pub fn parse_for_statement(parser: &mut Parser) -> cayResult<Stmt> {
     
     let body = Box::new(parse_statement(parser)?);
     
+    // for (;;) 等价于 while (true)，复用 while 循环的代码生成
+    if init.is_none() && condition.is_none() && update.is_none() {
+        return Ok(infinite_loop(body, loc));
+    }
+    
     Ok(Stmt::For(ForStmt {
         init,
         condition,
@@ -276,6 +351,43 @@ pub fn parse_for_statement(parser: &mut Parser) -> cayResult<Stmt> {
     }))
 }
 
+/// 解析 loop 语句：`loop { ... }` 是显式的无限循环
+pub fn parse_loop_statement(parser: &mut Parser) -> cayResult<Stmt> {
+    let loc = parser.current_loc();
+    parser.advance(); // consume 'loop'
+    
+    let body = Box::new(Stmt::Block(parse_block(parser)?));
+    
+    Ok(infinite_loop(body, loc))
+}
+
+/// 解析 repeat 语句: repeat (n) { ... }
+pub fn parse_repeat_statement(parser: &mut Parser) -> cayResult<Stmt> {
+    let loc = parser.current_loc();
+    parser.advance(); // consume 'repeat'
+
+    parser.consume(&crate::lexer::Token::LParen, "Expected '(' after 'repeat'")?;
+    let count = parse_expression(parser)?;
+    parser.consume(&crate::lexer::Token::RParen, "Expected ')' after repeat count")?;
+
+    let body = Box::new(parse_statement(parser)?);
+
+    Ok(Stmt::Repeat(RepeatStmt {
+        count,
+        body,
+        loc,
+    }))
+}
+
+/// 构造条件恒为 true 的 while 循环
+fn infinite_loop(body: Box<Stmt>, loc: SourceLocation) -> Stmt {
+    Stmt::While(WhileStmt {
+        condition: Expr::Literal(LiteralValue::Bool(true)),
+        body,
+        loc,
+    })
+}
+
 /// 解析 do-while 语句
 pub fn parse_do_while_statement(parser: &mut Parser) -> cayResult<Stmt> {
     let loc = parser.current_loc();
@@ -309,39 +421,45 @@ pub fn parse_switch_statement(parser: &mut Parser) -> cayResult<Stmt> {
     
     let mut cases = Vec::new();
     let mut default = None;
+    let mut default_index = 0;
     
     while !parser.check(&crate::lexer::Token::RBrace) && !parser.is_at_end() {
         if parser.match_token(&crate::lexer::Token::Case) {
-            // 解析 case 值
-            let value = match *parser.current_token() {
-                crate::lexer::Token::IntegerLiteral(Some((v, _))) => {
-                    let val = v;  // v 是 i64
-                    parser.advance();
-                    val
-                }
-                _ => return Err(parser.error("Expected integer literal in case")),
-            };
+            // 解析 case 值：整数/字符字面量或 const 常量，代码生成时折叠为整数
+            let value = parse_expression(parser)?;
             parser.consume(&crate::lexer::Token::Colon, "Expected ':' after case value")?;
             
             // 解析 case 体（直到遇到另一个 case、default 或 }）
+            // case 默认在末尾隐式 break，只有显式写出 fallthrough; 才会进入下一个 case
             let mut body = Vec::new();
-            while !parser.check(&crate::lexer::Token::Case) && !parser.check(&crate::lexer::Token::Default)
-                && !parser.check(&crate::lexer::Token::RBrace) && !parser.is_at_end() {
+            let mut fallthrough = false;
+            while !is_case_end(parser) {
+                if parser.match_token(&crate::lexer::Token::Fallthrough) {
+                    parser.consume(&crate::lexer::Token::Semicolon, "Expected ';' after fallthrough")?;
+                    if !is_case_end(parser) {
+                        return Err(parser.error("'fallthrough' must be the last statement of a switch case"));
+                    }
+                    fallthrough = true;
+                    break;
+                }
                 body.push(parse_statement(parser)?);
             }
             
-            cases.push(Case { value, body });
+            cases.push(Case { value, body, fallthrough });
         } else if parser.match_token(&crate::lexer::Token::Default) {
             parser.consume(&crate::lexer::Token::Colon, "Expected ':' after 'default'")?;
             
             // 解析 default 体
             let mut body = Vec::new();
-            while !parser.check(&crate::lexer::Token::Case) && !parser.check(&crate::lexer::Token::Default)
-                && !parser.check(&crate::lexer::Token::RBrace) && !parser.is_at_end() {
+            while !is_case_end(parser) {
+                if parser.check(&crate::lexer::Token::Fallthrough) {
+                    return Err(parser.error("'fallthrough' cannot be used in the default case"));
+                }
                 body.push(parse_statement(parser)?);
             }
             
             default = Some(body);
+            default_index = cases.len();
         } else {
             return Err(parser.error("Expected 'case' or 'default' in switch"));
         }
@@ -353,10 +471,19 @@ pub fn parse_switch_statement(parser: &mut Parser) -> cayResult<Stmt> {
         expr,
         cases,
         default,
+        default_index,
         loc,
     }))
 }
 
+/// 当前 case 体是否结束（遇到另一个 case、default 或 }）
+fn is_case_end(parser: &Parser) -> bool {
+    parser.check(&crate::lexer::Token::Case)
+        || parser.check(&crate::lexer::Token::Default)
+        || parser.check(&crate::lexer::Token::RBrace)
+        || parser.is_at_end()
+}
+
 /// 解析 return 语句
 pub fn parse_return_statement(parser: &mut Parser) -> cayResult<Stmt> {
     let _loc = parser.current_loc();
@@ -7,6 +7,8 @@ use super::Parser;
 /// 解析类型（支持多维数组）
 pub fn parse_type(parser: &mut Parser) -> cayResult<Type> {
     let base_type = match parser.current_token() {
+        crate::lexer::Token::Int8 => { parser.advance(); Type::Int8 }
+        crate::lexer::Token::Int16 => { parser.advance(); Type::Int16 }
         crate::lexer::Token::Int => { parser.advance(); Type::Int32 }
         crate::lexer::Token::Long => { parser.advance(); Type::Int64 }
         crate::lexer::Token::Float => { parser.advance(); Type::Float32 }
@@ -14,11 +16,15 @@ pub fn parse_type(parser: &mut Parser) -> cayResult<Type> {
         crate::lexer::Token::Bool => { parser.advance(); Type::Bool }
         crate::lexer::Token::String => { parser.advance(); Type::String }
         crate::lexer::Token::Char => { parser.advance(); Type::Char }
+        crate::lexer::Token::Identifier(name) if name == "StringView" => { parser.advance(); Type::StringView }
         crate::lexer::Token::Identifier(name) => {
             let name = name.clone();
             parser.advance();
             Type::Object(name)
         }
+        crate::lexer::Token::LParen => parse_tuple_type(parser)?,
+        // void 只能作为方法返回类型，由方法声明单独处理
+        crate::lexer::Token::Void => return Err(parser.error("'void' is not a valid variable type")),
         _ => return Err(parser.error("Expected type")),
     };
     
@@ -32,9 +38,25 @@ pub fn parse_type(parser: &mut Parser) -> cayResult<Type> {
     Ok(result_type)
 }
 
+/// 解析元组类型 (int, string)，至少包含两个元素
+fn parse_tuple_type(parser: &mut Parser) -> cayResult<Type> {
+    parser.consume(&crate::lexer::Token::LParen, "Expected '(' to start tuple type")?;
+    let mut elements = vec![parse_type(parser)?];
+    while parser.match_token(&crate::lexer::Token::Comma) {
+        elements.push(parse_type(parser)?);
+    }
+    parser.consume(&crate::lexer::Token::RParen, "Expected ')' after tuple element types")?;
+
+    if elements.len() < 2 {
+        return Err(parser.error("Tuple type must have at least two elements"));
+    }
+    Ok(Type::Tuple(elements))
+}
+
 /// 检查当前token是否是类型token
 pub fn is_type_token(parser: &Parser) -> bool {
     matches!(parser.current_token(),
+        crate::lexer::Token::Int8 | crate::lexer::Token::Int16 |
         crate::lexer::Token::Int | crate::lexer::Token::Long | crate::lexer::Token::Float |
         crate::lexer::Token::Double | crate::lexer::Token::Bool | crate::lexer::Token::String |
         crate::lexer::Token::Char | crate::lexer::Token::Identifier(_)
@@ -44,6 +66,7 @@ pub fn is_type_token(parser: &Parser) -> bool {
 /// 检查当前token是否是原始类型token
 pub fn is_primitive_type_token(parser: &Parser) -> bool {
     matches!(parser.current_token(),
+        crate::lexer::Token::Int8 | crate::lexer::Token::Int16 |
         crate::lexer::Token::Int | crate::lexer::Token::Long | crate::lexer::Token::Float |
         crate::lexer::Token::Double | crate::lexer::Token::Bool | crate::lexer::Token::String |
         crate::lexer::Token::Char
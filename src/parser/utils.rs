@@ -94,4 +94,65 @@ pub fn check_next(parser: &Parser, token: &Token) -> bool {
     } else {
         &parser.tokens[parser.pos + 1].token == token
     }
-}
\ No newline at end of file
+}
+
+/// 检查括号 `{}`、`()`、`[]` 是否配对
+///
+/// 用栈记录尚未闭合的开括号及其位置。遇到不匹配的闭括号，或到文件末尾仍有
+/// 未闭合的开括号时报错，并指出开括号所在的位置，避免只在文件末尾报一个含糊的错误。
+/// 缺少 `}` 时，内层的 `{` 会被外层的 `}` 错误地闭合；如果某个 `}` 比开括号
+/// 所在行缩进得更浅，就把那个开括号当作真正未闭合的括号报告。
+pub fn check_delimiters(parser: &Parser) -> cayResult<()> {
+    // 每行第一个令牌的列号，用来判断缩进
+    let mut line_indent = std::collections::HashMap::new();
+    for item in &parser.tokens {
+        line_indent.entry(item.loc.line).or_insert(item.loc.column);
+    }
+
+    let mut open: Vec<(char, &SourceLocation)> = Vec::new();
+    let mut misindented: Option<&SourceLocation> = None;
+
+    for item in &parser.tokens {
+        let (opener, closer) = match item.token {
+            Token::LBrace => { open.push(('{', &item.loc)); continue; }
+            Token::LParen => { open.push(('(', &item.loc)); continue; }
+            Token::LBracket => { open.push(('[', &item.loc)); continue; }
+            Token::RBrace => ('{', '}'),
+            Token::RParen => ('(', ')'),
+            Token::RBracket => ('[', ']'),
+            _ => continue,
+        };
+
+        match open.pop() {
+            Some((ch, loc)) if ch == opener => {
+                if misindented.is_none() && ch == '{' && item.loc.column < line_indent[&loc.line] {
+                    misindented = Some(loc);
+                }
+            }
+            Some((ch, loc)) => {
+                return Err(parser_error(item.loc.line, item.loc.column, format!(
+                    "Mismatched '{}': unmatched '{}' opened at line {}, column {}",
+                    closer, ch, loc.line, loc.column
+                )));
+            }
+            None => {
+                return Err(parser_error(item.loc.line, item.loc.column, format!(
+                    "Unexpected '{}' without a matching '{}'", closer, opener
+                )));
+            }
+        }
+    }
+
+    match open.pop() {
+        Some((ch, loc)) => {
+            let (ch, loc) = match misindented {
+                Some(culprit) if ch == '{' => ('{', culprit),
+                _ => (ch, loc),
+            };
+            Err(parser_error(loc.line, loc.column, format!(
+                "Unmatched '{}' opened at line {}, column {}", ch, loc.line, loc.column
+            )))
+        }
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,132 @@
+//! 分阶段驱动编译流程
+//!
+//! [`Compiler::compile_to_ir`] 一次性完成全部阶段；嵌入到其他工具时，
+//! 可以通过 [`Pipeline`] 逐个执行词法分析、语法分析、语义分析和代码生成，
+//! 在阶段之间检查或修改中间产物（token 序列、AST、类型注册表）。
+//!
+//! ```
+//! use cavvy::prelude::*;
+//!
+//! let compiler = Compiler::new();
+//! let pipeline = compiler.pipeline();
+//!
+//! let tokens = pipeline.lex(r#"
+//! public class Main {
+//!     public static void main() {
+//!         println(42);
+//!     }
+//! }
+//! "#)?;
+//! let program = pipeline.parse(tokens)?;
+//! assert_eq!(program.classes[0].name, "Main");
+//!
+//! let registry = pipeline.analyze(&program)?;
+//! assert!(registry.class_exists("Main"));
+//!
+//! let ir = pipeline.generate(&program, &registry)?;
+//! assert!(ir.contains("define"));
+//! # Ok::<(), cayError>(())
+//! ```
+
+use crate::ast::Program;
+use crate::codegen;
+use crate::codegen::source_map::SourceMap;
+use crate::error::cayResult;
+use crate::lexer::{self, TokenWithLocation};
+use crate::parser;
+use crate::semantic;
+use crate::types::TypeRegistry;
+use crate::Compiler;
+
+/// 编译流程的各个阶段，使用所属 [`Compiler`] 的配置
+pub struct Pipeline<'a> {
+    compiler: &'a Compiler,
+}
+
+impl<'a> Pipeline<'a> {
+    pub fn new(compiler: &'a Compiler) -> Self {
+        Self { compiler }
+    }
+
+    /// 词法分析：源代码（已预处理）转换为 token 序列
+    pub fn lex(&self, source: &str) -> cayResult<Vec<TokenWithLocation>> {
+        lexer::lex(source)
+    }
+
+    /// 语法分析：token 序列转换为 AST
+    pub fn parse(&self, tokens: Vec<TokenWithLocation>) -> cayResult<Program> {
+        parser::parse(tokens)
+    }
+
+    /// 语义分析：检查 AST 并返回收集到的类型注册表
+    ///
+    /// 警告按编译器配置输出（或在 `warnings_as_errors` 下转换为错误）。
+    pub fn analyze(&self, program: &Program) -> cayResult<TypeRegistry> {
+        let mut analyzer = semantic::SemanticAnalyzer::new();
+        analyzer.configure(self.compiler.options());
+        analyzer.set_builtins(self.compiler.builtins().clone());
+        analyzer.analyze(program)?;
+        self.compiler.report_warnings(analyzer.take_warnings())?;
+
+        // 按需输出符号表
+        if let Some(format) = self.compiler.options().dump_symbols {
+            let symbols = semantic::SymbolDump::collect(program, analyzer.get_type_registry());
+            match format {
+                crate::SymbolFormat::Text => println!("{}", symbols),
+                crate::SymbolFormat::Json => println!("{}", symbols.to_json()),
+            }
+        }
+
+        Ok(analyzer.get_type_registry().clone())
+    }
+
+    /// 代码生成：根据 AST 和语义分析得到的类型注册表生成 LLVM IR
+    pub fn generate(&self, program: &Program, registry: &TypeRegistry) -> cayResult<String> {
+        self.generate_ir(program, registry, false)
+    }
+
+    /// 代码生成，同时返回 IR 行到源代码位置的映射
+    pub fn generate_with_source_map(&self, program: &Program, registry: &TypeRegistry) -> cayResult<(String, SourceMap)> {
+        let ir = self.generate_ir(program, registry, true)?;
+        Ok(SourceMap::extract(&ir))
+    }
+
+    fn generate_ir(&self, program: &Program, registry: &TypeRegistry, source_markers: bool) -> cayResult<String> {
+        let options = self.compiler.options();
+        let mut ir_gen = codegen::IRGenerator::new();
+        ir_gen.set_source_markers(source_markers);
+        // 传递多平台配置
+        ir_gen.set_platform_config(options);
+        // 传递类型注册表以支持正确的方法名生成
+        ir_gen.set_type_registry(registry.clone());
+        ir_gen.set_builtins(self.compiler.builtins().clone());
+        let mut ir = ir_gen.generate(program)?;
+
+        // 按需内联小型静态方法
+        if let Some(threshold) = options.inline_threshold {
+            ir = codegen::inliner::SmallFunctionInliner::new(threshold).inline_ir(&ir);
+        }
+
+        // 调试构建下对生成的 IR 做健全性检查，尽早暴露代码生成器的缺陷
+        #[cfg(debug_assertions)]
+        if let Err(problems) = codegen::IRGenerator::validate_ir(&ir) {
+            return Err(crate::error::codegen_error(format!(
+                "IR validation failed:\n  {}", problems.join("\n  ")
+            )));
+        }
+
+        // 如果启用了混淆，应用IR混淆
+        if options.obfuscate {
+            use codegen::obfuscator::IRObfuscator;
+            let mut obfuscator = IRObfuscator::new();
+            ir = obfuscator.obfuscate_ir(&ir);
+        }
+
+        // 按需输出指令统计
+        if options.dump_ir_stats {
+            println!("{}", codegen::ir_stats::IrStats::from_ir(&ir));
+        }
+
+        Ok(ir)
+    }
+}
@@ -0,0 +1,10 @@
+//! 常用类型的统一导入
+//!
+//! ```
+//! use cavvy::prelude::*;
+//! ```
+
+pub use crate::{BuiltinSpec, Compiler, CompilerBuilder, CompilerOptions, Pipeline, RuntimeMode};
+pub use crate::ast::Program;
+pub use crate::error::{cayError, cayResult, cayWarning};
+pub use crate::types::Type;
@@ -4,8 +4,10 @@
 //! - #include "path"  - 文件包含（隐式 #pragma once）
 //! - #define NAME value  - 常量定义（无参数宏）
 //! - #ifdef / #ifndef / #endif  - 条件编译
-//! - #error "message"  - 编译期错误
-//! - #warning "message"  - 编译期警告
+//! - #error "message"  - 编译期错误（消息支持 \n、\t、\\、\" 转义）
+//! - #warning "message"  - 编译期警告（转义同 #error）
+//! - #pragma once  - 显式声明只包含一次（未知的 #pragma 给出警告并忽略）
+//! - 预定义宏：__FILE__（当前文件路径）、__LINE__（当前行号）、__CAVVY_VERSION__（编译器版本）
 //! 
 //! 设计约束：
 //! - 仅支持简单常量定义，禁止宏函数
@@ -15,7 +17,7 @@
 
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use crate::error::{cayResult, cayError};
+use crate::error::{cayResult, cayError, cayWarning, WarningKind};
 
 /// 预处理器状态
 pub struct Preprocessor {
@@ -33,6 +35,46 @@ pub struct Preprocessor {
     include_stack: Vec<String>,
     /// 系统包含路径列表
     system_include_paths: Vec<PathBuf>,
+    /// 收集到的 #warning 警告
+    warnings: Vec<cayWarning>,
+}
+
+/// 展开时按当前文件 / 行号计算值的预定义宏
+const FILE_MACRO: &str = "__FILE__";
+const LINE_MACRO: &str = "__LINE__";
+
+/// 预定义宏表：__FILE__ / __LINE__ 的值在展开时计算，这里只登记名称，
+/// 以便 #ifdef 能识别它们
+fn predefined_macros() -> HashMap<String, String> {
+    let mut defines = HashMap::new();
+    defines.insert(FILE_MACRO.to_string(), String::new());
+    defines.insert(LINE_MACRO.to_string(), String::new());
+    defines.insert("__CAVVY_VERSION__".to_string(), format!("\"{}\"", env!("CARGO_PKG_VERSION")));
+    defines
+}
+
+/// 处理 #error / #warning 消息中的转义序列（\n、\t、\\、\"），其他反斜杠保持原样
+fn unescape_message(message: &str) -> String {
+    let mut result = String::with_capacity(message.len());
+    let mut chars = message.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('\\') => result.push('\\'),
+            Some('"') => result.push('"'),
+            Some(other) => {
+                result.push('\\');
+                result.push(other);
+            }
+            None => result.push('\\'),
+        }
+    }
+    result
 }
 
 /// 条件编译状态
@@ -61,6 +103,10 @@ enum Directive {
     Error(String),
     /// #warning "message"
     Warning(String),
+    /// #pragma once
+    PragmaOnce,
+    /// 不认识的 #pragma，忽略并警告
+    UnknownPragma(String),
 }
 
 impl Preprocessor {
@@ -73,13 +119,14 @@ impl Preprocessor {
     /// 初始化后的预处理器
     pub fn new(base_dir: impl AsRef<Path>) -> Self {
         Self {
-            defines: HashMap::new(),
+            defines: predefined_macros(),
             included_files: HashSet::new(),
             base_dir: base_dir.as_ref().to_path_buf(),
             conditional_stack: Vec::new(),
             skipping: false,
             include_stack: Vec::new(),
             system_include_paths: Vec::new(),
+            warnings: Vec::new(),
         }
     }
 
@@ -93,13 +140,14 @@ impl Preprocessor {
     /// 初始化后的预处理器
     pub fn with_system_paths(base_dir: impl AsRef<Path>, system_paths: Vec<PathBuf>) -> Self {
         Self {
-            defines: HashMap::new(),
+            defines: predefined_macros(),
             included_files: HashSet::new(),
             base_dir: base_dir.as_ref().to_path_buf(),
             conditional_stack: Vec::new(),
             skipping: false,
             include_stack: Vec::new(),
             system_include_paths: system_paths,
+            warnings: Vec::new(),
         }
     }
 
@@ -139,7 +187,7 @@ impl Preprocessor {
             if trimmed.starts_with('#') {
                 match self.parse_directive(trimmed, line_number, file_path) {
                     Ok(Some(directive)) => {
-                        self.process_directive(directive, &mut output_lines, file_path)?;
+                        self.process_directive(directive, &mut output_lines, file_path, line_number)?;
                     }
                     Ok(None) => {
                         // 跳过空指令（如纯注释）
@@ -152,7 +200,7 @@ impl Preprocessor {
                 output_lines.push("".to_string());
             } else {
                 // 普通代码行，进行宏替换后输出
-                let processed = self.expand_macros(line);
+                let processed = self.expand_macros(line, file_path, line_number);
                 output_lines.push(processed);
             }
         }
@@ -194,7 +242,7 @@ impl Preprocessor {
         
         match directive_name {
             "include" => {
-                // 解析 #include "path"
+                // 解析 #include "path"，路径不处理转义，Windows 路径中的反斜杠保持原样
                 let path = self.parse_string_literal(args, line_num)?;
                 Ok(Some(Directive::Include(path)))
             }
@@ -224,18 +272,25 @@ impl Preprocessor {
             }
             "error" => {
                 let message = self.parse_string_literal(args, line_num)?;
-                Ok(Some(Directive::Error(message)))
+                Ok(Some(Directive::Error(unescape_message(&message))))
             }
             "warning" => {
                 let message = self.parse_string_literal(args, line_num)?;
-                Ok(Some(Directive::Warning(message)))
+                Ok(Some(Directive::Warning(unescape_message(&message))))
+            }
+            "pragma" => {
+                if args == "once" {
+                    Ok(Some(Directive::PragmaOnce))
+                } else {
+                    Ok(Some(Directive::UnknownPragma(args.to_string())))
+                }
             }
             _ => {
                 Err(cayError::Preprocessor {
                     line: line_num,
                     column: 1,
                     message: format!("未知的预处理指令: {}", directive_name),
-                    suggestion: "支持的指令: #include, #define, #ifdef, #ifndef, #endif, #error, #warning".to_string(),
+                    suggestion: "支持的指令: #include, #define, #ifdef, #ifndef, #endif, #error, #warning, #pragma".to_string(),
                 })
             }
         }
@@ -344,11 +399,12 @@ impl Preprocessor {
         directive: Directive,
         output_lines: &mut Vec<String>,
         file_path: &str,
+        line_number: usize,
     ) -> cayResult<()> {
         match directive {
             Directive::Include(path) => {
                 if !self.skipping {
-                    self.handle_include(&path, output_lines, file_path)?;
+                    self.handle_include(&path, output_lines, file_path, line_number)?;
                 }
             }
             Directive::Define(name, value) => {
@@ -379,8 +435,31 @@ impl Preprocessor {
             }
             Directive::Warning(message) => {
                 if !self.skipping {
-                    // 警告通过 eprintln 输出但不中断编译
-                    eprintln!("warning: {}", message);
+                    // 警告先收集起来，由编译器统一输出或按 --werror 转为错误
+                    self.warnings.push(cayWarning::new(
+                        WarningKind::Preprocessor,
+                        line_number,
+                        1,
+                        format!("#warning: {}", message),
+                    ));
+                }
+            }
+            Directive::PragmaOnce => {
+                // 被包含的文件在处理前已登记（隐式 #pragma once），
+                // 这里再登记当前文件本身，使其之后不会被再次包含
+                let canonical = Path::new(file_path).canonicalize().ok().filter(|_| !self.skipping);
+                if let Some(canonical) = canonical {
+                    self.included_files.insert(canonical.to_string_lossy().to_string());
+                }
+            }
+            Directive::UnknownPragma(pragma) => {
+                if !self.skipping {
+                    self.warnings.push(cayWarning::new(
+                        WarningKind::Preprocessor,
+                        line_number,
+                        1,
+                        format!("未知的 #pragma 指令已忽略: {}", pragma),
+                    ));
                 }
             }
         }
@@ -393,9 +472,10 @@ impl Preprocessor {
         path: &str,
         output_lines: &mut Vec<String>,
         current_file: &str,
+        line_number: usize,
     ) -> cayResult<()> {
         // 解析完整路径
-        let include_path = self.resolve_include_path(path, current_file)?;
+        let include_path = self.resolve_include_path(path, current_file, line_number)?;
         
         // 标准化路径用于去重检查
         let canonical_path = include_path.canonicalize()
@@ -449,38 +529,52 @@ impl Preprocessor {
     /// 2. 相对于当前文件目录
     /// 3. 相对于基础目录
     /// 4. 系统包含路径
-    fn resolve_include_path(&self, path: &str, current_file: &str) -> cayResult<PathBuf> {
+    ///
+    /// 都找不到时报错，并列出搜索过的目录
+    fn resolve_include_path(&self, path: &str, current_file: &str, line_number: usize) -> cayResult<PathBuf> {
         // 1. 绝对路径
         if Path::new(path).is_absolute() {
-            return Ok(PathBuf::from(path));
+            if Path::new(path).exists() {
+                return Ok(PathBuf::from(path));
+            }
+            return Err(cayError::Preprocessor {
+                line: line_number,
+                column: 1,
+                message: format!("找不到包含文件 '{}'", path),
+                suggestion: "请检查绝对路径是否正确".to_string(),
+            });
         }
-        
+
+        let mut search_dirs = Vec::new();
         // 2. 相对于当前文件目录
         if let Some(current_dir) = Path::new(current_file).parent() {
-            let relative_path = current_dir.join(path);
-            if relative_path.exists() {
-                return Ok(relative_path);
-            }
+            search_dirs.push(current_dir.to_path_buf());
         }
-        
-        // 3. 相对于基础目录
-        let base_path = self.base_dir.join(path);
-        if base_path.exists() {
-            return Ok(base_path);
+        // 3. 相对于基础目录（与当前文件目录相同时不重复搜索）
+        if !search_dirs.contains(&self.base_dir) {
+            search_dirs.push(self.base_dir.clone());
         }
-        
         // 4. 系统包含路径
-        for sys_path in &self.system_include_paths {
-            let sys_include_path = sys_path.join(path);
-            if sys_include_path.exists() {
-                return Ok(sys_include_path);
-            }
+        search_dirs.extend(self.system_include_paths.iter().cloned());
+
+        if let Some(found) = search_dirs.iter().map(|dir| dir.join(path)).find(|candidate| candidate.exists()) {
+            return Ok(found);
         }
-        
-        // 如果都找不到，返回相对于当前文件的路径（让后续错误处理报告）
-        let current_dir = Path::new(current_file).parent()
-            .unwrap_or(&self.base_dir);
-        Ok(current_dir.join(path))
+
+        let searched: Vec<String> = search_dirs.iter()
+            .map(|dir| if dir.as_os_str().is_empty() { ".".to_string() } else { dir.display().to_string() })
+            .collect();
+        Err(cayError::Preprocessor {
+            line: line_number,
+            column: 1,
+            message: format!("找不到包含文件 '{}'，已搜索的目录: {}", path, searched.join(", ")),
+            suggestion: "请检查文件名是否正确，或将文件所在目录加入系统包含路径".to_string(),
+        })
+    }
+
+    /// 取出预处理期间收集到的警告
+    pub fn take_warnings(&mut self) -> Vec<cayWarning> {
+        std::mem::take(&mut self.warnings)
     }
 
     /// 获取当前包含栈（用于错误报告）
@@ -519,7 +613,9 @@ impl Preprocessor {
     }
 
     /// 展开宏定义（简单的文本替换）
-    fn expand_macros(&self, line: &str) -> String {
+    ///
+    /// `__FILE__` 展开为当前文件路径的字符串字面量，`__LINE__` 展开为当前行号
+    fn expand_macros(&self, line: &str, file_path: &str, line_number: usize) -> String {
         let mut result = line.to_string();
         
         // 按名称长度降序排序，避免短名称替换干扰长名称
@@ -527,10 +623,18 @@ impl Preprocessor {
         macros.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
         
         for (name, value) in macros {
+            if !result.contains(name.as_str()) {
+                continue;
+            }
+            let value = match name.as_str() {
+                FILE_MACRO => format!("\"{}\"", file_path.replace('\\', "\\\\").replace('"', "\\\"")),
+                LINE_MACRO => line_number.to_string(),
+                _ => value.clone(),
+            };
             // 简单的字符串替换
             // 注意：这不处理注释、字符串字面量等边界情况
             // 对于 0.3.5.0 版本，这是可接受的简化
-            result = result.replace(name, value);
+            result = result.replace(name.as_str(), &value);
         }
         
         result
@@ -548,7 +652,11 @@ impl Preprocessor {
 /// 预处理后的源代码
 pub fn preprocess(source: &str, file_path: &str, base_dir: impl AsRef<Path>) -> cayResult<String> {
     let mut preprocessor = Preprocessor::new(base_dir);
-    preprocessor.process(source, file_path)
+    let result = preprocessor.process(source, file_path)?;
+    for warning in preprocessor.take_warnings() {
+        eprintln!("{}", warning);
+    }
+    Ok(result)
 }
 
 /// 带系统包含路径的预处理函数
@@ -568,5 +676,9 @@ pub fn preprocess_with_system_paths(
     system_paths: Vec<PathBuf>
 ) -> cayResult<String> {
     let mut preprocessor = Preprocessor::with_system_paths(base_dir, system_paths);
-    preprocessor.process(source, file_path)
+    let result = preprocessor.process(source, file_path)?;
+    for warning in preprocessor.take_warnings() {
+        eprintln!("{}", warning);
+    }
+    Ok(result)
 }
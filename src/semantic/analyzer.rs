@@ -1,8 +1,10 @@
 //! 语义分析器核心实现
 
+use std::collections::HashMap;
 use crate::ast::*;
 use crate::types::{Type, ParameterInfo, ClassInfo, MethodInfo, FieldInfo, TypeRegistry};
-use crate::error::{cayResult, semantic_error};
+use crate::error::{cayError, cayResult, semantic_error, cayWarning, WarningKind};
+use crate::builtins::{BuiltinRegistry, BuiltinSpec};
 use super::symbol_table::{SemanticSymbolTable, SemanticSymbolInfo};
 
 /// 语义分析器
@@ -14,6 +16,22 @@ pub struct SemanticAnalyzer {
     pub(super) current_method_is_static: bool,  // 当前方法是否是静态方法
     pub(super) current_method_is_constructor: bool,  // 当前是否是构造函数
     pub(super) errors: Vec<String>,
+    pub(super) warnings: Vec<cayWarning>,
+    pub(super) target_version: Option<String>,  // 目标 Cavvy 版本（--target-version）
+    pub(super) implicit_string_concat: bool,  // 字符串 + 基本类型隐式转换（--implicit-string-concat）
+    pub(super) warn_shadowed_params: bool,  // 局部变量遮蔽参数时警告（--warn-shadow）
+    pub(super) warn_infinite_loops: bool,  // 条件恒为真且无法退出的循环时警告（--warn-infinite-loop）
+    pub(super) warn_float_precision: bool,  // double 字面量赋给 float 丢失精度时警告（--warn-float-precision）
+    pub(super) immutable_final_arrays: bool,  // final 数组的元素不可修改（--immutable-final-arrays）
+    pub(super) current_params: Vec<String>,  // 当前方法/构造函数的参数名
+    pub(super) assigned_final_fields: Vec<String>,  // 当前构造函数中已赋值的 final 字段
+    pub(super) loop_depth: usize,  // 当前所在循环体的嵌套层数
+    pub(super) in_unchecked_scan: bool,  // 是否正在静默扫描不做类型检查的语句
+    pub(super) unchecked_write_error: Option<cayError>,  // 静默扫描中遇到的非法写入等错误，扫描结束后照常报告
+    pub(super) max_errors: usize,  // 错误数量上限，0 表示不限制（--max-errors）
+    pub(super) builtins: BuiltinRegistry,  // 宿主程序注册的自定义内置函数
+    pub(super) local_list_elements: HashMap<String, Type>,  // 当前方法中 List 局部变量/参数的元素类型
+    pub(super) field_list_elements: HashMap<String, Type>,  // List 字段的元素类型，以 "类名.字段名" 为键
 }
 
 impl SemanticAnalyzer {
@@ -26,6 +44,22 @@ impl SemanticAnalyzer {
             current_method_is_static: false,
             current_method_is_constructor: false,
             errors: Vec::new(),
+            warnings: Vec::new(),
+            target_version: None,
+            implicit_string_concat: false,
+            warn_shadowed_params: false,
+            warn_infinite_loops: false,
+            warn_float_precision: false,
+            immutable_final_arrays: false,
+            current_params: Vec::new(),
+            assigned_final_fields: Vec::new(),
+            loop_depth: 0,
+            in_unchecked_scan: false,
+            unchecked_write_error: None,
+            max_errors: 100,
+            builtins: BuiltinRegistry::new(),
+            local_list_elements: HashMap::new(),
+            field_list_elements: HashMap::new(),
         };
         
         // 注册内置函数
@@ -37,12 +71,27 @@ impl SemanticAnalyzer {
     fn register_builtin_functions(&mut self) {
         // 注册 print 函数 - 作为特殊处理
         // print 可以接受任意类型参数
+
+        // 注册内置 String / StringView 方法和 List 类
+        self.type_registry.register_builtin_string_methods();
+        self.type_registry.register_builtin_list_class();
     }
 
     pub fn analyze(&mut self, program: &Program) -> cayResult<()> {
         // 第一遍：收集所有类定义
         self.collect_classes(program)?;
 
+        // 折叠 const 常量，登记到常量表
+        self.collect_constants(program)?;
+
+        // 顶层 static_assert 在常量折叠后立即检查
+        for assertion in &program.static_asserts {
+            self.check_static_assert(assertion)?;
+        }
+
+        // @main/@entry 只能标记类
+        self.check_main_marker_placement(program)?;
+
         // 检查主类冲突（在收集类之后，类型检查之前）
         self.check_main_class_conflicts(program)?;
 
@@ -53,6 +102,7 @@ impl SemanticAnalyzer {
         self.check_inheritance(program)?;
 
         // 第四遍：类型检查
+        self.collect_list_signatures(program);
         self.type_check_program(program)?;
 
         if !self.errors.is_empty() {
@@ -62,6 +112,86 @@ impl SemanticAnalyzer {
         Ok(())
     }
 
+    /// 记录一条语义错误，继续分析以便一次报告多个错误
+    ///
+    /// 错误数量达到上限时停止分析，返回已收集的错误和 "too many errors" 提示。
+    pub fn add_error(&mut self, message: String) -> cayResult<()> {
+        self.errors.push(message);
+        if self.max_errors > 0 && self.errors.len() >= self.max_errors {
+            return Err(semantic_error(0, 0, format!(
+                "{}\ntoo many errors ({}), stopping analysis",
+                self.errors.join("\n"), self.errors.len()
+            )));
+        }
+        Ok(())
+    }
+
+    /// 记录一条语义警告
+    pub fn add_warning(&mut self, kind: WarningKind, line: usize, column: usize, message: impl Into<String>) {
+        self.warnings.push(cayWarning::new(kind, line, column, message));
+    }
+
+    /// 应用编译器配置中与语义分析相关的选项
+    pub fn configure(&mut self, options: &crate::CompilerOptions) {
+        self.set_target_version(options.target_version.clone());
+        self.set_implicit_string_concat(options.implicit_string_concat);
+        self.set_warn_shadowed_params(options.warn_shadowed_params);
+        self.set_warn_infinite_loops(options.warn_infinite_loops);
+        self.set_warn_float_precision(options.warn_float_precision);
+        self.set_immutable_final_arrays(options.immutable_final_arrays);
+        self.set_max_errors(options.max_errors);
+    }
+
+    /// 设置目标版本，用于检查 @SinceVersion 标记的方法
+    pub fn set_target_version(&mut self, version: Option<String>) {
+        self.target_version = version;
+    }
+
+    /// 设置是否允许字符串与基本类型用 + 隐式拼接
+    pub fn set_implicit_string_concat(&mut self, enabled: bool) {
+        self.implicit_string_concat = enabled;
+    }
+
+    /// 设置局部变量遮蔽方法参数时是否发出警告
+    pub fn set_warn_shadowed_params(&mut self, enabled: bool) {
+        self.warn_shadowed_params = enabled;
+    }
+
+    /// 设置条件恒为真且无法退出的循环是否发出警告
+    pub fn set_warn_infinite_loops(&mut self, enabled: bool) {
+        self.warn_infinite_loops = enabled;
+    }
+
+    /// 设置 double 字面量赋给 float 丢失精度时是否发出警告
+    pub fn set_warn_float_precision(&mut self, enabled: bool) {
+        self.warn_float_precision = enabled;
+    }
+
+    /// 设置是否禁止修改 final 数组的元素
+    pub fn set_immutable_final_arrays(&mut self, enabled: bool) {
+        self.immutable_final_arrays = enabled;
+    }
+
+    /// 设置错误数量上限，0 表示不限制
+    pub fn set_max_errors(&mut self, limit: usize) {
+        self.max_errors = limit;
+    }
+
+    /// 注册自定义内置函数
+    pub fn register_builtin(&mut self, spec: BuiltinSpec) {
+        self.builtins.insert(spec.name.clone(), spec);
+    }
+
+    /// 替换全部自定义内置函数
+    pub fn set_builtins(&mut self, builtins: BuiltinRegistry) {
+        self.builtins = builtins;
+    }
+
+    /// 取出语义分析期间收集到的警告
+    pub fn take_warnings(&mut self) -> Vec<cayWarning> {
+        std::mem::take(&mut self.warnings)
+    }
+
     /// 获取类型注册表（用于代码生成）
     pub fn get_type_registry(&self) -> &TypeRegistry {
         &self.type_registry
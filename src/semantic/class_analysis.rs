@@ -28,9 +28,16 @@ impl SemanticAnalyzer {
                 }
             });
 
+            let has_main_marker = class.modifiers.contains(&crate::ast::Modifier::Main);
             if has_main {
-                let has_main_marker = class.modifiers.contains(&crate::ast::Modifier::Main);
                 main_classes.push((class.name.clone(), has_main_marker));
+            } else if has_main_marker {
+                // @main 标记的类必须自己提供入口，否则标记会被静默忽略
+                return Err(semantic_error(
+                    class.loc.line,
+                    class.loc.column,
+                    format!("Class '{}' is marked @main but has no 'public static main' method", class.name)
+                ));
             }
         }
 
@@ -87,6 +94,50 @@ impl SemanticAnalyzer {
         }
     }
 
+    /// 检查 @main（及其别名 @entry）只出现在类声明上
+    pub fn check_main_marker_placement(&self, program: &Program) -> cayResult<()> {
+        let misplaced = |kind: &str, name: &str, loc: &crate::error::SourceLocation| semantic_error(
+            loc.line,
+            loc.column,
+            format!("@main/@entry can only be applied to a class, not to {} '{}'", kind, name)
+        );
+
+        for class in &program.classes {
+            for member in &class.members {
+                match member {
+                    ClassMember::Method(method) if method.modifiers.contains(&Modifier::Main) => {
+                        return Err(misplaced("method", &method.name, &method.loc));
+                    }
+                    ClassMember::Field(field) if field.modifiers.contains(&Modifier::Main) => {
+                        return Err(misplaced("field", &field.name, &field.loc));
+                    }
+                    ClassMember::Constructor(ctor) if ctor.modifiers.contains(&Modifier::Main) => {
+                        return Err(misplaced("constructor", &class.name, &ctor.loc));
+                    }
+                    ClassMember::Destructor(dtor) if dtor.modifiers.contains(&Modifier::Main) => {
+                        return Err(misplaced("destructor", &class.name, &dtor.loc));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        for interface in &program.interfaces {
+            if interface.modifiers.contains(&Modifier::Main) {
+                return Err(misplaced("interface", &interface.name, &interface.loc));
+            }
+            if let Some(method) = interface.methods.iter().find(|m| m.modifiers.contains(&Modifier::Main)) {
+                return Err(misplaced("method", &method.name, &method.loc));
+            }
+        }
+
+        if let Some(constant) = program.constants.iter().find(|c| c.modifiers.contains(&Modifier::Main)) {
+            return Err(misplaced("constant", &constant.name, &constant.loc));
+        }
+
+        Ok(())
+    }
+
     /// 收集类定义
     pub fn collect_classes(&mut self, program: &Program) -> cayResult<()> {
         // 首先收集接口定义
@@ -107,6 +158,10 @@ impl SemanticAnalyzer {
                     is_native: false,
                     is_override: false,
                     is_final: false,  // 接口方法不是final
+                    since_version: method.modifiers.iter().find_map(|m| match m {
+                        Modifier::SinceVersion(version) => Some(version.clone()),
+                        _ => None,
+                    }),
                 };
                 interface_info.add_method(method_info);
             }
@@ -136,10 +191,11 @@ impl SemanticAnalyzer {
                     ClassMember::Field(field) => {
                         let is_final = field.modifiers.contains(&Modifier::Final);
                         let is_static = field.modifiers.contains(&Modifier::Static);
-                        // static final 字段且初始化值为字面量时，标记为编译期常量
-                        let is_const_expr = is_static && is_final && field.initializer.as_ref().map_or(false, |e| {
-                            matches!(e, crate::ast::Expr::Literal(_))
-                        });
+                        // const 字段，或 static final 字段且初始化值为字面量时，标记为编译期常量
+                        let is_const_expr = field.modifiers.contains(&Modifier::Const)
+                            || (is_static && is_final && field.initializer.as_ref().map_or(false, |e| {
+                                matches!(e, crate::ast::Expr::Literal(_))
+                            }));
                         let field_info = FieldInfo {
                             name: field.name.clone(),
                             field_type: field.field_type.clone(),
@@ -192,6 +248,10 @@ impl SemanticAnalyzer {
                         is_native: method.modifiers.contains(&Modifier::Native),
                         is_override: method.modifiers.contains(&Modifier::Override),
                         is_final: method.modifiers.contains(&Modifier::Final),
+                        since_version: method.modifiers.iter().find_map(|m| match m {
+                            Modifier::SinceVersion(version) => Some(version.clone()),
+                            _ => None,
+                        }),
                     };
 
                     if let Some(class_info) = self.type_registry.classes.get_mut(&class.name) {
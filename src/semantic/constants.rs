@@ -0,0 +1,331 @@
+//! const 常量的收集与编译期折叠
+//!
+//! 顶层和类中的 `const` 声明要求初始化表达式能在编译期求值，
+//! 折叠后的值登记到 TypeRegistry 的常量表，代码生成时直接替换为字面量。
+//! `static_assert(condition, "message")` 复用同一套折叠逻辑，在编译期检查条件。
+
+use crate::ast::*;
+use crate::types::{ConstantInfo, Type};
+use crate::error::{cayResult, semantic_error};
+use super::analyzer::SemanticAnalyzer;
+
+impl SemanticAnalyzer {
+    /// 收集并折叠所有 const 常量
+    ///
+    /// 常量之间可以相互引用且与声明顺序无关：反复折叠直到不再有进展，
+    /// 剩下的声明说明初始化表达式不是编译期常量（或存在循环引用）。
+    pub fn collect_constants(&mut self, program: &Program) -> cayResult<()> {
+        let mut decls: Vec<(Option<&str>, &FieldDecl)> = program.constants.iter()
+            .map(|decl| (None, decl))
+            .collect();
+        for class in &program.classes {
+            for member in &class.members {
+                match member {
+                    ClassMember::Field(field) if field.modifiers.contains(&Modifier::Const) => {
+                        decls.push((Some(class.name.as_str()), field));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut pending = Vec::new();
+        for (class_name, decl) in decls {
+            match &decl.initializer {
+                Some(init) => pending.push((class_name, decl, init)),
+                None => return Err(semantic_error(
+                    decl.loc.line,
+                    decl.loc.column,
+                    format!("Constant '{}' must be initialized", decl.name)
+                )),
+            }
+        }
+
+        while let Some((_, first, _)) = pending.first() {
+            let first = *first;
+            let before = pending.len();
+            let mut remaining = Vec::new();
+            for (class_name, decl, init) in pending {
+                let Some(value) = self.fold_constant(init, class_name) else {
+                    remaining.push((class_name, decl, init));
+                    continue;
+                };
+                let value_type = literal_type(&value);
+                let Some(value) = coerce_constant(value, &decl.field_type) else {
+                    return Err(semantic_error(
+                        decl.loc.line,
+                        decl.loc.column,
+                        format!("Cannot initialize constant '{}' of type {} with a value of type {}",
+                            decl.name, decl.field_type, value_type)
+                    ));
+                };
+                self.type_registry.register_constant(class_name, ConstantInfo {
+                    name: decl.name.clone(),
+                    const_type: decl.field_type.clone(),
+                    value,
+                });
+            }
+
+            if remaining.len() == before {
+                return Err(semantic_error(
+                    first.loc.line,
+                    first.loc.column,
+                    format!("Initializer of constant '{}' is not a compile-time constant", first.name)
+                ));
+            }
+            pending = remaining;
+        }
+
+        Ok(())
+    }
+
+    /// 检查 static_assert(condition, "message")
+    ///
+    /// 条件必须能折叠为 bool 常量；为 false 时以断言消息报错。断言不生成任何运行时代码。
+    pub(super) fn check_static_assert(&self, call: &CallExpr) -> cayResult<()> {
+        let (line, column) = (call.loc.line, call.loc.column);
+        let message = match call.args.as_slice() {
+            [_, Expr::Literal(LiteralValue::String(message))] => message,
+            _ => return Err(semantic_error(
+                line,
+                column,
+                "static_assert() takes a condition and a message string literal"
+            )),
+        };
+
+        match self.fold_constant(&call.args[0], self.current_class.as_deref()) {
+            Some(LiteralValue::Bool(true)) => Ok(()),
+            Some(LiteralValue::Bool(false)) => Err(semantic_error(
+                line,
+                column,
+                format!("Static assertion failed: {}", message)
+            )),
+            Some(value) => Err(semantic_error(
+                line,
+                column,
+                format!("static_assert condition must be a bool, got {}", literal_type(&value))
+            )),
+            None => Err(semantic_error(
+                line,
+                column,
+                "static_assert condition is not a compile-time constant"
+            )),
+        }
+    }
+
+    /// 按名称查找当前上下文可见的常量（局部变量会遮蔽顶层常量）
+    pub(super) fn lookup_constant(&self, name: &str) -> Option<&ConstantInfo> {
+        if self.symbol_table.lookup(name).is_some() {
+            return None;
+        }
+        self.type_registry.resolve_constant(self.current_class.as_deref(), name)
+    }
+
+    /// 赋值目标是 const 常量时返回常量名（`NAME` 或 `ClassName.NAME`）
+    pub(super) fn assigned_constant(&self, target: &Expr) -> Option<String> {
+        match target {
+            Expr::Identifier(name) => self.lookup_constant(name).map(|_| name.clone()),
+            Expr::MemberAccess(member) => match member.object.as_ref() {
+                Expr::Identifier(owner) if self.symbol_table.lookup(owner).is_none() => self.type_registry
+                    .get_class_constant(owner, &member.member)
+                    .map(|_| format!("{}.{}", owner, member.member)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// 表达式是否是落在 int8/int16 范围内的整数常量，这样的常量可以直接赋给窄整数类型
+    pub(super) fn integer_constant_fits(&self, expr: &Expr, target: &Type) -> bool {
+        let range = match target {
+            Type::Int8 => i8::MIN as i64..=i8::MAX as i64,
+            Type::Int16 => i16::MIN as i64..=i16::MAX as i64,
+            _ => return false,
+        };
+        self.fold_constant(expr, self.current_class.as_deref())
+            .as_ref()
+            .and_then(as_integer)
+            .is_some_and(|n| range.contains(&n))
+    }
+
+    /// 表达式能否在编译期折叠为 true
+    pub(super) fn is_constant_true(&self, expr: &Expr) -> bool {
+        matches!(self.fold_constant(expr, self.current_class.as_deref()), Some(LiteralValue::Bool(true)))
+    }
+
+    /// 在编译期求值表达式，无法求值时返回 None
+    fn fold_constant(&self, expr: &Expr, class_name: Option<&str>) -> Option<LiteralValue> {
+        match expr {
+            Expr::Literal(LiteralValue::Null) => None,
+            Expr::Literal(lit) => Some(lit.clone()),
+            Expr::Identifier(name) if self.symbol_table.lookup(name).is_some() => None,
+            Expr::Identifier(name) => self.type_registry.resolve_constant(class_name, name)
+                .map(|info| info.value.clone()),
+            Expr::MemberAccess(member) => match member.object.as_ref() {
+                Expr::Identifier(owner) => self.type_registry.get_class_constant(owner, &member.member)
+                    .map(|info| info.value.clone()),
+                _ => None,
+            },
+            Expr::Unary(unary) => fold_unary(&unary.op, self.fold_constant(&unary.operand, class_name)?),
+            Expr::Binary(bin) => fold_binary(
+                &bin.op,
+                self.fold_constant(&bin.left, class_name)?,
+                self.fold_constant(&bin.right, class_name)?,
+            ),
+            Expr::Cast(cast) => cast_constant(self.fold_constant(&cast.expr, class_name)?, &cast.target_type),
+            _ => None,
+        }
+    }
+}
+
+/// 字面量对应的类型
+fn literal_type(value: &LiteralValue) -> Type {
+    match value {
+        LiteralValue::Int32(_) => Type::Int32,
+        LiteralValue::Int64(_) => Type::Int64,
+        LiteralValue::Float32(_) => Type::Float32,
+        LiteralValue::Float64(_) => Type::Float64,
+        LiteralValue::String(_) => Type::String,
+        LiteralValue::Bool(_) => Type::Bool,
+        LiteralValue::Char(_) => Type::Char,
+        LiteralValue::Null => Type::Object("Object".to_string()),
+    }
+}
+
+/// 整数类字面量（char 按字符码参与运算）
+fn as_integer(value: &LiteralValue) -> Option<i64> {
+    match value {
+        LiteralValue::Int32(n) => Some(*n as i64),
+        LiteralValue::Int64(n) => Some(*n),
+        LiteralValue::Char(c) => Some(*c as i64),
+        _ => None,
+    }
+}
+
+fn as_float(value: &LiteralValue) -> Option<f64> {
+    match value {
+        LiteralValue::Float32(f) => Some(*f as f64),
+        LiteralValue::Float64(f) => Some(*f),
+        _ => as_integer(value).map(|n| n as f64),
+    }
+}
+
+/// 按常量声明的类型转换折叠结果，转换规则与 types_compatible 一致
+fn coerce_constant(value: LiteralValue, target: &Type) -> Option<LiteralValue> {
+    match (&value, target) {
+        (LiteralValue::Int32(_), Type::Int32)
+        | (LiteralValue::Int64(_), Type::Int64)
+        | (LiteralValue::Float32(_), Type::Float32)
+        | (LiteralValue::Float64(_), Type::Float64)
+        | (LiteralValue::String(_), Type::String)
+        | (LiteralValue::Bool(_), Type::Bool)
+        | (LiteralValue::Char(_), Type::Char) => Some(value),
+        (LiteralValue::Int32(n), Type::Int64) => Some(LiteralValue::Int64(*n as i64)),
+        (LiteralValue::Int32(n), Type::Int8) if i8::try_from(*n).is_ok() => Some(value),
+        (LiteralValue::Int32(n), Type::Int16) if i16::try_from(*n).is_ok() => Some(value),
+        (LiteralValue::Char(c), Type::Int32) => Some(LiteralValue::Int32(*c as i32)),
+        (LiteralValue::Char(c), Type::Int64) => Some(LiteralValue::Int64(*c as i64)),
+        (LiteralValue::Int32(_) | LiteralValue::Float64(_), Type::Float32) => Some(LiteralValue::Float32(as_float(&value)? as f32)),
+        (LiteralValue::Int32(_) | LiteralValue::Int64(_) | LiteralValue::Float32(_), Type::Float64) => Some(LiteralValue::Float64(as_float(&value)?)),
+        _ => None,
+    }
+}
+
+/// 显式类型转换
+fn cast_constant(value: LiteralValue, target: &Type) -> Option<LiteralValue> {
+    let is_float = matches!(value, LiteralValue::Float32(_) | LiteralValue::Float64(_));
+    let int_value = || if is_float { as_float(&value).map(|f| f as i64) } else { as_integer(&value) };
+    match target {
+        Type::Int8 => Some(LiteralValue::Int32(int_value()? as i8 as i32)),
+        Type::Int16 => Some(LiteralValue::Int32(int_value()? as i16 as i32)),
+        Type::Int32 => Some(LiteralValue::Int32(int_value()? as i32)),
+        Type::Int64 => Some(LiteralValue::Int64(int_value()?)),
+        Type::Char => char::from_u32(int_value()? as u32).map(LiteralValue::Char),
+        Type::Float32 => Some(LiteralValue::Float32(as_float(&value)? as f32)),
+        Type::Float64 => Some(LiteralValue::Float64(as_float(&value)?)),
+        _ => coerce_constant(value, target),
+    }
+}
+
+fn fold_unary(op: &UnaryOp, value: LiteralValue) -> Option<LiteralValue> {
+    match (op, value) {
+        (UnaryOp::Neg, LiteralValue::Int32(n)) => Some(LiteralValue::Int32(n.wrapping_neg())),
+        (UnaryOp::Neg, LiteralValue::Int64(n)) => Some(LiteralValue::Int64(n.wrapping_neg())),
+        (UnaryOp::Neg, LiteralValue::Float32(f)) => Some(LiteralValue::Float32(-f)),
+        (UnaryOp::Neg, LiteralValue::Float64(f)) => Some(LiteralValue::Float64(-f)),
+        // char 先提升为 int
+        (UnaryOp::Neg, LiteralValue::Char(c)) => Some(LiteralValue::Int32(-(c as i32))),
+        (UnaryOp::Plus, LiteralValue::Char(c)) => Some(LiteralValue::Int32(c as i32)),
+        (UnaryOp::Plus, value @ (LiteralValue::Int32(_) | LiteralValue::Int64(_)
+            | LiteralValue::Float32(_) | LiteralValue::Float64(_))) => Some(value),
+        (UnaryOp::Not, LiteralValue::Bool(b)) => Some(LiteralValue::Bool(!b)),
+        (UnaryOp::BitNot, LiteralValue::Int32(n)) => Some(LiteralValue::Int32(!n)),
+        (UnaryOp::BitNot, LiteralValue::Int64(n)) => Some(LiteralValue::Int64(!n)),
+        _ => None,
+    }
+}
+
+fn fold_binary(op: &BinaryOp, left: LiteralValue, right: LiteralValue) -> Option<LiteralValue> {
+    match (&left, &right) {
+        (LiteralValue::String(l), LiteralValue::String(r)) => {
+            return match op {
+                BinaryOp::Add => Some(LiteralValue::String(format!("{}{}", l, r))),
+                _ => None,
+            };
+        }
+        (LiteralValue::Bool(l), LiteralValue::Bool(r)) => {
+            return match op {
+                BinaryOp::And => Some(LiteralValue::Bool(*l && *r)),
+                BinaryOp::Or => Some(LiteralValue::Bool(*l || *r)),
+                BinaryOp::Eq => Some(LiteralValue::Bool(l == r)),
+                BinaryOp::Ne => Some(LiteralValue::Bool(l != r)),
+                _ => None,
+            };
+        }
+        _ => {}
+    }
+
+    if let (Some(l), Some(r)) = (as_integer(&left), as_integer(&right)) {
+        let wide = matches!(left, LiteralValue::Int64(_)) || matches!(right, LiteralValue::Int64(_));
+        let result = match op {
+            BinaryOp::Add => l.wrapping_add(r),
+            BinaryOp::Sub => l.wrapping_sub(r),
+            BinaryOp::Mul => l.wrapping_mul(r),
+            BinaryOp::Div if r != 0 => l.wrapping_div(r),
+            BinaryOp::Mod if r != 0 => l.wrapping_rem(r),
+            BinaryOp::BitAnd => l & r,
+            BinaryOp::BitOr => l | r,
+            BinaryOp::BitXor => l ^ r,
+            BinaryOp::Shl => l.wrapping_shl(r as u32),
+            BinaryOp::Shr => l.wrapping_shr(r as u32),
+            BinaryOp::UnsignedShr if wide => ((l as u64).wrapping_shr(r as u32)) as i64,
+            BinaryOp::UnsignedShr => ((l as u32).wrapping_shr(r as u32)) as i64,
+            _ => return fold_comparison(op, l, r),
+        };
+        return Some(if wide { LiteralValue::Int64(result) } else { LiteralValue::Int32(result as i32) });
+    }
+
+    let (l, r) = (as_float(&left)?, as_float(&right)?);
+    let result = match op {
+        BinaryOp::Add => l + r,
+        BinaryOp::Sub => l - r,
+        BinaryOp::Mul => l * r,
+        BinaryOp::Div => l / r,
+        _ => return fold_comparison(op, l, r),
+    };
+    let double = matches!(left, LiteralValue::Float64(_)) || matches!(right, LiteralValue::Float64(_));
+    Some(if double { LiteralValue::Float64(result) } else { LiteralValue::Float32(result as f32) })
+}
+
+fn fold_comparison<T: PartialOrd>(op: &BinaryOp, l: T, r: T) -> Option<LiteralValue> {
+    let result = match op {
+        BinaryOp::Eq => l == r,
+        BinaryOp::Ne => l != r,
+        BinaryOp::Lt => l < r,
+        BinaryOp::Le => l <= r,
+        BinaryOp::Gt => l > r,
+        BinaryOp::Ge => l >= r,
+        _ => return None,
+    };
+    Some(LiteralValue::Bool(result))
+}
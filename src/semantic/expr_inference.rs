@@ -1,8 +1,8 @@
 //! 表达式类型推断
 
 use crate::ast::*;
-use crate::types::Type;
-use crate::error::{cayResult, semantic_error};
+use crate::types::{Type, LIST_CLASS};
+use crate::error::{cayError, cayResult, semantic_error, SourceLocation};
 use super::analyzer::SemanticAnalyzer;
 use super::symbol_table::SemanticSymbolInfo;
 
@@ -29,30 +29,38 @@ impl SemanticAnalyzer {
                     ));
                 }
                 
-                // 首先检查是否是当前类的字段（包括静态和非静态）
+                // 首先检查是否是当前类的字段（包括静态和非静态，以及父类中非私有的字段）
                 if let Some(current_class_name) = &self.current_class {
-                    if let Some(class_info) = self.type_registry.get_class(current_class_name) {
-                        if let Some(field_info) = class_info.fields.get(name) {
-                            if field_info.is_static {
-                                return Ok(field_info.field_type.clone());
-                            } else if self.current_method_is_static {
-                                // 静态方法中不能访问非静态字段
-                                return Err(semantic_error(
-                                    0, 0,
-                                    format!("non-static variable {} cannot be referenced from a static context", name)
-                                ));
-                            }
-                            // 非静态方法中返回字段类型
+                    let own_field = self.type_registry.get_class(current_class_name)
+                        .is_some_and(|c| c.fields.contains_key(name));
+                    let field = self.type_registry.get_field(current_class_name, name)
+                        .filter(|f| own_field || !f.is_private);
+                    if let Some(field_info) = field {
+                        if field_info.is_static {
                             return Ok(field_info.field_type.clone());
+                        } else if self.current_method_is_static {
+                            // 静态方法中不能访问非静态字段
+                            return Err(semantic_error(
+                                0, 0,
+                                format!("non-static variable {} cannot be referenced from a static context", name)
+                            ));
                         }
+                        // 非静态方法中返回字段类型
+                        return Ok(field_info.field_type.clone());
                     }
                 }
                 
                 if let Some(info) = self.symbol_table.lookup(name) {
                     Ok(info.symbol_type.clone())
+                } else if let Some(constant) = self.lookup_constant(name) {
+                    // 顶层 const 常量
+                    Ok(constant.const_type.clone())
                 } else if self.type_registry.class_exists(name) {
-                    // 标识符是类名，返回类类型（用于静态成员访问）
-                    Ok(Type::Object(name.clone()))
+                    // 类名只能作为静态成员访问的前缀（见 infer_object_type），不能当作值使用
+                    Err(semantic_error(
+                        0, 0,
+                        format!("Class name '{}' cannot be used as a value; it can only prefix a static member access like {}.member", name, name)
+                    ))
                 } else {
                     Err(semantic_error(0, 0, format!("Undefined variable: {}", name)))
                 }
@@ -71,7 +79,30 @@ impl SemanticAnalyzer {
             Expr::Lambda(lambda) => self.infer_lambda_type(lambda),
             Expr::Ternary(ternary) => self.infer_ternary_type(ternary),
             Expr::InstanceOf(instanceof) => self.infer_instanceof_type(instanceof),
+            Expr::Tuple(tuple) => {
+                let elements = tuple.elements.iter()
+                    .map(|element| self.infer_expr_type(element))
+                    .collect::<cayResult<Vec<_>>>()?;
+                Ok(Type::Tuple(elements))
+            }
+        }
+    }
+
+    /// 推断成员访问或方法调用中 `.` 左侧对象的类型
+    ///
+    /// 左侧是类名（且没有被同名变量、字段或常量遮蔽）时表示静态成员访问，
+    /// 返回该类的类型；其余情况按普通表达式推断。
+    fn infer_object_type(&mut self, object: &Expr) -> cayResult<Type> {
+        if let Expr::Identifier(name) = object {
+            let shadowed = self.symbol_table.lookup(name).is_some()
+                || self.lookup_constant(name).is_some()
+                || self.current_class.as_ref()
+                    .is_some_and(|c| self.type_registry.get_field(c, name).is_some());
+            if !shadowed && self.type_registry.class_exists(name) {
+                return Ok(Type::Object(name.clone()));
+            }
         }
+        self.infer_expr_type(object)
     }
 
     /// 推断二元表达式类型
@@ -90,7 +121,11 @@ impl SemanticAnalyzer {
                     Ok(Type::String)
                 }
                 // char + 字符串：允许，结果为字符串
-                else if left_type == Type::Char && right_type == Type::String {
+                // 隐式拼接模式下，字符串与任意基本类型相加时基本类型先转换为字符串
+                else if (left_type == Type::Char && right_type == Type::String)
+                    || (self.implicit_string_concat
+                        && ((left_type == Type::String && right_type.is_primitive())
+                            || (left_type.is_primitive() && right_type == Type::String))) {
                     Ok(Type::String)
                 }
                 // 数值加法：两个操作数都必须是基本数值类型
@@ -137,6 +172,19 @@ impl SemanticAnalyzer {
             BinaryOp::Eq | BinaryOp::Ne | BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => {
                 Ok(Type::Bool)
             }
+            BinaryOp::Is | BinaryOp::IsNot => {
+                // 引用同一性比较只对引用类型有意义
+                if left_type.is_reference_type() && right_type.is_reference_type() {
+                    Ok(Type::Bool)
+                } else {
+                    let op = if bin.op == BinaryOp::Is { "===" } else { "!==" };
+                    Err(semantic_error(
+                        bin.loc.line,
+                        bin.loc.column,
+                        format!("Operator {} requires reference type operands, got {} and {}", op, left_type, right_type)
+                    ))
+                }
+            }
             BinaryOp::And | BinaryOp::Or => {
                 if left_type == Type::Bool && right_type == Type::Bool {
                     Ok(Type::Bool)
@@ -179,9 +227,29 @@ impl SemanticAnalyzer {
 
     /// 推断一元表达式类型
     fn infer_unary_type(&mut self, unary: &UnaryExpr) -> cayResult<Type> {
+        let is_inc_dec = matches!(unary.op, UnaryOp::PreInc | UnaryOp::PreDec | UnaryOp::PostInc | UnaryOp::PostDec);
+        if let Some(name) = self.assigned_constant(&unary.operand).filter(|_| is_inc_dec) {
+            return Err(self.write_error(&unary.loc, format!("Cannot assign a value to constant '{}'", name)));
+        }
+        if let Some(name) = self.mutated_final_array(&unary.operand).filter(|_| is_inc_dec) {
+            return Err(self.write_error(&unary.loc, format!("Cannot modify an element of final array '{}'", name)));
+        }
+        let final_field = if is_inc_dec { self.assigned_final_field(&unary.operand)? } else { None };
+        if let Some((name, _)) = final_field {
+            return Err(self.write_error(&unary.loc, format!("Cannot assign a value to final field '{}'", name)));
+        }
         let operand_type = self.infer_expr_type(&unary.operand)?;
         match unary.op {
-            UnaryOp::Neg => Ok(operand_type),
+            // 与二元运算一致，char/int8/int16 取正负时提升为 int
+            UnaryOp::Neg | UnaryOp::Plus => match operand_type {
+                Type::Char | Type::Int8 | Type::Int16 => Ok(Type::Int32),
+                Type::Int32 | Type::Int64 | Type::Float32 | Type::Float64 => Ok(operand_type),
+                other => Err(semantic_error(
+                    unary.loc.line,
+                    unary.loc.column,
+                    format!("Cannot apply unary '{}' to {}", if unary.op == UnaryOp::Neg { "-" } else { "+" }, other)
+                )),
+            },
             UnaryOp::Not => {
                 if operand_type == Type::Bool {
                     Ok(Type::Bool)
@@ -202,16 +270,69 @@ impl SemanticAnalyzer {
     fn infer_call_type(&mut self, call: &CallExpr) -> cayResult<Type> {
         // 特殊处理内置函数
         if let Expr::Identifier(name) = call.callee.as_ref() {
+            // 宿主程序注册的内置函数
+            if let Some(spec) = self.builtins.get(name).filter(|_| !self.current_class_has_method(name)).cloned() {
+                return self.check_custom_builtin_call(&spec, &call.args, call.loc.line, call.loc.column);
+            }
+
             // 内置输入函数的类型推断
+            if let Some(read_type) = super::type_utils::read_builtin_type(name) {
+                return self.check_read_call(name, read_type, &call.args, call.loc.line, call.loc.column);
+            }
+
             match name.as_str() {
-                "print" | "println" => return Ok(Type::Void),
-                "readInt" => return Ok(Type::Int32),
-                "readLong" => return Ok(Type::Int64),
-                "readFloat" => return Ok(Type::Float32),
-                "readDouble" => return Ok(Type::Float64),
-                "readLine" => return Ok(Type::String),
-                "readChar" => return Ok(Type::Char),
-                "readBool" => return Ok(Type::Bool),
+                "print" | "println" if call.args.len() == 2 && !self.current_class_has_method(name) => {
+                    // print(value, base) 按进制输出整数，print(value, precision) 按小数位输出浮点数
+                    self.check_print_format_args(name, &call.args, call.loc.line, call.loc.column)?;
+                    return Ok(Type::Void);
+                }
+                "print" | "println" => {
+                    for arg in &call.args {
+                        // 元组没有统一的输出格式，需要解构后逐个输出
+                        if let Some(tuple_type @ Type::Tuple(_)) = self.scan_unchecked_expr(arg)? {
+                            return Err(self.always_reported(semantic_error(
+                                call.loc.line,
+                                call.loc.column,
+                                format!("Cannot pass a tuple {} to {}(), destructure it and print its elements", tuple_type, name),
+                            )));
+                        }
+                    }
+                    return Ok(Type::Void);
+                }
+                "printf" if !self.current_class_has_method("printf") => {
+                    self.check_printf_args(&call.args, call.loc.line, call.loc.column)?;
+                    return Ok(Type::Void);
+                }
+                // 当前类自定义的 valueOf 方法优先于内置函数
+                "valueOf" if !self.current_class_has_method("valueOf") => {
+                    return self.infer_value_of_call(&call.args, call.loc.line, call.loc.column);
+                }
+                "addressOf" if !self.current_class_has_method("addressOf") => {
+                    return self.infer_address_of_call(&call.args, call.loc.line, call.loc.column);
+                }
+                "static_assert" if !self.current_class_has_method("static_assert") => {
+                    self.check_static_assert(call)?;
+                    return Ok(Type::Void);
+                }
+                "readFile" if !self.current_class_has_method("readFile") => {
+                    self.check_string_args(name, &["path"], &call.args, call.loc.line, call.loc.column)?;
+                    return Ok(Type::String);
+                }
+                "writeFile" if !self.current_class_has_method("writeFile") => {
+                    self.check_string_args(name, &["path", "content"], &call.args, call.loc.line, call.loc.column)?;
+                    return Ok(Type::Bool);
+                }
+                "arrayEquals" if !self.current_class_has_method("arrayEquals") => {
+                    self.check_array_equals_args(&call.args, call.loc.line, call.loc.column)?;
+                    return Ok(Type::Bool);
+                }
+                "toString" if !self.current_class_has_method("toString") => {
+                    self.check_radix_args(name, &call.args, call.loc.line, call.loc.column)?;
+                    return Ok(Type::String);
+                }
+                "floorMod" if !self.current_class_has_method("floorMod") => {
+                    return self.check_floor_mod_args(&call.args, call.loc.line, call.loc.column);
+                }
                 _ => {}
             }
 
@@ -224,27 +345,58 @@ impl SemanticAnalyzer {
                 }
 
                 // 使用参数类型查找匹配的方法
+                self.check_ambiguous_call(current_class, name, &arg_types, call.loc.line, call.loc.column)?;
                 if let Some(method_info) = self.type_registry.find_method(current_class, name, &arg_types) {
+                    if self.current_method_is_static && !method_info.is_static {
+                        // 静态方法中没有隐式的 this，不能直接调用实例方法
+                        return Err(semantic_error(
+                            call.loc.line,
+                            call.loc.column,
+                            format!("non-static method {} cannot be referenced from a static context", method_info.signature())
+                        ));
+                    }
                     let return_type = method_info.return_type.clone();
                     let params = method_info.params.clone();
+                    let since_version = method_info.since_version.clone();
                     // 检查参数类型兼容性（支持可变参数）
                     if let Err(msg) = self.check_arguments_compatible(&call.args, &params, call.loc.line, call.loc.column) {
                         return Err(semantic_error(call.loc.line, call.loc.column, msg));
                     }
+                    self.record_list_arguments(current_class, name, &call.args, &params);
 
+                    self.check_since_version(name, since_version.as_deref(), call.loc.line, call.loc.column);
                     return Ok(return_type);
                 }
             }
         }
 
+        // String.valueOf(...) 内置静态方法
+        if matches!(call.callee.as_ref(), Expr::MemberAccess(member)
+            if member.member == "valueOf" && matches!(member.object.as_ref(), Expr::Identifier(name) if name == "String"))
+        {
+            return self.infer_value_of_call(&call.args, call.loc.line, call.loc.column);
+        }
+
         // 支持成员调用: obj.method(...) 或 ClassName.method()（静态方法）
         if let Expr::MemberAccess(member) = call.callee.as_ref() {
             // 推断对象类型
-            let obj_type = self.infer_expr_type(&member.object)?;
+            let obj_type = self.infer_object_type(&member.object)?;
 
-            // 处理 String 类型方法调用
-            if obj_type == Type::String {
-                return self.infer_string_method_call(&member.member, &call.args, call.loc.line, call.loc.column);
+            // 内置 List 的 add/get/size
+            if Self::is_list_type(&obj_type) {
+                if matches!(member.object.as_ref(), Expr::Identifier(name) if name == LIST_CLASS && self.symbol_table.lookup(name).is_none()) {
+                    return Err(semantic_error(call.loc.line, call.loc.column,
+                        format!("List.{}() must be called on a List instance", member.member)));
+                }
+                return self.infer_list_call(member, call);
+            }
+
+            // arr.clone() - 数组浅拷贝，返回同类型的新数组
+            if matches!(obj_type, Type::Array(_)) && member.member == "clone" {
+                if !call.args.is_empty() {
+                    return Err(semantic_error(call.loc.line, call.loc.column, format!("clone() takes no arguments, got {}", call.args.len())));
+                }
+                return Ok(obj_type);
             }
 
             // 检查是否是类名（静态方法调用）- 支持方法重载
@@ -256,17 +408,21 @@ impl SemanticAnalyzer {
                     arg_types.push(self.infer_expr_type(arg)?);
                 }
 
+                self.check_ambiguous_call(&class_name, &member.member, &arg_types, call.loc.line, call.loc.column)?;
                 if let Some(class_info) = self.type_registry.get_class(&class_name) {
                     // 使用参数类型查找匹配的静态方法
                     if let Some(method_info) = class_info.find_method(&member.member, &arg_types) {
                         if method_info.is_static {
                             let return_type = method_info.return_type.clone();
                             let params = method_info.params.clone();
+                            let since_version = method_info.since_version.clone();
                             // 检查参数类型兼容性（支持可变参数）
                             if let Err(msg) = self.check_arguments_compatible(&call.args, &params, call.loc.line, call.loc.column) {
                                 return Err(semantic_error(call.loc.line, call.loc.column, msg));
                             }
+                            self.record_list_arguments(&class_name, &member.member, &call.args, &params);
 
+                            self.check_since_version(&member.member, since_version.as_deref(), call.loc.line, call.loc.column);
                             return Ok(return_type);
                         }
                     }
@@ -274,7 +430,31 @@ impl SemanticAnalyzer {
             }
 
             // 处理类实例方法调用 - 支持方法重载
-            if let Type::Object(class_name) = obj_type {
+            // String / StringView 的内置方法登记在类型注册表中，与类实例方法走同一查找路径
+            let instance_class = match &obj_type {
+                Type::Object(class_name) => Some(class_name.clone()),
+                Type::String => Some("String".to_string()),
+                Type::StringView => Some("StringView".to_string()),
+                _ => None,
+            };
+            if let Some(class_name) = instance_class {
+                // obj.is(other) - 所有对象都支持的引用同一性比较（类自定义 is 方法时优先）
+                if member.member == "is" && matches!(obj_type, Type::Object(_))
+                    && self.type_registry.get_method(&class_name, "is").is_none() {
+                    if call.args.len() != 1 {
+                        return Err(semantic_error(call.loc.line, call.loc.column, "is() takes 1 argument".to_string()));
+                    }
+                    let arg_type = self.infer_expr_type(&call.args[0])?;
+                    if !arg_type.is_reference_type() {
+                        return Err(semantic_error(
+                            call.loc.line,
+                            call.loc.column,
+                            format!("Argument of is() must be a reference type, got {}", arg_type)
+                        ));
+                    }
+                    return Ok(Type::Bool);
+                }
+
                 // 先推断所有参数类型
                 let mut arg_types = Vec::new();
                 for arg in &call.args {
@@ -282,15 +462,22 @@ impl SemanticAnalyzer {
                 }
 
                 // 使用参数类型查找匹配的方法
+                self.check_ambiguous_call(&class_name, &member.member, &arg_types, call.loc.line, call.loc.column)?;
                 if let Some(method_info) = self.type_registry.find_method(&class_name, &member.member, &arg_types) {
                     let return_type = method_info.return_type.clone();
                     let params = method_info.params.clone();
+                    let since_version = method_info.since_version.clone();
                     // 检查参数类型兼容性（支持可变参数）
                     if let Err(msg) = self.check_arguments_compatible(&call.args, &params, call.loc.line, call.loc.column) {
                         return Err(semantic_error(call.loc.line, call.loc.column, msg));
                     }
+                    self.record_list_arguments(&class_name, &member.member, &call.args, &params);
 
+                    self.check_since_version(&member.member, since_version.as_deref(), call.loc.line, call.loc.column);
                     return Ok(return_type);
+                } else if self.type_registry.get_method(&class_name, &member.member).is_some() {
+                    let message = self.argument_mismatch_message(&class_name, &member.member, &call.args)?;
+                    return Err(semantic_error(call.loc.line, call.loc.column, message));
                 } else {
                     return Err(semantic_error(
                         call.loc.line,
@@ -306,14 +493,10 @@ impl SemanticAnalyzer {
         if let Expr::Identifier(name) = call.callee.as_ref() {
             if let Some(ref current_class) = self.current_class {
                 // 检查是否存在同名方法（参数不匹配）
-                if let Some(class_info) = self.type_registry.get_class(current_class) {
-                    if class_info.methods.contains_key(name) {
-                        return Err(semantic_error(
-                            call.loc.line,
-                            call.loc.column,
-                            format!("Method '{}' in class '{}' cannot be applied to given types: argument mismatch", name, current_class)
-                        ));
-                    }
+                if self.type_registry.get_class(current_class).is_some_and(|c| c.methods.contains_key(name)) {
+                    let current_class = current_class.clone();
+                    let message = self.argument_mismatch_message(&current_class, name, &call.args)?;
+                    return Err(semantic_error(call.loc.line, call.loc.column, message));
                 }
             }
             return Err(semantic_error(
@@ -325,18 +508,12 @@ impl SemanticAnalyzer {
 
         if let Expr::MemberAccess(member) = call.callee.as_ref() {
             if let Expr::Identifier(class_name) = &*member.object {
-                return Err(semantic_error(
-                    call.loc.line,
-                    call.loc.column,
-                    format!("Method '{}' in class '{}' cannot be applied to given types: argument mismatch", member.member, class_name)
-                ));
+                let message = self.argument_mismatch_message(class_name, &member.member, &call.args)?;
+                return Err(semantic_error(call.loc.line, call.loc.column, message));
             }
-            if let Type::Object(class_name) = self.infer_expr_type(&member.object)? {
-                return Err(semantic_error(
-                    call.loc.line,
-                    call.loc.column,
-                    format!("Method '{}' in class '{}' cannot be applied to given types: argument mismatch", member.member, class_name)
-                ));
+            if let Type::Object(class_name) = self.infer_object_type(&member.object)? {
+                let message = self.argument_mismatch_message(&class_name, &member.member, &call.args)?;
+                return Err(semantic_error(call.loc.line, call.loc.column, message));
             }
         }
 
@@ -379,7 +556,7 @@ impl SemanticAnalyzer {
         }
 
         // 成员访问类型检查
-        let obj_type = self.infer_expr_type(&member.object)?;
+        let obj_type = self.infer_object_type(&member.object)?;
 
         // 特殊处理数组的 .length 属性
         if member.member == "length" {
@@ -412,42 +589,33 @@ impl SemanticAnalyzer {
 
         // 类成员访问
         if let Type::Object(class_name) = obj_type {
-            if let Some(class_info) = self.type_registry.get_class(&class_name) {
-                if let Some(field_info) = class_info.fields.get(&member.member) {
-                    // 检查静态方法中是否访问非静态字段
-                    if self.current_method_is_static && !field_info.is_static {
-                        // 检查是否是当前类的实例字段
-                        if let Some(current_class) = &self.current_class {
-                            if current_class == &class_name {
-                                return Err(semantic_error(
-                                    member.loc.line,
-                                    member.loc.column,
-                                    format!("non-static variable {} cannot be referenced from a static context", member.member)
-                                ));
-                            }
-                        }
-                    }
-                    
-                    // 检查私有字段访问权限
-                    if !field_info.is_public {
-                        if let Some(current_class) = &self.current_class {
-                            if current_class != &class_name {
-                                return Err(semantic_error(
-                                    member.loc.line,
-                                    member.loc.column,
-                                    format!("{} has private access in {}", member.member, class_name)
-                                ));
-                            }
-                        } else {
+            if let Some(field_info) = self.type_registry.get_field(&class_name, &member.member) {
+                // 检查静态方法中是否访问非静态字段
+                if self.current_method_is_static && !field_info.is_static {
+                    // 检查是否是当前类的实例字段
+                    if let Some(current_class) = &self.current_class {
+                        if current_class == &class_name {
                             return Err(semantic_error(
                                 member.loc.line,
                                 member.loc.column,
-                                format!("{} has private access in {}", member.member, class_name)
+                                format!("non-static variable {} cannot be referenced from a static context", member.member)
                             ));
                         }
                     }
-                    return Ok(field_info.field_type.clone());
                 }
+                
+                // 检查私有字段访问权限：按声明字段的类判断，子类不能访问父类的私有字段
+                if !field_info.is_public {
+                    let owner = self.type_registry.field_owner(&class_name, &member.member).unwrap_or(&class_name);
+                    if self.current_class.as_deref() != Some(owner) {
+                        return Err(semantic_error(
+                            member.loc.line,
+                            member.loc.column,
+                            format!("{} has private access in {}", member.member, owner)
+                        ));
+                    }
+                }
+                return Ok(field_info.field_type.clone());
             }
             return Err(semantic_error(
                 member.loc.line,
@@ -465,6 +633,10 @@ impl SemanticAnalyzer {
 
     /// 推断 new 表达式类型
     fn infer_new_type(&mut self, new_expr: &NewExpr) -> cayResult<Type> {
+        if new_expr.class_name == LIST_CLASS && !new_expr.args.is_empty() {
+            return Err(semantic_error(new_expr.loc.line, new_expr.loc.column,
+                format!("List() takes no arguments, got {}", new_expr.args.len())));
+        }
         if self.type_registry.class_exists(&new_expr.class_name) {
             Ok(Type::Object(new_expr.class_name.clone()))
         } else {
@@ -491,20 +663,140 @@ impl SemanticAnalyzer {
             }
         }
 
+        // const 常量不能重新赋值
+        if let Some(name) = self.assigned_constant(&assign.target) {
+            return Err(self.write_error(&assign.loc, format!("Cannot assign a value to constant '{}'", name)));
+        }
+
+        // --immutable-final-arrays 下 final 数组的元素也不能修改
+        if let Some(name) = self.mutated_final_array(&assign.target) {
+            return Err(self.write_error(&assign.loc, format!("Cannot modify an element of final array '{}'", name)));
+        }
+
+        // final 实例字段只能在构造函数中通过 this 赋值一次，不能在循环中赋值
+        if let Some((name, is_own)) = self.assigned_final_field(&assign.target)? {
+            if !(self.current_method_is_constructor && is_own) {
+                return Err(self.write_error(&assign.loc, format!("Cannot assign a value to final field '{}'", name)));
+            }
+            if self.loop_depth > 0 {
+                return Err(self.write_error(&assign.loc, format!("Cannot assign final field '{}' inside a loop", name)));
+            }
+            if self.assigned_final_fields.contains(&name) {
+                return Err(self.write_error(&assign.loc, format!("Final field '{}' has already been assigned", name)));
+            }
+            self.assigned_final_fields.push(name);
+        }
+
         let target_type = self.infer_expr_type(&assign.target)?;
         let value_type = self.infer_expr_type(&assign.value)?;
+        self.check_float_precision(&assign.value, &target_type, assign.loc.line, assign.loc.column);
 
-        if self.types_compatible(&value_type, &target_type) {
+        if self.is_assignable(&assign.value, &value_type, &target_type) {
+            if Self::is_list_type(&target_type) {
+                self.assign_list_variable(&assign.target, &assign.value);
+            }
             Ok(target_type)
         } else {
             Err(semantic_error(
                 assign.loc.line,
                 assign.loc.column,
-                format!("Cannot assign {} to {}", value_type, target_type)
+                format!("Cannot assign {} to {}{}", value_type, target_type,
+                    self.read_mismatch_hint(&assign.value, &target_type))
             ))
         }
     }
 
+    /// 非法写入的错误：在静默扫描的分支和循环体中同样报告
+    fn write_error(&mut self, loc: &SourceLocation, message: String) -> cayError {
+        self.always_reported(semantic_error(loc.line, loc.column, message))
+    }
+
+    /// 记录在静默扫描中也要报告的错误
+    fn always_reported(&mut self, error: cayError) -> cayError {
+        if self.in_unchecked_scan && self.unchecked_write_error.is_none() {
+            self.unchecked_write_error = Some(error.clone());
+        }
+        error
+    }
+
+    /// 赋值目标是 final 实例字段时，返回字段名以及它是否属于当前对象（`f` 或 `this.f`）
+    fn assigned_final_field(&mut self, target: &Expr) -> cayResult<Option<(String, bool)>> {
+        let (class_name, field_name, is_own) = match target {
+            // 局部变量和参数优先于隐式 this 的字段
+            Expr::Identifier(name) if self.symbol_table.lookup(name).is_none() => {
+                match self.current_class.clone() {
+                    Some(class_name) => (class_name, name, true),
+                    None => return Ok(None),
+                }
+            }
+            Expr::MemberAccess(member) => {
+                let is_this = matches!(member.object.as_ref(), Expr::Identifier(owner) if owner == "this");
+                // 类名访问的是静态字段，不在此检查
+                if matches!(member.object.as_ref(), Expr::Identifier(owner) if self.symbol_table.lookup(owner).is_none()) {
+                    return Ok(None);
+                }
+                match self.infer_expr_type(&member.object)? {
+                    Type::Object(class_name) => (class_name, &member.member, is_this),
+                    _ => return Ok(None),
+                }
+            }
+            _ => return Ok(None),
+        };
+        Ok(self.type_registry.get_field(&class_name, field_name)
+            .filter(|field| field.is_final && !field.is_static)
+            .map(|_| (field_name.clone(), is_own)))
+    }
+
+    /// 赋值目标是 final 数组的元素时（如 `arr[i]`、`this.table[i][j]`），返回该数组的名字
+    ///
+    /// 仅在启用 --immutable-final-arrays 时检查；final 局部变量、参数和字段都视为不可修改的数组。
+    fn mutated_final_array(&self, target: &Expr) -> Option<String> {
+        if !self.immutable_final_arrays {
+            return None;
+        }
+        let Expr::ArrayAccess(outer) = target else {
+            return None;
+        };
+        let mut access = outer;
+        while let Expr::ArrayAccess(inner) = access.array.as_ref() {
+            access = inner;
+        }
+
+        let is_final_array = |ty: &Type, is_final: bool| is_final && matches!(ty, Type::Array(_));
+        match access.array.as_ref() {
+            Expr::Identifier(name) => {
+                let is_final = match self.symbol_table.lookup(name) {
+                    Some(info) => is_final_array(&info.symbol_type, info.is_final),
+                    // 隐式 this 的数组字段
+                    None => self.current_class.as_ref()
+                        .and_then(|class_name| self.type_registry.get_field(class_name, name))
+                        .is_some_and(|field| is_final_array(&field.field_type, field.is_final)),
+                };
+                is_final.then(|| name.clone())
+            }
+            Expr::MemberAccess(member) => {
+                let Expr::Identifier(owner) = member.object.as_ref() else {
+                    return None;
+                };
+                let class_name = if owner == "this" {
+                    self.current_class.clone()?
+                } else {
+                    match self.symbol_table.lookup(owner) {
+                        Some(info) => match &info.symbol_type {
+                            Type::Object(class_name) => class_name.clone(),
+                            _ => return None,
+                        },
+                        None => owner.clone(),
+                    }
+                };
+                let field = self.type_registry.get_field(&class_name, &member.member)?;
+                is_final_array(&field.field_type, field.is_final)
+                    .then(|| format!("{}.{}", owner, member.member))
+            }
+            _ => None,
+        }
+    }
+
     /// 推断类型转换表达式类型
     fn infer_cast_type(&mut self, cast: &CastExpr) -> cayResult<Type> {
         // TODO: 检查转换是否合法
@@ -593,7 +885,16 @@ impl SemanticAnalyzer {
         // 方法引用: ClassName::methodName 或 obj::methodName
         // 返回函数类型（这里简化为 Object 类型，实际应该返回函数类型）
         // TODO: 实现完整的函数类型系统
-        if let Some(ref class_name) = method_ref.class_name {
+        let class_name = match (&method_ref.class_name, &method_ref.object) {
+            // 作用域中的变量优先于同名的类：obj::methodName 引用对象所属类的方法
+            (Some(name), _) if self.symbol_table.lookup(name).is_some() => {
+                Some(self.method_ref_object_class(&Expr::Identifier(name.clone()), method_ref)?)
+            }
+            (Some(name), _) => Some(name.clone()),
+            (None, Some(object)) => Some(self.method_ref_object_class(object, method_ref)?),
+            (None, None) => None,
+        };
+        if let Some(ref class_name) = class_name {
             // 检查类是否存在
             if !self.type_registry.class_exists(class_name) {
                 return Err(semantic_error(
@@ -617,6 +918,18 @@ impl SemanticAnalyzer {
         Ok(Type::Object("Function".to_string()))
     }
 
+    /// obj::methodName 中对象所属的类
+    fn method_ref_object_class(&mut self, object: &Expr, method_ref: &MethodRefExpr) -> cayResult<String> {
+        match self.infer_expr_type(object)? {
+            Type::Object(class_name) => Ok(class_name),
+            other => Err(semantic_error(
+                method_ref.loc.line,
+                method_ref.loc.column,
+                format!("Cannot reference method '{}' on a value of type {}", method_ref.method_name, other)
+            )),
+        }
+    }
+
     /// 推断 Lambda 表达式类型
     fn infer_lambda_type(&mut self, lambda: &LambdaExpr) -> cayResult<Type> {
         // Lambda 表达式: (params) -> { body }
@@ -724,6 +1037,6 @@ impl SemanticAnalyzer {
 
     /// 辅助方法：检查类型是否为数值类型
     fn is_numeric_type_helper(ty: &Type) -> bool {
-        matches!(ty, Type::Int32 | Type::Int64 | Type::Float32 | Type::Float64 | Type::Char)
+        matches!(ty, Type::Int8 | Type::Int16 | Type::Int32 | Type::Int64 | Type::Float32 | Type::Float64 | Type::Char)
     }
 }
@@ -0,0 +1,82 @@
+//! printf 格式字符串解析
+//!
+//! 语义分析用它检查转换说明与实参的数量和类型，
+//! 代码生成用它按实参的实际宽度改写长度修饰符。
+
+/// 格式字符串中的一个转换说明（如 `%-8.3f`）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatSpec {
+    /// `%` 所在的字节位置
+    pub start: usize,
+    /// 长度修饰符（如 `l`、`ll`）开始的字节位置，没有修饰符时等于 `conversion_pos`
+    pub length_pos: usize,
+    /// 转换字符所在的字节位置
+    pub conversion_pos: usize,
+    /// 转换字符
+    pub conversion: char,
+    /// 宽度和精度中 `*` 的个数，每个 `*` 额外消耗一个 int 实参
+    pub star_count: usize,
+}
+
+impl FormatSpec {
+    /// 是否是整数转换（d、i、o、u、x、X）
+    pub fn is_integer(&self) -> bool {
+        matches!(self.conversion, 'd' | 'i' | 'o' | 'u' | 'x' | 'X')
+    }
+
+    /// 是否是浮点转换（f、e、g、a 及其大写形式）
+    pub fn is_float(&self) -> bool {
+        matches!(self.conversion, 'f' | 'F' | 'e' | 'E' | 'g' | 'G' | 'a' | 'A')
+    }
+}
+
+/// 解析格式字符串中的全部转换说明，`%%` 不算转换说明
+pub fn parse_format_specs(format: &str) -> Result<Vec<FormatSpec>, String> {
+    let bytes = format.as_bytes();
+    let mut specs = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] != b'%' {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        i += 1;
+        if bytes.get(i) == Some(&b'%') {
+            i += 1;
+            continue;
+        }
+
+        let mut star_count = 0;
+        while i < bytes.len() && b"-+ #0".contains(&bytes[i]) {
+            i += 1;
+        }
+        // 宽度和精度：数字或 *
+        let mut in_precision = false;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'0'..=b'9' => {}
+                b'*' => star_count += 1,
+                b'.' if !in_precision => in_precision = true,
+                _ => break,
+            }
+            i += 1;
+        }
+        let length_pos = i;
+        while i < bytes.len() && b"hlLqjzt".contains(&bytes[i]) {
+            i += 1;
+        }
+
+        match bytes.get(i).map(|&b| b as char) {
+            Some(c) if "diouxXfFeEgGaAcsp".contains(c) => {
+                specs.push(FormatSpec { start, length_pos, conversion_pos: i, conversion: c, star_count });
+                i += 1;
+            }
+            Some(c) => return Err(format!("Unsupported conversion '%{}' at position {} of format string", c, start)),
+            None => return Err(format!("Incomplete conversion at position {} of format string", start)),
+        }
+    }
+
+    Ok(specs)
+}
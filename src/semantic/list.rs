@@ -0,0 +1,366 @@
+//! 内置 List 的语义检查
+//!
+//! List 是没有泛型参数的动态数组：元素类型由第一次 `add` 的值确定，
+//! 之后的 `add` 必须与之兼容，`get` 返回该类型。
+//! 元素类型按 List 所在的局部变量/参数（每个方法重新开始）或字段记录，
+//! 并沿实参和返回值传给被调用方法的 List 参数和调用者。
+//! 每个 add/get 调用处的元素类型记录到类型注册表，代码生成据此转换元素值。
+//! 元素类型尚未确定时 `get` 按 Object 处理（与 null 一样可以赋给任何类型）。
+
+use crate::ast::*;
+use crate::types::{Type, ParameterInfo, TypeRegistry, LIST_CLASS};
+use crate::error::{cayResult, semantic_error, WarningKind};
+use super::analyzer::SemanticAnalyzer;
+
+/// 记录元素类型的位置
+enum ListKey {
+    /// 局部变量或参数
+    Local(String),
+    /// 字段，"类名.字段名"
+    Field(String),
+    /// 方法返回值，"类名.方法名()"；只能读取
+    Return(String),
+}
+
+impl SemanticAnalyzer {
+    /// 类型是否是内置 List
+    pub(super) fn is_list_type(ty: &Type) -> bool {
+        matches!(ty, Type::Object(name) if name == LIST_CLASS)
+    }
+
+    /// 静默检查所有方法，收集沿参数和返回值传递的 List 元素类型
+    ///
+    /// 调用者可能先于被调用的方法检查，此时还不知道参数和返回值的元素类型。
+    /// 程序中有以 List 为参数或返回值的方法时，先逐个方法静默检查一遍，
+    /// 出错的方法不影响其他方法的收集。
+    pub(super) fn collect_list_signatures(&mut self, program: &Program) {
+        let passes_lists = program.classes.iter().flat_map(|class| &class.members).any(|member| match member {
+            ClassMember::Method(method) => Self::is_list_type(&method.return_type)
+                || method.params.iter().any(|p| Self::is_list_type(&p.param_type)),
+            _ => false,
+        });
+        if !passes_lists {
+            return;
+        }
+
+        let previous_class = self.current_class.take();
+        for class in &program.classes {
+            self.current_class = Some(class.name.clone());
+            for member in &class.members {
+                if let ClassMember::Method(method) = member {
+                    self.silently(|analyzer| analyzer.type_check_method(method));
+                }
+            }
+        }
+        self.current_class = previous_class;
+    }
+
+    /// 推断 List 方法调用（add/get/size）的类型
+    pub(super) fn infer_list_call(&mut self, member: &MemberAccessExpr, call: &CallExpr) -> cayResult<Type> {
+        let (line, column) = (call.loc.line, call.loc.column);
+        let expect_args = |count: usize| {
+            if call.args.len() == count {
+                Ok(())
+            } else {
+                Err(semantic_error(line, column, format!(
+                    "List.{}() takes {} argument{}, got {}",
+                    member.member, count, if count == 1 { "" } else { "s" }, call.args.len()
+                )))
+            }
+        };
+
+        let key = self.list_key(&member.object);
+        match member.member.as_str() {
+            "size" => {
+                expect_args(0)?;
+                Ok(Type::Int32)
+            }
+            "add" => {
+                expect_args(1)?;
+                let value = &call.args[0];
+                let value_type = self.infer_expr_type(value)?;
+                match key.as_ref().and_then(|k| self.list_element_type(k)) {
+                    Some(element) => {
+                        if !self.is_assignable(value, &value_type, &element) {
+                            return Err(semantic_error(line, column, format!(
+                                "Cannot add {} to a List of {}", value_type, element
+                            )));
+                        }
+                        self.type_registry.record_list_call_element(&call.loc, element);
+                    }
+                    None => {
+                        if matches!(value, Expr::Literal(LiteralValue::Null)) || value_type == Type::Void {
+                            return Err(semantic_error(line, column,
+                                "Cannot infer the List element type from this value".to_string()));
+                        }
+                        if let Some(key) = &key {
+                            self.set_list_element_type(key, value_type.clone());
+                        }
+                        self.type_registry.record_list_call_element(&call.loc, value_type);
+                    }
+                }
+                Ok(Type::Void)
+            }
+            "get" => {
+                expect_args(1)?;
+                let index_type = self.infer_expr_type(&call.args[0])?;
+                if !index_type.is_integer() {
+                    return Err(semantic_error(line, column, format!(
+                        "List index must be an integer, got {}", index_type
+                    )));
+                }
+                match key.as_ref().and_then(|k| self.list_element_type(k)) {
+                    Some(element) => {
+                        self.type_registry.record_list_call_element(&call.loc, element.clone());
+                        Ok(element)
+                    }
+                    None => Ok(Type::Object("Object".to_string())),
+                }
+            }
+            other => Err(semantic_error(line, column, format!("Unknown method '{}' for class List", other))),
+        }
+    }
+
+    /// 声明 List 局部变量：清除同名变量之前的元素类型，初始化为另一个 List 时沿用其元素类型
+    pub(super) fn declare_list_variable(&mut self, name: &str, initializer: Option<&Expr>) {
+        self.local_list_elements.remove(name);
+        if let Some(element) = initializer.and_then(|init| self.list_key(init)).and_then(|k| self.list_element_type(&k)) {
+            self.local_list_elements.insert(name.to_string(), element);
+        }
+    }
+
+    /// 给 List 变量或字段赋值：目标沿用值的元素类型，值是新的 List 时元素类型重新确定
+    pub(super) fn assign_list_variable(&mut self, target: &Expr, value: &Expr) {
+        let Some(target) = self.list_key(target) else { return };
+        match self.list_key(value).and_then(|k| self.list_element_type(&k)) {
+            Some(element) => self.set_list_element_type(&target, element),
+            None => self.clear_list_element_type(&target),
+        }
+    }
+
+    /// 进入方法时，List 参数沿用调用处实参的元素类型
+    pub(super) fn declare_list_params(&mut self, params: &[ParameterInfo]) {
+        let (Some(class_name), Some(method_name)) = (&self.current_class, &self.current_method) else { return };
+        for param in params.iter().filter(|p| Self::is_list_type(&p.param_type)) {
+            let key = TypeRegistry::list_param_key(class_name, method_name, &param.name);
+            if let Some(element) = self.type_registry.list_slot_element(&key).cloned() {
+                self.local_list_elements.insert(param.name.clone(), element);
+            }
+        }
+    }
+
+    /// 调用方法时，把 List 实参的元素类型记录给对应的 List 参数
+    pub(super) fn record_list_arguments(&mut self, class_name: &str, method_name: &str, args: &[Expr], params: &[ParameterInfo]) {
+        for (arg, param) in args.iter().zip(params) {
+            if !Self::is_list_type(&param.param_type) {
+                continue;
+            }
+            if let Some(element) = self.list_key(arg).and_then(|k| self.list_element_type(&k)) {
+                let key = TypeRegistry::list_param_key(class_name, method_name, &param.name);
+                self.type_registry.record_list_slot_element(key, element);
+            }
+        }
+    }
+
+    /// 返回 List 时记录其元素类型，调用者由此得知返回的 List 的元素类型
+    pub(super) fn record_list_return(&mut self, value: &Expr) {
+        let (Some(class_name), Some(method_name)) = (self.current_class.clone(), self.current_method.clone()) else { return };
+        if let Some(element) = self.list_key(value).and_then(|k| self.list_element_type(&k)) {
+            self.type_registry.record_list_slot_element(TypeRegistry::list_return_key(&class_name, &method_name), element);
+        }
+    }
+
+    /// 在不做类型检查的控制流语句中静默推断一遍，记录其中 List add/get 的元素类型
+    ///
+    /// 这些语句的类型错误和一般警告仍不报告，对 final 字段、const 常量和 final 数组元素的非法写入
+    /// 以及 @SinceVersion 版本警告与直接位于方法体中时一样报告。
+    pub(super) fn scan_unchecked_statement(&mut self, stmt: &Stmt, expected_return: Option<&Type>) -> cayResult<()> {
+        self.scan_reporting_writes(|analyzer| analyzer.scan_statement(stmt, expected_return))
+    }
+
+    /// 静默推断不做类型检查的表达式（如 println 的实参），记录其中 List 调用的元素类型并报告版本警告
+    ///
+    /// 推断成功时返回表达式的类型。
+    pub(super) fn scan_unchecked_expr(&mut self, expr: &Expr) -> cayResult<Option<Type>> {
+        let mut expr_type = None;
+        self.scan_reporting_writes(|analyzer| {
+            expr_type = Some(analyzer.infer_expr_type(expr)?);
+            Ok(())
+        })?;
+        Ok(expr_type)
+    }
+
+    /// 静默扫描，返回扫描中遇到的第一个非法写入错误
+    ///
+    /// 嵌套的扫描把错误留给最外层的扫描返回。
+    fn scan_reporting_writes(&mut self, scan: impl FnOnce(&mut Self) -> cayResult<()>) -> cayResult<()> {
+        let was_scanning = std::mem::replace(&mut self.in_unchecked_scan, true);
+        self.silently(scan);
+        self.in_unchecked_scan = was_scanning;
+        match self.unchecked_write_error.take() {
+            Some(error) if !was_scanning => Err(error),
+            pending => {
+                self.unchecked_write_error = pending;
+                Ok(())
+            }
+        }
+    }
+
+    /// 执行推断并丢弃其间产生的错误、警告和作用域变化
+    ///
+    /// 扫描不做类型检查的语句时保留版本警告。
+    fn silently(&mut self, scan: impl FnOnce(&mut Self) -> cayResult<()>) {
+        let (errors, warnings) = (self.errors.len(), self.warnings.len());
+        let (depth, loop_depth) = (self.symbol_table.depth(), self.loop_depth);
+        let _ = scan(self);
+        self.symbol_table.exit_to(depth);
+        self.loop_depth = loop_depth;
+        self.errors.truncate(errors);
+        let in_unchecked_scan = self.in_unchecked_scan;
+        let kept: Vec<_> = self.warnings.split_off(warnings).into_iter()
+            .filter(|warning| in_unchecked_scan && warning.kind == WarningKind::Version)
+            .collect();
+        self.warnings.extend(kept);
+    }
+
+    fn scan_statement(&mut self, stmt: &Stmt, expected_return: Option<&Type>) -> cayResult<()> {
+        match stmt {
+            Stmt::If(if_stmt) => {
+                self.infer_expr_type(&if_stmt.condition)?;
+                let before = self.assigned_final_fields.clone();
+                self.scan_statement(&if_stmt.then_branch, expected_return)?;
+                if let Some(else_branch) = &if_stmt.else_branch {
+                    // 两个分支各自从 if 之前的状态开始；之后任一分支赋过值的 final 字段都不能再赋值
+                    let after_then = std::mem::replace(&mut self.assigned_final_fields, before);
+                    self.scan_statement(else_branch, expected_return)?;
+                    merge_fields(&mut self.assigned_final_fields, after_then);
+                }
+            }
+            Stmt::While(while_stmt) => {
+                self.infer_expr_type(&while_stmt.condition)?;
+                self.loop_depth += 1;
+                self.scan_statement(&while_stmt.body, expected_return)?;
+                self.loop_depth -= 1;
+            }
+            Stmt::DoWhile(do_while) => {
+                self.loop_depth += 1;
+                self.scan_statement(&do_while.body, expected_return)?;
+                self.loop_depth -= 1;
+                self.infer_expr_type(&do_while.condition)?;
+            }
+            Stmt::For(for_stmt) => {
+                self.symbol_table.enter_scope();
+                if let Some(init) = &for_stmt.init {
+                    self.type_check_statement(init, expected_return)?;
+                }
+                if let Some(condition) = &for_stmt.condition {
+                    self.infer_expr_type(condition)?;
+                }
+                self.loop_depth += 1;
+                self.scan_statement(&for_stmt.body, expected_return)?;
+                if let Some(update) = &for_stmt.update {
+                    self.infer_expr_type(update)?;
+                }
+                self.loop_depth -= 1;
+                self.symbol_table.exit_scope();
+            }
+            Stmt::Switch(switch) => {
+                self.infer_expr_type(&switch.expr)?;
+                self.symbol_table.enter_scope();
+                let before = self.assigned_final_fields.clone();
+                let mut after = before.clone();
+                for body in switch.cases.iter().map(|c| &c.body).chain(switch.default.iter()) {
+                    self.assigned_final_fields = before.clone();
+                    for stmt in body {
+                        self.scan_statement(stmt, expected_return)?;
+                    }
+                    merge_fields(&mut after, std::mem::take(&mut self.assigned_final_fields));
+                }
+                self.assigned_final_fields = after;
+                self.symbol_table.exit_scope();
+            }
+            Stmt::Block(block) => {
+                // 一条语句推断失败不影响检查后面的语句
+                self.symbol_table.enter_scope();
+                let (depth, loop_depth) = (self.symbol_table.depth(), self.loop_depth);
+                for stmt in &block.statements {
+                    let _ = self.scan_statement(stmt, expected_return);
+                    self.symbol_table.exit_to(depth);
+                    self.loop_depth = loop_depth;
+                }
+                self.symbol_table.exit_scope();
+            }
+            Stmt::Break(_) | Stmt::Continue | Stmt::Empty => {}
+            other => self.type_check_statement(other, expected_return)?,
+        }
+        Ok(())
+    }
+
+    /// List 表达式对应的元素类型记录位置；临时 List（如 new List()）没有记录位置
+    fn list_key(&mut self, expr: &Expr) -> Option<ListKey> {
+        match expr {
+            Expr::Identifier(name) if self.symbol_table.lookup(name).is_some() => Some(ListKey::Local(name.clone())),
+            Expr::Identifier(name) => {
+                let class_name = self.current_class.clone()?;
+                self.type_registry.get_field(&class_name, name)?;
+                Some(ListKey::Field(format!("{}.{}", class_name, name)))
+            }
+            Expr::MemberAccess(member) => {
+                let Type::Object(class_name) = self.infer_expr_type(&member.object).ok()? else { return None };
+                Some(ListKey::Field(format!("{}.{}", class_name, member.member)))
+            }
+            Expr::Call(call) => {
+                let (class_name, method_name) = match call.callee.as_ref() {
+                    Expr::Identifier(name) => (self.current_class.clone()?, name.clone()),
+                    Expr::MemberAccess(member) => match (self.infer_expr_type(&member.object).ok(), member.object.as_ref()) {
+                        (Some(Type::Object(class_name)), _) => (class_name, member.member.clone()),
+                        // 静态方法调用
+                        (None, Expr::Identifier(class_name)) => (class_name.clone(), member.member.clone()),
+                        _ => return None,
+                    },
+                    _ => return None,
+                };
+                Some(ListKey::Return(TypeRegistry::list_return_key(&class_name, &method_name)))
+            }
+            _ => None,
+        }
+    }
+
+    fn list_element_type(&self, key: &ListKey) -> Option<Type> {
+        match key {
+            ListKey::Local(name) => self.local_list_elements.get(name),
+            ListKey::Field(name) => self.field_list_elements.get(name),
+            ListKey::Return(key) => self.type_registry.list_slot_element(key),
+        }
+        .cloned()
+    }
+
+    fn clear_list_element_type(&mut self, key: &ListKey) {
+        match key {
+            ListKey::Local(name) => self.local_list_elements.remove(name),
+            ListKey::Field(name) => self.field_list_elements.remove(name),
+            ListKey::Return(_) => None,
+        };
+    }
+
+    fn set_list_element_type(&mut self, key: &ListKey, element: Type) {
+        match key {
+            ListKey::Local(name) => self.local_list_elements.insert(name.clone(), element),
+            ListKey::Field(name) => {
+                // 字段的元素类型供其他方法和代码生成使用
+                self.type_registry.record_list_slot_element(name.clone(), element.clone());
+                self.field_list_elements.insert(name.clone(), element)
+            }
+            ListKey::Return(_) => None,
+        };
+    }
+}
+
+/// 把另一条控制流路径上赋过值的 final 字段并入 `into`
+fn merge_fields(into: &mut Vec<String>, other: Vec<String>) {
+    for name in other {
+        if !into.contains(&name) {
+            into.push(name);
+        }
+    }
+}
@@ -10,7 +10,13 @@ mod class_analysis;
 mod type_check;
 mod expr_inference;
 mod type_utils;
+mod constants;
+mod format;
+mod symbol_dump;
+mod list;
 
 // 公开导出
 pub use symbol_table::{SemanticSymbolTable, SemanticSymbolInfo};
 pub use analyzer::SemanticAnalyzer;
+pub use format::{FormatSpec, parse_format_specs};
+pub use symbol_dump::{SymbolDump, ClassSymbol, FieldSymbol, MethodSymbol};
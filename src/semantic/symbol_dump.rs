@@ -0,0 +1,230 @@
+//! 符号表导出
+//!
+//! 语义分析之后遍历 [`TypeRegistry`]，列出程序中每个类的字段、构造函数和方法，
+//! 供 `--dump-symbols` 模式以纯文本或 JSON 输出，用于生成文档。
+//! 类、字段和方法按源代码中的声明顺序排列。
+
+use std::fmt;
+
+use crate::ast::{ClassMember, Program};
+use crate::types::{ParameterInfo, Type, TypeRegistry};
+
+/// 字段的导出信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSymbol {
+    pub name: String,
+    pub field_type: String,
+    pub modifiers: Vec<&'static str>,
+}
+
+/// 方法或构造函数的导出信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodSymbol {
+    pub name: String,
+    /// 签名，如 `int add(int, int)`；构造函数没有返回类型，如 `Point(int, int)`
+    pub signature: String,
+    pub modifiers: Vec<&'static str>,
+}
+
+/// 类的导出信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassSymbol {
+    pub name: String,
+    pub parent: Option<String>,
+    pub interfaces: Vec<String>,
+    pub modifiers: Vec<&'static str>,
+    pub fields: Vec<FieldSymbol>,
+    pub constructors: Vec<MethodSymbol>,
+    pub methods: Vec<MethodSymbol>,
+}
+
+/// 整个程序的符号
+#[derive(Debug, Clone, Default)]
+pub struct SymbolDump {
+    pub classes: Vec<ClassSymbol>,
+}
+
+impl SymbolDump {
+    /// 按程序中的声明顺序，从类型注册表收集每个类的符号
+    pub fn collect(program: &Program, registry: &TypeRegistry) -> Self {
+        let classes = program.classes.iter()
+            .filter_map(|class| {
+                let info = registry.get_class(&class.name)?;
+                let mut symbol = ClassSymbol {
+                    name: info.name.clone(),
+                    parent: info.parent.clone(),
+                    interfaces: info.interfaces.clone(),
+                    modifiers: modifier_names(false, false, false, false, info.is_final, info.is_abstract),
+                    fields: Vec::new(),
+                    constructors: Vec::new(),
+                    methods: Vec::new(),
+                };
+                if class.modifiers.contains(&crate::ast::Modifier::Public) {
+                    symbol.modifiers.insert(0, "public");
+                }
+
+                for member in &class.members {
+                    match member {
+                        ClassMember::Field(field) => {
+                            if let Some(f) = info.fields.get(&field.name) {
+                                symbol.fields.push(FieldSymbol {
+                                    name: f.name.clone(),
+                                    field_type: f.field_type.to_string(),
+                                    modifiers: modifier_names(f.is_public, f.is_private, f.is_protected, f.is_static, f.is_final, false),
+                                });
+                            }
+                        }
+                        ClassMember::Method(method) => {
+                            let declared = info.methods.get(&method.name).and_then(|overloads| {
+                                overloads.iter().find(|m| same_params(&m.params, &method.params))
+                            });
+                            if let Some(m) = declared {
+                                let mut modifiers = modifier_names(m.is_public, m.is_private, m.is_protected, m.is_static, m.is_final, false);
+                                if m.is_native {
+                                    modifiers.push("native");
+                                }
+                                symbol.methods.push(MethodSymbol {
+                                    name: m.name.clone(),
+                                    signature: m.signature(),
+                                    modifiers,
+                                });
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+
+                for ctor in &info.constructors {
+                    symbol.constructors.push(MethodSymbol {
+                        name: info.name.clone(),
+                        signature: format!("{}({})", info.name, param_list(&ctor.params)),
+                        modifiers: modifier_names(ctor.is_public, ctor.is_private, ctor.is_protected, false, false, false),
+                    });
+                }
+
+                Some(symbol)
+            })
+            .collect();
+
+        Self { classes }
+    }
+
+    /// 按类名查找
+    pub fn get(&self, name: &str) -> Option<&ClassSymbol> {
+        self.classes.iter().find(|c| c.name == name)
+    }
+
+    /// 以 JSON 格式输出
+    pub fn to_json(&self) -> String {
+        let modifiers = |list: &[&str]| {
+            let items: Vec<String> = list.iter().map(|m| json_string(m)).collect();
+            format!("[{}]", items.join(", "))
+        };
+        let methods = |list: &[MethodSymbol]| {
+            let items: Vec<String> = list.iter().map(|m| format!(
+                "{{\"name\": {}, \"signature\": {}, \"modifiers\": {}}}",
+                json_string(&m.name), json_string(&m.signature), modifiers(&m.modifiers)
+            )).collect();
+            format!("[{}]", items.join(", "))
+        };
+
+        let classes: Vec<String> = self.classes.iter().map(|class| {
+            let fields: Vec<String> = class.fields.iter().map(|f| format!(
+                "{{\"name\": {}, \"type\": {}, \"modifiers\": {}}}",
+                json_string(&f.name), json_string(&f.field_type), modifiers(&f.modifiers)
+            )).collect();
+            let interfaces: Vec<String> = class.interfaces.iter().map(|i| json_string(i)).collect();
+            format!(
+                "    {{\"name\": {}, \"parent\": {}, \"interfaces\": [{}], \"modifiers\": {}, \"fields\": [{}], \"constructors\": {}, \"methods\": {}}}",
+                json_string(&class.name),
+                class.parent.as_deref().map_or("null".to_string(), json_string),
+                interfaces.join(", "),
+                modifiers(&class.modifiers),
+                fields.join(", "),
+                methods(&class.constructors),
+                methods(&class.methods),
+            )
+        }).collect();
+
+        if classes.is_empty() {
+            "{\"classes\": []}".to_string()
+        } else {
+            format!("{{\"classes\": [\n{}\n]}}", classes.join(",\n"))
+        }
+    }
+}
+
+impl fmt::Display for SymbolDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "符号表:")?;
+        for class in &self.classes {
+            write!(f, "  {}class {}", prefix(&class.modifiers), class.name)?;
+            if let Some(parent) = &class.parent {
+                write!(f, " extends {}", parent)?;
+            }
+            if !class.interfaces.is_empty() {
+                write!(f, " implements {}", class.interfaces.join(", "))?;
+            }
+            writeln!(f)?;
+            for field in &class.fields {
+                writeln!(f, "    field {}{} {}", prefix(&field.modifiers), field.field_type, field.name)?;
+            }
+            for ctor in &class.constructors {
+                writeln!(f, "    constructor {}{}", prefix(&ctor.modifiers), ctor.signature)?;
+            }
+            for method in &class.methods {
+                writeln!(f, "    method {}{}", prefix(&method.modifiers), method.signature)?;
+            }
+        }
+        write!(f, "  共 {} 个类", self.classes.len())
+    }
+}
+
+/// 修饰符按 Java 的惯用顺序排列
+fn modifier_names(is_public: bool, is_private: bool, is_protected: bool, is_static: bool, is_final: bool, is_abstract: bool) -> Vec<&'static str> {
+    [
+        (is_public, "public"),
+        (is_private, "private"),
+        (is_protected, "protected"),
+        (is_abstract, "abstract"),
+        (is_static, "static"),
+        (is_final, "final"),
+    ]
+    .into_iter()
+    .filter_map(|(set, name)| set.then_some(name))
+    .collect()
+}
+
+/// 修饰符列表后接一个空格，没有修饰符时为空
+fn prefix(modifiers: &[&str]) -> String {
+    modifiers.iter().map(|m| format!("{} ", m)).collect()
+}
+
+fn same_params(registered: &[ParameterInfo], declared: &[ParameterInfo]) -> bool {
+    registered.len() == declared.len()
+        && registered.iter().zip(declared).all(|(r, d)| r.param_type == d.param_type)
+}
+
+fn param_list(params: &[ParameterInfo]) -> String {
+    params.iter().map(|p| match (&p.param_type, p.is_varargs) {
+        (Type::Array(elem), true) => format!("{}...", elem),
+        (ty, _) => ty.to_string(),
+    }).collect::<Vec<_>>().join(", ")
+}
+
+/// JSON 字符串字面量
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
@@ -32,6 +32,16 @@ impl SemanticSymbolTable {
         }
     }
 
+    /// 当前作用域层数
+    pub fn depth(&self) -> usize {
+        self.scopes.len()
+    }
+
+    /// 退出作用域直到只剩 `depth` 层，用于出错提前返回后恢复作用域
+    pub fn exit_to(&mut self, depth: usize) {
+        self.scopes.truncate(depth.max(1));
+    }
+
     pub fn declare(&mut self, name: String, info: SemanticSymbolInfo) {
         if let Some(scope) = self.scopes.last_mut() {
             scope.insert(name, info);
@@ -2,7 +2,7 @@
 
 use crate::ast::*;
 use crate::types::{Type, ParameterInfo};
-use crate::error::{cayResult, semantic_error};
+use crate::error::{cayResult, semantic_error, SourceLocation, WarningKind};
 use super::analyzer::SemanticAnalyzer;
 use super::symbol_table::SemanticSymbolInfo;
 
@@ -11,54 +11,16 @@ impl SemanticAnalyzer {
     pub fn type_check_program(&mut self, program: &Program) -> cayResult<()> {
         for class in &program.classes {
             self.current_class = Some(class.name.clone());
+            self.check_final_fields_have_constructor(class)?;
             
             for member in &class.members {
                 match member {
-                    ClassMember::Method(method) => {
-                        self.current_method = Some(method.name.clone());
-                        self.current_method_is_static = method.modifiers.contains(&Modifier::Static);
-                        self.current_method_is_constructor = false;
-                        self.symbol_table.enter_scope();
-                        
-                        // 非静态方法需要添加 this
-                        if !self.current_method_is_static {
-                            if let Some(current_class) = &self.current_class {
-                                self.symbol_table.declare(
-                                    "this".to_string(),
-                                    SemanticSymbolInfo {
-                                        name: "this".to_string(),
-                                        symbol_type: Type::Object(current_class.clone()),
-                                        is_final: true,
-                                        is_initialized: true,
-                                    }
-                                );
-                            }
+                    ClassMember::Method(method) => self.type_check_method(method)?,
+                    ClassMember::Field(field) => {
+                        // 字段类型检查暂不实现，只检查初始化值的精度
+                        if let Some(init) = &field.initializer {
+                            self.check_float_precision(init, &field.field_type, field.loc.line, field.loc.column);
                         }
-                        
-                        // 添加参数到符号表
-                        for param in &method.params {
-                            self.symbol_table.declare(
-                                param.name.clone(),
-                                SemanticSymbolInfo {
-                                    name: param.name.clone(),
-                                    symbol_type: param.param_type.clone(),
-                                    is_final: false,
-                                    is_initialized: true,
-                                }
-                            );
-                        }
-                        
-                        // 类型检查方法体
-                        if let Some(body) = &method.body {
-                            self.type_check_statement(&Stmt::Block(body.clone()), Some(&method.return_type))?;
-                        }
-                        
-                        self.symbol_table.exit_scope();
-                        self.current_method = None;
-                        self.current_method_is_static = false;
-                    }
-                    ClassMember::Field(_) => {
-                        // 字段类型检查暂不实现
                     }
                     ClassMember::Constructor(ctor) => {
                         // 构造函数类型检查
@@ -78,6 +40,8 @@ impl SemanticAnalyzer {
                         );
                         
                         // 添加参数到符号表
+                        self.local_list_elements.clear();
+                        self.current_params = ctor.params.iter().map(|p| p.name.clone()).collect();
                         for param in &ctor.params {
                             self.symbol_table.declare(
                                 param.name.clone(),
@@ -90,10 +54,23 @@ impl SemanticAnalyzer {
                             );
                         }
                         
+                        // 带初始化值的 final 字段已经赋值；委托 this(...) 时由被调用的构造函数赋值
+                        let delegates = matches!(ctor.constructor_call, Some(ConstructorCall::This(_)));
+                        self.assigned_final_fields = final_instance_fields(class)
+                            .filter(|field| delegates || field.initializer.is_some())
+                            .map(|field| field.name.clone())
+                            .collect();
+
                         // 类型检查构造函数体
                         self.type_check_statement(&Stmt::Block(ctor.body.clone()), Some(&Type::Void))?;
+
+                        if !delegates {
+                            self.check_final_fields_assigned(class, ctor)?;
+                        }
                         
                         self.symbol_table.exit_scope();
+                        self.current_params.clear();
+                        self.assigned_final_fields.clear();
                         self.current_method_is_constructor = false;
                     }
                     ClassMember::Destructor(dtor) => {
@@ -143,6 +120,91 @@ impl SemanticAnalyzer {
         Ok(())
     }
 
+    /// 没有初始化值的 final 实例字段必须在每个构造函数中赋值
+    fn check_final_fields_assigned(&mut self, class: &ClassDecl, ctor: &ConstructorDecl) -> cayResult<()> {
+        let unassigned: Vec<String> = final_instance_fields(class)
+            .filter(|field| field.initializer.is_none())
+            .filter(|field| !ctor.body.statements.iter().any(|s| assigns_field(s, &field.name)))
+            .map(|field| field.name.clone())
+            .collect();
+        for name in unassigned {
+            self.add_error(format!(
+                "Final field '{}' is not assigned in constructor of '{}' at line {}",
+                name, class.name, ctor.loc.line
+            ))?;
+        }
+        Ok(())
+    }
+
+    /// 类声明了没有初始化值的 final 实例字段时，必须提供构造函数为其赋值
+    fn check_final_fields_have_constructor(&mut self, class: &ClassDecl) -> cayResult<()> {
+        if class.members.iter().any(|m| matches!(m, ClassMember::Constructor(_))) {
+            return Ok(());
+        }
+        let unassigned: Vec<String> = final_instance_fields(class)
+            .filter(|field| field.initializer.is_none())
+            .map(|field| field.name.clone())
+            .collect();
+        for name in unassigned {
+            self.add_error(format!(
+                "Final field '{}' of class '{}' is never assigned: add a constructor or an initializer",
+                name, class.name
+            ))?;
+        }
+        Ok(())
+    }
+
+    /// 类型检查类中的一个方法（current_class 已设置）
+    pub(super) fn type_check_method(&mut self, method: &MethodDecl) -> cayResult<()> {
+        self.current_method = Some(method.name.clone());
+        self.current_method_is_static = method.modifiers.contains(&Modifier::Static);
+        self.current_method_is_constructor = false;
+        self.symbol_table.enter_scope();
+        
+        // 非静态方法需要添加 this
+        if !self.current_method_is_static {
+            if let Some(current_class) = &self.current_class {
+                self.symbol_table.declare(
+                    "this".to_string(),
+                    SemanticSymbolInfo {
+                        name: "this".to_string(),
+                        symbol_type: Type::Object(current_class.clone()),
+                        is_final: true,
+                        is_initialized: true,
+                    }
+                );
+            }
+        }
+        
+        // 添加参数到符号表
+        self.local_list_elements.clear();
+        self.current_params = method.params.iter().map(|p| p.name.clone()).collect();
+        for param in &method.params {
+            self.symbol_table.declare(
+                param.name.clone(),
+                SemanticSymbolInfo {
+                    name: param.name.clone(),
+                    symbol_type: param.param_type.clone(),
+                    is_final: false,
+                    is_initialized: true,
+                }
+            );
+        }
+        
+        self.declare_list_params(&method.params);
+
+        // 类型检查方法体
+        if let Some(body) = &method.body {
+            self.type_check_statement(&Stmt::Block(body.clone()), Some(&method.return_type))?;
+        }
+        
+        self.symbol_table.exit_scope();
+        self.current_params.clear();
+        self.current_method = None;
+        self.current_method_is_static = false;
+        Ok(())
+    }
+
     /// 类型检查语句
     pub fn type_check_statement(&mut self, stmt: &Stmt, expected_return: Option<&Type>) -> cayResult<()> {
         match stmt {
@@ -152,28 +214,43 @@ impl SemanticAnalyzer {
             Stmt::VarDecl(var) => {
                 let mut var_type = var.var_type.clone();
                 
-                // 处理 auto 类型推断
+                // 处理 var/let/auto 类型推断，推断结果记录到类型注册表供代码生成使用
                 if var_type == Type::Auto {
                     if let Some(init) = &var.initializer {
                         var_type = self.infer_expr_type(init)?;
+                        self.type_registry.record_inferred_local(&var.loc, var_type.clone());
                     } else {
-                        self.errors.push(format!(
-                            "'auto' variable declaration requires an initializer at line {}",
-                            var.loc.line
-                        ));
+                        self.add_error(format!(
+                            "Variable '{}' declared without a type requires an initializer at line {}",
+                            var.name, var.loc.line
+                        ))?;
                         var_type = Type::Int32; // 默认回退类型
                     }
                 }
                 
                 if let Some(init) = &var.initializer {
                     let init_type = self.infer_expr_type(init)?;
-                    if !self.types_compatible(&init_type, &var_type) {
-                        self.errors.push(format!(
-                            "Cannot assign {} to {} at line {}",
-                            init_type, var_type, var.loc.line
-                        ));
+                    self.check_float_precision(init, &var_type, var.loc.line, var.loc.column);
+                    if !self.is_assignable(init, &init_type, &var_type) {
+                        self.add_error(format!(
+                            "Cannot assign {} to {} at line {}{}",
+                            init_type, var_type, var.loc.line, self.read_mismatch_hint(init, &var_type)
+                        ))?;
                     }
                 }
+
+                if Self::is_list_type(&var_type) {
+                    self.declare_list_variable(&var.name, var.initializer.as_ref());
+                }
+
+                if self.warn_shadowed_params && self.current_params.contains(&var.name) {
+                    self.add_warning(
+                        WarningKind::Semantic,
+                        var.loc.line,
+                        var.loc.column,
+                        format!("Local variable '{}' shadows a parameter of the enclosing method", var.name),
+                    );
+                }
                 
                 self.symbol_table.declare(
                     var.name.clone(),
@@ -185,6 +262,43 @@ impl SemanticAnalyzer {
                     }
                 );
             }
+            Stmt::TupleDestructure(destructure) => {
+                let init_type = self.infer_expr_type(&destructure.initializer)?;
+                let element_types = match init_type {
+                    Type::Tuple(elements) if elements.len() == destructure.names.len() => elements,
+                    other => {
+                        self.add_error(format!(
+                            "Cannot destructure {} into {} variables at line {}",
+                            other, destructure.names.len(), destructure.loc.line
+                        ))?;
+                        vec![Type::Int32; destructure.names.len()]
+                    }
+                };
+
+                for (name, element_type) in destructure.names.iter().zip(element_types) {
+                    self.symbol_table.declare(
+                        name.clone(),
+                        SemanticSymbolInfo {
+                            name: name.clone(),
+                            symbol_type: element_type,
+                            is_final: destructure.is_final,
+                            is_initialized: true,
+                        }
+                    );
+                }
+            }
+            Stmt::Repeat(repeat) => {
+                let count_type = self.infer_expr_type(&repeat.count)?;
+                if !count_type.is_integer() {
+                    self.add_error(format!(
+                        "repeat count must be an integer, got {} at line {}",
+                        count_type, repeat.loc.line
+                    ))?;
+                }
+                self.loop_depth += 1;
+                self.type_check_statement(&repeat.body, expected_return)?;
+                self.loop_depth -= 1;
+            }
             Stmt::Return(expr) => {
                 let return_type = if let Some(e) = expr {
                     self.infer_expr_type(e)?
@@ -193,11 +307,18 @@ impl SemanticAnalyzer {
                 };
                 
                 if let Some(expected) = expected_return {
-                    if !self.types_compatible(&return_type, expected) {
-                        self.errors.push(format!(
+                    let assignable = match expr {
+                        Some(e) => self.is_assignable(e, &return_type, expected),
+                        None => self.types_compatible(&return_type, expected),
+                    };
+                    if !assignable {
+                        self.add_error(format!(
                             "Return type mismatch: expected {}, got {}",
                             expected, return_type
-                        ));
+                        ))?;
+                    }
+                    if let (Some(e), true) = (expr, Self::is_list_type(expected)) {
+                        self.record_list_return(e);
                     }
                 }
             }
@@ -208,9 +329,147 @@ impl SemanticAnalyzer {
                 }
                 self.symbol_table.exit_scope();
             }
-            _ => {}
+            // 其余语句暂不做类型检查，只检查其中误写的空循环体/分支，并记录其中 List 调用的元素类型
+            other => {
+                self.check_empty_bodies(other);
+                self.scan_unchecked_statement(other, expected_return)?;
+            }
         }
         
         Ok(())
     }
+
+    /// 检查语句中 if/while/for 的分支或循环体是否只有一个分号
+    ///
+    /// `while (cond);` 这样的空语句体几乎总是误写的分号，真正的循环体反而只执行一次。
+    fn check_empty_bodies(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::If(if_stmt) => {
+                self.warn_empty_body("if", &if_stmt.then_branch, &if_stmt.loc);
+                self.check_empty_bodies(&if_stmt.then_branch);
+                if let Some(else_branch) = &if_stmt.else_branch {
+                    self.check_empty_bodies(else_branch);
+                }
+            }
+            Stmt::While(while_stmt) => {
+                self.warn_empty_body("while", &while_stmt.body, &while_stmt.loc);
+                if self.is_constant_true(&while_stmt.condition) {
+                    self.warn_infinite_loop("while", &while_stmt.body, &while_stmt.loc);
+                }
+                self.check_empty_bodies(&while_stmt.body);
+            }
+            Stmt::For(for_stmt) => {
+                self.warn_empty_body("for", &for_stmt.body, &for_stmt.loc);
+                // 省略条件的 for (;;) 同样恒为真
+                if for_stmt.condition.as_ref().is_none_or(|c| self.is_constant_true(c)) {
+                    self.warn_infinite_loop("for", &for_stmt.body, &for_stmt.loc);
+                }
+                self.check_empty_bodies(&for_stmt.body);
+            }
+            Stmt::DoWhile(do_while) => {
+                if self.is_constant_true(&do_while.condition) {
+                    self.warn_infinite_loop("do-while", &do_while.body, &do_while.loc);
+                }
+                self.check_empty_bodies(&do_while.body);
+            }
+            Stmt::Repeat(repeat) => self.check_empty_bodies(&repeat.body),
+            Stmt::Switch(switch) => {
+                let bodies = switch.cases.iter().map(|c| &c.body).chain(switch.default.iter());
+                for stmt in bodies.flatten() {
+                    self.check_empty_bodies(stmt);
+                }
+            }
+            Stmt::Block(block) => {
+                for stmt in &block.statements {
+                    self.check_empty_bodies(stmt);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// 条件恒为真的循环体中没有能离开循环的 break/return 时发出警告（--warn-infinite-loop）
+    fn warn_infinite_loop(&mut self, keyword: &str, body: &Stmt, loc: &SourceLocation) {
+        if self.warn_infinite_loops && !can_exit_loop(body, true, &[]) {
+            self.add_warning(
+                WarningKind::Semantic,
+                loc.line,
+                loc.column,
+                format!("'{}' loop condition is always true and the loop has no reachable 'break' or 'return'", keyword),
+            );
+        }
+    }
+
+    fn warn_empty_body(&mut self, keyword: &str, body: &Stmt, loc: &SourceLocation) {
+        if matches!(body, Stmt::Empty) {
+            self.add_warning(
+                WarningKind::Semantic,
+                loc.line,
+                loc.column,
+                format!("Empty body after '{}' (misplaced ';'?)", keyword),
+            );
+        }
+    }
+}
+
+/// 语句中是否有能离开当前循环的 break 或 return
+///
+/// `in_loop` 为 false 时位于嵌套的循环或 switch 中，不带标签的 break 只离开内层语句。
+/// `inner_labels` 是循环体内带标签的语句块：`break label;` 指向它们时只离开该语句块，
+/// 指向循环外的语句块时才离开循环。
+fn can_exit_loop(stmt: &Stmt, in_loop: bool, inner_labels: &[&str]) -> bool {
+    match stmt {
+        Stmt::Return(_) => true,
+        Stmt::Break(None) => in_loop,
+        Stmt::Break(Some(label)) => !inner_labels.contains(&label.as_str()),
+        Stmt::Block(block) => match &block.label {
+            Some(label) => {
+                let inner_labels: Vec<&str> = inner_labels.iter().copied().chain([label.as_str()]).collect();
+                block.statements.iter().any(|s| can_exit_loop(s, in_loop, &inner_labels))
+            }
+            None => block.statements.iter().any(|s| can_exit_loop(s, in_loop, inner_labels)),
+        },
+        Stmt::If(if_stmt) => can_exit_loop(&if_stmt.then_branch, in_loop, inner_labels)
+            || if_stmt.else_branch.as_ref().is_some_and(|e| can_exit_loop(e, in_loop, inner_labels)),
+        Stmt::While(while_stmt) => can_exit_loop(&while_stmt.body, false, inner_labels),
+        Stmt::For(for_stmt) => can_exit_loop(&for_stmt.body, false, inner_labels),
+        Stmt::DoWhile(do_while) => can_exit_loop(&do_while.body, false, inner_labels),
+        Stmt::Repeat(repeat) => can_exit_loop(&repeat.body, false, inner_labels),
+        Stmt::Switch(switch) => switch.cases.iter().map(|c| &c.body).chain(switch.default.iter())
+            .flatten()
+            .any(|s| can_exit_loop(s, false, inner_labels)),
+        _ => false,
+    }
+}
+
+/// 类中的 final 实例字段
+fn final_instance_fields(class: &ClassDecl) -> impl Iterator<Item = &FieldDecl> {
+    class.members.iter().filter_map(|member| match member {
+        ClassMember::Field(field) if field.modifiers.contains(&Modifier::Final)
+            && !field.modifiers.contains(&Modifier::Static) => Some(field),
+        _ => None,
+    })
+}
+
+/// 语句执行完时是否一定已经给当前对象字段 `name` 或 `this.name` 赋值
+///
+/// if 需要两个分支都赋值，switch 需要有 default 且每个非空分支都赋值；
+/// 循环体可能一次也不执行（其中的赋值本身也会报错），不算赋值。
+fn assigns_field(stmt: &Stmt, name: &str) -> bool {
+    match stmt {
+        Stmt::Expr(Expr::Assignment(assign)) => match assign.target.as_ref() {
+            Expr::Identifier(target) => target == name,
+            Expr::MemberAccess(member) => member.member == name
+                && matches!(member.object.as_ref(), Expr::Identifier(owner) if owner == "this"),
+            _ => false,
+        },
+        Stmt::Block(block) => block.statements.iter().any(|s| assigns_field(s, name)),
+        Stmt::If(if_stmt) => assigns_field(&if_stmt.then_branch, name)
+            && if_stmt.else_branch.as_ref().is_some_and(|e| assigns_field(e, name)),
+        Stmt::Switch(switch) => switch.default.as_ref().is_some_and(|d| d.iter().any(|s| assigns_field(s, name)))
+            && switch.cases.iter()
+                .filter(|c| !c.body.is_empty())
+                .all(|c| c.body.iter().any(|s| assigns_field(s, name))),
+        _ => false,
+    }
 }
@@ -1,8 +1,8 @@
 //! 类型工具函数
 
-use crate::ast::Expr;
+use crate::ast::{Expr, LiteralValue};
 use crate::types::{Type, ParameterInfo};
-use crate::error::cayResult;
+use crate::error::{cayResult, WarningKind};
 use super::analyzer::SemanticAnalyzer;
 
 impl SemanticAnalyzer {
@@ -22,6 +22,9 @@ impl SemanticAnalyzer {
 
         // 基本类型之间的兼容
         match (from, to) {
+            // int8/int16 可以拓宽为更宽的整数和浮点数
+            (Type::Int8, Type::Int16 | Type::Int32 | Type::Int64 | Type::Float32 | Type::Float64) => true,
+            (Type::Int16, Type::Int32 | Type::Int64 | Type::Float32 | Type::Float64) => true,
             (Type::Int32, Type::Int64) => true,
             (Type::Int32, Type::Float32) => true,
             (Type::Int32, Type::Float64) => true,
@@ -36,6 +39,11 @@ impl SemanticAnalyzer {
             (Type::Array(from_elem), Type::Array(to_elem)) => {
                 self.types_compatible(from_elem, to_elem)
             }
+            // 元组类型：元素个数相同且逐个兼容
+            (Type::Tuple(from_elems), Type::Tuple(to_elems)) => {
+                from_elems.len() == to_elems.len()
+                    && from_elems.iter().zip(to_elems).all(|(f, t)| self.types_compatible(f, t))
+            }
             _ => false,
         }
     }
@@ -46,17 +54,23 @@ impl SemanticAnalyzer {
             (Type::Float64, _) | (_, Type::Float64) => Type::Float64,
             (Type::Float32, _) | (_, Type::Float32) => Type::Float32,
             (Type::Int64, _) | (_, Type::Int64) => Type::Int64,
-            // char 类型在算术运算中提升为 int32
-            (Type::Char, Type::Char) => Type::Int32,
-            (Type::Char, Type::Int32) | (Type::Int32, Type::Char) => Type::Int32,
-            (Type::Int32, Type::Int32) => Type::Int32,
+            // char、int8、int16 在算术运算中提升为 int32
+            (Type::Char | Type::Int8 | Type::Int16 | Type::Int32, Type::Char | Type::Int8 | Type::Int16 | Type::Int32) => Type::Int32,
             _ => left.clone(),
         }
     }
 
     /// 检查类型是否为数值类型
     pub fn is_numeric_type(ty: &Type) -> bool {
-        matches!(ty, Type::Int32 | Type::Int64 | Type::Float32 | Type::Float64 | Type::Char)
+        matches!(ty, Type::Int8 | Type::Int16 | Type::Int32 | Type::Int64 | Type::Float32 | Type::Float64 | Type::Char)
+    }
+
+    /// 检查表达式的值能否赋给目标类型：类型兼容，或者是目标范围内的整数常量
+    ///
+    /// 与 Java 的 byte/short 一样，`int8 b = 100;` 这样的常量不需要显式转换，
+    /// 超出范围的常量和非常量的 int 表达式仍然需要 `(int8)` 转换。
+    pub fn is_assignable(&self, expr: &Expr, from: &Type, to: &Type) -> bool {
+        self.types_compatible(from, to) || self.integer_constant_fits(expr, to)
     }
 
     /// 整数类型提升
@@ -125,63 +139,384 @@ impl SemanticAnalyzer {
         Ok(())
     }
 
-    /// 推断 String 方法调用的返回类型
-    pub fn infer_string_method_call(&mut self, method_name: &str, args: &[Expr], line: usize, column: usize) -> cayResult<Type> {
+    /// 推断 valueOf / String.valueOf 调用的返回类型
+    ///
+    /// 支持 int/long/float/double/bool/char 六种重载，均返回 String
+    pub fn infer_value_of_call(&mut self, args: &[Expr], line: usize, column: usize) -> cayResult<Type> {
         use crate::error::semantic_error;
-        
-        match method_name {
-            "length" => {
-                if !args.is_empty() {
-                    return Err(semantic_error(line, column, "String.length() takes no arguments".to_string()));
-                }
-                Ok(Type::Int32)
-            }
-            "substring" => {
-                if args.is_empty() || args.len() > 2 {
-                    return Err(semantic_error(line, column, "String.substring() takes 1 or 2 arguments".to_string()));
-                }
-                // 检查参数类型
-                for (i, arg) in args.iter().enumerate() {
-                    let arg_type = self.infer_expr_type(arg)?;
-                    if !arg_type.is_integer() {
-                        return Err(semantic_error(line, column, format!("Argument {} of substring() must be integer, got {}", i + 1, arg_type)));
-                    }
+
+        if args.len() != 1 {
+            return Err(semantic_error(line, column, format!("String.valueOf() takes 1 argument, got {}", args.len())));
+        }
+        let arg_type = self.infer_expr_type(&args[0])?;
+        if !arg_type.is_primitive() {
+            return Err(semantic_error(line, column, format!("No String.valueOf() overload for type {}", arg_type)));
+        }
+        Ok(Type::String)
+    }
+
+    /// 推断 addressOf 调用的返回类型
+    ///
+    /// 参数必须是对象、数组或字符串引用，结果为 long 形式的地址
+    pub fn infer_address_of_call(&mut self, args: &[Expr], line: usize, column: usize) -> cayResult<Type> {
+        use crate::error::semantic_error;
+
+        if args.len() != 1 {
+            return Err(semantic_error(line, column, format!("addressOf() takes 1 argument, got {}", args.len())));
+        }
+        if matches!(args[0], Expr::Literal(LiteralValue::Null)) {
+            return Err(semantic_error(line, column, "addressOf() requires an object, array or string, got null".to_string()));
+        }
+        let arg_type = self.infer_expr_type(&args[0])?;
+        if !matches!(arg_type, Type::Object(_) | Type::Array(_) | Type::String) {
+            return Err(semantic_error(line, column, format!("addressOf() requires an object, array or string, got {}", arg_type)));
+        }
+        Ok(Type::Int64)
+    }
+
+    /// 重载解析失败时的错误信息，列出实参类型和所有候选签名
+    pub fn argument_mismatch_message(&mut self, class_name: &str, method_name: &str, args: &[Expr]) -> cayResult<String> {
+        let mut arg_types = Vec::new();
+        for arg in args {
+            arg_types.push(self.infer_expr_type(arg)?.to_string());
+        }
+        let candidates: Vec<String> = self.type_registry.method_candidates(class_name, method_name)
+            .iter()
+            .map(|m| format!("  {}", m.signature()))
+            .collect();
+        if candidates.is_empty() {
+            return Ok(format!("Unknown method '{}' for class {}", method_name, class_name));
+        }
+        Ok(format!(
+            "Method '{}' in class '{}' cannot be applied to given types ({}); candidates are:\n{}",
+            method_name, class_name, arg_types.join(", "), candidates.join("\n")
+        ))
+    }
+
+    /// 检查方法调用是否有多个同样具体的重载可选
+    pub fn check_ambiguous_call(&self, class_name: &str, method_name: &str, arg_types: &[Type], line: usize, column: usize) -> cayResult<()> {
+        use crate::error::semantic_error;
+
+        let Err(candidates) = self.type_registry.resolve_overload(class_name, method_name, arg_types) else {
+            return Ok(());
+        };
+        let arg_types: Vec<String> = arg_types.iter().map(|t| t.to_string()).collect();
+        let candidates: Vec<String> = candidates.iter().map(|m| format!("  {}", m.signature())).collect();
+        Err(semantic_error(line, column, format!(
+            "Call to '{}' in class '{}' is ambiguous for argument types ({}); candidates are:\n{}",
+            method_name, class_name, arg_types.join(", "), candidates.join("\n")
+        )))
+    }
+
+    /// 检查 arrayEquals(a, b) 的参数
+    ///
+    /// 两个参数必须是元素类型相同的基本类型一维数组
+    pub fn check_array_equals_args(&mut self, args: &[Expr], line: usize, column: usize) -> cayResult<()> {
+        use crate::error::semantic_error;
+
+        if args.len() != 2 {
+            return Err(semantic_error(line, column, format!("arrayEquals() takes 2 arguments (a, b), got {}", args.len())));
+        }
+        let left = self.infer_expr_type(&args[0])?;
+        let right = self.infer_expr_type(&args[1])?;
+        if !matches!(&left, Type::Array(element) if element.is_primitive()) {
+            return Err(semantic_error(line, column, format!("arrayEquals() requires arrays of a primitive type, got {}", left)));
+        }
+        if left != right {
+            return Err(semantic_error(line, column, format!("arrayEquals() requires two arrays of the same type, got {} and {}", left, right)));
+        }
+        Ok(())
+    }
+
+    /// 检查 printf(fmt, ...) 的参数
+    ///
+    /// 格式必须是字符串字面量，转换说明的个数和类型要与后面的实参一致
+    pub fn check_printf_args(&mut self, args: &[Expr], line: usize, column: usize) -> cayResult<()> {
+        use crate::error::semantic_error;
+        use crate::ast::LiteralValue;
+
+        let format = match args.first() {
+            Some(Expr::Literal(LiteralValue::String(format))) => format,
+            _ => return Err(semantic_error(line, column, "printf() format must be a String literal".to_string())),
+        };
+        let specs = super::format::parse_format_specs(format)
+            .map_err(|msg| semantic_error(line, column, format!("printf(): {}", msg)))?;
+
+        let expected: usize = specs.iter().map(|s| s.star_count + 1).sum();
+        if expected != args.len() - 1 {
+            return Err(semantic_error(line, column, format!(
+                "printf() format string expects {} argument{}, got {}",
+                expected, if expected == 1 { "" } else { "s" }, args.len() - 1
+            )));
+        }
+
+        let mut values = args[1..].iter();
+        for spec in &specs {
+            for _ in 0..spec.star_count {
+                let arg_type = self.infer_expr_type(values.next().unwrap())?;
+                if !arg_type.is_integer() {
+                    return Err(semantic_error(line, column, format!("printf() '*' width or precision must be an int, got {}", arg_type)));
                 }
-                Ok(Type::String)
             }
-            "indexOf" => {
-                if args.len() != 1 {
-                    return Err(semantic_error(line, column, "String.indexOf() takes 1 argument".to_string()));
-                }
-                let arg_type = self.infer_expr_type(&args[0])?;
-                if arg_type != Type::String {
-                    return Err(semantic_error(line, column, format!("Argument of indexOf() must be string, got {}", arg_type)));
-                }
-                Ok(Type::Int32)
+            let arg_type = self.infer_expr_type(values.next().unwrap())?;
+            let accepted = match spec.conversion {
+                'c' => arg_type.is_integer() || arg_type == Type::Char,
+                's' => arg_type == Type::String,
+                'p' => arg_type.is_reference_type(),
+                _ if spec.is_float() => matches!(arg_type, Type::Float32 | Type::Float64),
+                _ => arg_type.is_integer() || matches!(arg_type, Type::Char | Type::Bool),
+            };
+            if !accepted {
+                return Err(semantic_error(line, column, format!(
+                    "printf() conversion '%{}' does not accept an argument of type {}", spec.conversion, arg_type
+                )));
             }
-            "charAt" => {
-                if args.len() != 1 {
-                    return Err(semantic_error(line, column, "String.charAt() takes 1 argument".to_string()));
-                }
-                let arg_type = self.infer_expr_type(&args[0])?;
-                if !arg_type.is_integer() {
-                    return Err(semantic_error(line, column, format!("Argument of charAt() must be integer, got {}", arg_type)));
-                }
-                Ok(Type::Char)
+        }
+        Ok(())
+    }
+
+    /// 检查 print(value, x) / println(value, x) 的参数
+    ///
+    /// 浮点值时第二个参数是保留的小数位数，可以是任意整数表达式，字面量不能为负；
+    /// 其余情况按 print(value, base) 检查进制
+    pub fn check_print_format_args(&mut self, func_name: &str, args: &[Expr], line: usize, column: usize) -> cayResult<()> {
+        use crate::error::semantic_error;
+
+        let value_type = self.infer_expr_type(&args[0])?;
+        if !matches!(value_type, Type::Float32 | Type::Float64) {
+            return self.check_radix_args(func_name, args, line, column);
+        }
+        let precision_type = self.infer_expr_type(&args[1])?;
+        if !precision_type.is_integer() {
+            return Err(semantic_error(line, column, format!("The precision of {}() must be an int, got {}", func_name, precision_type)));
+        }
+        match radix_literal(&args[1]) {
+            Some(precision) if precision < 0 => Err(semantic_error(line, column, format!("The precision of {}() must not be negative, got {}", func_name, precision))),
+            _ => Ok(()),
+        }
+    }
+
+    /// 检查 toString(value, base) / print(value, base) 的参数
+    ///
+    /// 值必须是 int 或 long，进制必须是 2、8、10、16 之一的整数字面量
+    pub fn check_radix_args(&mut self, func_name: &str, args: &[Expr], line: usize, column: usize) -> cayResult<()> {
+        use crate::error::semantic_error;
+
+        if args.len() != 2 {
+            return Err(semantic_error(line, column, format!("{}() takes 2 arguments (value, base), got {}", func_name, args.len())));
+        }
+        let value_type = self.infer_expr_type(&args[0])?;
+        if !value_type.is_integer() {
+            return Err(semantic_error(line, column, format!("{}() requires an int or long value, got {}", func_name, value_type)));
+        }
+        match radix_literal(&args[1]) {
+            Some(2 | 8 | 10 | 16) => Ok(()),
+            Some(base) => Err(semantic_error(line, column, format!("Unsupported base {} for {}(): expected 2, 8, 10 or 16", base, func_name))),
+            None => Err(semantic_error(line, column, format!("The base of {}() must be an integer literal", func_name))),
+        }
+    }
+
+    /// 检查 floorMod(a, b) 的参数并返回结果类型
+    ///
+    /// 两个参数都必须是整数；任一参数为 long 时结果为 long，否则为 int。
+    pub fn check_floor_mod_args(&mut self, args: &[Expr], line: usize, column: usize) -> cayResult<Type> {
+        use crate::error::semantic_error;
+
+        if args.len() != 2 {
+            return Err(semantic_error(line, column, format!("floorMod() takes 2 arguments (dividend, divisor), got {}", args.len())));
+        }
+        let dividend_type = self.infer_expr_type(&args[0])?;
+        let divisor_type = self.infer_expr_type(&args[1])?;
+        if !dividend_type.is_integer() || !divisor_type.is_integer() {
+            return Err(semantic_error(line, column, format!(
+                "floorMod() requires int or long arguments, got {} and {}", dividend_type, divisor_type
+            )));
+        }
+        if dividend_type == Type::Int64 || divisor_type == Type::Int64 {
+            Ok(Type::Int64)
+        } else {
+            Ok(Type::Int32)
+        }
+    }
+
+    /// 检查输入函数（readInt() 等）的调用并返回读取的类型
+    pub fn check_read_call(&mut self, func_name: &str, read_type: Type, args: &[Expr], line: usize, column: usize) -> cayResult<Type> {
+        use crate::error::semantic_error;
+
+        if !args.is_empty() {
+            return Err(semantic_error(line, column, format!("{}() takes no arguments, got {}", func_name, args.len())));
+        }
+        Ok(read_type)
+    }
+
+    /// 输入函数的结果赋给类型不同的变量时，补充说明应使用的输入函数
+    ///
+    /// 如 `int x = readDouble();` 得到 ": readDouble() returns double, use readInt() instead"；
+    /// 赋值的值不是输入函数调用时返回空字符串。
+    pub fn read_mismatch_hint(&self, value: &Expr, target: &Type) -> String {
+        let Expr::Call(call) = value else { return String::new() };
+        let Expr::Identifier(name) = call.callee.as_ref() else { return String::new() };
+        let Some(read_type) = read_builtin_type(name) else { return String::new() };
+
+        match read_builtin_name(target) {
+            Some(other) => format!(": {}() returns {}, use {}() instead", name, read_type, other),
+            None => format!(": {}() returns {}", name, read_type),
+        }
+    }
+
+    /// double 字面量赋给 float 时丢失精度则发出警告（--warn-float-precision）
+    ///
+    /// 只检查没有 `f` 后缀的浮点字面量（及其取负）。转换为 float 后的最短表示
+    /// 不能还原原值时才算丢失精度，因此 `float f = 3.14;` 不会警告，
+    /// `float f = 3.141592653589793;` 会警告。
+    pub fn check_float_precision(&mut self, value: &Expr, target: &Type, line: usize, column: usize) {
+        if !self.warn_float_precision || *target != Type::Float32 {
+            return;
+        }
+        let Some(literal) = double_literal_value(value) else { return };
+        let narrowed = literal as f32;
+        if narrowed.to_string().parse::<f64>().is_ok_and(|v| v == literal) {
+            return;
+        }
+        self.add_warning(
+            WarningKind::Semantic,
+            line,
+            column,
+            format!(
+                "Double literal {} loses precision when assigned to float (becomes {}); add an 'f' suffix if this is intended",
+                literal, narrowed
+            ),
+        );
+    }
+
+    /// 检查参数全部为 String 的内置函数（如 readFile(path)）
+    ///
+    /// `param_names` 给出各参数的名字，用于错误信息
+    pub fn check_string_args(&mut self, func_name: &str, param_names: &[&str], args: &[Expr], line: usize, column: usize) -> cayResult<()> {
+        use crate::error::semantic_error;
+
+        if args.len() != param_names.len() {
+            return Err(semantic_error(line, column, format!(
+                "{}() takes {} argument{} ({}), got {}",
+                func_name,
+                param_names.len(),
+                if param_names.len() == 1 { "" } else { "s" },
+                param_names.join(", "),
+                args.len()
+            )));
+        }
+        for (name, arg) in param_names.iter().zip(args) {
+            let arg_type = self.infer_expr_type(arg)?;
+            if arg_type != Type::String {
+                return Err(semantic_error(line, column, format!(
+                    "Argument '{}' of {}() must be a String, got {}", name, func_name, arg_type
+                )));
             }
-            "replace" => {
-                if args.len() != 2 {
-                    return Err(semantic_error(line, column, "String.replace() takes 2 arguments".to_string()));
-                }
-                for (i, arg) in args.iter().enumerate() {
-                    let arg_type = self.infer_expr_type(arg)?;
-                    if arg_type != Type::String {
-                        return Err(semantic_error(line, column, format!("Argument {} of replace() must be string, got {}", i + 1, arg_type)));
-                    }
-                }
-                Ok(Type::String)
+        }
+        Ok(())
+    }
+
+    /// 检查自定义内置函数的实参个数和类型，返回声明的返回类型
+    pub fn check_custom_builtin_call(&mut self, spec: &crate::builtins::BuiltinSpec, args: &[Expr], line: usize, column: usize) -> cayResult<Type> {
+        use crate::error::semantic_error;
+
+        if args.len() != spec.params.len() {
+            return Err(semantic_error(line, column, format!(
+                "{}() takes {} argument{}, got {}",
+                spec.name,
+                spec.params.len(),
+                if spec.params.len() == 1 { "" } else { "s" },
+                args.len()
+            )));
+        }
+        for (i, (param_type, arg)) in spec.params.iter().zip(args).enumerate() {
+            let arg_type = self.infer_expr_type(arg)?;
+            if !self.types_compatible(&arg_type, param_type) {
+                return Err(semantic_error(line, column, format!(
+                    "Argument {} of {}() must be {}, got {}", i + 1, spec.name, param_type, arg_type
+                )));
             }
-            _ => Err(semantic_error(line, column, format!("Unknown String method '{}'", method_name))),
         }
+        Ok(spec.return_type.clone())
+    }
+
+    /// 方法的引入版本高于目标版本时发出警告
+    pub fn check_since_version(&mut self, method_name: &str, since_version: Option<&str>, line: usize, column: usize) {
+        let (since, target) = match (since_version, self.target_version.clone()) {
+            (Some(since), Some(target)) => (since, target),
+            _ => return,
+        };
+        if compare_versions(since, &target) == std::cmp::Ordering::Greater {
+            let message = format!(
+                "Method '{}' was added in version {}, but target version is {}",
+                method_name, since, target
+            );
+            self.add_warning(WarningKind::Version, line, column, message);
+        }
+    }
+
+    /// 检查当前类是否定义了指定名称的方法
+    pub fn current_class_has_method(&self, name: &str) -> bool {
+        self.current_class.as_ref()
+            .and_then(|c| self.type_registry.get_class(c))
+            .is_some_and(|c| c.methods.contains_key(name))
+    }
+}
+
+/// 输入函数读取的类型
+pub fn read_builtin_type(name: &str) -> Option<Type> {
+    match name {
+        "readInt" => Some(Type::Int32),
+        "readLong" => Some(Type::Int64),
+        "readFloat" => Some(Type::Float32),
+        "readDouble" => Some(Type::Float64),
+        "readLine" => Some(Type::String),
+        "readChar" => Some(Type::Char),
+        "readBool" => Some(Type::Bool),
+        _ => None,
+    }
+}
+
+/// 读取指定类型的输入函数
+fn read_builtin_name(ty: &Type) -> Option<&'static str> {
+    ["readInt", "readLong", "readFloat", "readDouble", "readLine", "readChar", "readBool"]
+        .into_iter()
+        .find(|name| read_builtin_type(name).as_ref() == Some(ty))
+}
+
+/// 按语义化版本规则比较两个版本号（缺失的部分视为 0）
+pub fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    let parse = |v: &str| -> Vec<u64> {
+        v.trim_start_matches('v')
+            .split('.')
+            .map(|part| part.trim().parse().unwrap_or(0))
+            .collect()
+    };
+    let (a, b) = (parse(a), parse(b));
+    for i in 0..a.len().max(b.len()) {
+        let x = a.get(i).copied().unwrap_or(0);
+        let y = b.get(i).copied().unwrap_or(0);
+        if x != y {
+            return x.cmp(&y);
+        }
+    }
+    std::cmp::Ordering::Equal
+}
+
+/// 进制参数：只接受整数字面量
+fn radix_literal(expr: &Expr) -> Option<i64> {
+    match expr {
+        Expr::Literal(crate::ast::LiteralValue::Int32(n)) => Some(*n as i64),
+        Expr::Literal(crate::ast::LiteralValue::Int64(n)) => Some(*n),
+        _ => None,
+    }
+}
+
+/// 没有 `f` 后缀的浮点字面量的值，`-字面量` 取负值；其他表达式返回 None
+fn double_literal_value(expr: &Expr) -> Option<f64> {
+    match expr {
+        Expr::Literal(crate::ast::LiteralValue::Float64(value)) => Some(*value),
+        Expr::Unary(unary) if unary.op == crate::ast::UnaryOp::Neg => double_literal_value(&unary.operand).map(|v| -v),
+        _ => None,
     }
 }
@@ -4,16 +4,20 @@ use std::collections::HashMap;
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Type {
     Void,
+    Int8,   // int8，8 位有符号整数
+    Int16,  // int16，16 位有符号整数
     Int32,
     Int64,
     Float32,
     Float64,
     Bool,
     String,
+    StringView,  // 字符串视图 { i8*, i32 }，按值传递，不分配内存
     Char,
     Object(String),
     Array(Box<Type>),
     Function(Box<FunctionType>),
+    Tuple(Vec<Type>),  // 元组 (int, string)，按值传递
     Auto,  // 自动类型推断占位符
 }
 
@@ -62,12 +66,59 @@ impl ClassInfo {
     }
 
     /// 根据方法名和参数类型查找方法（支持可变参数）
+    ///
+    /// 二义性调用返回 None，由 [`ClassInfo::resolve_overload`] 给出候选
     pub fn find_method(&self, name: &str, arg_types: &[Type]) -> Option<&MethodInfo> {
-        self.methods.get(name)?.iter().find(|m| {
-            Self::match_method_params(&m.params, arg_types)
+        self.resolve_overload(name, arg_types).ok().flatten()
+    }
+
+    /// 重载解析：精确匹配优先，否则选择最具体的可拓宽重载
+    ///
+    /// 多个重载同样具体时返回 `Err`，其中是这些互相冲突的候选
+    pub fn resolve_overload(&self, name: &str, arg_types: &[Type]) -> Result<Option<&MethodInfo>, Vec<&MethodInfo>> {
+        let applicable: Vec<&MethodInfo> = match self.methods.get(name) {
+            Some(methods) => methods.iter()
+                .filter(|m| Self::match_method_params(&m.params, arg_types))
+                .collect(),
+            None => return Ok(None),
+        };
+        Self::select_most_specific(applicable, |m| {
+            !m.params.last().is_some_and(|p| p.is_varargs)
+                && m.params.iter().map(|p| &p.param_type).eq(arg_types.iter())
         })
     }
 
+    /// 从可调用的重载中选出一个：精确匹配 > 定长参数 > 可变参数，同一级别内取最具体的
+    ///
+    /// 一个重载的每个参数都能拓宽为另一个重载的对应参数时，前者更具体（如 f(int) 比 f(long) 具体）
+    pub fn select_most_specific(
+        applicable: Vec<&MethodInfo>,
+        is_exact: impl Fn(&MethodInfo) -> bool,
+    ) -> Result<Option<&MethodInfo>, Vec<&MethodInfo>> {
+        if let Some(exact) = applicable.iter().find(|m| is_exact(m)) {
+            return Ok(Some(exact));
+        }
+
+        let is_varargs = |m: &MethodInfo| m.params.last().is_some_and(|p| p.is_varargs);
+        let fixed: Vec<&MethodInfo> = applicable.iter().copied().filter(|m| !is_varargs(m)).collect();
+        let pool = if fixed.is_empty() { applicable } else { fixed };
+        if pool.is_empty() {
+            return Ok(None);
+        }
+
+        let as_specific = |m: &MethodInfo, other: &MethodInfo| {
+            m.params.len() == other.params.len()
+                && other.params.iter().zip(&m.params).all(|(o, p)| Self::types_match(&o.param_type, &p.param_type))
+        };
+        let best: Vec<&MethodInfo> = pool.iter().copied()
+            .filter(|m| pool.iter().all(|other| as_specific(m, other)))
+            .collect();
+        match best.as_slice() {
+            [only] => Ok(Some(only)),
+            _ => Err(pool),
+        }
+    }
+
     /// 匹配方法参数（支持可变参数）
     fn match_method_params(params: &[ParameterInfo], arg_types: &[Type]) -> bool {
         if params.is_empty() {
@@ -117,12 +168,14 @@ impl ClassInfo {
     }
 
     /// 检查类型是否匹配（支持基本类型转换）
-    fn types_match(param_type: &Type, arg_type: &Type) -> bool {
+    pub fn types_match(param_type: &Type, arg_type: &Type) -> bool {
         if param_type == arg_type {
             return true;
         }
         // 允许 int -> long, int -> float, int -> double 等隐式转换
         match (param_type, arg_type) {
+            (Type::Int16 | Type::Int32 | Type::Int64 | Type::Float32 | Type::Float64, Type::Int8) => true,
+            (Type::Int32 | Type::Int64 | Type::Float32 | Type::Float64, Type::Int16) => true,
             (Type::Int64, Type::Int32) => true,
             (Type::Float32, Type::Int32) => true,
             (Type::Float64, Type::Int32) => true,
@@ -146,6 +199,18 @@ pub struct MethodInfo {
     pub is_native: bool,
     pub is_override: bool,  // 标记是否是重写方法
     pub is_final: bool,  // 是否是final方法（禁止重写）
+    pub since_version: Option<String>,  // @SinceVersion 标记的引入版本
+}
+
+impl MethodInfo {
+    /// 方法签名，如 `int add(int, int)`，用于错误信息中列出候选重载
+    pub fn signature(&self) -> String {
+        let params: Vec<String> = self.params.iter().map(|p| match (&p.param_type, p.is_varargs) {
+            (Type::Array(elem), true) => format!("{}...", elem),
+            (ty, _) => ty.to_string(),
+        }).collect();
+        format!("{} {}({})", self.return_type, self.name, params.join(", "))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -190,6 +255,8 @@ impl Type {
     pub fn size_in_bytes(&self) -> usize {
         match self {
             Type::Void => 0,
+            Type::Int8 => 1,
+            Type::Int16 => 2,
             Type::Int32 => 4,
             Type::Int64 => 8,
             Type::Float32 => 4,
@@ -197,15 +264,19 @@ impl Type {
             Type::Bool => 1,
             Type::Char => 1,
             Type::String => 8, // 指针大小
+            Type::StringView => 16, // 指针 + 长度（含填充）
             Type::Object(_) => 8, // 引用类型
             Type::Array(_) => 8, // 指针大小
             Type::Function(_) => 8, // 函数指针
+            Type::Tuple(elements) => elements.iter().map(Type::size_in_bytes).sum(),
             Type::Auto => panic!("Cannot get size of auto type - type inference not completed"),
         }
     }
 
     pub fn is_primitive(&self) -> bool {
         matches!(self, 
+            Type::Int8 | 
+            Type::Int16 | 
             Type::Int32 | 
             Type::Int64 | 
             Type::Float32 | 
@@ -220,7 +291,7 @@ impl Type {
     }
 
     pub fn is_integer(&self) -> bool {
-        matches!(self, Type::Int32 | Type::Int64)
+        matches!(self, Type::Int8 | Type::Int16 | Type::Int32 | Type::Int64)
     }
 }
 
@@ -228,12 +299,15 @@ impl fmt::Display for Type {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Type::Void => write!(f, "void"),
+            Type::Int8 => write!(f, "int8"),
+            Type::Int16 => write!(f, "int16"),
             Type::Int32 => write!(f, "int"),
             Type::Int64 => write!(f, "long"),
             Type::Float32 => write!(f, "float"),
             Type::Float64 => write!(f, "double"),
             Type::Bool => write!(f, "bool"),
             Type::String => write!(f, "string"),
+            Type::StringView => write!(f, "StringView"),
             Type::Char => write!(f, "char"),
             Type::Object(name) => write!(f, "{}", name),
             Type::Array(inner) => write!(f, "{}[]", inner),
@@ -247,15 +321,40 @@ impl fmt::Display for Type {
                 }
                 write!(f, ") -> {}", func_type.return_type)
             }
+            Type::Tuple(elements) => {
+                write!(f, "(")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", element)?;
+                }
+                write!(f, ")")
+            }
             Type::Auto => write!(f, "auto"),
         }
     }
 }
 
+/// const 常量信息，值在语义分析阶段折叠完成
+#[derive(Debug, Clone)]
+pub struct ConstantInfo {
+    pub name: String,
+    pub const_type: Type,
+    pub value: crate::ast::LiteralValue,
+}
+
+/// 内置动态数组的类名
+pub const LIST_CLASS: &str = "List";
+
 #[derive(Debug, Clone)]
 pub struct TypeRegistry {
     pub classes: HashMap<String, ClassInfo>,
     pub interfaces: HashMap<String, InterfaceInfo>,
+    pub constants: HashMap<String, ConstantInfo>,  // 顶层常量以名称为键，类常量以 "类名.名称" 为键
+    pub inferred_locals: HashMap<crate::error::SourceLocation, Type>,  // var/let/auto 局部变量推断出的类型，以声明位置为键
+    pub list_call_elements: HashMap<crate::error::SourceLocation, Type>,  // List add/get 调用处 List 的元素类型，以调用位置为键
+    pub list_slot_elements: HashMap<String, Type>,  // List 字段、参数和返回值的元素类型，以 "类名.字段名"、list_param_key 或 list_return_key 为键
 }
 
 impl TypeRegistry {
@@ -263,6 +362,10 @@ impl TypeRegistry {
         Self {
             classes: HashMap::new(),
             interfaces: HashMap::new(),
+            constants: HashMap::new(),
+            inferred_locals: HashMap::new(),
+            list_call_elements: HashMap::new(),
+            list_slot_elements: HashMap::new(),
         }
     }
 
@@ -290,6 +393,83 @@ impl TypeRegistry {
         Ok(())
     }
 
+    /// 注册内置 String / StringView 方法
+    ///
+    /// 字符串方法由运行时函数实现，这里只登记签名，
+    /// 使语义分析可以通过统一的 `find_method` 路径完成重载解析和参数检查。
+    pub fn register_builtin_string_methods(&mut self) {
+        // (方法名, 参数, 返回类型, 引入版本)
+        let string_methods = vec![
+            ("length", vec![], Type::Int32, "0.3.4"),
+            ("substring", vec![("beginIndex", Type::Int32)], Type::String, "0.3.4"),
+            ("substring", vec![("beginIndex", Type::Int32), ("endIndex", Type::Int32)], Type::String, "0.3.4"),
+            ("subview", vec![("beginIndex", Type::Int32), ("endIndex", Type::Int32)], Type::StringView, "0.4.4"),
+            ("indexOf", vec![("str", Type::String)], Type::Int32, "0.3.4"),
+            ("charAt", vec![("index", Type::Int32)], Type::Char, "0.3.4"),
+            ("replace", vec![("target", Type::String), ("replacement", Type::String)], Type::String, "0.3.4"),
+            ("compareTo", vec![("other", Type::String)], Type::Int32, "0.4.4"),
+            ("isEmpty", vec![], Type::Bool, "0.4.4"),
+            ("isBlank", vec![], Type::Bool, "0.4.4"),
+        ];
+        let view_methods = vec![
+            ("length", vec![], Type::Int32, "0.4.4"),
+            ("charAt", vec![("index", Type::Int32)], Type::Char, "0.4.4"),
+            ("equals", vec![("str", Type::String)], Type::Bool, "0.4.4"),
+            ("toString", vec![], Type::String, "0.4.4"),
+        ];
+
+        for (class_name, methods) in [("String", string_methods), ("StringView", view_methods)] {
+            let mut class_info = ClassInfo {
+                name: class_name.to_string(),
+                methods: HashMap::new(),
+                fields: HashMap::new(),
+                constructors: Vec::new(),
+                has_destructor: false,
+                parent: None,
+                interfaces: Vec::new(),
+                is_abstract: false,
+                is_final: true,
+            };
+            for (name, params, return_type, since_version) in methods {
+                class_info.add_method(MethodInfo {
+                    name: name.to_string(),
+                    class_name: class_name.to_string(),
+                    params: params.into_iter()
+                        .map(|(n, t)| ParameterInfo::new(n.to_string(), t))
+                        .collect(),
+                    return_type,
+                    is_public: true,
+                    is_private: false,
+                    is_protected: false,
+                    is_static: false,
+                    is_native: true,
+                    is_override: false,
+                    is_final: true,
+                    since_version: Some(since_version.to_string()),
+                });
+            }
+            self.classes.insert(class_name.to_string(), class_info);
+        }
+    }
+
+    /// 注册内置动态数组类 List
+    ///
+    /// List 的方法（add/get/size）的参数和返回类型取决于元素类型，
+    /// 由语义分析单独检查，这里只登记类名，使 `List` 可以用作类型和 `new List()`。
+    pub fn register_builtin_list_class(&mut self) {
+        self.classes.insert(LIST_CLASS.to_string(), ClassInfo {
+            name: LIST_CLASS.to_string(),
+            methods: HashMap::new(),
+            fields: HashMap::new(),
+            constructors: Vec::new(),
+            has_destructor: false,
+            parent: None,
+            interfaces: Vec::new(),
+            is_abstract: false,
+            is_final: true,
+        });
+    }
+
     pub fn get_interface(&self, name: &str) -> Option<&InterfaceInfo> {
         self.interfaces.get(name)
     }
@@ -302,6 +482,89 @@ impl TypeRegistry {
         self.classes.get(name)
     }
 
+    /// 登记 const 常量；`class_name` 为 None 表示顶层常量
+    pub fn register_constant(&mut self, class_name: Option<&str>, info: ConstantInfo) {
+        let key = match class_name {
+            Some(class_name) => format!("{}.{}", class_name, info.name),
+            None => info.name.clone(),
+        };
+        self.constants.insert(key, info);
+    }
+
+    /// 记录未写类型的局部变量声明推断出的类型
+    pub fn record_inferred_local(&mut self, loc: &crate::error::SourceLocation, ty: Type) {
+        self.inferred_locals.insert(loc.clone(), ty);
+    }
+
+    /// 获取某个声明位置的局部变量推断出的类型
+    pub fn inferred_local(&self, loc: &crate::error::SourceLocation) -> Option<&Type> {
+        self.inferred_locals.get(loc)
+    }
+
+    /// 记录 List add/get 调用处 List 的元素类型
+    pub fn record_list_call_element(&mut self, loc: &crate::error::SourceLocation, ty: Type) {
+        self.list_call_elements.insert(loc.clone(), ty);
+    }
+
+    /// 获取某个调用位置的 List 元素类型
+    pub fn list_call_element(&self, loc: &crate::error::SourceLocation) -> Option<&Type> {
+        self.list_call_elements.get(loc)
+    }
+
+    /// List 参数元素类型的键："类名.方法名.参数名"
+    pub fn list_param_key(class_name: &str, method_name: &str, param_name: &str) -> String {
+        format!("{}.{}.{}", class_name, method_name, param_name)
+    }
+
+    /// List 返回值元素类型的键："类名.方法名()"
+    pub fn list_return_key(class_name: &str, method_name: &str) -> String {
+        format!("{}.{}()", class_name, method_name)
+    }
+
+    /// 记录 List 字段（"类名.字段名"）、参数或方法返回值的元素类型
+    pub fn record_list_slot_element(&mut self, key: String, ty: Type) {
+        self.list_slot_elements.insert(key, ty);
+    }
+
+    /// 获取 List 字段、参数或方法返回值的元素类型
+    pub fn list_slot_element(&self, key: &str) -> Option<&Type> {
+        self.list_slot_elements.get(key)
+    }
+
+    /// 获取类常量（支持继承）
+    pub fn get_class_constant(&self, class_name: &str, name: &str) -> Option<&ConstantInfo> {
+        match self.constants.get(&format!("{}.{}", class_name, name)) {
+            Some(info) => Some(info),
+            None => self.get_class_constant(self.classes.get(class_name)?.parent.as_deref()?, name),
+        }
+    }
+
+    /// 按名称解析常量：先查当前类（及父类），再查顶层常量
+    pub fn resolve_constant(&self, current_class: Option<&str>, name: &str) -> Option<&ConstantInfo> {
+        current_class
+            .and_then(|class_name| self.get_class_constant(class_name, name))
+            .or_else(|| self.constants.get(name))
+    }
+
+    /// 根据类名和字段名获取字段（支持继承，先查当前类再递归父类）
+    pub fn get_field(&self, class_name: &str, field_name: &str) -> Option<&FieldInfo> {
+        let class_info = self.classes.get(class_name)?;
+        match class_info.fields.get(field_name) {
+            Some(field) => Some(field),
+            None => self.get_field(class_info.parent.as_deref()?, field_name),
+        }
+    }
+
+    /// 声明字段的类：在类本身和父类中查找，返回第一个声明了该字段的类名
+    pub fn field_owner(&self, class_name: &str, field_name: &str) -> Option<&str> {
+        let (owner, class_info) = self.classes.get_key_value(class_name)?;
+        if class_info.fields.contains_key(field_name) {
+            Some(owner)
+        } else {
+            self.field_owner(class_info.parent.as_deref()?, field_name)
+        }
+    }
+
     /// 根据类名和方法名获取方法（获取第一个匹配的方法，用于无参数类型信息的情况，支持继承）
     pub fn get_method(&self, class_name: &str, method_name: &str) -> Option<&MethodInfo> {
         if let Some(class_info) = self.classes.get(class_name) {
@@ -316,19 +579,36 @@ impl TypeRegistry {
         None
     }
 
+    /// 列出类及其父类中所有同名方法，用于重载解析失败时提示候选签名
+    pub fn method_candidates(&self, class_name: &str, method_name: &str) -> Vec<&MethodInfo> {
+        let mut candidates = Vec::new();
+        let mut current = self.classes.get(class_name);
+        while let Some(class_info) = current {
+            if let Some(methods) = class_info.methods.get(method_name) {
+                candidates.extend(methods.iter());
+            }
+            current = class_info.parent.as_deref().and_then(|parent| self.classes.get(parent));
+        }
+        candidates
+    }
+
     /// 根据类名、方法名和参数类型查找方法（支持重载和继承）
     pub fn find_method(&self, class_name: &str, method_name: &str, arg_types: &[Type]) -> Option<&MethodInfo> {
-        // 首先在当前类中查找
-        if let Some(class_info) = self.classes.get(class_name) {
-            if let Some(method) = class_info.find_method(method_name, arg_types) {
-                return Some(method);
-            }
-            // 如果在当前类中没找到，递归在父类中查找
-            if let Some(ref parent_name) = class_info.parent {
-                return self.find_method(parent_name, method_name, arg_types);
-            }
+        self.resolve_overload(class_name, method_name, arg_types).ok().flatten()
+    }
+
+    /// 重载解析（支持继承）：当前类有可调用的重载时不再查找父类
+    pub fn resolve_overload(&self, class_name: &str, method_name: &str, arg_types: &[Type]) -> Result<Option<&MethodInfo>, Vec<&MethodInfo>> {
+        let Some(class_info) = self.classes.get(class_name) else {
+            return Ok(None);
+        };
+        match class_info.resolve_overload(method_name, arg_types)? {
+            Some(method) => Ok(Some(method)),
+            None => match &class_info.parent {
+                Some(parent_name) => self.resolve_overload(parent_name, method_name, arg_types),
+                None => Ok(None),
+            },
         }
-        None
     }
 
     /// 根据类名、方法名和参数类型查找方法，只在当前类中查找（不递归父类）
@@ -66,12 +66,18 @@ fn compile_eol_expect_error(source_path: &str) -> Result<String, String> {
 
 /// 编译并运行 EOL 文件，期望执行失败（用于运行时错误测试），返回错误信息
 fn compile_and_run_expect_error(source_path: &str) -> Result<String, String> {
+    compile_and_run_expect_error_with_flags(source_path, &[])
+}
+
+/// 同 `compile_and_run_expect_error`，编译时附加额外的编译器选项（如 --bounds-check）
+fn compile_and_run_expect_error_with_flags(source_path: &str, flags: &[&str]) -> Result<String, String> {
     let exe_path = source_path.replace(".cay", ".exe");
     let ir_path = source_path.replace(".cay", ".ll");
 
     // 1. 编译 EOL -> EXE (使用 release 版本)
     let output = Command::new("./target/release/cayc.exe")
-        .args(&[source_path, &exe_path])
+        .args(flags)
+        .args([source_path, exe_path.as_str()])
         .output()
         .map_err(|e| format!("Failed to execute cayc: {}", e))?;
 
@@ -1960,6 +1966,16 @@ fn test_include_nested() {
             "Nested include test should pass, got: {}", output);
 }
 
+#[test]
+fn test_include_relative_chain() {
+    // 每一级包含都相对于直接包含它的文件所在目录解析
+    let output = compile_and_run_eol("examples/test_include_relative_chain.cay")
+        .expect("relative include chain should compile and run");
+    assert!(output.contains("6"), "macros from all three levels should be visible, got: {}", output);
+    assert!(output.contains("Relative include chain test PASSED!"),
+            "Relative include chain test should pass, got: {}", output);
+}
+
 #[test]
 fn test_include_pragma_once() {
     let output = compile_and_run_eol("examples/test_include_pragma_once.cay").expect("include pragma once should compile and run");
@@ -1967,6 +1983,15 @@ fn test_include_pragma_once() {
             "Pragma once test should pass (multiple includes handled correctly), got: {}", output);
 }
 
+#[test]
+fn test_include_explicit_pragma_once() {
+    let output = compile_and_run_eol("examples/test_include_explicit_pragma_once.cay")
+        .expect("explicit #pragma once should compile and run");
+    assert!(output.contains("14"), "header should be included once, got: {}", output);
+    assert!(output.contains("Explicit pragma once test PASSED!"),
+            "Explicit pragma once test should pass, got: {}", output);
+}
+
 #[test]
 fn test_error_include_cycle() {
     let error = compile_eol_expect_error("examples/errors/error_include_cycle.cay")
@@ -2148,3 +2173,1525 @@ fn test_0_4_3_simple() {
     assert!(output.contains("9999"),
             "Should output 9999 (final marker), got: {}", output);
 }
+
+#[test]
+fn test_field_chain() {
+    let output = compile_and_run_eol("examples/test_field_chain.cay")
+        .expect("Field chain test should compile and run");
+    assert!(output.contains("Value: 42"),
+            "Should read two-level field chain, got: {}", output);
+    assert!(output.contains("Label: nested"),
+            "Should read string field through chain, got: {}", output);
+    assert!(output.contains("Doubled: 84"),
+            "Should use field chain in expression, got: {}", output);
+    assert!(output.contains("Sum: 84"),
+            "Should access field chain through this, got: {}", output);
+    assert!(output.contains("Id: 1"),
+            "Should output id field, got: {}", output);
+}
+
+#[test]
+fn test_error_field_chain_primitive() {
+    let error = compile_eol_expect_error("examples/errors/error_field_chain_primitive.cay")
+        .expect("member access on primitive field should fail to compile");
+    assert!(
+        error.contains("Cannot access member") || error.contains("on type int"),
+        "Should report member access on primitive type, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_for_scope() {
+    let output = compile_and_run_eol("examples/test_for_scope.cay")
+        .expect("Consecutive for loops should compile and run");
+    assert!(output.contains("Simple: 20"),
+            "Should allow redeclaring loop variable in consecutive for loops, got: {}", output);
+    assert!(output.contains("Total: 25"),
+            "Should handle break/continue in scoped for loops, got: {}", output);
+    assert!(output.contains("Pairs: 7"),
+            "Should handle nested for loops, got: {}", output);
+}
+
+#[test]
+fn test_for_ssa_counter() {
+    let output = compile_and_run_eol("examples/test_for_ssa_counter.cay")
+        .expect("For loops with SSA counters should compile and run");
+    assert!(output.contains("Sum: 25"),
+            "Read-only counter should survive break/continue, got: {}", output);
+    assert!(output.contains("Steps: 10 7 4 1"),
+            "Long counter with custom step should work, got: {}", output);
+    assert!(output.contains("Acc: 38"),
+            "Nested counters used as array indices should work, got: {}", output);
+    assert!(output.contains("Visited: 5"),
+            "Counter written in body should fall back to a stack slot, got: {}", output);
+}
+
+#[test]
+fn test_method_ref_signature() {
+    let output = compile_and_run_eol("examples/test_method_ref_signature.cay")
+        .expect("Method references with non-trivial signatures should compile and run");
+    assert!(output.contains("Same method: equal"),
+            "References to the same method should be identical, got: {}", output);
+    assert!(output.contains("Different methods: distinct"),
+            "References to different methods should differ, got: {}", output);
+    assert!(output.contains("Instance method ref: ok"),
+            "Instance method references should produce a function pointer, got: {}", output);
+    assert!(output.contains("Object method ref: same method"),
+            "obj::method and this::method should resolve through the object's class, got: {}", output);
+    assert!(output.contains("pi is big"),
+            "Referenced methods should still be callable directly, got: {}", output);
+}
+
+#[test]
+fn test_bool_array_print() {
+    let output = compile_and_run_eol("examples/test_bool_array_print.cay")
+        .expect("Printing bool array elements should compile and run");
+    assert!(output.contains("flags[0] = true"), "Should print true for bool element, got: {}", output);
+    assert!(output.contains("flags[1] = false"), "Should print false for bool element, got: {}", output);
+    assert!(output.contains("marks[0] = false"), "Zero-initialized bool should print false, got: {}", output);
+    assert!(output.contains("marks[1] = true"), "Assigned bool element should print true, got: {}", output);
+    assert!(output.contains("!flags[2] = false"), "Negated bool element should print false, got: {}", output);
+    assert!(output.contains("both = true"), "Bool expression should print true, got: {}", output);
+}
+
+#[test]
+fn test_infinite_loop() {
+    let output = compile_and_run_eol("examples/test_infinite_loop.cay")
+        .expect("for(;;) and loop {} should compile and run");
+    assert!(output.contains("for(;;) count: 5"), "break should exit for(;;), got: {}", output);
+    assert!(output.contains("Fibonacci steps: 11, first above 100: 144"), "break should exit loop {{}}, got: {}", output);
+    assert!(output.contains("Sum without 4 and 8: 43"), "continue should work inside loop {{}}, got: {}", output);
+    assert!(output.contains("012"), "for loop without init clause should run, got: {}", output);
+}
+
+#[test]
+fn test_ternary_short_circuit() {
+    let output = compile_and_run_eol("examples/test_ternary_short_circuit.cay")
+        .expect("Ternary with side-effecting branches should compile and run");
+    assert!(output.contains("a = 1"), "Should select then branch, got: {}", output);
+    assert!(output.contains("b = 2"), "Should select else branch, got: {}", output);
+    assert!(output.contains("calls = 2"), "Only the selected branch should be evaluated, got: {}", output);
+    assert_eq!(output.matches("evaluated then").count(), 1, "then branch should run once, got: {}", output);
+    assert_eq!(output.matches("evaluated else").count(), 1, "else branch should run once, got: {}", output);
+    assert!(output.contains("evaluated medium") && !output.contains("evaluated big") && !output.contains("evaluated small"),
+        "Nested ternary should evaluate a single path, got: {}", output);
+    assert!(output.contains("c = 2"), "Nested ternary result should be 2, got: {}", output);
+    assert!(output.contains("d = 5000000000"), "Mixed int/long branches should be promoted, got: {}", output);
+    assert!(output.contains("e = 7"), "Side-effect free ternary should pick larger value, got: {}", output);
+    assert!(output.contains("s = greater"), "String ternary should work, got: {}", output);
+}
+
+#[test]
+fn test_int_var_width() {
+    let output = compile_and_run_eol("examples/test_int_var_width.cay")
+        .expect("int and long variables should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec!["2147483647", "-2147483648", "9223372036854775807", "2147483648", "65"],
+        "int/long variables should keep their full range, got: {}",
+        output
+    );
+}
+
+#[test]
+fn test_shift_comparison() {
+    let output = compile_and_run_eol("examples/test_shift_comparison.cay")
+        .expect("Shift followed by comparison should compile and run");
+    assert!(output.contains("x >> 2 > 1: true"), "Shift should bind tighter than >, got: {}", output);
+    assert!(output.contains("x > 2: true"), "Plain > should work, got: {}", output);
+    assert!(output.contains("x >>> 3 >= 8: true"), ">>> followed by >= should work, got: {}", output);
+    assert!(output.contains("x>>1>x>>2: true"), "Unspaced shifts and > should tokenize correctly, got: {}", output);
+    assert!(output.contains("x>>>2>=17: false"), "Unspaced >>> and >= should tokenize correctly, got: {}", output);
+    assert!(output.contains("y >> 2 = -4"), "Arithmetic shift should keep sign, got: {}", output);
+    assert!(output.contains("y >>> 28 = 15"), "Logical shift should fill with zeros, got: {}", output);
+}
+
+#[test]
+fn test_address_of() {
+    let output = compile_and_run_eol("examples/test_address_of.cay")
+        .expect("addressOf should compile and run");
+    assert!(output.contains("alias address: same"), "Two references to one object should share an address, got: {}", output);
+    assert!(output.contains("other address: different"), "Distinct objects should have different addresses, got: {}", output);
+    assert!(output.contains("array address: same"), "Array aliases should share an address, got: {}", output);
+    assert!(output.contains("string address: same"), "String aliases should share an address, got: {}", output);
+    assert!(output.contains("non-null: yes"), "Object address should be non-zero, got: {}", output);
+}
+
+#[test]
+fn test_switch_explicit_fallthrough() {
+    let output = compile_and_run_eol("examples/test_switch_explicit_fallthrough.cay")
+        .expect("switch with explicit fallthrough should compile and run");
+    assert!(output.contains("1: one\n") || output.contains("1: one\r\n"), "Case without break should not fall through, got: {}", output);
+    assert!(output.contains("2: two three"), "fallthrough should continue into the next case, got: {}", output);
+    assert!(output.contains("3: three"), "Case 3 should run alone, got: {}", output);
+    assert!(output.contains("4: four-or-five default"), "Empty case should share the next body, got: {}", output);
+    assert!(output.contains("5: four-or-five default"), "fallthrough into default should work, got: {}", output);
+    assert!(output.contains("9: default"), "Unmatched value should run default, got: {}", output);
+    assert!(output.lines().any(|l| l.trim_end() == "1,1: inner-one"), "Nested switch ending a case should not fall through, got: {}", output);
+    assert!(output.lines().any(|l| l.trim_end() == "1,2: inner-default"), "Nested switch default should run, got: {}", output);
+    assert!(output.lines().any(|l| l.trim_end() == "2,1: outer-default"), "Outer default should skip the nested switch, got: {}", output);
+}
+
+#[test]
+fn test_inherited_fields() {
+    let output = compile_and_run_eol("examples/test_inherited_fields.cay")
+        .expect("Inherited instance fields should compile and run");
+    assert!(output.contains("Rex has 4 legs and knows 2 tricks"), "Subclass methods should access parent fields, got: {}", output);
+    assert!(output.contains("d.legs = 4"), "Parent field should be readable through subclass object, got: {}", output);
+    assert!(output.contains("d.tricks = 2"), "Own field should follow parent fields, got: {}", output);
+    assert!(output.contains("legsOf(dog) = 4"), "Parent field offset should match in subclass object, got: {}", output);
+    assert!(output.contains("Bit: legs=3 tricks=1 age=1"), "Multi-level inheritance should keep all fields, got: {}", output);
+    assert!(output.contains("legsOf(puppy) = 3"), "Grandchild should keep parent field offset, got: {}", output);
+    assert!(output.contains("a.legs = 2"), "Parent class object should still work, got: {}", output);
+}
+
+#[test]
+fn test_array_length_fold() {
+    let output = compile_and_run_eol("examples/test_array_length_fold.cay")
+        .expect("Array length folding example should compile and run");
+    assert!(output.contains("a.length = 3"), "Literal array length should be 3, got: {}", output);
+    assert!(output.contains("a.length after element write = 3"), "Element writes should keep length, got: {}", output);
+    assert!(output.contains("b.length = 2"), "Reassigned array should report initial length, got: {}", output);
+    assert!(output.contains("b.length after reassignment = 7"), "Reassigned array should read length at runtime, got: {}", output);
+    assert!(output.contains("c.length = 4"), "Dynamic array length should be read from header, got: {}", output);
+    assert!(output.contains("sum = 14"), "Loop bounded by folded length should visit all elements, got: {}", output);
+}
+
+#[test]
+fn test_nested_ternary() {
+    let output = compile_and_run_eol("examples/test_nested_ternary.cay")
+        .expect("Nested ternary should compile and run");
+    assert!(output.contains("-5: negative"), "First condition should win, got: {}", output);
+    assert!(output.contains("0: zero"), "Nested else-branch ternary should be evaluated, got: {}", output);
+    assert!(output.contains("7: small"), "Deeply nested ternary should be evaluated, got: {}", output);
+    assert!(output.contains("42: large"), "Final else branch should be reached, got: {}", output);
+    assert!(output.contains("sign(-3) = -1"), "Ternary in then-branch should parse, got: {}", output);
+    assert!(output.contains("sign(0) = 0"), "Ternary in then-branch should evaluate, got: {}", output);
+    assert!(output.contains("sign(9) = 1"), "Ternary in then-branch should evaluate, got: {}", output);
+    assert!(output.contains("d = 3.0"), "Nested branches should be promoted to double, got: {}", output);
+}
+
+#[test]
+fn test_const_declarations() {
+    let output = compile_and_run_eol("examples/test_const.cay")
+        .expect("const declarations should compile and run");
+    assert!(output.contains("24"), "const should be usable in expressions, got: {}", output);
+    assert!(output.contains("8"), "const should be usable as array size, got: {}", output);
+    assert!(output.contains("green"), "const should be usable as case label, got: {}", output);
+    assert!(output.contains("blue"), "qualified const expression should be usable as case label, got: {}", output);
+    assert!(output.contains("unknown"), "unmatched value should skip const cases, got: {}", output);
+    assert!(output.contains("1099511627776"), "long const should be folded, got: {}", output);
+    assert!(output.contains("const ok"), "String const should be substituted, got: {}", output);
+}
+
+#[test]
+fn test_const_shadowing() {
+    let output = compile_and_run_eol("examples/test_const_shadowing.cay")
+        .expect("locals shadowing a class const should compile and run");
+    assert!(output.contains("100\n6\n7\n100\n"), "locals and params should shadow the const, got: {}", output);
+}
+
+#[test]
+fn test_forward_method_ref() {
+    let output = compile_and_run_eol("examples/test_forward_method_ref.cay")
+        .expect("Forward method references should compile and run");
+    assert!(output.contains("42"), "Static method declared later should be callable, got: {}", output);
+    assert!(output.contains("5"), "Instance method declared later should be callable, got: {}", output);
+    assert!(output.contains("10"), "Instance method should call a later method, got: {}", output);
+}
+
+#[test]
+fn test_do_while_break() {
+    let output = compile_and_run_eol("examples/test_do_while_break.cay")
+        .expect("do-while with break should compile and run");
+    assert!(output.contains("runs = 1"), "Loop body should run exactly once, got: {}", output);
+    assert!(output.contains("checks = 0"), "break should skip the condition, got: {}", output);
+    assert!(output.contains("once = 1"), "do-while body should run once even if the condition is false, got: {}", output);
+    assert!(output.contains("n = 3"), "Conditional break should stop the loop, got: {}", output);
+    assert!(output.contains("checks = 2"), "Condition should not be evaluated after break, got: {}", output);
+}
+
+#[test]
+fn test_tuple() {
+    let output = compile_and_run_eol("examples/test_tuple.cay")
+        .expect("tuple example should compile and run");
+    assert!(output.contains("q = 6"), "First tuple element should be destructured, got: {}", output);
+    assert!(output.contains("r = inexact"), "Second tuple element should be destructured, got: {}", output);
+    assert!(output.contains("first = 2"), "Tuple-typed local should destructure, got: {}", output);
+    assert!(output.contains("scaled = 3000000000"), "int element should widen to long, got: {}", output);
+    assert!(output.contains("half = 0.5"), "int element should widen to double, got: {}", output);
+    assert!(output.contains("a = 7"), "Tuple literal should destructure, got: {}", output);
+    assert!(output.contains("b = true"), "bool tuple element should destructure, got: {}", output);
+}
+
+#[test]
+fn test_repeat() {
+    let output = compile_and_run_eol("examples/test_repeat.cay")
+        .expect("repeat example should compile and run");
+    assert!(output.contains("runs = 5"), "repeat body should run exactly 5 times, got: {}", output);
+    assert!(output.contains("total = 30"), "repeat count should be evaluated once, got: {}", output);
+    assert!(output.contains("odd = 4"), "continue should skip to the next iteration, got: {}", output);
+    assert!(output.contains("step = 8"), "break should leave the repeat loop, got: {}", output);
+    assert!(!output.contains("never"), "repeat (0) should not run its body, got: {}", output);
+    assert!(output.contains("****"), "repeat should accept a long count, got: {}", output);
+}
+
+#[test]
+fn test_to_string_base() {
+    let output = compile_and_run_eol("examples/test_to_string_base.cay")
+        .expect("toString with a base should compile and run");
+    assert!(output.contains("hex = ff"), "Base 16 formatting failed, got: {}", output);
+    assert!(output.contains("oct = 10"), "Base 8 formatting failed, got: {}", output);
+    assert!(output.contains("bin = 1010"), "Base 2 formatting failed, got: {}", output);
+    assert!(output.contains("dec = -42"), "Base 10 formatting failed, got: {}", output);
+    assert!(output.contains("big hex = 100000000"), "long values should be formatted, got: {}", output);
+    assert!(output.contains("neg hex = -ff"), "Negative values should keep their sign, got: {}", output);
+    assert!(output.contains("zero = 0"), "Zero should format as a single digit, got: {}", output);
+    assert!(output.contains("print bin = 101"), "println(value, base) failed, got: {}", output);
+    assert!(output.contains("print oct = 100"), "print(value, base) failed, got: {}", output);
+}
+
+#[test]
+fn test_static_assert() {
+    let output = compile_and_run_eol("examples/test_static_assert.cay")
+        .expect("passing static asserts should compile and run");
+    assert!(output.contains("payload: 12"), "program should run after static asserts, got: {}", output);
+    assert!(output.contains("static asserts passed"), "static asserts should not affect runtime, got: {}", output);
+}
+
+#[test]
+fn test_labeled_block() {
+    let output = compile_and_run_eol("examples/test_labeled_block.cay")
+        .expect("labeled blocks should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(&lines[..3], ["-1", "0", "1"], "break label should exit the labeled block, got: {}", output);
+    assert!(output.contains("found: 15"), "break label should exit a block from inside a loop, got: {}", output);
+    assert!(!output.contains("not reached"), "statements after break label should be skipped, got: {}", output);
+    assert!(output.contains("inside inner") && !output.contains("skipped"), "break should exit the named outer block, got: {}", output);
+    assert!(output.contains("after outer"), "execution should continue after the labeled block, got: {}", output);
+    assert!(output.contains("loop exited at 3"), "plain break should still target the innermost loop, got: {}", output);
+}
+
+#[test]
+fn test_read_file() {
+    let data_path = "target/cavvy_read_file.txt";
+    fs::write(data_path, "first line\nsecond line\n").expect("should write temp file");
+    let output = compile_and_run_eol("examples/test_read_file.cay");
+    let _ = fs::remove_file(data_path);
+
+    let output = output.expect("readFile should compile and run");
+    assert!(output.contains("length: 23"), "readFile should return the whole file, got: {}", output);
+    assert!(output.contains("first line") && output.contains("second line"), "file content should be printed, got: {}", output);
+    assert!(output.contains("missing length: 0"), "missing file should read as empty string, got: {}", output);
+}
+
+#[test]
+fn test_write_file() {
+    let output = compile_and_run_eol("examples/test_write_file.cay");
+    let written = fs::read_to_string("target/cavvy_write_file.txt");
+    let _ = fs::remove_file("target/cavvy_write_file.txt");
+
+    let output = output.expect("writeFile should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec!["true", "true", "alpha", "beta", "gamma", "short", "false"],
+        "writeFile should write content that readFile reads back, got: {}",
+        output
+    );
+    assert_eq!(written.expect("written file should exist"), "short", "second write should overwrite the file");
+}
+
+#[test]
+fn test_char_int_cast() {
+    let output = compile_and_run_eol("examples/test_char_int_cast.cay")
+        .expect("char/int casts should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec!["true", "true", "65", "true", "200", "200", "65"],
+        "(char) should truncate and (int) should zero-extend chars, got: {}",
+        output
+    );
+}
+
+#[test]
+fn test_class_name_shadowing() {
+    let output = compile_and_run_eol("examples/test_class_name_shadowing.cay")
+        .expect("class names as static prefixes should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(lines, vec!["11", "6"], "local variable should shadow the class name, got: {}", output);
+}
+
+#[test]
+fn test_long_print() {
+    let output = compile_and_run_eol("examples/test_long_print.cay")
+        .expect("long printing should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "-9000000000",
+            "9223372036854775806",
+            "-9223372036854775807",
+            "9223372036854775807",
+            "-9000000000",
+            "value: 9223372036854775807",
+            "9223372036854775806",
+        ],
+        "long values should print with all digits and the correct sign, got: {}",
+        output
+    );
+}
+
+#[test]
+fn test_predefined_macros() {
+    let output = compile_and_run_eol("examples/test_predefined_macros.cay")
+        .expect("predefined macros should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "file: examples/test_predefined_macros.cay".to_string(),
+            "5".to_string(),
+            format!("version: {}", env!("CARGO_PKG_VERSION")),
+        ],
+        "__FILE__, __LINE__ and __CAVVY_VERSION__ should expand in place, got: {}",
+        output
+    );
+}
+
+#[test]
+fn test_print_precision() {
+    let output = compile_and_run_eol("examples/test_print_precision.cay")
+        .expect("print with a precision should compile and run");
+    assert!(output.contains("3.14\n"), "println(double, 2) should print two decimals, got: {}", output);
+    assert!(output.contains("3.1416"), "print(double, 4) should print four decimals, got: {}", output);
+    assert!(output.contains("2.500"), "float values should honour the precision, got: {}", output);
+    assert!(output.contains("10.0\n"), "a runtime precision should be supported, got: {}", output);
+    assert!(output.contains("ff"), "integers should still be printed with a base, got: {}", output);
+    assert!(output.contains("Print precision test PASSED!"), "Print precision test should pass, got: {}", output);
+}
+
+#[test]
+fn test_inc_dec_types() {
+    let output = compile_and_run_eol("examples/test_inc_dec_types.cay")
+        .expect("++/-- on long, float and double should compile and run");
+    assert!(output.contains("4294967298"), "long post-decrement should return the old value, got: {}", output);
+    assert!(output.contains("4294967297"), "long should be decremented, got: {}", output);
+    assert!(output.contains("3.500000"), "float pre-increment should return the new value, got: {}", output);
+    assert!(output.contains("1.250000"), "double should be incremented, got: {}", output);
+    assert!(output.contains("Inc/dec types test PASSED!"), "Inc/dec types test should pass, got: {}", output);
+}
+
+#[test]
+fn test_overload_widening() {
+    let output = compile_and_run_eol("examples/test_overload_widening.cay")
+        .expect("overload resolution with widening should compile and run");
+    assert!(output.contains("int\nlong\ndouble"), "exact matches should be preferred, got: {}", output);
+    assert!(output.contains("42"), "int argument should widen to long, got: {}", output);
+    assert!(output.contains("2.500000"), "int argument should widen to double, got: {}", output);
+    assert!(output.contains("Overload widening test PASSED!"), "Overload widening test should pass, got: {}", output);
+}
+
+#[test]
+fn test_printf() {
+    let output = compile_and_run_eol("examples/test_printf.cay")
+        .expect("printf should compile and run");
+    assert!(output.contains("5 and hi"), "printf with int and string failed, got: {}", output);
+    assert!(output.contains("long: 9000000000, hex: ff"), "long arguments should print in full, got: {}", output);
+    assert!(output.contains("float: 1.50, double: 2.250"), "float should be promoted to double, got: {}", output);
+    assert!(output.contains("char: A, bool: 1"), "char and bool should be promoted to int, got: {}", output);
+    assert!(output.contains("width: [   42] [42   ] [   7]"), "width and '*' should work, got: {}", output);
+    assert!(output.contains("percent: 100%"), "%% should print a percent sign, got: {}", output);
+    assert!(output.contains("Printf test PASSED!"), "Printf test should pass, got: {}", output);
+}
+
+#[test]
+fn test_array_length_header() {
+    let output = compile_and_run_eol("examples/test_array_length_header.cay")
+        .expect("array lengths should be available for every kind of array");
+    assert!(output.contains("3\n4\n5\n"), "outer, inner and static array lengths are wrong, got: {}", output);
+    assert!(output.contains("Array length header test PASSED!"), "Array length header test should pass, got: {}", output);
+}
+
+#[test]
+fn test_array_clone() {
+    let output = compile_and_run_eol("examples/test_array_clone.cay")
+        .expect("array clone should compile and run");
+    assert!(output.contains("1\n4\n100\n400\n4\n"), "mutating the copy must not change the original, got: {}", output);
+    assert!(output.contains("2.500000"), "double arrays should be cloned, got: {}", output);
+    assert!(output.contains("b\nz\n"), "String arrays should be cloned, got: {}", output);
+    assert!(output.contains("Array clone test PASSED!"), "Array clone test should pass, got: {}", output);
+}
+
+#[test]
+fn test_array_equals() {
+    let output = compile_and_run_eol("examples/test_array_equals.cay")
+        .expect("arrayEquals should compile and run");
+    assert!(output.contains("true\nfalse\nfalse\ntrue\nfalse\n"), "arrayEquals should compare lengths and elements, got: {}", output);
+    assert!(output.contains("Array equals test PASSED!"), "Array equals test should pass, got: {}", output);
+}
+
+#[test]
+fn test_this_shadowing() {
+    let output = compile_and_run_eol("examples/test_this_shadowing.cay")
+        .expect("this.field / this.method() test should compile and run");
+    assert!(output.contains("50\nalice\nalice!\n107\n"), "this should resolve to the current object's members, got: {}", output);
+    assert!(output.contains("This shadowing test PASSED!"), "This shadowing test should pass, got: {}", output);
+}
+
+#[test]
+fn test_long_for_counter() {
+    let output = compile_and_run_eol("examples/test_long_for_counter.cay")
+        .expect("long for counter test should compile and run");
+    assert!(output.contains("3000000000\n3000000001\n3000000002\n3000000003\n12000000006\n"),
+            "long counters beyond the int range must not be truncated, got: {}", output);
+    assert!(output.contains("2147483646\n2147483647\n2147483648\n3\n"),
+            "long counters should cross the int maximum, got: {}", output);
+    assert!(output.contains("\n3\nLong for counter test PASSED!"), "decrementing long counter should run 3 times, got: {}", output);
+}
+
+#[test]
+fn test_char_escapes() {
+    let output = compile_and_run_eol("examples/test_char_escapes.cay")
+        .expect("char escapes test should compile and run");
+    assert!(output.contains("65\n9\n0\n122\n39\n92\n"), "char escapes should decode to their code points, got: {}", output);
+    assert!(output.contains("Char escapes test PASSED!"), "Char escapes test should pass, got: {}", output);
+}
+
+#[test]
+fn test_ternary_print() {
+    let output = compile_and_run_eol("examples/test_ternary_print.cay")
+        .expect("ternary print test should compile and run");
+    assert!(output.contains("pos\nneg\ndown\nsmall\nnegative\n1\n1.500000\nsign: -\n"),
+            "print should output the selected branch, got: {}", output);
+    assert!(output.contains("Ternary print test PASSED!"), "Ternary print test should pass, got: {}", output);
+}
+
+#[test]
+fn test_switch_char() {
+    let output = compile_and_run_eol("examples/test_switch_char.cay")
+        .expect("switch over char should compile and run");
+    assert!(output.contains("vowel\nother\nseparator\ntab\nzero\n"), "char case labels should match by code value, got: {}", output);
+    assert!(output.contains("\n1\nSwitch char test PASSED!"), "switch inside a loop should count the vowels, got: {}", output);
+}
+
+#[test]
+fn test_long_to_string_limits() {
+    let output = compile_and_run_eol("examples/test_long_to_string_limits.cay")
+        .expect("long to string conversion should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "max: 9223372036854775807",
+            "min: -9223372036854775808",
+            "length: 20",
+            "neg one: -1",
+        ],
+        "long extremes should keep every digit and the sign"
+    );
+}
+
+#[test]
+fn test_sized_ints() {
+    let output = compile_and_run_eol("examples/test_sized_ints.cay")
+        .expect("explicit-width integers should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "-128",
+            "-84",
+            "-100",
+            "-100",
+            "-200",
+            "56",
+            "4464",
+            "2147483648",
+            "-128 4464",
+            "switch ok",
+            "Sized ints test PASSED!",
+        ],
+        "int8 should wrap at 256 and sign-extend when widened"
+    );
+}
+
+#[test]
+fn test_object_array() {
+    let output = compile_and_run_eol("examples/test_object_array.cay")
+        .expect("object arrays should compile and run");
+    assert!(output.contains("1\n20\n22\n33\n"), "fields and methods should be reachable through array elements, got: {}", output);
+    assert!(output.contains("42\n17\nnull element\n"), "element stores and object array literals should keep object pointers, got: {}", output);
+    assert!(output.contains("42\n99\n"), "array fields of objects should share the stored elements, got: {}", output);
+    assert!(output.contains("Object array test PASSED!"), "Object array test should pass, got: {}", output);
+}
+
+#[test]
+fn test_do_while_false() {
+    let output = compile_and_run_eol("examples/test_do_while_false.cay")
+        .expect("do-while(false) blocks should compile and run");
+    assert!(output.contains("1\n1\ntoo large\n"), "the block should run exactly once and break/continue should leave it, got: {}", output);
+    assert!(output.contains("Do-while false test PASSED!"), "Do-while false test should pass, got: {}", output);
+}
+
+#[test]
+fn test_entry_annotation() {
+    let output = compile_and_run_eol("examples/test_entry_annotation.cay")
+        .expect("@entry annotation example should compile and run");
+    assert!(output.contains("EntryClass is the entry point!"), "@entry should select the main class like @main, got: {}", output);
+    assert!(!output.contains("This should not be the entry point!"), "Should not output from HelperClass, got: {}", output);
+}
+
+#[test]
+fn test_object_equality() {
+    let output = compile_and_run_eol("examples/test_object_equality.cay")
+        .expect("object and string equality should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "false", "true", "true", "false", "false",
+            "true", "false", "false", "true", "true",
+            "true", "false", "false",
+            "Object equality test PASSED!",
+        ],
+        "objects should compare by identity and strings by content"
+    );
+}
+
+#[test]
+fn test_final_field_constructor() {
+    let output = compile_and_run_eol("examples/test_final_field_constructor.cay")
+        .expect("final fields assigned in constructors should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec!["7", "500", "20", "8", "0", "Final field constructor test PASSED!"],
+        "constructors should run with their arguments and set final fields once"
+    );
+}
+
+#[test]
+fn test_string_compare() {
+    let output = compile_and_run_eol("examples/test_string_compare.cay")
+        .expect("String.compareTo should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "true", "true", "0", "true", "true", "true", "0", "true", "fig",
+            "String compareTo test PASSED!",
+        ],
+        "compareTo should order strings lexicographically and return 0 for equal content"
+    );
+}
+
+#[test]
+fn test_array_arguments() {
+    let output = compile_and_run_eol("examples/test_array_arguments.cay")
+        .expect("array arguments should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec!["24", "60", "10", "5000000007", "42", "3", "4", "-1", "3", "Array arguments test PASSED!"],
+        "arrays should reach the callee as pointers, including null arguments"
+    );
+}
+
+#[test]
+fn test_arrow_method() {
+    let output = compile_and_run_eol("examples/test_arrow_method.cay")
+        .expect("arrow method bodies should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec!["49", "27", "9", "Hello, Cavvy", "10", "24", "true", "Arrow method test PASSED!"],
+        "'-> expr;' bodies should return the expression"
+    );
+}
+
+#[test]
+fn test_floor_mod() {
+    let output = compile_and_run_eol("examples/test_floor_mod.cay")
+        .expect("floorMod should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "-1", "2", "1", "-2", "-1", "0", "2", "2",
+            "2147483646", "0", "2147483645", "9223372036854775806", "-2",
+            "3", "floorMod test PASSED!",
+        ],
+        "floorMod should take the sign of the divisor while % keeps the sign of the dividend"
+    );
+}
+
+#[test]
+fn test_unary_char() {
+    let output = compile_and_run_eol("examples/test_unary_char.cay")
+        .expect("unary +/- on char should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec!["-65", "true", "-122", "122", "-200", "5", "8", "Unary char test PASSED!"],
+        "unary +/- should promote char to int before negating"
+    );
+}
+
+#[test]
+fn test_float_to_string() {
+    let output = compile_and_run_eol("examples/test_float_to_string.cay")
+        .expect("float to string example should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "pi = 3.14",
+            "three = 3.0",
+            "hundred = 100.0",
+            "tenth = 0.1",
+            "third = 0.3333333333333333",
+            "negative = -2.5",
+            "large = 1.5e+20",
+            "tiny = 1e-05",
+            "float = 3.14",
+            "float cast = 2.0",
+            "All float to string tests completed!",
+        ],
+        "floats should use the shortest round-trip form without trailing zeros"
+    );
+}
+
+#[test]
+fn test_object_from_method() {
+    let output = compile_and_run_eol("examples/test_object_from_method.cay")
+        .expect("object from method example should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "auto from method = 2",
+            "auto from new = 1",
+            "chained = 1",
+            "assigned = 3",
+        ]
+    );
+}
+
+#[test]
+fn test_switch_default_position() {
+    let output = compile_and_run_eol("examples/test_switch_default_position.cay")
+        .expect("switch with default before cases should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "first 1: one",
+            "first 2: two",
+            "first 7: default",
+            "middle 1: one default",
+            "middle 2: two",
+            "middle 7: default",
+            "shared 1: one",
+            "shared 2: two-or-other",
+            "shared 7: two-or-other",
+        ]
+    );
+}
+
+#[test]
+fn test_var_inference() {
+    let output = compile_and_run_eol("examples/test_var_inference.cay")
+        .expect("var type inference example should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "count = 6",
+            "ratio = 5.0",
+            "name = Cavvy (5)",
+            "sum = 10",
+            "big = 10000000001",
+            "halved = 2.5",
+            "limit = 10",
+        ]
+    );
+}
+
+#[test]
+fn test_string_empty_blank() {
+    let output = compile_and_run_eol("examples/test_string_empty_blank.cay")
+        .expect("String.isEmpty/isBlank should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "empty: isEmpty=true isBlank=true",
+            "spaces: isEmpty=false isBlank=true",
+            "tabs: isEmpty=false isBlank=true",
+            "text: isEmpty=false isBlank=false",
+            "padded: isEmpty=false isBlank=false",
+            "name is empty",
+            "Cavvy is not blank",
+        ]
+    );
+}
+
+#[test]
+fn test_list() {
+    let output = compile_and_run_eol("examples/test_list.cay")
+        .expect("List should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec!["0", "20", "0", "49", "361", "2470", "hello world", "3.500000", "7", "3", "2", "pear"]
+    );
+}
+
+#[test]
+fn test_array_param_classes() {
+    let output = compile_and_run_eol("examples/test_array_param_classes.cay")
+        .expect("object array params with the same name should compile and run");
+    assert!(output.contains("33"), "each function should resolve its own array element class, got: {}", output);
+}
+
+#[test]
+fn test_int8_widening() {
+    let output = compile_and_run_eol("examples/test_int8_widening.cay")
+        .expect("int8 widening should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec!["-2", "-7", "-3", "-5", "-2 -7", "switch ok", "65", "n=65", "n=-2", "c=A", "200"],
+        "int8 array elements, call results and fields should sign-extend and print as numbers, char should zero-extend"
+    );
+}
+
+#[test]
+fn test_string_equality_sources() {
+    let output = compile_and_run_eol("examples/test_string_equality_sources.cay")
+        .expect("string equality on computed strings should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec!["true", "true", "false", "true", "true"],
+        "strings from array elements and builtin or method calls should compare by content"
+    );
+}
+
+#[test]
+fn test_argument_widening() {
+    let output = compile_and_run_eol("examples/test_argument_widening.cay")
+        .expect("widening method, constructor and printf arguments should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "-2", "-600", "130", "-3.000000", "65.000000",
+            "-3", "65", "-300",
+            "-3 -300 A",
+        ],
+        "int8/int16 arguments should sign-extend and char arguments should zero-extend"
+    );
+}
+
+#[test]
+fn test_list_params() {
+    let output = compile_and_run_eol("examples/test_list_params.cay")
+        .expect("List parameters and return values should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec!["42", "7", "8", "3.000000", "abcd"],
+        "List parameters and return values should keep the caller's element type"
+    );
+}
+
+#[test]
+fn test_char_widening() {
+    let output = compile_and_run_eol("examples/test_char_widening.cay")
+        .expect("implicit char widening should compile and run");
+    let lines: Vec<&str> = output.lines().map(|l| l.trim()).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "200.000000", "200", "200", "200", "200", "200", "200", "200", "200", "200", "200",
+            "true", "true", "201", "100.000000", "-2", "-2.000000", "true",
+        ],
+        "char should widen as an unsigned code in casts, stores, returns and arithmetic"
+    );
+}
+
+#[test]
+fn test_string_view() {
+    let output = compile_and_run_eol("examples/test_string_view.cay")
+        .expect("StringView test should compile and run");
+    assert!(output.contains("key\nvalue42\n"),
+            "Should print views with their own length, got: {}", output);
+    assert!(output.contains("Key length: 3"),
+            "Should output view length, got: {}", output);
+    assert!(output.contains("First char: v"),
+            "Should read char from view, got: {}", output);
+    assert!(output.contains("Key equals: true"),
+            "Should compare view with string, got: {}", output);
+    assert!(output.contains("Value differs: true"),
+            "Should detect different lengths, got: {}", output);
+    assert!(output.contains("Copy: value42!"),
+            "Should convert view to string, got: {}", output);
+    assert!(output.contains("Digits: 2"),
+            "Should pass view by value, got: {}", output);
+    assert!(output.contains("e42;"),
+            "Should clamp out of range end index, got: {}", output);
+}
+
+#[test]
+fn test_subview_matches_substring() {
+    let substring_output = compile_and_run_eol("examples/test_substring_loop.cay")
+        .expect("substring loop should compile and run");
+    let subview_output = compile_and_run_eol("examples/test_subview_loop.cay")
+        .expect("subview loop should compile and run");
+
+    assert!(substring_output.contains("Matches: 10000") && substring_output.contains("Total: 40000"),
+            "substring loop output mismatch, got: {}", substring_output);
+    assert_eq!(substring_output, subview_output,
+               "subview should produce the same result as substring");
+}
+
+#[test]
+fn test_string_valueof() {
+    let output = compile_and_run_eol("examples/test_string_valueof.cay")
+        .expect("String.valueOf test should compile and run");
+    assert!(output.contains("int: 42"),
+            "Should convert int, got: {}", output);
+    assert!(output.contains("long: 9000000000"),
+            "Should convert long, got: {}", output);
+    assert!(output.contains("float: 1.5"),
+            "Should convert float, got: {}", output);
+    assert!(output.contains("double: 2.25"),
+            "Should convert double, got: {}", output);
+    assert!(output.contains("bool: true"),
+            "Should convert bool, got: {}", output);
+    assert!(output.contains("char: Z"),
+            "Should convert char, got: {}", output);
+    assert!(output.contains("bare: 43"),
+            "Should support bare valueOf builtin, got: {}", output);
+    assert!(output.contains("false: false"),
+            "Should convert bool literal, got: {}", output);
+    assert!(output.contains("length: 5"),
+            "Should return a usable String, got: {}", output);
+}
+
+#[test]
+fn test_error_string_valueof_object() {
+    let error = compile_eol_expect_error("examples/errors/error_string_valueof_object.cay")
+        .expect("String.valueOf with object argument should fail to compile");
+    assert!(
+        error.contains("No String.valueOf() overload"),
+        "Should report missing valueOf overload, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_identity_ops() {
+    let output = compile_and_run_eol("examples/test_identity_ops.cay")
+        .expect("Identity operators test should compile and run");
+    assert!(output.contains("a === a: true"),
+            "Object should be identical to itself, got: {}", output);
+    assert!(output.contains("a === c: true"),
+            "Aliased references should be identical, got: {}", output);
+    assert!(output.contains("a !== b: true"),
+            "Distinct objects should not be identical, got: {}", output);
+    assert!(output.contains("new !== new: true"),
+            "Two new objects should not be identical, got: {}", output);
+    assert!(output.contains("a.is(c): true") && output.contains("a.is(b): false"),
+            "is() should compare identity, got: {}", output);
+    assert!(output.contains("s !== copy: true"),
+            "Equal strings at different addresses should not be identical, got: {}", output);
+    assert!(output.contains("a !== null: true"),
+            "Should compare with null, got: {}", output);
+}
+
+#[test]
+fn test_error_identity_primitive() {
+    let error = compile_eol_expect_error("examples/errors/error_identity_primitive.cay")
+        .expect("=== on primitives should fail to compile");
+    assert!(
+        error.contains("requires reference type operands"),
+        "Should report non-reference operands, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_string_method_args() {
+    let error = compile_eol_expect_error("examples/errors/error_string_method_args.cay")
+        .expect("String method with wrong argument types should fail to compile");
+    assert!(
+        error.contains("Method 'substring' in class 'String' cannot be applied"),
+        "Should resolve String methods through the type registry, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_address_of_primitive() {
+    let error = compile_eol_expect_error("examples/errors/error_address_of_primitive.cay")
+        .expect("addressOf on a primitive should fail to compile");
+    assert!(
+        error.contains("addressOf() requires an object, array or string"),
+        "Should reject primitive addressOf argument, got: {}",
+        error
+    );
+}
+
+
+#[test]
+fn test_error_fallthrough_not_last() {
+    let error = compile_eol_expect_error("examples/errors/error_fallthrough_not_last.cay")
+        .expect("fallthrough followed by statements should fail to compile");
+    assert!(
+        error.contains("'fallthrough' must be the last statement of a switch case"),
+        "Should reject statements after fallthrough, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_const_not_constant() {
+    let error = compile_eol_expect_error("examples/errors/error_const_not_constant.cay")
+        .expect("const with a non-constant initializer should fail to compile");
+    assert!(
+        error.contains("Initializer of constant 'LIMIT' is not a compile-time constant"),
+        "Should reject non-constant const initializer, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_const_reassign() {
+    let error = compile_eol_expect_error("examples/errors/error_const_reassign.cay")
+        .expect("assigning to a const should fail to compile");
+    assert!(
+        error.contains("Cannot assign a value to constant 'MAX'"),
+        "Should reject const reassignment, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_tuple_destructure_arity() {
+    let error = compile_eol_expect_error("examples/errors/error_tuple_destructure_arity.cay")
+        .expect("destructuring a tuple into the wrong number of names should fail to compile");
+    assert!(
+        error.contains("Cannot destructure (int, int) into 3 variables"),
+        "Should reject tuple arity mismatch, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_repeat_non_integer() {
+    let error = compile_eol_expect_error("examples/errors/error_repeat_non_integer.cay")
+        .expect("repeat with a non-integer count should fail to compile");
+    assert!(
+        error.contains("repeat count must be an integer, got double"),
+        "Should reject non-integer repeat count, got: {}",
+        error
+    );
+}
+
+
+#[test]
+fn test_error_to_string_bad_base() {
+    let error = compile_eol_expect_error("examples/errors/error_to_string_bad_base.cay")
+        .expect("toString with an unsupported base should fail to compile");
+    assert!(
+        error.contains("Unsupported base 3 for toString(): expected 2, 8, 10 or 16"),
+        "Should reject unsupported base, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_to_string_base_not_literal() {
+    let error = compile_eol_expect_error("examples/errors/error_to_string_base_not_literal.cay")
+        .expect("toString with a non-literal base should fail to compile");
+    assert!(
+        error.contains("The base of toString() must be an integer literal"),
+        "Should reject non-literal base, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_static_assert_failed() {
+    let error = compile_eol_expect_error("examples/errors/error_static_assert_failed.cay")
+        .expect("failing static_assert should fail to compile");
+    assert!(
+        error.contains("Static assertion failed: VERSION must be at least 4"),
+        "Should report the static_assert message, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_static_assert_not_constant() {
+    let error = compile_eol_expect_error("examples/errors/error_static_assert_not_constant.cay")
+        .expect("static_assert over a runtime value should fail to compile");
+    assert!(
+        error.contains("static_assert condition is not a compile-time constant"),
+        "Should reject non-constant static_assert condition, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_break_undefined_label() {
+    let error = compile_eol_expect_error("examples/errors/error_break_undefined_label.cay")
+        .expect("break with an unknown label should fail to compile");
+    assert!(
+        error.contains("Undefined label 'finished' in break statement"),
+        "Should reject break to an undefined label, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_read_file_non_string() {
+    let error = compile_eol_expect_error("examples/errors/error_read_file_non_string.cay")
+        .expect("readFile with a non-string path should fail to compile");
+    assert!(
+        error.contains("Argument 'path' of readFile() must be a String, got int"),
+        "Should reject non-string path, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_write_file_arity() {
+    let error = compile_eol_expect_error("examples/errors/error_write_file_arity.cay")
+        .expect("writeFile with one argument should fail to compile");
+    assert!(
+        error.contains("writeFile() takes 2 arguments (path, content), got 1"),
+        "Should reject wrong writeFile arity, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_class_name_as_value() {
+    let error = compile_eol_expect_error("examples/errors/error_class_name_as_value.cay")
+        .expect("using a class name as a value should fail to compile");
+    assert!(
+        error.contains("Class name 'Counter' cannot be used as a value"),
+        "Should reject class name used as a value, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_include_not_found() {
+    let error = compile_eol_expect_error("examples/errors/error_include_not_found.cay")
+        .expect("including a missing file should fail to compile");
+    assert!(
+        error.contains("找不到包含文件 'no_such_header.cay'") && error.contains("examples/errors"),
+        "Should report the missing include and the searched directories, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_print_negative_precision() {
+    let error = compile_eol_expect_error("examples/errors/error_print_negative_precision.cay")
+        .expect("a negative print precision should fail to compile");
+    assert!(
+        error.contains("precision") && error.contains("negative"),
+        "Should report the negative precision, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_overload_candidates() {
+    let error = compile_eol_expect_error("examples/errors/error_overload_candidates.cay")
+        .expect("a call matching no overload should fail to compile");
+    assert!(
+        error.contains("cannot be applied to given types")
+            && error.contains("int add(int, int)")
+            && error.contains("double add(double, double)"),
+        "Should list the candidate overloads, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_overload_ambiguous() {
+    let error = compile_eol_expect_error("examples/errors/error_overload_ambiguous.cay")
+        .expect("an ambiguous overloaded call should fail to compile");
+    assert!(
+        error.contains("is ambiguous") && error.contains("long combine(long, double)") && error.contains("long combine(double, long)"),
+        "Should report the ambiguous call with its candidates, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_printf_arg_count() {
+    let error = compile_eol_expect_error("examples/errors/error_printf_arg_count.cay")
+        .expect("printf with too few arguments should fail to compile");
+    assert!(
+        error.contains("printf() format string expects 2 arguments, got 1"),
+        "Should report the argument count mismatch, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_array_negative_index() {
+    let error = compile_and_run_expect_error_with_flags("examples/errors/error_array_negative_index.cay", &["--bounds-check"])
+        .expect("a negative index should abort when bounds checks are enabled");
+    assert!(
+        error.contains("Array index -1 out of bounds for length 3"),
+        "Should report the index and the array length, got: {}",
+        error
+    );
+    assert!(!error.contains("should not be reached"), "execution should stop at the bad access, got: {}", error);
+}
+
+#[test]
+fn test_error_array_clone_args() {
+    let error = compile_eol_expect_error("examples/errors/error_array_clone_args.cay")
+        .expect("clone() with arguments should fail to compile");
+    assert!(
+        error.contains("clone() takes no arguments"),
+        "Should report the extra argument, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_array_equals_mismatch() {
+    let error = compile_eol_expect_error("examples/errors/error_array_equals_mismatch.cay")
+        .expect("arrayEquals() on different array types should fail to compile");
+    assert!(
+        error.contains("arrayEquals() requires two arrays of the same type"),
+        "Should report the mismatched array types, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_static_calls_instance_method() {
+    let error = compile_eol_expect_error("examples/errors/error_static_calls_instance_method.cay")
+        .expect("calling an instance method from a static method should fail to compile");
+    assert!(
+        error.contains("non-static method int next() cannot be referenced from a static context"),
+        "Should report the implicit instance call, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_invalid_char_escape() {
+    let error = compile_eol_expect_error("examples/errors/error_invalid_char_escape.cay")
+        .expect("an invalid char escape should fail to compile");
+    assert!(
+        error.contains("Invalid escape sequence in character literal"),
+        "Should report the invalid escape, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_directive_multiline() {
+    let error = compile_eol_expect_error("examples/errors/error_directive_multiline.cay")
+        .expect("#error should fail to compile");
+    assert!(
+        error.contains("#error: unsupported configuration\nset TARGET before compiling"),
+        "The \\n escape in #error should become a newline, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_unmatched_brace() {
+    let error = compile_eol_expect_error("examples/errors/error_unmatched_brace.cay")
+        .expect("a missing closing brace should fail to compile");
+    assert!(
+        error.contains("Unmatched '{' opened at line 5, column 20"),
+        "Should point at the unclosed if block, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_int8_out_of_range() {
+    let error = compile_eol_expect_error("examples/errors/error_int8_out_of_range.cay")
+        .expect("an int constant outside the int8 range should fail to compile");
+    assert!(
+        error.contains("Cannot assign int to int8"),
+        "Should reject the out-of-range constant, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_final_array_element() {
+    let error = compile_and_run_expect_error_with_flags("examples/errors/error_final_array_element.cay", &["--immutable-final-arrays"])
+        .expect("assigning to an element of a final array should fail with --immutable-final-arrays");
+    assert!(
+        error.contains("Cannot modify an element of final array 'primes'"),
+        "Should reject the element assignment, got: {}",
+        error
+    );
+    assert!(!error.contains("runtime error"), "the check should happen at compile time, got: {}", error);
+}
+
+#[test]
+fn test_error_main_on_method() {
+    let error = compile_eol_expect_error("examples/errors/error_main_on_method.cay")
+        .expect("@main on a method should fail to compile");
+    assert!(
+        error.contains("@main/@entry can only be applied to a class, not to method 'main'"),
+        "Should reject @main outside a class declaration, got: {}",
+        error
+    );
+    assert!(error.contains("[3:5]"), "Should point at the misplaced annotation, got: {}", error);
+}
+
+#[test]
+fn test_error_final_field_double_assign() {
+    let error = compile_eol_expect_error("examples/errors/error_final_field_double_assign.cay")
+        .expect("assigning a final field twice in a constructor should fail to compile");
+    assert!(
+        error.contains("Final field 'x' has already been assigned"),
+        "Should reject the second assignment, got: {}",
+        error
+    );
+    assert!(error.contains("[7:9]"), "Should point at the second assignment, got: {}", error);
+}
+
+#[test]
+fn test_error_final_field_mutation() {
+    let error = compile_eol_expect_error("examples/errors/error_final_field_mutation.cay")
+        .expect("assigning a final field after construction should fail to compile");
+    assert!(
+        error.contains("Cannot assign a value to final field 'x'"),
+        "Should reject the post-construction assignment, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_void_variable() {
+    let error = compile_eol_expect_error("examples/errors/error_void_variable.cay")
+        .expect("declaring a void variable should fail to compile");
+    assert!(
+        error.contains("'void' is not a valid variable type"),
+        "Should explain that void is not a variable type, got: {}",
+        error
+    );
+    assert!(error.contains("[5:9]"), "Should point at 'void', got: {}", error);
+}
+
+#[test]
+fn test_error_void_parameter() {
+    let error = compile_eol_expect_error("examples/errors/error_void_parameter.cay")
+        .expect("declaring a void parameter should fail to compile");
+    assert!(
+        error.contains("'void' is not a valid variable type"),
+        "Should explain that void is not a parameter type, got: {}",
+        error
+    );
+    assert!(error.contains("[4:32]"), "Should point at 'void', got: {}", error);
+}
+
+#[test]
+fn test_error_read_type_mismatch() {
+    let error = compile_eol_expect_error("examples/errors/error_read_type_mismatch.cay")
+        .expect("assigning readDouble() to an int should fail to compile");
+    assert!(
+        error.contains("Cannot assign double to int at line 5"),
+        "Should report the assignment type mismatch, got: {}",
+        error
+    );
+    assert!(
+        error.contains("readDouble() returns double, use readInt() instead"),
+        "Should suggest the matching read function, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_var_without_initializer() {
+    let error = compile_eol_expect_error("examples/errors/error_var_without_initializer.cay")
+        .expect("var without a type or initializer should fail to compile");
+    assert!(
+        error.contains("Variable 'x' declared without a type requires an initializer at line 5"),
+        "Should require an initializer to infer the type, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_main_without_method() {
+    let error = compile_eol_expect_error("examples/errors/error_main_without_method.cay")
+        .expect("@main on a class without a main method should fail to compile");
+    assert!(
+        error.contains("Class 'Foo' is marked @main but has no 'public static main' method"),
+        "Should reject @main on a class without an entry point, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_list_index_out_of_bounds() {
+    let error = compile_and_run_expect_error("examples/errors/error_list_index_out_of_bounds.cay")
+        .expect("reading past the end of a List should abort");
+    assert!(error.contains("30"), "elements within bounds should be readable, got: {}", error);
+    assert!(
+        error.contains("List index 3 out of bounds for size 3"),
+        "Should report the index and the List size, got: {}",
+        error
+    );
+    assert!(!error.contains("should not be reached"), "execution should stop at the bad access, got: {}", error);
+}
+
+#[test]
+fn test_error_list_mixed_elements() {
+    let error = compile_eol_expect_error("examples/errors/error_list_mixed_elements.cay")
+        .expect("adding a string to a List of int should fail to compile");
+    assert!(
+        error.contains("Cannot add string to a List of int"),
+        "Should reject elements of a different type, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_unary_plus_string() {
+    let error = compile_eol_expect_error("examples/errors/error_unary_plus_string.cay")
+        .expect("unary plus on a string should fail to compile");
+    assert!(
+        error.contains("Cannot apply unary '+' to string"),
+        "Should reject unary plus on a non-numeric operand, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_address_of_null() {
+    let error = compile_eol_expect_error("examples/errors/error_address_of_null.cay")
+        .expect("addressOf(null) should fail to compile");
+    assert!(
+        error.contains("addressOf() requires an object, array or string, got null"),
+        "Should reject a null addressOf argument, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_inherited_private_field() {
+    let error = compile_eol_expect_error("examples/errors/error_inherited_private_field.cay")
+        .expect("a subclass reading a parent's private field should fail to compile");
+    assert!(
+        error.contains("secret has private access in Base"),
+        "Should check access against the declaring class, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_no_matching_constructor() {
+    let error = compile_eol_expect_error("examples/errors/error_no_matching_constructor.cay")
+        .expect("new with arguments no constructor accepts should fail to compile");
+    assert!(
+        error.contains("No constructor of Box accepts arguments"),
+        "Should report the missing constructor, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_final_field_nested_mutation() {
+    let error = compile_eol_expect_error("examples/errors/error_final_field_nested_mutation.cay")
+        .expect("assigning a final field inside an if body should fail to compile");
+    assert!(
+        error.contains("Cannot assign a value to final field 'v'"),
+        "Should check final field writes inside nested bodies, got: {}",
+        error
+    );
+    assert!(error.contains("[11:13]"), "Should point at the nested assignment, got: {}", error);
+}
+
+#[test]
+fn test_error_final_field_loop_assign() {
+    let error = compile_eol_expect_error("examples/errors/error_final_field_loop_assign.cay")
+        .expect("assigning a final field inside a loop should fail to compile");
+    assert!(
+        error.contains("Cannot assign final field 'v' inside a loop"),
+        "Should reject the assignment in the loop body, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_final_field_partial_assign() {
+    let error = compile_eol_expect_error("examples/errors/error_final_field_partial_assign.cay")
+        .expect("a final field assigned on only one branch should fail to compile");
+    assert!(
+        error.contains("Final field 'x' is not assigned in constructor of 'Point'"),
+        "Should require the assignment on every path, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_const_reassign_nested() {
+    let error = compile_eol_expect_error("examples/errors/error_const_reassign_nested.cay")
+        .expect("assigning to a const inside an if body should fail to compile");
+    assert!(
+        error.contains("Cannot assign a value to constant 'K'"),
+        "Should reject const reassignment in nested bodies, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_final_array_element_nested() {
+    let error = compile_and_run_expect_error_with_flags("examples/errors/error_final_array_element_nested.cay", &["--immutable-final-arrays"])
+        .expect("assigning to a final array element inside a loop should fail with --immutable-final-arrays");
+    assert!(
+        error.contains("Cannot modify an element of final array 'arr'"),
+        "Should reject the element assignment in the loop body, got: {}",
+        error
+    );
+    assert!(!error.contains("runtime error"), "the check should happen at compile time, got: {}", error);
+}
+
+#[test]
+fn test_error_print_tuple() {
+    let error = compile_eol_expect_error("examples/errors/error_print_tuple.cay")
+        .expect("printing a tuple should fail to compile");
+    assert!(
+        error.contains("Cannot pass a tuple (int, int) to println()"),
+        "Should reject the tuple argument, got: {}",
+        error
+    );
+}
+
+#[test]
+fn test_error_method_ref_non_object() {
+    let error = compile_eol_expect_error("examples/errors/error_method_ref_non_object.cay")
+        .expect("a method reference on an int variable should fail to compile");
+    assert!(
+        error.contains("Cannot reference method 'twice' on a value of type int"),
+        "Should reject the method reference on a non-object, got: {}",
+        error
+    );
+}